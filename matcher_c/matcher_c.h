@@ -1,6 +1,15 @@
 void* init_matcher(char* match_table_dict_bytes);
+void* init_matcher_with_options(char* match_table_dict_bytes, char* matcher_options_json);
+void* try_init_matcher_with_options(char* match_table_dict_bytes, char* matcher_options_json, char** error_msg);
+void* init_matcher_from_bundle(char* rule_bundle_bytes, size_t rule_bundle_len);
+void* try_init_matcher_from_bundle(char* rule_bundle_bytes, size_t rule_bundle_len, char** error_msg);
+bool matcher_check_input(void* matcher, char* text);
 bool matcher_is_match(void* matcher, char* text);
 char* matcher_word_match(void* matcher, char* text);
+char* matcher_word_match_full(void* matcher, char* text);
+// 已加载词表的概况，json数组，按 (match_id, table_id) 升序；返回的字符串由drop_matcher_tables释放
+char* matcher_tables(void* matcher);
+void drop_matcher_tables(char* ptr);
 void drop_matcher(void* matcher);
 
 void* init_simple_matcher(char* simple_wordlist_dict_bytes);
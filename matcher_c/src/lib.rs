@@ -3,7 +3,10 @@ use std::{
     str::from_utf8_unchecked,
 };
 
-use matcher_rs::{MatchTableDict, Matcher, SimpleMatcher, SimpleWordlistDict, TextMatcherTrait};
+use matcher_rs::{
+    MatchTableDict, Matcher, MatcherOptions, RuleBundle, SimpleMatcher, SimpleWordlistDict,
+    TextMatcherTrait,
+};
 
 #[no_mangle]
 pub extern "C" fn init_matcher(match_table_dict_bytes: *const i8) -> *mut Matcher {
@@ -21,6 +24,140 @@ pub extern "C" fn init_matcher(match_table_dict_bytes: *const i8) -> *mut Matche
     }
 }
 
+// matcher_options_json 为json格式的MatcherOptions，eg. {"max_input_bytes": 1048576, "over_limit_policy": "reject"}
+#[no_mangle]
+pub extern "C" fn init_matcher_with_options(
+    match_table_dict_bytes: *const i8,
+    matcher_options_json: *const i8,
+) -> *mut Matcher {
+    unsafe {
+        let match_table_dict: MatchTableDict = match rmp_serde::from_slice(
+            CStr::from_ptr(match_table_dict_bytes).to_bytes(),
+        ) {
+            Ok(match_table_dict) => match_table_dict,
+            Err(e) => {
+                panic!("Deserialize match_table_dict_bytes failed, Please check the input data.\nErr: {}", e.to_string())
+            }
+        };
+        let matcher_options: MatcherOptions = match serde_json::from_slice(
+            CStr::from_ptr(matcher_options_json).to_bytes(),
+        ) {
+            Ok(matcher_options) => matcher_options,
+            Err(e) => {
+                panic!("Deserialize matcher_options_json failed, Please check the input data.\nErr: {}", e.to_string())
+            }
+        };
+
+        Box::into_raw(Box::new(Matcher::new_with_options(
+            &match_table_dict,
+            matcher_options,
+        )))
+    }
+}
+
+// 同init_matcher_with_options，词表校验失败时返回NULL，并将错误信息写入error_msg，由调用方通过drop_string释放
+#[no_mangle]
+pub extern "C" fn try_init_matcher_with_options(
+    match_table_dict_bytes: *const i8,
+    matcher_options_json: *const i8,
+    error_msg: *mut *mut i8,
+) -> *mut Matcher {
+    unsafe {
+        let result = rmp_serde::from_slice::<MatchTableDict>(
+            CStr::from_ptr(match_table_dict_bytes).to_bytes(),
+        )
+        .map_err(|e| format!("Deserialize match_table_dict_bytes failed.\nErr: {e}"))
+        .and_then(|match_table_dict| {
+            let matcher_options: MatcherOptions =
+                serde_json::from_slice(CStr::from_ptr(matcher_options_json).to_bytes())
+                    .map_err(|e| format!("Deserialize matcher_options_json failed.\nErr: {e}"))?;
+            Matcher::try_new_with_options(&match_table_dict, matcher_options)
+                .map(|(matcher, _)| matcher)
+                .map_err(|e| format!("Build matcher failed.\nErr: {e}"))
+        });
+
+        match result {
+            Ok(matcher) => Box::into_raw(Box::new(matcher)),
+            Err(e) => {
+                if !error_msg.is_null() {
+                    *error_msg = CString::new(e.replace('\0', "")).unwrap().into_raw();
+                }
+                std::ptr::null_mut()
+            }
+        }
+    }
+}
+
+// rule_bundle_bytes 为RuleBundle::to_bytes的输出，含有\0，需要同时传入长度
+#[no_mangle]
+pub extern "C" fn init_matcher_from_bundle(
+    rule_bundle_bytes: *const u8,
+    rule_bundle_len: usize,
+) -> *mut Matcher {
+    unsafe {
+        let rule_bundle = match RuleBundle::from_bytes(std::slice::from_raw_parts(
+            rule_bundle_bytes,
+            rule_bundle_len,
+        )) {
+            Ok(rule_bundle) => rule_bundle,
+            Err(e) => {
+                panic!(
+                    "Deserialize rule_bundle_bytes failed, Please check the input data.\nErr: {}",
+                    e.to_string()
+                )
+            }
+        };
+
+        Box::into_raw(Box::new(Matcher::from_bundle(&rule_bundle)))
+    }
+}
+
+// 同init_matcher_from_bundle，bundle无法解析或构建失败时返回NULL，并将错误信息写入error_msg，由drop_string释放
+#[no_mangle]
+pub extern "C" fn try_init_matcher_from_bundle(
+    rule_bundle_bytes: *const u8,
+    rule_bundle_len: usize,
+    error_msg: *mut *mut i8,
+) -> *mut Matcher {
+    unsafe {
+        let result = RuleBundle::from_bytes(std::slice::from_raw_parts(
+            rule_bundle_bytes,
+            rule_bundle_len,
+        ))
+        .map_err(|e| format!("Deserialize rule_bundle_bytes failed.\nErr: {e}"))
+        .and_then(|rule_bundle| {
+            Matcher::try_new_with_options(
+                &MatchTableDict::from(&rule_bundle.table_map),
+                rule_bundle.matcher_options(),
+            )
+            .map(|(matcher, _)| matcher)
+            .map_err(|e| format!("Build matcher failed.\nErr: {e}"))
+        });
+
+        match result {
+            Ok(matcher) => Box::into_raw(Box::new(matcher)),
+            Err(e) => {
+                if !error_msg.is_null() {
+                    *error_msg = CString::new(e.replace('\0', "")).unwrap().into_raw();
+                }
+                std::ptr::null_mut()
+            }
+        }
+    }
+}
+
+// over_limit_policy 为 reject 且文本超长时返回false，此时 matcher_word_match 返回空结果
+#[no_mangle]
+pub extern "C" fn matcher_check_input(matcher: *mut Matcher, text: *const i8) -> bool {
+    unsafe {
+        matcher
+            .as_ref()
+            .unwrap()
+            .check_input(from_utf8_unchecked(CStr::from_ptr(text).to_bytes()))
+            .is_ok()
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn matcher_is_match(matcher: *mut Matcher, text: *const i8) -> bool {
     unsafe {
@@ -49,6 +186,36 @@ pub extern "C" fn matcher_word_match(matcher: *mut Matcher, text: *const i8) ->
     res.into_raw()
 }
 
+// 同matcher_word_match，但被豁免的match_id不丢弃，放在exempted中并附带命中的豁免词
+#[no_mangle]
+pub extern "C" fn matcher_word_match_full(matcher: *mut Matcher, text: *const i8) -> *mut i8 {
+    let res = unsafe {
+        CString::new(
+            matcher
+                .as_ref()
+                .unwrap()
+                .word_match_full_as_string(from_utf8_unchecked(CStr::from_ptr(text).to_bytes())),
+        )
+        .unwrap()
+    };
+
+    res.into_raw()
+}
+
+// 已加载词表的概况，json数组，按 (match_id, table_id) 升序，由调用方通过drop_matcher_tables释放
+#[no_mangle]
+pub extern "C" fn matcher_tables(matcher: *mut Matcher) -> *mut i8 {
+    let res = unsafe { CString::new(matcher.as_ref().unwrap().tables_as_string()).unwrap() };
+
+    res.into_raw()
+}
+
+// 释放matcher_tables返回的字符串，同drop_string
+#[no_mangle]
+pub extern "C" fn drop_matcher_tables(ptr: *mut i8) {
+    drop_string(ptr)
+}
+
 #[no_mangle]
 pub extern "C" fn drop_matcher(matcher: *mut Matcher) {
     unsafe { drop(Box::from_raw(matcher)) }
@@ -1 +1,8 @@
-from .matcher_py import Matcher, SimpleMatcher
\ No newline at end of file
+from .matcher_py import (
+    Matcher,
+    ReloadableMatcher,
+    SimMatcher,
+    SimpleMatcher,
+    fingerprint,
+    parse_simple_match_type,
+)
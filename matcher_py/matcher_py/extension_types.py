@@ -1,5 +1,5 @@
 from enum import Enum, IntFlag
-from typing import Dict, List
+from typing import Dict, List, Literal, Optional, Union
 
 import msgspec
 
@@ -9,6 +9,7 @@ class MatchTableType(Enum):
     Acrostic = "acrostic"
     SimilarTextLevenshtein = "similar_text_levenshtein"
     regex = "regex"
+    Wildcard = "wildcard"
 
 
 class SimpleMatchType(IntFlag):
@@ -18,6 +19,36 @@ class SimpleMatchType(IntFlag):
     MatchPinYin = 0b00010000
     MatchPinYinChar = 0b00100000
 
+    @classmethod
+    def _missing_(cls, value):
+        # 支持可读名称，eg. SimpleMatchType("fanjian_delete_normalize")
+        if isinstance(value, str):
+            from .matcher_py import parse_simple_match_type
+
+            return cls(parse_simple_match_type(value))
+        return super()._missing_(value)
+
+
+AcrosticPosition = Union[str, Dict[str, int]]  # "initial" or {"nth": n}
+AcrosticSeparator = Union[str, Dict[str, int]]  # "punctuation" or {"gap": n}
+
+
+class SimMatchType(Enum):
+    Levenshtein = "levenshtein"
+    JaroWinkler = "jaro_winkler"
+    DamerauLevenshtein = "damerau_levenshtein"
+    LevenshteinSubstring = "levenshtein_substring"
+
+
+SimMatchTypeLike = Union[SimMatchType, Dict[str, Dict[str, int]]]  # SimMatchType or {"ngram_cosine": {"n": n}}
+
+
+class RegexFlags(msgspec.Struct):
+    case_insensitive: bool = False
+    multi_line: bool = False
+    dot_matches_new_line: bool = False
+    unicode: bool = True
+
 
 class MatchTable(msgspec.Struct):
     table_id: int
@@ -25,6 +56,12 @@ class MatchTable(msgspec.Struct):
     wordlist: List[str]
     exemption_wordlist: List[str]
     simple_match_type: SimpleMatchType
+    acrostic_position: AcrosticPosition = "initial"
+    acrostic_separator: AcrosticSeparator = "punctuation"
+    prefer_matched_text: bool = False
+    sim_match_type: SimMatchTypeLike = SimMatchType.Levenshtein
+    regex_flags: RegexFlags = msgspec.field(default_factory=RegexFlags)
+    simple_match_type_override: Dict[str, SimpleMatchType] = msgspec.field(default_factory=dict)
 
 
 MatchTableDict = Dict[str, MatchTable]
@@ -32,6 +69,7 @@ MatchTableDict = Dict[str, MatchTable]
 class MatchResult(msgspec.Struct):
     table_id: int
     word: str
+    table_type: Optional[Literal["simple", "regex", "similar"]] = None
 
 
 MatcherMatchResult = Dict[str, List[MatchResult]]
@@ -1,19 +1,73 @@
-from typing import Dict, List, Optional, Tuple, TypedDict
+from typing import Dict, List, Literal, Optional, Tuple, TypedDict, Union, overload
 
 import numpy as np
+from typing_extensions import NotRequired
+from .extension_types import SimpleMatchType, SimpleWord
+
+class PartHit(TypedDict):
+    part: str
+    count: int
 
 class SimpleResult(TypedDict):
     word_id: int
     word: str
+    parts: NotRequired[List[PartHit]]
+
+class SimResult(TypedDict):
+    match_id: str
+    table_id: int
+    word_id: int
+    word: str
+    similarity: float
+
+class TableInfo(TypedDict):
+    match_id: str
+    table_id: int
+    match_table_type: str
+    word_count: int
+    exemption_count: int
+
+OverLimitPolicy = Literal["truncate", "chunk_and_union", "reject"]
 
 class Matcher:
-    def __init__(self, match_table_dict_bytes: bytes) -> None: ...
-    def __getnewargs__(self) -> Tuple[bytes, str, str]: ...
+    def __init__(
+        self,
+        match_table_dict_bytes: bytes,
+        max_input_bytes: Optional[int] = None,
+        over_limit_policy: OverLimitPolicy = "truncate",
+        chunk_overlap_bytes: int = 256,
+        report_span: bool = False,
+    ) -> None: ...
+    @staticmethod
+    def from_bundle_file(path: str) -> Matcher: ...
+    def __getnewargs__(self) -> Tuple[bytes, Optional[int], str, int, bool]: ...
     def __getstate__(self) -> Dict: ...
     def __setstate__(self, state_dict: Dict): ...
+    def export_table_dict(self) -> bytes: ...
+    def match_ids(self) -> List[str]: ...
+    def tables(self) -> List[TableInfo]: ...
     def is_match(self, text: str) -> bool: ...
-    def word_match(self, text: str) -> Dict[str, str]: ...
+    @overload
+    def word_match(
+        self,
+        text: str,
+        raw: bool = False,
+        timeout_ms: Optional[int] = None,
+        partial_on_timeout: Literal[False] = False,
+    ) -> Dict[str, str]: ...
+    @overload
+    def word_match(
+        self,
+        text: str,
+        raw: bool = False,
+        timeout_ms: Optional[int] = None,
+        *,
+        partial_on_timeout: Literal[True],
+    ) -> Tuple[Dict[str, str], bool]: ...
+    def try_word_match(self, text: str) -> Dict[str, str]: ...
     def word_match_as_string(self, text: str) -> str: ...
+    def word_match_full_as_string(self, text: str) -> str: ...
+    def word_match_by_table(self, text: str) -> Dict[str, Dict[int, str]]: ...
     def batch_word_match_as_dict(
         self, text_array: List[str]
     ) -> List[Dict[str, str]]: ...
@@ -25,8 +79,24 @@ class Matcher:
         self, text_array: np.ndarray, inplace=False
     ) -> Optional[np.ndarray]: ...
 
+class ReloadableMatcher:
+    def __init__(self, match_table_dict_bytes: bytes) -> None: ...
+    def reload(self, match_table_dict_bytes: bytes) -> None: ...
+    def is_match(self, text: str) -> bool: ...
+    def word_match(self, text: str) -> Dict[str, str]: ...
+    def word_match_as_string(self, text: str) -> str: ...
+
 class SimpleMatcher:
-    def __init__(self, simple_wordlist_dict_bytes: bytes) -> None: ...
+    def __init__(
+        self,
+        simple_wordlist_dict: Union[
+            bytes,
+            Dict[
+                Union[SimpleMatchType, int, str],
+                Union[Dict[int, Union[str, SimpleWord]], List[SimpleWord]],
+            ],
+        ],
+    ) -> None: ...
     def __getnewargs__(self) -> bytes: ...
     def __getstate__(self) -> bytes: ...
     def __setstate__(self, simple_wordlist_dict_bytes: bytes): ...
@@ -38,3 +108,13 @@ class SimpleMatcher:
     def numpy_simple_process(
         self, text_array: np.ndarray, inplace=False
     ) -> Optional[np.ndarray]: ...
+
+class SimMatcher:
+    def __init__(self, match_table_dict_bytes: bytes) -> None: ...
+    def __getnewargs__(self) -> bytes: ...
+    def __getstate__(self) -> bytes: ...
+    def __setstate__(self, match_table_dict_bytes: bytes): ...
+    def nearest(self, text: str, k: int) -> List[SimResult]: ...
+
+def parse_simple_match_type(name: str) -> int: ...
+def fingerprint(match_id: str, table_id: int, word: str) -> int: ...
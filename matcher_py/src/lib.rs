@@ -1,14 +1,22 @@
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
 use numpy::PyArray1;
-use pyo3::exceptions::PyValueError;
-use pyo3::prelude::{pyclass, pymethods, pymodule, Py, PyModule, PyObject, PyResult, Python};
+use pyo3::exceptions::{PyIOError, PyTimeoutError, PyTypeError, PyValueError};
+use pyo3::prelude::{
+    pyclass, pyfunction, pymethods, pymodule, Py, PyModule, PyObject, PyResult, Python,
+};
 use pyo3::types::{PyBytes, PyDict, PyList, PyString};
-use pyo3::{intern, IntoPy, PyAny};
+use pyo3::{intern, wrap_pyfunction, IntoPy, PyAny};
 
 use matcher_rs::{
-    MatchTableDict as MatchTableDictRs, Matcher as MatcherRs, SimpleMatcher as SimpleMatcherRs,
-    SimpleResult as SimpleResultRs, SimpleWordlistDict as SimpleWordlistDictRs, TextMatcherTrait,
+    match_fingerprint, MatchTableDict as MatchTableDictRs, MatchTableType as MatchTableTypeRs,
+    Matcher as MatcherRs, MatcherHandle as MatcherHandleRs, MatcherOptions as MatcherOptionsRs,
+    OverLimitPolicy as OverLimitPolicyRs, OwnedMatchTableDict as OwnedMatchTableDictRs,
+    RuleBundle as RuleBundleRs, RuleBundleError as RuleBundleErrorRs, SimMatcher as SimMatcherRs,
+    SimResult as SimResultRs, SimTable as SimTableRs, SimpleMatchType as SimpleMatchTypeRs,
+    SimpleMatcher as SimpleMatcherRs, SimpleResult as SimpleResultRs, SimpleWord as SimpleWordRs,
+    SimpleWordlistDict as SimpleWordlistDictRs, TextMatcherTrait, DEFAULT_SIM_THRESHOLD,
 };
 
 struct SimpleResult<'a>(SimpleResultRs<'a>);
@@ -21,6 +29,44 @@ impl<'a> IntoPy<PyObject> for SimpleResult<'a> {
             .unwrap();
         dict.set_item(intern!(py, "word"), self.0.word.as_ref())
             .unwrap();
+        if let Some(parts) = self.0.parts {
+            let part_list = PyList::empty(py);
+            for part_hit in parts {
+                let part_dict = PyDict::new(py);
+                part_dict
+                    .set_item(intern!(py, "part"), part_hit.part.as_ref())
+                    .unwrap();
+                part_dict
+                    .set_item(intern!(py, "count"), part_hit.count)
+                    .unwrap();
+                part_list.append(part_dict).unwrap();
+            }
+            dict.set_item(intern!(py, "parts"), part_list).unwrap();
+        }
+
+        dict.into()
+    }
+}
+
+struct SimResult<'a>(SimResultRs<'a>);
+
+impl<'a> IntoPy<PyObject> for SimResult<'a> {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        let dict = PyDict::new(py);
+
+        dict.set_item(intern!(py, "match_id"), self.0.match_id)
+            .unwrap();
+        dict.set_item(intern!(py, "table_id"), self.0.table_id)
+            .unwrap();
+        dict.set_item(intern!(py, "word_id"), self.0.word_id)
+            .unwrap();
+        dict.set_item(intern!(py, "word"), self.0.word.as_ref())
+            .unwrap();
+        dict.set_item(intern!(py, "similarity"), self.0.similarity)
+            .unwrap();
+        if let Some(window) = self.0.window {
+            dict.set_item(intern!(py, "window"), window).unwrap();
+        }
 
         dict.into()
     }
@@ -30,16 +76,96 @@ impl<'a> IntoPy<PyObject> for SimpleResult<'a> {
 struct Matcher {
     matcher: MatcherRs,
     match_table_dict_bytes: Py<PyBytes>,
+    max_input_bytes: Option<usize>,
+    over_limit_policy: String,
+    chunk_overlap_bytes: usize,
+    report_span: bool,
+    rule_bundle_bytes: Option<Py<PyBytes>>, // 由rule bundle构建时，pickle保存bundle以保留转换映射表覆盖以及全部构建选项
+}
+
+impl Matcher {
+    fn build_options(
+        max_input_bytes: Option<usize>,
+        over_limit_policy: &str,
+        chunk_overlap_bytes: usize,
+        report_span: bool,
+    ) -> PyResult<MatcherOptionsRs> {
+        let over_limit_policy = match over_limit_policy {
+            "truncate" => OverLimitPolicyRs::Truncate,
+            "chunk_and_union" => OverLimitPolicyRs::ChunkAndUnion {
+                overlap_bytes: chunk_overlap_bytes,
+            },
+            "reject" => OverLimitPolicyRs::Reject,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown over_limit_policy: {over_limit_policy}, expected one of truncate / chunk_and_union / reject"
+                )))
+            }
+        };
+
+        Ok(MatcherOptionsRs {
+            max_input_bytes,
+            over_limit_policy,
+            report_span,
+            ..Default::default()
+        })
+    }
+
+    fn build_from_bundle(
+        py: Python,
+        rule_bundle: RuleBundleRs,
+        rule_bundle_bytes: &[u8],
+    ) -> Matcher {
+        let (over_limit_policy, chunk_overlap_bytes) = match rule_bundle.options.over_limit_policy {
+            OverLimitPolicyRs::Truncate => ("truncate", 256),
+            OverLimitPolicyRs::ChunkAndUnion { overlap_bytes } => {
+                ("chunk_and_union", overlap_bytes)
+            }
+            OverLimitPolicyRs::Reject => ("reject", 256),
+        };
+
+        Matcher {
+            matcher: MatcherRs::from_bundle(&rule_bundle),
+            match_table_dict_bytes: PyBytes::new(
+                py,
+                &rmp_serde::to_vec_named(&rule_bundle.table_map).unwrap(),
+            )
+            .into(),
+            max_input_bytes: rule_bundle.options.max_input_bytes,
+            over_limit_policy: over_limit_policy.to_owned(),
+            chunk_overlap_bytes,
+            report_span: rule_bundle.options.report_span,
+            rule_bundle_bytes: Some(PyBytes::new(py, rule_bundle_bytes).into()),
+        }
+    }
 }
 
 #[pymethods]
 impl Matcher {
+    // max_input_bytes 单条文本的最大字节数，超长文本按 over_limit_policy 截断 / 切分重叠窗口后合并 / 拒绝
+    // report_span 命中结果附带命中位置 start / end（字节位置）以及所在文本变体 variant
     #[new]
-    fn new(_py: Python, match_table_dict_bytes: &PyBytes) -> PyResult<Matcher> {
+    #[pyo3(signature=(match_table_dict_bytes, max_input_bytes = None, over_limit_policy = "truncate", chunk_overlap_bytes = 256, report_span = false))]
+    fn new(
+        _py: Python,
+        match_table_dict_bytes: &PyBytes,
+        max_input_bytes: Option<usize>,
+        over_limit_policy: &str,
+        chunk_overlap_bytes: usize,
+        report_span: bool,
+    ) -> PyResult<Matcher> {
+        let matcher_options = Self::build_options(
+            max_input_bytes,
+            over_limit_policy,
+            chunk_overlap_bytes,
+            report_span,
+        )?;
+
         // 之所以用msgpack而不是json，是因为serde json在做zero copy deserialization时，无法分辨一些特殊字符，eg. "It's /\/\y duty"
-        let match_table_dict: MatchTableDictRs =
+        // 反序列化为完全拥有的词表，Matcher构建后不再依赖输入的bytes
+        let owned_match_table_dict: OwnedMatchTableDictRs =
             match rmp_serde::from_slice(match_table_dict_bytes.as_bytes()) {
-                Ok(match_table_dict) => match_table_dict,
+                Ok(owned_match_table_dict) => owned_match_table_dict,
                 Err(e) => {
                     return Err(PyValueError::new_err(format!(
                 "Deserialize match_table_dict_bytes failed, Please check the input data.\nErr: {}",
@@ -48,28 +174,122 @@ impl Matcher {
                 }
             };
 
+        // 词表校验失败时抛出ValueError，而不是构建出缺少部分词的Matcher
+        let (matcher, _) = MatcherRs::try_new_with_options(
+            &MatchTableDictRs::from(&owned_match_table_dict),
+            matcher_options,
+        )
+        .map_err(|e| PyValueError::new_err(format!("Build matcher failed.\nErr: {e}")))?;
+
         Ok(Matcher {
-            matcher: MatcherRs::new(&match_table_dict),
+            matcher,
             match_table_dict_bytes: match_table_dict_bytes.into(),
+            max_input_bytes,
+            over_limit_policy: over_limit_policy.to_owned(),
+            chunk_overlap_bytes,
+            report_span,
+            rule_bundle_bytes: None,
         })
     }
 
+    // 由RuleBundle文件构建，词表、转换映射表覆盖以及构建选项均来自bundle
+    #[staticmethod]
+    fn from_bundle_file(py: Python, path: &str) -> PyResult<Matcher> {
+        let rule_bundle_bytes = std::fs::read(path).map_err(|e| {
+            PyIOError::new_err(format!("Read rule bundle file {path} failed.\nErr: {e}"))
+        })?;
+        let rule_bundle = RuleBundleRs::from_bytes(&rule_bundle_bytes).map_err(|e| {
+            PyValueError::new_err(format!(
+                "Deserialize rule bundle file {path} failed, Please check the input data.\nErr: {e}"
+            ))
+        })?;
+
+        Ok(Self::build_from_bundle(py, rule_bundle, &rule_bundle_bytes))
+    }
+
     // __getnewargs__, __getstate__, __setstate__ 3个函数都是为pickle实现的，spark executor在调用这些方法时，需要用pickle序列化反序列化这些实例
-    fn __getnewargs__(&self, py: Python) -> Py<PyBytes> {
-        self.match_table_dict_bytes.clone_ref(py)
+    fn __getnewargs__(&self, py: Python) -> (Py<PyBytes>, Option<usize>, String, usize, bool) {
+        (
+            self.match_table_dict_bytes.clone_ref(py),
+            self.max_input_bytes,
+            self.over_limit_policy.clone(),
+            self.chunk_overlap_bytes,
+            self.report_span,
+        )
     }
 
     fn __getstate__(&self, py: Python) -> Py<PyBytes> {
-        self.match_table_dict_bytes.clone_ref(py)
+        self.rule_bundle_bytes
+            .as_ref()
+            .unwrap_or(&self.match_table_dict_bytes)
+            .clone_ref(py)
     }
 
-    fn __setstate__(&mut self, match_table_dict_bytes: &PyBytes) -> PyResult<()> {
-        self.matcher =
-            MatcherRs::new(&rmp_serde::from_slice(match_table_dict_bytes.as_bytes()).unwrap());
+    fn __setstate__(&mut self, py: Python, match_table_dict_bytes: &PyBytes) -> PyResult<()> {
+        match RuleBundleRs::from_bytes(match_table_dict_bytes.as_bytes()) {
+            Ok(rule_bundle) => {
+                *self = Self::build_from_bundle(py, rule_bundle, match_table_dict_bytes.as_bytes());
+                return Ok(());
+            }
+            Err(RuleBundleErrorRs::InvalidHeader) => {}
+            Err(e) => return Err(PyValueError::new_err(e.to_string())),
+        }
+
+        let owned_match_table_dict: OwnedMatchTableDictRs =
+            rmp_serde::from_slice(match_table_dict_bytes.as_bytes()).unwrap();
+        (self.matcher, _) = MatcherRs::try_new_with_options(
+            &MatchTableDictRs::from(&owned_match_table_dict),
+            Self::build_options(
+                self.max_input_bytes,
+                &self.over_limit_policy,
+                self.chunk_overlap_bytes,
+                self.report_span,
+            )?,
+        )
+        .map_err(|e| PyValueError::new_err(format!("Build matcher failed.\nErr: {e}")))?;
 
         Ok(())
     }
 
+    // 由匹配器内部状态还原当前实际加载的词表，msgpack编码，格式同构造参数，用于审计
+    fn export_table_dict(&self, py: Python) -> Py<PyBytes> {
+        PyBytes::new(
+            py,
+            &rmp_serde::to_vec_named(&self.matcher.export_table_dict()).unwrap(),
+        )
+        .into()
+    }
+
+    // 已加载的match_id，升序
+    fn match_ids(&self) -> Vec<&str> {
+        self.matcher.match_ids()
+    }
+
+    // 已加载词表的概况，按 (match_id, table_id) 升序，用于热加载后的健康检查
+    fn tables(&self, py: Python) -> Py<PyList> {
+        let table_info_list = PyList::empty(py);
+        for table_info in self.matcher.tables() {
+            let dict = PyDict::new(py);
+            dict.set_item(intern!(py, "match_id"), table_info.match_id)
+                .unwrap();
+            dict.set_item(intern!(py, "table_id"), table_info.table_id)
+                .unwrap();
+            dict.set_item(
+                intern!(py, "match_table_type"),
+                serde_json::to_value(table_info.match_table_type)
+                    .unwrap()
+                    .as_str(),
+            )
+            .unwrap();
+            dict.set_item(intern!(py, "word_count"), table_info.word_count)
+                .unwrap();
+            dict.set_item(intern!(py, "exemption_count"), table_info.exemption_count)
+                .unwrap();
+            table_info_list.append(dict).unwrap();
+        }
+        table_info_list.into()
+    }
+
     fn is_match(&self, _py: Python, text: &PyAny) -> bool {
         text.downcast::<PyString>().map_or(false, |text| {
             self.matcher
@@ -77,11 +297,93 @@ impl Matcher {
         })
     }
 
-    fn word_match(&self, _py: Python, text: &PyAny) -> HashMap<&str, String> {
-        text.downcast::<PyString>().map_or(HashMap::new(), |text| {
-            self.matcher
-                .word_match(unsafe { text.to_str().unwrap_unchecked() })
-        })
+    // raw为true时忽略豁免词，用于评估豁免词的效果
+    // timeout_ms 匹配超时时间，超时抛出TimeoutError；partial_on_timeout为true时不抛出异常，返回 (已匹配的部分结果, 是否超时)
+    #[pyo3(signature=(text, raw = false, timeout_ms = None, partial_on_timeout = false))]
+    fn word_match(
+        &self,
+        py: Python,
+        text: &PyAny,
+        raw: bool,
+        timeout_ms: Option<u64>,
+        partial_on_timeout: bool,
+    ) -> PyResult<PyObject> {
+        let Ok(text) = text.downcast::<PyString>() else {
+            let result_dict: HashMap<&str, String> = HashMap::new();
+            return Ok(if partial_on_timeout {
+                (result_dict, false).into_py(py)
+            } else {
+                result_dict.into_py(py)
+            });
+        };
+        let text = unsafe { text.to_str().unwrap_unchecked() };
+
+        let Some(timeout_ms) = timeout_ms else {
+            let result_dict = if raw {
+                self.matcher.word_match_raw(text)
+            } else {
+                self.matcher.word_match(text)
+            };
+            return Ok(if partial_on_timeout {
+                (result_dict, false).into_py(py)
+            } else {
+                result_dict.into_py(py)
+            });
+        };
+        if raw {
+            return Err(PyValueError::new_err(
+                "timeout_ms is not supported together with raw",
+            ));
+        }
+
+        let deadline = Instant::now() + Duration::from_millis(timeout_ms);
+        let (result_dict, deadline_exceeded) = self.matcher.word_match_until(text, deadline);
+
+        if partial_on_timeout {
+            Ok((result_dict, deadline_exceeded).into_py(py))
+        } else if deadline_exceeded {
+            Err(PyTimeoutError::new_err(format!(
+                "word_match exceeded timeout_ms: {timeout_ms}"
+            )))
+        } else {
+            Ok(result_dict.into_py(py))
+        }
+    }
+
+    // 同word_match，over_limit_policy为reject且文本超长时抛出ValueError，而不是返回空结果
+    fn try_word_match(&self, _py: Python, text: &PyAny) -> PyResult<HashMap<&str, String>> {
+        text.downcast::<PyString>()
+            .map_or(Ok(HashMap::new()), |text| {
+                self.matcher
+                    .try_word_match(unsafe { text.to_str().unwrap_unchecked() })
+                    .map_err(|e| PyValueError::new_err(e.to_string()))
+            })
+    }
+
+    // 同word_match，按词表分组，match_id -> table_id -> 命中结果的json字符串
+    fn word_match_by_table(&self, py: Python, text: &PyAny) -> PyObject {
+        let Ok(text) = text.downcast::<PyString>() else {
+            return PyDict::new(py).into();
+        };
+
+        self.matcher
+            .word_match_by_table(unsafe { text.to_str().unwrap_unchecked() })
+            .into_iter()
+            .map(|(match_id, table_result_dict)| {
+                (
+                    match_id,
+                    table_result_dict
+                        .into_iter()
+                        .map(|(table_id, result_list)| {
+                            (table_id, unsafe {
+                                serde_json::to_string(&result_list).unwrap_unchecked()
+                            })
+                        })
+                        .collect::<HashMap<u32, String>>(),
+                )
+            })
+            .collect::<HashMap<&str, HashMap<u32, String>>>()
+            .into_py(py)
     }
 
     fn word_match_as_string(&self, py: Python, text: &PyAny) -> Py<PyString> {
@@ -97,26 +399,47 @@ impl Matcher {
             .into()
     }
 
-    fn batch_word_match_as_dict(&self, py: Python, text_array: &PyList) -> Py<PyList> {
+    // 保留被豁免的命中结果及命中的豁免词，便于审计
+    fn word_match_full_as_string(&self, py: Python, text: &PyAny) -> Py<PyString> {
+        text.downcast::<PyString>()
+            .map_or(
+                PyString::intern(
+                    py,
+                    r#"{"matched":{},"exempted":{},"variants_truncated":false}"#,
+                ),
+                |text| {
+                    PyString::intern(
+                        py,
+                        &self
+                            .matcher
+                            .word_match_full_as_string(unsafe { text.to_str().unwrap_unchecked() }),
+                    )
+                },
+            )
+            .into()
+    }
+
+    // 批量接口每条文本匹配前检查信号，Ctrl-C可中断长时间的批量调用
+    fn batch_word_match_as_dict(&self, py: Python, text_array: &PyList) -> PyResult<Py<PyList>> {
         let result_list = PyList::empty(py);
 
-        text_array.iter().for_each(|text| {
-            result_list.append(self.word_match(py, text)).unwrap();
-        });
+        for text in text_array.iter() {
+            py.check_signals()?;
+            result_list.append(self.word_match(py, text, false, None, false)?)?;
+        }
 
-        result_list.into()
+        Ok(result_list.into())
     }
 
-    fn batch_word_match_as_string(&self, py: Python, text_array: &PyList) -> Py<PyList> {
+    fn batch_word_match_as_string(&self, py: Python, text_array: &PyList) -> PyResult<Py<PyList>> {
         let result_list = PyList::empty(py);
 
-        text_array.iter().for_each(|text| {
-            result_list
-                .append(self.word_match_as_string(py, text))
-                .unwrap();
-        });
+        for text in text_array.iter() {
+            py.check_signals()?;
+            result_list.append(self.word_match_as_string(py, text))?;
+        }
 
-        result_list.into()
+        Ok(result_list.into())
     }
 
     #[pyo3(signature=(text_array, inplace = false))]
@@ -125,21 +448,20 @@ impl Matcher {
         py: Python,
         text_array: &PyArray1<PyObject>,
         inplace: bool,
-    ) -> Option<Py<PyArray1<PyObject>>> {
+    ) -> PyResult<Option<Py<PyArray1<PyObject>>>> {
         if inplace {
-            unsafe { text_array.as_array_mut() }.map_inplace(|text| {
-                *text = self.word_match(py, text.as_ref(py)).into_py(py);
-            });
-            None
+            for text in unsafe { text_array.as_array_mut() }.iter_mut() {
+                py.check_signals()?;
+                *text = self.word_match(py, text.as_ref(py), false, None, false)?;
+            }
+            Ok(None)
         } else {
-            Some(
-                PyArray1::<PyObject>::from_owned_array(
-                    py,
-                    unsafe { text_array.as_array() }
-                        .map(|text| self.word_match(py, text.as_ref(py)).into_py(py)),
-                )
-                .into(),
-            )
+            let mut result_list = Vec::with_capacity(text_array.len());
+            for text in unsafe { text_array.as_array() }.iter() {
+                py.check_signals()?;
+                result_list.push(self.word_match(py, text.as_ref(py), false, None, false)?);
+            }
+            Ok(Some(PyArray1::<PyObject>::from_vec(py, result_list).into()))
         }
     }
 
@@ -149,23 +471,149 @@ impl Matcher {
         py: Python,
         text_array: &PyArray1<PyObject>,
         inplace: bool,
-    ) -> Option<Py<PyArray1<PyObject>>> {
+    ) -> PyResult<Option<Py<PyArray1<PyObject>>>> {
         if inplace {
-            unsafe { text_array.as_array_mut() }.map_inplace(|text| {
+            for text in unsafe { text_array.as_array_mut() }.iter_mut() {
+                py.check_signals()?;
                 *text = self.word_match_as_string(py, text.as_ref(py)).into_py(py);
-            });
-            None
+            }
+            Ok(None)
         } else {
-            Some(
-                PyArray1::<PyObject>::from_owned_array(
+            let mut result_list = Vec::with_capacity(text_array.len());
+            for text in unsafe { text_array.as_array() }.iter() {
+                py.check_signals()?;
+                result_list.push(self.word_match_as_string(py, text.as_ref(py)).into_py(py));
+            }
+            Ok(Some(PyArray1::<PyObject>::from_vec(py, result_list).into()))
+        }
+    }
+}
+
+#[pyclass(module = "matcher_py")]
+struct ReloadableMatcher {
+    matcher_handle: MatcherHandleRs,
+}
+
+impl ReloadableMatcher {
+    fn build_matcher(py: Python, match_table_dict_bytes: &PyBytes) -> PyResult<MatcherRs> {
+        let owned_match_table_dict: OwnedMatchTableDictRs =
+            match rmp_serde::from_slice(match_table_dict_bytes.as_bytes()) {
+                Ok(owned_match_table_dict) => owned_match_table_dict,
+                Err(e) => {
+                    return Err(PyValueError::new_err(format!(
+                "Deserialize match_table_dict_bytes failed, Please check the input data.\nErr: {}",
+                e
+            )))
+                }
+            };
+
+        // 构建期间释放GIL，其他python线程可继续使用旧的Matcher
+        Ok(py.allow_threads(|| MatcherRs::from_owned_dict(&owned_match_table_dict)))
+    }
+}
+
+#[pymethods]
+impl ReloadableMatcher {
+    #[new]
+    fn new(py: Python, match_table_dict_bytes: &PyBytes) -> PyResult<ReloadableMatcher> {
+        Ok(ReloadableMatcher {
+            matcher_handle: MatcherHandleRs::new(Self::build_matcher(py, match_table_dict_bytes)?),
+        })
+    }
+
+    fn reload(&self, py: Python, match_table_dict_bytes: &PyBytes) -> PyResult<()> {
+        self.matcher_handle
+            .swap(Self::build_matcher(py, match_table_dict_bytes)?);
+
+        Ok(())
+    }
+
+    fn is_match(&self, _py: Python, text: &PyAny) -> bool {
+        text.downcast::<PyString>().is_ok_and(|text| {
+            self.matcher_handle
+                .load()
+                .is_match(unsafe { text.to_str().unwrap_unchecked() })
+        })
+    }
+
+    fn word_match(&self, _py: Python, text: &PyAny) -> HashMap<String, String> {
+        text.downcast::<PyString>().map_or(HashMap::new(), |text| {
+            self.matcher_handle
+                .load()
+                .word_match(unsafe { text.to_str().unwrap_unchecked() })
+                .into_iter()
+                .map(|(match_id, result)| (match_id.to_owned(), result))
+                .collect()
+        })
+    }
+
+    fn word_match_as_string(&self, py: Python, text: &PyAny) -> Py<PyString> {
+        text.downcast::<PyString>()
+            .map_or(PyString::intern(py, "{}"), |text| {
+                PyString::intern(
                     py,
-                    unsafe { text_array.as_array() }
-                        .map(|text| self.word_match_as_string(py, text.as_ref(py)).into_py(py)),
+                    &self
+                        .matcher_handle
+                        .load()
+                        .word_match_as_string(unsafe { text.to_str().unwrap_unchecked() }),
                 )
-                .into(),
-            )
+            })
+            .into()
+    }
+}
+
+// 解析python侧的SimpleWordlistDict，key为SimpleMatchType(IntFlag)、int或可读名称，
+// value为{word_id: word | SimpleWord}或[SimpleWord]
+fn extract_simple_wordlist_dict(
+    py: Python,
+    simple_wordlist_dict: &PyDict,
+) -> PyResult<Vec<(SimpleMatchTypeRs, Vec<(u64, String)>)>> {
+    let mut owned_wordlist_dict = Vec::with_capacity(simple_wordlist_dict.len());
+
+    for (key, value) in simple_wordlist_dict.iter() {
+        let simple_match_type = match key.downcast::<PyString>() {
+            Ok(name) => {
+                SimpleMatchTypeRs::from_bits_retain(parse_simple_match_type(name.to_str()?)?)
+            }
+            Err(_) => SimpleMatchTypeRs::from_bits_retain(key.extract::<u8>().map_err(|_| {
+                PyTypeError::new_err(format!(
+                    "simple_match_type key must be SimpleMatchType, int or str, got {}",
+                    key
+                ))
+            })?),
+        };
+
+        let mut word_list = Vec::new();
+        if let Ok(word_dict) = value.downcast::<PyDict>() {
+            for (word_id, word) in word_dict.iter() {
+                let word = match word.downcast::<PyString>() {
+                    Ok(word) => word.to_str()?.to_owned(),
+                    Err(_) => word.getattr(intern!(py, "word"))?.extract::<String>()?,
+                };
+                word_list.push((word_id.extract::<u64>()?, word));
+            }
+        } else if let Ok(simple_word_list) = value.downcast::<PyList>() {
+            for simple_word in simple_word_list.iter() {
+                word_list.push((
+                    simple_word
+                        .getattr(intern!(py, "word_id"))?
+                        .extract::<u64>()?,
+                    simple_word
+                        .getattr(intern!(py, "word"))?
+                        .extract::<String>()?,
+                ));
+            }
+        } else {
+            return Err(PyTypeError::new_err(format!(
+                "wordlist of {} must be dict[int, str | SimpleWord] or list[SimpleWord]",
+                key
+            )));
         }
+
+        owned_wordlist_dict.push((simple_match_type, word_list));
     }
+
+    Ok(owned_wordlist_dict)
 }
 
 #[pyclass(module = "matcher_py")]
@@ -174,21 +622,72 @@ struct SimpleMatcher {
     simple_wordlist_dict_bytes: Py<PyBytes>,
 }
 
+impl SimpleMatcher {
+    // 词ID重复等构建错误时抛出ValueError，而不是panic
+    fn build_simple_matcher(
+        simple_wordlist_dict: &SimpleWordlistDictRs,
+    ) -> PyResult<SimpleMatcherRs> {
+        SimpleMatcherRs::try_new(simple_wordlist_dict).map_err(|e| {
+            PyValueError::new_err(format!("Build simple_matcher failed.\nErr: {e}"))
+        })
+    }
+}
+
 #[pymethods]
 impl SimpleMatcher {
+    // 同时接受msgpack bytes与dict[SimpleMatchType | int | str, dict[int, str | SimpleWord] | list[SimpleWord]]
     #[new]
-    fn new(simple_wordlist_dict_bytes: &PyBytes) -> PyResult<SimpleMatcher> {
-        let simple_wordlist_dict: SimpleWordlistDictRs =
-            match rmp_serde::from_slice(simple_wordlist_dict_bytes.as_bytes()) {
-                Ok(simple_wordlist_dict) => simple_wordlist_dict,
-                Err(e) => return Err(PyValueError::new_err(
-                    format!("Deserialize simple_wordlist_dict_bytes failed, Please check the input data.\n Err: {}", e.to_string()),
-                )),
-            };
+    fn new(py: Python, simple_wordlist_dict: &PyAny) -> PyResult<SimpleMatcher> {
+        if let Ok(simple_wordlist_dict_bytes) = simple_wordlist_dict.downcast::<PyBytes>() {
+            let simple_wordlist_dict: SimpleWordlistDictRs =
+                match rmp_serde::from_slice(simple_wordlist_dict_bytes.as_bytes()) {
+                    Ok(simple_wordlist_dict) => simple_wordlist_dict,
+                    Err(e) => return Err(PyValueError::new_err(
+                        format!("Deserialize simple_wordlist_dict_bytes failed, Please check the input data.\n Err: {}", e.to_string()),
+                    )),
+                };
+
+            return Ok(SimpleMatcher {
+                simple_matcher: Self::build_simple_matcher(&simple_wordlist_dict)?,
+                simple_wordlist_dict_bytes: simple_wordlist_dict_bytes.into(),
+            });
+        }
+
+        let Ok(simple_wordlist_dict) = simple_wordlist_dict.downcast::<PyDict>() else {
+            return Err(PyTypeError::new_err(
+                "simple_wordlist_dict must be msgpack bytes or a dict",
+            ));
+        };
+        let owned_wordlist_dict = extract_simple_wordlist_dict(py, simple_wordlist_dict)?;
+        let simple_wordlist_dict: SimpleWordlistDictRs = owned_wordlist_dict
+            .iter()
+            .map(|(simple_match_type, word_list)| {
+                (
+                    *simple_match_type,
+                    word_list
+                        .iter()
+                        .map(|(word_id, word)| SimpleWordRs {
+                            word_id: *word_id,
+                            word,
+                        })
+                        .collect(),
+                )
+            })
+            .collect();
+        // pickle时仍以msgpack bytes作为构造参数
+        let simple_wordlist_dict_bytes = match rmp_serde::to_vec_named(&simple_wordlist_dict) {
+            Ok(simple_wordlist_dict_bytes) => simple_wordlist_dict_bytes,
+            Err(e) => {
+                return Err(PyValueError::new_err(format!(
+                    "Serialize simple_wordlist_dict failed.\n Err: {}",
+                    e
+                )))
+            }
+        };
 
         Ok(SimpleMatcher {
-            simple_matcher: SimpleMatcherRs::new(&simple_wordlist_dict),
-            simple_wordlist_dict_bytes: simple_wordlist_dict_bytes.into(),
+            simple_matcher: Self::build_simple_matcher(&simple_wordlist_dict)?,
+            simple_wordlist_dict_bytes: PyBytes::new(py, &simple_wordlist_dict_bytes).into(),
         })
     }
 
@@ -261,9 +760,112 @@ impl SimpleMatcher {
     }
 }
 
+// 只使用match_table_dict中的similar_text_levenshtein词表，用于规则编写时查询最相似的词
+#[pyclass(module = "matcher_py")]
+struct SimMatcher {
+    sim_matcher: SimMatcherRs,
+    match_table_dict_bytes: Py<PyBytes>,
+}
+
+impl SimMatcher {
+    fn build_sim_matcher(match_table_dict_bytes: &PyBytes) -> PyResult<SimMatcherRs> {
+        let owned_match_table_dict: OwnedMatchTableDictRs =
+            match rmp_serde::from_slice(match_table_dict_bytes.as_bytes()) {
+                Ok(owned_match_table_dict) => owned_match_table_dict,
+                Err(e) => {
+                    return Err(PyValueError::new_err(format!(
+                "Deserialize match_table_dict_bytes failed, Please check the input data.\nErr: {}",
+                e
+            )))
+                }
+            };
+        let match_table_dict = MatchTableDictRs::from(&owned_match_table_dict);
+
+        let sim_table_list: Vec<SimTableRs> = match_table_dict
+            .iter()
+            .flat_map(|(&match_id, table_list)| {
+                table_list
+                    .iter()
+                    .filter(|table| {
+                        matches!(
+                            table.match_table_type,
+                            MatchTableTypeRs::SimilarTextLevenshtein
+                        )
+                    })
+                    .map(move |table| SimTableRs {
+                        table_id: table.table_id,
+                        match_id,
+                        wordlist: &table.wordlist,
+                        threshold: DEFAULT_SIM_THRESHOLD,
+                        simple_match_type: table.simple_match_type,
+                        process_words: true,
+                        sim_match_type: table.sim_match_type,
+                        top_k: None,
+                    })
+            })
+            .collect();
+
+        Ok(SimMatcherRs::new(&sim_table_list))
+    }
+}
+
+#[pymethods]
+impl SimMatcher {
+    #[new]
+    fn new(match_table_dict_bytes: &PyBytes) -> PyResult<SimMatcher> {
+        Ok(SimMatcher {
+            sim_matcher: Self::build_sim_matcher(match_table_dict_bytes)?,
+            match_table_dict_bytes: match_table_dict_bytes.into(),
+        })
+    }
+
+    fn __getnewargs__(&self, py: Python) -> (Py<PyBytes>,) {
+        (self.match_table_dict_bytes.clone_ref(py),)
+    }
+
+    fn __getstate__(&self, py: Python) -> Py<PyBytes> {
+        self.match_table_dict_bytes.clone_ref(py)
+    }
+
+    fn __setstate__(&mut self, match_table_dict_bytes: &PyBytes) -> PyResult<()> {
+        self.sim_matcher = Self::build_sim_matcher(match_table_dict_bytes)?;
+        self.match_table_dict_bytes = match_table_dict_bytes.into();
+
+        Ok(())
+    }
+
+    fn nearest(&self, _py: Python, text: &PyAny, k: usize) -> Vec<SimResult> {
+        text.downcast::<PyString>().map_or(Vec::new(), |text| {
+            self.sim_matcher
+                .nearest(unsafe { text.to_str().unwrap_unchecked() }, k)
+                .into_iter()
+                .map(SimResult)
+                .collect()
+        })
+    }
+}
+
+// 将可读名称解析为SimpleMatchType的bits，eg. "fanjian_delete_normalize" -> 15
+#[pyfunction]
+fn parse_simple_match_type(name: &str) -> PyResult<u8> {
+    name.parse::<SimpleMatchTypeRs>()
+        .map(|simple_match_type| simple_match_type.bits())
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+// 命中结果的稳定指纹，与matcher_rs的match_fingerprint一致，用于对word_match结果去重
+#[pyfunction]
+fn fingerprint(match_id: &str, table_id: u32, word: &str) -> u64 {
+    match_fingerprint(match_id, table_id, word)
+}
+
 #[pymodule]
 fn matcher_py(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(parse_simple_match_type, m)?)?;
+    m.add_function(wrap_pyfunction!(fingerprint, m)?)?;
     m.add_class::<Matcher>()?;
+    m.add_class::<ReloadableMatcher>()?;
     m.add_class::<SimpleMatcher>()?;
+    m.add_class::<SimMatcher>()?;
     Ok(())
 }
@@ -0,0 +1,119 @@
+import json
+import signal
+import time
+import unittest
+
+import msgspec
+
+from matcher_py import Matcher
+
+msgpack_encoder = msgspec.msgpack.Encoder()
+
+# 相似词表逐词计算编辑距离，词数足够多时单条文本的匹配耗时明显，用于触发超时以及中断批量调用
+# 单个词表的词表总长度受限，拆分为多个词表
+SIM_TABLE_COUNT = 16
+SIM_WORD_COUNT_PER_TABLE = 1000
+
+
+def build_sim_matcher() -> Matcher:
+    return Matcher(
+        msgpack_encoder.encode(
+            {
+                "sim": [
+                    {
+                        "table_id": table_id,
+                        "match_table_type": "similar_text_levenshtein",
+                        "wordlist": [
+                            f"word{table_id:02}{index:03} lorem ipsum"
+                            for index in range(SIM_WORD_COUNT_PER_TABLE)
+                        ],
+                        "exemption_wordlist": [],
+                        "simple_match_type": 0,
+                    }
+                    for table_id in range(SIM_TABLE_COUNT)
+                ]
+            }
+        )
+    )
+
+
+class TestTimeout(unittest.TestCase):
+    @classmethod
+    def setUpClass(cls):
+        cls.matcher = build_sim_matcher()
+
+    def test_timeout_raises(self):
+        with self.assertRaises(TimeoutError):
+            self.matcher.word_match("word01042 lorem ipsum", timeout_ms=0)
+
+    def test_partial_on_timeout(self):
+        result_dict, deadline_exceeded = self.matcher.word_match(
+            "word01042 lorem ipsum", timeout_ms=0, partial_on_timeout=True
+        )
+        self.assertIsInstance(result_dict, dict)
+        self.assertTrue(deadline_exceeded)
+
+    def test_no_timeout(self):
+        result_dict, deadline_exceeded = self.matcher.word_match(
+            "word01042 lorem ipsum", timeout_ms=60_000, partial_on_timeout=True
+        )
+        self.assertFalse(deadline_exceeded)
+        self.assertIn("sim", result_dict)
+        self.assertEqual(
+            result_dict,
+            self.matcher.word_match("word01042 lorem ipsum", timeout_ms=60_000),
+        )
+
+
+class TestWordMatchFull(unittest.TestCase):
+    @classmethod
+    def setUpClass(cls):
+        cls.matcher = Matcher(
+            msgpack_encoder.encode(
+                {
+                    "test": [
+                        {
+                            "table_id": 1,
+                            "match_table_type": "simple",
+                            "wordlist": ["hello"],
+                            "exemption_wordlist": [],
+                            "simple_match_type": 1,
+                        }
+                    ]
+                }
+            )
+        )
+
+    def test_variants_truncated(self):
+        # 非字符串输入返回的空结果与正常结果的字段保持一致
+        for text in ["hello world", None]:
+            result = json.loads(self.matcher.word_match_full_as_string(text))
+            self.assertEqual(
+                set(result), {"matched", "exempted", "variants_truncated"}
+            )
+            self.assertFalse(result["variants_truncated"])
+
+
+class TestBatchInterrupt(unittest.TestCase):
+    @classmethod
+    def setUpClass(cls):
+        cls.matcher = build_sim_matcher()
+
+    def test_batch_keyboard_interrupt(self):
+        # 批量接口每条文本匹配前检查信号，整批跑完需要很久，中断后应立即抛出KeyboardInterrupt
+        # 批量调用期间不释放GIL，其他线程无法执行_thread.interrupt_main，这里用定时器信号模拟Ctrl-C
+        text_list = ["word01042 lorem ipsum"] * 100_000
+        previous_handler = signal.signal(signal.SIGALRM, signal.default_int_handler)
+        signal.setitimer(signal.ITIMER_REAL, 0.2)
+        start = time.monotonic()
+        try:
+            with self.assertRaises(KeyboardInterrupt):
+                self.matcher.batch_word_match_as_dict(text_list)
+        finally:
+            signal.setitimer(signal.ITIMER_REAL, 0)
+            signal.signal(signal.SIGALRM, previous_handler)
+        self.assertLess(time.monotonic() - start, 30)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -11,8 +11,8 @@ fn bench(c: &mut Criterion) {
             table_id: 1,
             match_table_type: MatchTableType::Simple,
             wordlist: VarZeroVec::from(&["你好,123"]),
-            exemption_wordlist: VarZeroVec::new(),
             simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+            ..Default::default()
         }],
     )]);
     let matcher = Matcher::new(&match_table_dict);
@@ -36,6 +36,21 @@ fn bench(c: &mut Criterion) {
         b.iter(|| matcher.word_match(black_box("")))
     });
 
+    let exemption_matcher = Matcher::new(&AHashMap::from([(
+        "test",
+        vec![MatchTable {
+            table_id: 1,
+            match_table_type: MatchTableType::Simple,
+            wordlist: VarZeroVec::from(&["你好,123"]),
+            exemption_wordlist: VarZeroVec::from(&["你好,123456"]),
+            ..Default::default()
+        }],
+    )]));
+    // 豁免词使用更重的匹配类型，未命中的文本不会为豁免词生成变体
+    c.bench_function("word_match_exemption_negative_text", |b| {
+        b.iter(|| exemption_matcher.word_match(black_box("gasbhkjdbsauhjkv不就代表沙发就卡死，倍去我空间恶化就啊不对劲啊是贵宾卡我了，没了叫你起床加巴西办公室就看到，nhrqjmwjhxb 吃了好几遍五块钱2，恶魔发微博")))
+    });
+
     let simple_wordlist_dict = AHashMap::from([(
         SimpleMatchType::FanjianDeleteNormalize,
         vec![SimpleWord {
@@ -63,6 +78,322 @@ fn bench(c: &mut Criterion) {
     c.bench_function("simple_process_empty_text", |b| {
         b.iter(|| simple_matcher.process(black_box("")))
     });
+
+    let simple_matcher_final_only = SimpleMatcher::new_with_options(
+        &simple_wordlist_dict,
+        SimpleMatcherOptions {
+            variant_strategy: VariantStrategy::FinalOnly,
+            ..Default::default()
+        },
+    );
+
+    c.bench_function("simple_process_final_only_super_long_text", |b| {
+        b.iter(|| simple_matcher_final_only.process(black_box("dsahbdj12pu980-120opo[sad[d]pas;l[;'.,zmc;as'k[aepe所有的沙发博客看后289UI哈哈不可得兼萨马拉州，女把wejlhjp0iidasbwdjksabfadghjaklsekjniwh123powhudbasbasmdsal,d.as,dlasfjsaifjbo39p9eu12p0poaspopofjsapdaksdpsa【】萨达省；c'xzlk.asd，萨。，但马上，队列即可领取王杰饿哦啥屁；但那是没法解开了吗你只需龙祥怎么了华北地区房东啥尽快帮我去IE请问i两节课大赛不好发不出吗你只需把vaf打死就不会发生的旅程啊，sd阿斯顿啥都怕是个大傻大叔的吧到那时  dsabjx· ds····           巴士到家啦vxzmdm")))
+    });
+    c.bench_function("simple_process_final_only_long_text", |b| {
+        b.iter(|| simple_matcher_final_only.process(black_box("gasbhkjdbsauhjkv不就代表沙发就卡死，倍去我空间恶化就啊不对劲啊是贵宾卡我了，没了叫你起床加巴西办公室就看到，nhrqjmwjhxb 吃了好几遍五块钱2，恶魔发微博")))
+    });
+    c.bench_function("simple_process_final_only_hit_text", |b| {
+        b.iter(|| simple_matcher_final_only.process(black_box("1dsa你好,12312das")))
+    });
+
+    let simple_matcher_prefilter = SimpleMatcher::new_with_options(
+        &simple_wordlist_dict,
+        SimpleMatcherOptions {
+            prefilter: true,
+            ..Default::default()
+        },
+    );
+    // 不含任何触发字符的文本，对比开启前置过滤前后的未命中路径
+    let negative_text = "繁體中文測試數據體驗優質網絡環境請勿傳播違規內容謝謝".repeat(100);
+
+    c.bench_function("simple_process_negative_text", |b| {
+        b.iter(|| simple_matcher.process(black_box(&negative_text)))
+    });
+    c.bench_function("simple_process_prefilter_negative_text", |b| {
+        b.iter(|| simple_matcher_prefilter.process(black_box(&negative_text)))
+    });
+
+    let pinyin_simple_matcher = SimpleMatcher::new(&AHashMap::from([(
+        SimpleMatchType::PinYin,
+        vec![SimpleWord {
+            word_id: 1,
+            word: "你好",
+        }],
+    )]));
+    // 约1MB的繁体中文文本，几乎每个字符都会被拼音转换替换
+    let traditional_text = "繁體中文測試數據體驗優質網絡環境請勿傳播違規內容謝謝".repeat(13000);
+
+    c.bench_function("simple_process_pinyin_traditional_text", |b| {
+        b.iter(|| pinyin_simple_matcher.process(black_box(&traditional_text)))
+    });
+
+    #[cfg(feature = "rayon")]
+    {
+        let super_long_text = "dsahbdj12pu980-120opo[sad[d]pas;l[;'.,zmc;as'k[aepe所有的沙发博客看后289UI哈哈不可得兼萨马拉州，女把wejlhjp0iidasbwdjksabfadghjaklsekjniwh123powhudbasbasmdsal,d.as,dlasfjsaifjbo39p9eu12p0poaspopofjsapdaksdpsa【】萨达省；c'xzlk.asd，萨。，但马上，队列即可领取王杰饿哦啥屁；但那是没法解开了吗你只需龙祥怎么了华北地区房东啥尽快帮我去IE请问i两节课大赛不好发不出吗你只需把vaf打死就不会发生的旅程啊，sd阿斯顿啥都怕是个大傻大叔的吧到那时  dsabjx· ds····           巴士到家啦vxzmdm".repeat(200);
+
+        c.bench_function("simple_process_huge_text", |b| {
+            b.iter(|| simple_matcher.process(black_box(&super_long_text)))
+        });
+        c.bench_function("simple_par_process_huge_text", |b| {
+            b.iter(|| simple_matcher.par_process(black_box(&super_long_text)))
+        });
+    }
+}
+
+// 固定种子的xorshift64，保证每次运行生成的文本一致，结果可跨运行对比
+fn haystack(seed: u64, char_pool: &str, char_count: usize) -> String {
+    let char_list: Vec<char> = char_pool.chars().collect();
+    let mut state = seed;
+    (0..char_count)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            char_list[(state % char_list.len() as u64) as usize]
+        })
+        .collect()
+}
+
+const CN_CHAR_POOL: &str = "你好世界我们是中国人繁體測試數據網絡環境請勿傳播違規內容謝謝学生无法天西安八一，。！？【】 ①②③１２３ＡＢＣ";
+const EN_CHAR_POOL: &str =
+    "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789 ,.!?'/\\-_ⓐⓑⓒ";
+const HAYSTACK_SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+
+// 文本处理流水线：单个转换类型以及组合转换树的变体生成，不含ac匹配
+fn bench_process(c: &mut Criterion) {
+    let haystack_list = [
+        ("cn", haystack(HAYSTACK_SEED, CN_CHAR_POOL, 1000)),
+        ("en", haystack(HAYSTACK_SEED, EN_CHAR_POOL, 1000)),
+    ];
+
+    let mut group = c.benchmark_group("process_type");
+    for (name, simple_match_type) in [
+        ("fanjian", SimpleMatchType::Fanjian),
+        ("text_delete", SimpleMatchType::TextDelete),
+        ("normalize", SimpleMatchType::Normalize),
+        ("pinyin", SimpleMatchType::PinYin),
+        ("pinyin_char", SimpleMatchType::PinYinChar),
+        (
+            "fanjian_delete_normalize",
+            SimpleMatchType::FanjianDeleteNormalize,
+        ),
+    ] {
+        let simple_matcher = SimpleMatcher::new(&AHashMap::from([(
+            simple_match_type,
+            vec![SimpleWord {
+                word_id: 1,
+                word: "你好",
+            }],
+        )]));
+        for (haystack_name, haystack) in &haystack_list {
+            group.bench_function(format!("{name}/{haystack_name}"), |b| {
+                b.iter(|| simple_matcher.preprocess(black_box(haystack)))
+            });
+        }
+    }
+    group.finish();
+}
+
+// Matcher的结果汇总路径：simple + regex + sim混合词表，以及每条文本都需要匹配豁免词的情况
+fn bench_matcher_aggregation(c: &mut Criterion) {
+    let cn_haystack = haystack(HAYSTACK_SEED, CN_CHAR_POOL, 1000);
+
+    let mixed_matcher = Matcher::new(&AHashMap::from([
+        (
+            "simple",
+            vec![MatchTable {
+                table_id: 1,
+                match_table_type: MatchTableType::Simple,
+                wordlist: VarZeroVec::from(&["你好", "世界", "学生", "无,法,无,天", "abc"]),
+                simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                ..Default::default()
+            }],
+        ),
+        (
+            "regex",
+            vec![
+                MatchTable {
+                    table_id: 2,
+                    match_table_type: MatchTableType::Regex,
+                    wordlist: VarZeroVec::from(&[r"[0-9]{3,}", r"中.人"]),
+                    ..Default::default()
+                },
+                MatchTable {
+                    table_id: 3,
+                    match_table_type: MatchTableType::SimilarChar,
+                    wordlist: VarZeroVec::from(&["你,妳", "好,号"]),
+                    ..Default::default()
+                },
+            ],
+        ),
+        (
+            "sim",
+            vec![MatchTable {
+                table_id: 4,
+                match_table_type: MatchTableType::SimilarTextLevenshtein,
+                wordlist: VarZeroVec::from(&["你好世界", "我们是中国人"]),
+                ..Default::default()
+            }],
+        ),
+    ]));
+
+    let exemption_wordlist: Vec<String> = (0..1000)
+        .map(|index| haystack(HAYSTACK_SEED + index, CN_CHAR_POOL, 4))
+        .collect();
+    let exemption_matcher = Matcher::new(&AHashMap::from([(
+        "exemption",
+        vec![MatchTable {
+            table_id: 1,
+            match_table_type: MatchTableType::Simple,
+            wordlist: VarZeroVec::from(&["你", "好"]),
+            exemption_wordlist: VarZeroVec::from(&exemption_wordlist),
+            simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+            ..Default::default()
+        }],
+    )]));
+
+    let mut group = c.benchmark_group("matcher_aggregation");
+    group.bench_function("word_match_mixed", |b| {
+        b.iter(|| mixed_matcher.word_match(black_box(&cn_haystack)))
+    });
+    group.bench_function("word_match_exemption_heavy", |b| {
+        b.iter(|| exemption_matcher.word_match(black_box(&cn_haystack)))
+    });
+    group.finish();
+}
+
+// 首个命中出现在长文本开头时，is_match完成命中即返回，process需要扫描完整个文本
+fn bench_is_match_early_hit(c: &mut Criterion) {
+    let simple_matcher = SimpleMatcher::new(&AHashMap::from([(
+        SimpleMatchType::FanjianDeleteNormalize,
+        vec![
+            SimpleWord {
+                word_id: 1,
+                word: "你好",
+            },
+            SimpleWord {
+                word_id: 2,
+                word: "无,法,无,天",
+            },
+        ],
+    )]));
+    let early_hit_haystack = "你好".to_owned() + &haystack(HAYSTACK_SEED, EN_CHAR_POOL, 100_000);
+
+    let mut group = c.benchmark_group("is_match_early_hit");
+    group.bench_function("is_match", |b| {
+        b.iter(|| simple_matcher.is_match(black_box(&early_hit_haystack)))
+    });
+    group.bench_function("process", |b| {
+        b.iter(|| {
+            !simple_matcher
+                .process(black_box(&early_hit_haystack))
+                .is_empty()
+        })
+    });
+    group.finish();
+}
+
+// 大量短文本的批量匹配：逐条处理与process_batch对比，开启rayon且线程池不止一个线程时process_batch并行处理
+#[cfg(feature = "rayon")]
+fn bench_process_batch(c: &mut Criterion) {
+    let matcher = Matcher::new(&AHashMap::from([(
+        "simple",
+        vec![MatchTable {
+            table_id: 1,
+            match_table_type: MatchTableType::Simple,
+            wordlist: VarZeroVec::from(&["你好", "世界", "学生", "无,法,无,天", "abc"]),
+            simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+            ..Default::default()
+        }],
+    )]));
+    let cn_haystack_list: Vec<String> = (0..10_000)
+        .map(|index| haystack(HAYSTACK_SEED + index, CN_CHAR_POOL, 100))
+        .collect();
+    let cn_haystack_list: Vec<&str> = cn_haystack_list.iter().map(String::as_str).collect();
+
+    let mut group = c.benchmark_group("process_batch");
+    group.sample_size(20);
+    group.bench_function("loop", |b| {
+        b.iter(|| {
+            black_box(&cn_haystack_list)
+                .iter()
+                .map(|&text| matcher.process(text))
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("process_batch", |b| {
+        b.iter(|| matcher.process_batch(black_box(&cn_haystack_list)))
+    });
+    group.finish();
+}
+
+// 1k词的相似词表：编辑距离与字符n-gram余弦相似度对比，n-gram模式下词向量在构建时计算
+fn bench_sim_match_type(c: &mut Criterion) {
+    let wordlist: Vec<String> = (0..1000)
+        .map(|word_index| haystack(HAYSTACK_SEED + word_index, CN_CHAR_POOL, 8))
+        .collect();
+    let wordlist: VarZeroVec<str> = VarZeroVec::from(&wordlist);
+    let cn_haystack = haystack(HAYSTACK_SEED, CN_CHAR_POOL, 50);
+
+    let mut group = c.benchmark_group("sim_match_type");
+    for (name, sim_match_type) in [
+        ("levenshtein", SimMatchType::Levenshtein),
+        ("ngram_cosine_3", SimMatchType::NgramCosine { n: 3 }),
+    ] {
+        let sim_matcher = SimMatcher::new(&vec![SimTable {
+            table_id: 1,
+            match_id: "1",
+            wordlist: &wordlist,
+            threshold: DEFAULT_SIM_THRESHOLD,
+            simple_match_type: SimpleMatchType::None,
+            process_words: true,
+            sim_match_type,
+            top_k: None,
+        }]);
+        group.bench_function(name, |b| {
+            b.iter(|| sim_matcher.process(black_box(&cn_haystack)))
+        });
+    }
+    group.finish();
+}
+
+// 大词表rule bundle的读取，开启rayon时各词表分段并行反序列化
+fn bench_rule_bundle(c: &mut Criterion) {
+    let table_map = OwnedMatchTableDict(
+        (0..1000)
+            .map(|match_index| {
+                (
+                    format!("match_{match_index}"),
+                    vec![OwnedMatchTable {
+                        table_id: match_index,
+                        match_table_type: MatchTableType::Simple,
+                        wordlist: (0..500)
+                            .map(|word_index| {
+                                haystack(
+                                    HAYSTACK_SEED + (match_index * 500 + word_index) as u64,
+                                    CN_CHAR_POOL,
+                                    4,
+                                )
+                            })
+                            .collect(),
+                        simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                        ..Default::default()
+                    }],
+                )
+            })
+            .collect(),
+    );
+    let bundle_bytes = RuleBundle::new(table_map, MatcherOptions::default())
+        .to_bytes(BundleCompression::None)
+        .unwrap();
+
+    let mut group = c.benchmark_group("rule_bundle");
+    group.sample_size(20);
+    group.bench_function("from_bytes_500k_words", |b| {
+        b.iter(|| RuleBundle::from_bytes(black_box(&bundle_bytes)).unwrap())
+    });
+    group.finish();
 }
 
 criterion_group! {
@@ -70,4 +401,19 @@ criterion_group! {
     config = Criterion::default().significance_level(0.05).sample_size(1000);
     targets = bench
 }
-criterion_main!(benches);
+// 流水线以及汇总路径的单次耗时较长，使用较小的样本数
+criterion_group! {
+    name = pipeline_benches;
+    config = Criterion::default().significance_level(0.05).sample_size(100);
+    targets = bench_process, bench_matcher_aggregation, bench_is_match_early_hit, bench_sim_match_type, bench_rule_bundle
+}
+#[cfg(feature = "rayon")]
+criterion_group! {
+    name = batch_benches;
+    config = Criterion::default().significance_level(0.05);
+    targets = bench_process_batch
+}
+#[cfg(feature = "rayon")]
+criterion_main!(benches, pipeline_benches, batch_benches);
+#[cfg(not(feature = "rayon"))]
+criterion_main!(benches, pipeline_benches);
@@ -3,16 +3,84 @@
 #[global_allocator]
 static GLOBAL: mimalloc_rust::GlobalMiMalloc = mimalloc_rust::GlobalMiMalloc;
 
+pub trait TextMatcherTrait<'a, T> {
+    fn is_match(&self, text: &str) -> bool; // 是否命中
+    fn process(&'a self, text: &str) -> Vec<T>; // 处理文本，解析得到命中词
+    fn is_match_processed(&self, processed_text: &ProcessedText) -> bool {
+        // 同is_match，复用已生成的文本变体，不依赖文本变体的匹配器直接匹配原文本
+        self.is_match(processed_text.text())
+    }
+    fn process_processed(&'a self, processed_text: &ProcessedText) -> Vec<T> {
+        // 同process，复用已生成的文本变体
+        self.process(processed_text.text())
+    }
+    fn process_iter(&'a self, text: &'a str) -> Box<dyn Iterator<Item = T> + 'a>
+    where
+        T: 'a,
+    {
+        // 同process，逐个返回命中结果，支持惰性匹配的匹配器在调用方提前停止时省去剩余的匹配
+        // 默认实现以及SimpleMatcher的detailed模式下先完整process再逐个返回，并不惰性
+        Box::new(self.process(text).into_iter())
+    }
+    fn find_first(&'a self, text: &str) -> Option<T> {
+        // 只需要任一命中结果时使用，各匹配器得到第一个结果后即停止匹配；SimpleMatcher的detailed模式下仍完整process
+        self.process(text).into_iter().next()
+    }
+    fn batch_process(&'a self, text_array: &[&str]) -> Vec<Vec<T>> {
+        // 批量处理文本
+        text_array.iter().map(|&text| self.process(text)).collect()
+    }
+}
+
+// 关闭serde时只保留SimpleMatcher及文本转换，词表类型以及依赖词表类型的匹配器均需要serde
+#[cfg(feature = "serde")]
 mod matcher;
-pub use matcher::{MatchTable, MatchTableDict, MatchTableType, Matcher, TextMatcherTrait};
+#[cfg(feature = "serde")]
+pub use matcher::{
+    match_fingerprint, parse_match_table_dict, BuildMode, BuildReport, DedupPolicy,
+    EmptyWordlistPolicy, ExemptedResult, ExemptionHit, FullMatchResult, MatchResult, MatchTable,
+    MatchTableDict, MatchTableType, Matcher, MatcherError, MatcherOptions, MatcherStats,
+    MatcherWarning, OverLimitPolicy, OwnedMatchTable, OwnedMatchTableDict, Segment, SkippedTable,
+    TableIdSet, TableInfo, TableType,
+};
+
+#[cfg(feature = "serde")]
+mod matcher_builder;
+#[cfg(feature = "serde")]
+pub use matcher_builder::{MatchIdBuilder, MatcherBuilder};
 
 mod simple_matcher;
 pub use simple_matcher::{
-    SimpleMatchType, SimpleMatcher, SimpleResult, SimpleWord, SimpleWordlistDict,
+    escape_simple_word, CaseMode, DetailedResult, MatchSpan, NegatedResult,
+    ParseSimpleMatchTypeError, PartDetail, PartHit, ProcessTypeTree, ProcessedText,
+    SimpleFullResult, SimpleMatchType, SimpleMatcher, SimpleMatcherError, SimpleMatcherOptions,
+    SimpleProcessIter, SimpleResult, SimpleWord, SimpleWordlistDict, VariantStrategy,
+    MAX_AC_PATTERN_COUNT,
 };
 
+#[cfg(feature = "serde")]
 mod regex_matcher;
-pub use regex_matcher::{RegexMatcher, RegexResult, RegexTable};
+#[cfg(feature = "serde")]
+pub use regex_matcher::{
+    AcrosticPosition, AcrosticSeparator, PatternWarning, RegexBuildError, RegexCompileError,
+    RegexFlags, RegexMatcher, RegexMatcherOptions, RegexMatcherStats, RegexResult, RegexTable,
+    RegexTableStats, RegexWarningCallback,
+};
 
+#[cfg(feature = "serde")]
 mod sim_matcher;
-pub use sim_matcher::{SimMatcher, SimResult, SimTable};
+#[cfg(feature = "serde")]
+pub use sim_matcher::{
+    SimBuildError, SimMatchType, SimMatcher, SimMatcherOptions, SimResult, SimSort, SimTable,
+    DEFAULT_SIM_THRESHOLD,
+};
+
+#[cfg(feature = "serde")]
+mod rule_bundle;
+#[cfg(feature = "serde")]
+pub use rule_bundle::{BundleCompression, RuleBundle, RuleBundleError, RULE_BUNDLE_FORMAT_VERSION};
+
+#[cfg(feature = "arc-swap")]
+mod matcher_handle;
+#[cfg(feature = "arc-swap")]
+pub use matcher_handle::MatcherHandle;
@@ -1,22 +1,151 @@
-#[global_allocator]
-static GLOBAL: mimalloc_rust::GlobalMiMalloc = mimalloc_rust::GlobalMiMalloc;
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
 
-use hyperscan::{pattern, BlockDatabase, Builder, Matching};
+use serde_json::json;
 
-fn main() {
-    let pattern = pattern! {"test"; CASELESS | SOM_LEFTMOST};
-    let db: BlockDatabase = pattern.build().unwrap();
-    let scratch = db.alloc_scratch().unwrap();
-    let mut matches = vec![];
+use matcher_rs::{
+    MatchTableDict, Matcher, MatcherOptions, OwnedMatchTableDict, SimpleMatcher,
+    SimpleWordlistDict, TextMatcherTrait,
+};
 
-    db.scan("some test data", &scratch, |id, from, to, _| {
-        println!("found pattern #{} @ [{}, {})", id, from, to);
+const USAGE: &str = "\
+Usage: matcher_rs [--simple-table] <TABLE_PATH>
 
-        matches.push(from..to);
+Build a matcher from the json file at TABLE_PATH, match every line read from stdin,
+print one json result per line to stdout, and the build / match timings to stderr.
 
-        Matching::Continue
-    })
-    .unwrap();
+Options:
+  --simple-table  TABLE_PATH is a SimpleWordlistDict, run SimpleMatcher only
+  -h, --help      Print this help";
 
-    assert_eq!(matches, vec![5..9]);
+struct Args {
+    table_path: String,
+    simple_table: bool, // 词表为SimpleWordlistDict，只运行SimpleMatcher
+}
+
+impl Args {
+    fn parse() -> Result<Args, String> {
+        let mut table_path = None;
+        let mut simple_table = false;
+
+        for arg in env::args().skip(1) {
+            match arg.as_str() {
+                "--simple-table" => simple_table = true,
+                "-h" | "--help" => return Err(String::new()),
+                _ if arg.starts_with('-') => return Err(format!("unknown option: {arg}")),
+                _ if table_path.is_none() => table_path = Some(arg),
+                _ => return Err(format!("unexpected argument: {arg}")),
+            }
+        }
+
+        Ok(Args {
+            table_path: table_path.ok_or("missing TABLE_PATH")?,
+            simple_table,
+        })
+    }
+}
+
+// 逐行匹配stdin中的文本，返回匹配的文本数以及总耗时，不含读写耗时
+fn match_lines<F>(mut match_fn: F) -> io::Result<(usize, Duration)>
+where
+    F: FnMut(&str) -> String,
+{
+    let mut writer = BufWriter::new(io::stdout().lock());
+    let mut text_count = 0;
+    let mut match_duration = Duration::ZERO;
+
+    for line in io::stdin().lock().lines() {
+        let text = line?;
+
+        let match_start = Instant::now();
+        let result = match_fn(&text);
+        match_duration += match_start.elapsed();
+        text_count += 1;
+
+        writeln!(writer, "{result}")?;
+    }
+    writer.flush()?;
+
+    Ok((text_count, match_duration))
+}
+
+fn run(args: &Args) -> Result<(), String> {
+    let table_json = fs::read_to_string(&args.table_path)
+        .map_err(|e| format!("failed to read {}: {e}", args.table_path))?;
+
+    let timing = if args.simple_table {
+        let simple_wordlist_dict: SimpleWordlistDict = serde_json::from_str(&table_json)
+            .map_err(|e| format!("failed to parse {}: {e}", args.table_path))?;
+
+        let build_start = Instant::now();
+        let simple_matcher =
+            SimpleMatcher::try_new(&simple_wordlist_dict).map_err(|e| e.to_string())?;
+        let build_duration = build_start.elapsed();
+
+        let (text_count, match_duration) =
+            match_lines(|text| serde_json::to_string(&simple_matcher.process(text)).unwrap())
+                .map_err(|e| e.to_string())?;
+
+        json!({
+            "build_duration": build_duration,
+            "text_count": text_count,
+            "match_duration": match_duration,
+        })
+    } else {
+        // 词表中的字符串可能含有json转义字符，反序列化为完全拥有的词表
+        let owned_match_table_dict: OwnedMatchTableDict = serde_json::from_str(&table_json)
+            .map_err(|e| format!("failed to parse {}: {e}", args.table_path))?;
+
+        let build_start = Instant::now();
+        let (matcher, warning_list, build_report) = Matcher::try_new_with_report(
+            &MatchTableDict::from(&owned_match_table_dict),
+            MatcherOptions::default(),
+        )
+        .map_err(|e| e.to_string())?;
+        let build_duration = build_start.elapsed();
+
+        for warning in &warning_list {
+            eprintln!("warning: {warning}");
+        }
+
+        let (text_count, match_duration) =
+            match_lines(|text| matcher.word_match_as_string(text)).map_err(|e| e.to_string())?;
+
+        json!({
+            "build_duration": build_duration,
+            "build_report": build_report,
+            "stats": matcher.stats(),
+            "text_count": text_count,
+            "match_duration": match_duration,
+        })
+    };
+
+    eprintln!("{timing}");
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = match Args::parse() {
+        Ok(args) => args,
+        Err(message) if message.is_empty() => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {message}");
+            ExitCode::FAILURE
+        }
+    }
 }
@@ -1,27 +1,39 @@
 use std::borrow::Cow;
+use std::cell::OnceCell;
 use std::collections::HashMap;
+use std::fmt;
 use std::intrinsics::{likely, unlikely};
-use std::rc::Rc;
+use std::io::{self, Write};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use ahash::AHashMap;
+use ahash::{AHashMap, AHashSet};
+use nohash_hasher::{IntMap, IntSet};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::to_string;
+use tinyvec::TinyVec;
+#[cfg(feature = "unicode-segmentation")]
+use unicode_segmentation::GraphemeCursor;
 use zerovec::VarZeroVec;
 
-use crate::regex_matcher::{RegexMatcher, RegexTable};
-use crate::sim_matcher::{SimMatcher, SimTable};
-use crate::simple_matcher::{SimpleMatchType, SimpleMatcher, SimpleWord};
+use super::TextMatcherTrait;
+use crate::regex_matcher::{
+    AcrosticPosition, AcrosticSeparator, RegexBuildError, RegexCompileError, RegexFlags,
+    RegexMatcher, RegexMatcherOptions, RegexMatcherStats, RegexResult, RegexTable,
+};
+use crate::rule_bundle::RuleBundle;
+use crate::sim_matcher::{
+    SimBuildError, SimMatchType, SimMatcher, SimMatcherOptions, SimResult, SimTable,
+    DEFAULT_SIM_THRESHOLD,
+};
+use crate::simple_matcher::{
+    is_blank, mask_span_list, MatchSpan, PartHit, ProcessedText, SimpleMatchType, SimpleMatcher,
+    SimpleMatcherError, SimpleMatcherOptions, SimpleResult, SimpleWord,
+};
 
-pub trait TextMatcherTrait<'a, T> {
-    fn is_match(&self, text: &str) -> bool; // 是否命中
-    fn process(&'a self, text: &str) -> Vec<T>; // 处理文本，解析得到命中词
-    fn batch_process(&'a self, text_array: &[&str]) -> Vec<Vec<T>> {
-        // 批量处理文本
-        text_array.iter().map(|&text| self.process(text)).collect()
-    }
-}
-
-#[derive(Serialize, Deserialize, Clone, Copy)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum MatchTableType {
     Simple,                 // simple 敏感词，其中 精准 / 繁简 / 归一 / 拼音 / 拼音字符
@@ -29,244 +41,3248 @@ pub enum MatchTableType {
     Acrostic,               // acrostic 藏头诗，regex_matcher实现
     SimilarTextLevenshtein, // similar_text_levenshtein 编辑距离，sim_matcher实现
     Regex,                  // regex 正则，regex_matcher实现
+    Wildcard, // wildcard 通配符，*匹配任意个字符，?匹配一个字符，其余字符按字面匹配，转换为正则后由regex_matcher实现
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct MatchTable<'a> {
     pub table_id: u32,                    // 词表ID
     pub match_table_type: MatchTableType, // 词表类型
     #[serde(borrow)]
     pub wordlist: VarZeroVec<'a, str>, // 词表
     #[serde(borrow)]
-    pub exemption_wordlist: VarZeroVec<'a, str>, // 豁免词表，默认 繁简+归一，simple_matcher实现
+    pub exemption_wordlist: VarZeroVec<'a, str>, // 豁免词表，默认 繁简+归一，simple_matcher实现；正则及通配符词表的豁免词与词表中的词同样按正则 / 通配符匹配
     pub simple_match_type: SimpleMatchType, // 匹配类型，6 bit 从左到右分别为 繁简 词删除 文本删除 替换归一 拼音 拼音字符
+    #[serde(default)]
+    pub acrostic_position: AcrosticPosition, // 藏头位置，默认句首，仅对acrostic词表生效
+    #[serde(default)]
+    pub prefer_matched_text: bool, // 命中词使用文本中实际命中的子串，而不是各组命中字符的拼接，仅对similar_char词表生效
+    #[serde(default)]
+    pub sim_match_type: SimMatchType, // 相似度算法，默认编辑距离，仅对similar_text_levenshtein词表生效
+    #[serde(default)]
+    pub regex_flags: RegexFlags, // 正则的编译标志，默认不开启任何标志，仅对regex及wildcard词表生效
+    #[serde(default)]
+    pub acrostic_separator: AcrosticSeparator, // 藏头字之间的分隔方式，默认空白或标点，仅对句首藏头的acrostic词表生效
+    #[serde(default)]
+    pub simple_match_type_override: AHashMap<Cow<'a, str>, SimpleMatchType>, // 单独指定匹配类型的词，eg. {"你好": "pinyin"}，其余词使用simple_match_type，不在词表中的词忽略，仅对simple词表生效
+}
+
+// 空的simple词表，不做任何转换，其余选项与反序列化时的默认值一致，用于 ..Default::default() 只写出关心的字段
+impl Default for MatchTable<'_> {
+    fn default() -> Self {
+        MatchTable {
+            table_id: 0,
+            match_table_type: MatchTableType::Simple,
+            wordlist: VarZeroVec::new(),
+            exemption_wordlist: VarZeroVec::new(),
+            simple_match_type: SimpleMatchType::None,
+            acrostic_position: AcrosticPosition::default(),
+            prefer_matched_text: false,
+            sim_match_type: SimMatchType::default(),
+            regex_flags: RegexFlags::default(),
+            acrostic_separator: AcrosticSeparator::default(),
+            simple_match_type_override: AHashMap::new(),
+        }
+    }
+}
+
+impl MatchTable<'_> {
+    // 词表中的词实际使用的所有匹配类型，单独指定的匹配类型排在词表的匹配类型之后
+    fn simple_match_type_iter(&self) -> impl Iterator<Item = SimpleMatchType> + '_ {
+        std::iter::once(self.simple_match_type)
+            .chain(self.simple_match_type_override.values().copied())
+    }
+}
+
+// MatchTable的完全拥有版本，反序列化时不借用输入，输入中含转义字符或需要提前释放输入时使用
+#[derive(Serialize, Deserialize, Clone)]
+pub struct OwnedMatchTable {
+    pub table_id: u32,                      // 词表ID
+    pub match_table_type: MatchTableType,   // 词表类型
+    pub wordlist: Vec<String>,              // 词表
+    pub exemption_wordlist: Vec<String>,    // 豁免词表
+    pub simple_match_type: SimpleMatchType, // 匹配类型
+    #[serde(default)]
+    pub acrostic_position: AcrosticPosition, // 藏头位置
+    #[serde(default)]
+    pub prefer_matched_text: bool, // 命中词使用文本中实际命中的子串
+    #[serde(default)]
+    pub sim_match_type: SimMatchType, // 相似度算法
+    #[serde(default)]
+    pub regex_flags: RegexFlags, // 正则的编译标志
+    #[serde(default)]
+    pub acrostic_separator: AcrosticSeparator, // 藏头字之间的分隔方式
+    #[serde(default)]
+    pub simple_match_type_override: AHashMap<String, SimpleMatchType>, // 单独指定匹配类型的词
+}
+
+// 同MatchTable的默认值
+impl Default for OwnedMatchTable {
+    fn default() -> Self {
+        OwnedMatchTable::from(&MatchTable::default())
+    }
+}
+
+impl From<&MatchTable<'_>> for OwnedMatchTable {
+    fn from(match_table: &MatchTable<'_>) -> OwnedMatchTable {
+        OwnedMatchTable {
+            table_id: match_table.table_id,
+            match_table_type: match_table.match_table_type,
+            wordlist: match_table.wordlist.iter().map(str::to_owned).collect(),
+            exemption_wordlist: match_table
+                .exemption_wordlist
+                .iter()
+                .map(str::to_owned)
+                .collect(),
+            simple_match_type: match_table.simple_match_type,
+            acrostic_position: match_table.acrostic_position,
+            prefer_matched_text: match_table.prefer_matched_text,
+            sim_match_type: match_table.sim_match_type,
+            regex_flags: match_table.regex_flags,
+            acrostic_separator: match_table.acrostic_separator,
+            simple_match_type_override: match_table
+                .simple_match_type_override
+                .iter()
+                .map(|(word, &simple_match_type)| (word.to_string(), simple_match_type))
+                .collect(),
+        }
+    }
+}
+
+impl<'a> From<&'a OwnedMatchTable> for MatchTable<'a> {
+    fn from(owned_match_table: &'a OwnedMatchTable) -> MatchTable<'a> {
+        MatchTable {
+            table_id: owned_match_table.table_id,
+            match_table_type: owned_match_table.match_table_type,
+            wordlist: VarZeroVec::from(&owned_match_table.wordlist),
+            exemption_wordlist: VarZeroVec::from(&owned_match_table.exemption_wordlist),
+            simple_match_type: owned_match_table.simple_match_type,
+            acrostic_position: owned_match_table.acrostic_position,
+            prefer_matched_text: owned_match_table.prefer_matched_text,
+            sim_match_type: owned_match_table.sim_match_type,
+            regex_flags: owned_match_table.regex_flags,
+            acrostic_separator: owned_match_table.acrostic_separator,
+            simple_match_type_override: owned_match_table
+                .simple_match_type_override
+                .iter()
+                .map(|(word, &simple_match_type)| (Cow::Borrowed(word.as_str()), simple_match_type))
+                .collect(),
+        }
+    }
 }
 
 #[derive(Debug)]
 struct WordTableConf {
     match_id: String,   // 匹配ID
     table_id: u32,      // 词表ID
-    is_exemption: bool, // 是否豁免
+    table_index: usize, // 词表在Matcher中的下标，用于TableIdSet
+    first_word_id: u64, // 词表中第一个词的词ID，同一词表的词ID连续分配，词ID减去该值即词在词表中的下标
 }
 
-#[derive(Serialize)]
+// 词表的构建参数，与词表下标一一对应，用于导出已加载的词表
+struct TableConf {
+    match_id: String,
+    table_id: u32,
+    match_table_type: MatchTableType,
+    simple_match_type: SimpleMatchType,
+    acrostic_position: AcrosticPosition,
+    prefer_matched_text: bool,
+    sim_match_type: SimMatchType,
+    regex_flags: RegexFlags,
+    acrostic_separator: AcrosticSeparator,
+    simple_match_type_override: AHashMap<String, SimpleMatchType>,
+}
+
+// 命中结果所属的匹配器类型，similar_char以及acrostic词表由regex_matcher实现，归为regex
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TableType {
+    Simple,
+    Regex,
+    Similar,
+}
+
+#[derive(Serialize, Clone)]
 pub struct MatchResult<'a> {
-    table_id: u32,      // 命中词表ID
+    table_id: u32, // 命中词表ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    table_type: Option<TableType>, // 命中词表类型，仅在report_table_type开启时返回
     word: Cow<'a, str>, // 命中词
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parts: Option<Vec<PartHit<'a>>>, // 组合词各部分的命中次数，仅simple词表在detailed模式下返回
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fingerprint: Option<u64>, // 命中结果的稳定指纹，仅在result_fingerprint开启时返回
+    #[serde(flatten)]
+    span: Option<MatchSpan>, // 命中位置，展开为start / end / variant，仅在report_span开启时返回
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    captures: Vec<(String, String)>, // 具名捕获组 (组名, 捕获文本)，仅正则词表中带具名捕获组的正则返回
+}
+
+// highlight切分出的文本片段
+#[derive(Serialize, Clone)]
+pub struct Segment<'a> {
+    pub text: &'a str,                // 原文本中的片段
+    pub hit: Option<MatchResult<'a>>, // 片段对应的命中结果，未命中片段为None
+}
+
+impl MatchResult<'_> {
+    // 同match_fingerprint，MatchResult本身不含match_id，需由调用方传入结果所在的match_id
+    pub fn fingerprint(&self, match_id: &str) -> u64 {
+        match_fingerprint(match_id, self.table_id, &self.word)
+    }
+
+    pub fn captures(&self) -> &[(String, String)] {
+        &self.captures
+    }
+}
+
+// 命中结果的稳定指纹，用于重试等场景下对相同命中去重，不受json字段顺序以及格式变化影响
+// 算法固定为FNV-1a 64位，match_id与word按 小端u64长度 + utf8字节 编码，table_id按小端u32编码，跨版本、跨进程以及不同构建顺序保持不变
+// 词ID依赖构建顺序，不参与计算；word为返回的命中词，受display_separator等结果选项影响
+pub fn match_fingerprint(match_id: &str, table_id: u32, word: &str) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    let mut hash = FNV_OFFSET_BASIS;
+    let mut write = |bytes: &[u8]| {
+        for &byte in bytes {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    };
+    write(&(match_id.len() as u64).to_le_bytes());
+    write(match_id.as_bytes());
+    write(&table_id.to_le_bytes());
+    write(&(word.len() as u64).to_le_bytes());
+    write(word.as_bytes());
+
+    hash
+}
+
+// 抑制命中结果的豁免词
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct ExemptionHit<'a> {
+    pub table_id: u32,      // 豁免词所在词表ID
+    pub word: Cow<'a, str>, // 命中的豁免词
+    pub similarity: f64,    // 豁免依据的强度，simple豁免词恒为1.0
+}
+
+// match_id被豁免时的命中结果以及命中的豁免词
+#[derive(Serialize)]
+pub struct ExemptedResult<'a> {
+    pub result_list: Vec<MatchResult<'a>>, // 被豁免的命中结果
+    pub exemption_hit_list: Vec<ExemptionHit<'a>>, // 命中的豁免词
+}
+
+// word_match_full的结果，未被豁免与被豁免的match_id分开返回
+#[derive(Serialize, Default)]
+pub struct FullMatchResult<'a> {
+    pub matched: AHashMap<&'a str, Vec<MatchResult<'a>>>, // 同word_match
+    pub exempted: AHashMap<&'a str, ExemptedResult<'a>>,  // 被豁免的match_id
+    pub variants_truncated: bool, // 是否因变体数超出max_variants跳过了部分转换，此时结果可能不完整
 }
 
+#[derive(Clone)]
+enum ExemptionUpdate {
+    Add {
+        match_id: String,
+        table_id: u32,
+        word: String,
+    },
+    Remove {
+        match_id: String,
+        table_id: u32,
+        word: String,
+    },
+}
+
+#[derive(Clone, Default)]
+struct ExemptionOverlay {
+    base_word_id_dict: AHashMap<(String, u32, String), Vec<u64>>, // 构建时的豁免词对其词ID的映射，用于移除
+    removed_word_id_set: IntSet<u64>,                             // 已移除的构建时豁免词ID
+    base_regex_exemption_set: AHashSet<(String, u32, String)>, // 构建时正则及通配符词表的豁免词 (match_id, table_id, 词)
+    removed_regex_exemption_set: AHashSet<(String, u32, String)>, // 已移除的构建时正则豁免词
+    word_list: Vec<(Arc<WordTableConf>, String)>, // 运行时添加的豁免词，下标即overlay匹配器中的词ID
+    simple_matcher: Option<SimpleMatcher>, // 由运行时添加的豁免词构建，不影响正常词所在的simple匹配器
+    pending_update_list: Vec<ExemptionUpdate>, // 待commit的变更
+}
+
+// 分块匹配时命中结果在窗口之间去重的键，只合并重叠区内在相邻窗口重复产生的命中
+// simple词按全局词ID、正则以及相似词表按词，每条文本各只返回一次；邻近字词表返回每次命中，按其在原文本中的位置区分
+#[derive(Hash, PartialEq, Eq)]
+enum ChunkResultKey<'a> {
+    Simple(u64),
+    Regex(&'a str, u32, u64, Option<(usize, usize)>),
+    Sim(&'a str, u32, u64),
+}
+
+#[derive(Default)]
 struct ResultDict<'a> {
-    result_list: Vec<MatchResult<'a>>, // 匹配结果列表
-    exemption_flag: bool,              // 是否命中过豁免词
+    result_list: Vec<MatchResult<'a>>,         // 匹配结果列表
+    exemption_hit_list: Vec<ExemptionHit<'a>>, // 命中的豁免词，非空即被豁免
 }
 
 pub type MatchTableDict<'a> = AHashMap<&'a str, Vec<MatchTable<'a>>>;
 
-pub struct Matcher {
-    word_table_list: Vec<Rc<WordTableConf>>, // 词ID对匹配ID，词表ID，是否豁免的映射关系，利用Rc指针共享数据
-    simple_matcher: Option<SimpleMatcher>, // simple匹配器，精准 / 繁简 / 归一 / 拼音 / 拼音字符 等匹配方式组合的快速实现
-    regex_matcher: Option<RegexMatcher>,   // regex匹配器，邻近字 / 藏头诗 / 正则匹配的实现
-    sim_matcher: Option<SimMatcher>,       // sim匹配器，编辑距离匹配的实现
+// MatchTableDict的完全拥有版本，序列化格式一致
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(transparent)]
+pub struct OwnedMatchTableDict(pub AHashMap<String, Vec<OwnedMatchTable>>);
+
+impl From<&MatchTableDict<'_>> for OwnedMatchTableDict {
+    fn from(match_table_dict: &MatchTableDict<'_>) -> OwnedMatchTableDict {
+        OwnedMatchTableDict(
+            match_table_dict
+                .iter()
+                .map(|(&match_id, table_list)| {
+                    (
+                        match_id.to_owned(),
+                        table_list.iter().map(OwnedMatchTable::from).collect(),
+                    )
+                })
+                .collect(),
+        )
+    }
 }
 
-impl Matcher {
-    pub fn new(match_table_dict: &MatchTableDict) -> Matcher {
-        let mut word_id: u64 = 0; // 词ID 全局唯一
-        let mut word_table_list: Vec<Rc<WordTableConf>> = Vec::new();
+impl<'a> From<&'a OwnedMatchTableDict> for MatchTableDict<'a> {
+    fn from(owned_match_table_dict: &'a OwnedMatchTableDict) -> MatchTableDict<'a> {
+        owned_match_table_dict
+            .0
+            .iter()
+            .map(|(match_id, owned_table_list)| {
+                (
+                    match_id.as_str(),
+                    owned_table_list.iter().map(MatchTable::from).collect(),
+                )
+            })
+            .collect()
+    }
+}
 
-        let mut simple_wordlist_dict: AHashMap<SimpleMatchType, Vec<SimpleWord>> = AHashMap::new();
+// 从json反序列化借用输入的词表，json字符串中的转义字符无法零拷贝借用，此时错误信息提示改用OwnedMatchTableDict
+pub fn parse_match_table_dict(json: &str) -> Result<MatchTableDict<'_>, serde_json::Error> {
+    serde_json::from_str(json).map_err(|e| {
+        if e.is_data() && e.to_string().contains("borrowed") {
+            <serde_json::Error as serde::de::Error>::custom(format!(
+                "{e}, strings containing escape sequences cannot be borrowed from the input, deserialize into OwnedMatchTableDict instead"
+            ))
+        } else {
+            e
+        }
+    })
+}
 
-        let mut regex_table_list: Vec<RegexTable> = Vec::new();
-        let mut sim_table_list: Vec<SimTable> = Vec::new();
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EmptyWordlistPolicy {
+    #[default]
+    Warn, // 空词表返回EmptyWordlist告警，照常构建
+    Reject, // 空词表返回EmptyWordlist错误
+}
 
-        for (&match_id, table_list) in match_table_dict {
-            for table in table_list {
-                let table_id = table.table_id;
-                let match_table_type = &table.match_table_type;
-                let wordlist = &table.wordlist;
-                let exemption_wordlist = &table.exemption_wordlist;
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OverLimitPolicy {
+    #[default]
+    Truncate, // 在字符边界处截断，只匹配前max_input_bytes字节
+    ChunkAndUnion {
+        overlap_bytes: usize,
+    }, // 切分为相邻重叠overlap_bytes字节的窗口分别匹配，结果合并去重，跨越窗口的词需要落在重叠区内才能命中
+    Reject, // 拒绝匹配，返回InputTooLong错误
+}
 
-                if !wordlist.is_empty() {
-                    match match_table_type {
-                        MatchTableType::Simple => {
-                            let word_table_conf = Rc::new(WordTableConf {
-                                match_id: match_id.to_owned(),
-                                table_id,
-                                is_exemption: false,
-                            });
-                            let simple_word_list = simple_wordlist_dict
-                                .entry(table.simple_match_type)
-                                .or_default();
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DedupPolicy {
+    #[default]
+    KeepAll, // 保留所有重复词，每个词各自命中
+    KeepFirst, // 同一词表内重复的词只保留第一个
+    Error,     // try_new时返回DuplicateWord错误，不返回错误的构建方式等同KeepFirst
+}
 
-                            for word in wordlist.iter() {
-                                word_table_list.push(Rc::clone(&word_table_conf));
-                                simple_word_list.push(SimpleWord { word_id, word });
-                                word_id += 1;
-                            }
-                        }
-                        MatchTableType::SimilarTextLevenshtein => sim_table_list.push(SimTable {
-                            table_id,
-                            match_id,
-                            wordlist,
-                        }),
-                        _ => regex_table_list.push(RegexTable {
-                            table_id,
-                            match_id,
-                            match_table_type,
-                            wordlist,
-                        }),
-                    }
-                }
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BuildMode {
+    #[default]
+    Strict, // 任一词表校验失败或含无法编译的正则时返回错误
+    Lenient, // 跳过校验失败、table_id重复以及含无法编译正则的词表，其余词表正常构建
+}
 
-                if !exemption_wordlist.is_empty() {
-                    let word_table_conf = Rc::new(WordTableConf {
-                        match_id: match_id.to_owned(),
-                        table_id,
-                        is_exemption: true,
-                    });
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct MatcherOptions {
+    pub simple_matcher_options: SimpleMatcherOptions, // simple匹配器选项
+    pub regex_matcher_options: RegexMatcherOptions,   // regex匹配器选项
+    pub sim_matcher_options: SimMatcherOptions,       // sim匹配器选项
+    pub max_input_bytes: Option<usize>, // 单条文本的最大字节数，超长文本按over_limit_policy处理，默认不限制
+    pub over_limit_policy: OverLimitPolicy, // 超长文本处理策略
+    pub inherit_simple_match_type_for_exemptions: bool, // 豁免词默认按繁简删除归一匹配，开启后额外并上所在simple词表的匹配类型，eg. 拼音词表的豁免词也按拼音匹配
+    pub dedup_words: DedupPolicy,                       // 同一词表内重复词的处理策略
+    pub treat_blank_as_empty: bool, // 只含空白以及零宽字符的文本视为空文本，不做任何处理，默认开启
+    pub normalize_newlines: bool, // 匹配前将\r\n、单独的\r以及\u{2028}、\u{2029}统一转为\n，与转换类型无关，保证正则的行首行尾以及分句在各平台换行符下一致
+    pub result_fingerprint: bool, // 命中结果的json中附带稳定指纹fingerprint，见match_fingerprint，默认关闭
+    pub report_span: bool, // 命中结果的json中附带命中位置start / end（字节位置）以及所在文本变体variant，仅simple与regex词表返回，默认关闭
+    pub report_table_type: bool, // 命中结果的json中附带命中词表类型table_type，见TableType，默认关闭
+    pub min_parallel_batch: usize, // process_batch / is_match_batch并行处理的最小文本数，少于该值时逐条处理，仅在开启rayon时生效
+    pub empty_wordlist_policy: EmptyWordlistPolicy, // try_new对空词表的处理
+}
 
-                    let simple_word_list = simple_wordlist_dict
-                        .entry(SimpleMatchType::FanjianDeleteNormalize)
-                        .or_default();
+impl Default for MatcherOptions {
+    fn default() -> Self {
+        MatcherOptions {
+            simple_matcher_options: SimpleMatcherOptions::default(),
+            regex_matcher_options: RegexMatcherOptions::default(),
+            sim_matcher_options: SimMatcherOptions::default(),
+            max_input_bytes: None,
+            over_limit_policy: OverLimitPolicy::default(),
+            inherit_simple_match_type_for_exemptions: false,
+            dedup_words: DedupPolicy::default(),
+            treat_blank_as_empty: true,
+            normalize_newlines: false,
+            result_fingerprint: false,
+            report_span: false,
+            report_table_type: false,
+            min_parallel_batch: 64,
+            empty_wordlist_policy: EmptyWordlistPolicy::default(),
+        }
+    }
+}
 
-                    for exemption_word in exemption_wordlist.iter() {
-                        word_table_list.push(Rc::clone(&word_table_conf));
-                        simple_word_list.push(SimpleWord {
-                            word_id,
-                            word: exemption_word,
-                        });
-                        word_id += 1;
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatcherError {
+    EmptyWordlist {
+        match_id: String,
+        table_id: u32,
+    }, // 词表为空，仅在EmptyWordlistPolicy::Reject时返回
+    ConflictingSimpleMatchType {
+        match_id: String,
+        table_id: u32,
+        simple_match_type: SimpleMatchType,
+    }, // 拼音 与 拼音字符 同时存在
+    InputTooLong {
+        text_bytes: usize,
+        max_input_bytes: usize,
+    }, // 文本超过max_input_bytes，且策略为Reject
+    DuplicateWord {
+        match_id: String,
+        table_id: u32,
+        word: String,
+    }, // 同一词表内存在重复的词，且策略为Error
+    DeadlineExceeded, // 匹配超过截止时间
+    InvalidRegex {
+        match_id: String,
+        table_id: u32,
+        pattern: String,
+        source: RegexCompileError, // fancy_regex的编译错误
+    }, // 正则词表中的正则无法编译，或邻近字 / 藏头诗词表生成的正则无法编译，每个正则一个错误
+    InvalidThreshold {
+        match_id: String,
+        table_id: u32,
+        threshold: f64,
+    }, // 相似度阈值不在 0~1 之间，含NaN
+    TooManyPatterns {
+        simple_match_type: SimpleMatchType,
+        pattern_count: usize,
+        max_pattern_count: usize,
+    }, // 同一匹配类型下simple词展开后的ac词数超过上限
+    DisabledProcessType {
+        simple_match_type: SimpleMatchType,
+        str_conv_type: SimpleMatchType,
+        feature: &'static str,
+    }, // 词表的匹配类型需要未开启feature的内置映射表，且未覆盖
+    DuplicateTableId {
+        match_id: String,
+        table_id: u32,
+    }, // 同一match_id下存在相同table_id的词表
+    EmptyWord {
+        match_id: String,
+        table_id: u32,
+    }, // 词表或豁免词表中存在空字符串，邻近字词表的空分组除外
+    MultipleErrors {
+        error_list: Vec<MatcherError>,
+    }, // 校验发现多个错误，逐个列出
+    DuplicateWordId {
+        word_id: u64,
+    }, // 内部词ID重复，词ID由构建时的计数器分配，出现时说明构建逻辑有误
+    InvalidOverlap {
+        overlap_bytes: usize,
+        max_input_bytes: usize,
+    }, // ChunkAndUnion的重叠字节数不小于max_input_bytes，窗口每次只能前进一个字符
+}
+
+impl fmt::Display for MatcherError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatcherError::EmptyWordlist { match_id, table_id } => {
+                write!(f, "match_id: {match_id}, table_id: {table_id}, wordlist is empty")
+            }
+            MatcherError::ConflictingSimpleMatchType {
+                match_id,
+                table_id,
+                simple_match_type,
+            } => write!(
+                f,
+                "match_id: {match_id}, table_id: {table_id}, simple_match_type {:#b} contains both PinYin and PinYinChar",
+                simple_match_type.bits()
+            ),
+            MatcherError::InputTooLong {
+                text_bytes,
+                max_input_bytes,
+            } => write!(
+                f,
+                "text of {text_bytes} bytes exceeds max_input_bytes: {max_input_bytes}"
+            ),
+            MatcherError::DuplicateWord {
+                match_id,
+                table_id,
+                word,
+            } => write!(
+                f,
+                "match_id: {match_id}, table_id: {table_id}, duplicate word: {word}"
+            ),
+            MatcherError::DeadlineExceeded => write!(f, "match exceeded the deadline"),
+            MatcherError::InvalidRegex {
+                match_id,
+                table_id,
+                pattern,
+                source,
+            } => write!(
+                f,
+                "match_id: {match_id}, table_id: {table_id}, invalid regex pattern {pattern:?}: {source}"
+            ),
+            MatcherError::InvalidThreshold {
+                match_id,
+                table_id,
+                threshold,
+            } => write!(
+                f,
+                "match_id: {match_id}, table_id: {table_id}, threshold {threshold} is not within 0..=1"
+            ),
+            MatcherError::TooManyPatterns {
+                simple_match_type,
+                pattern_count,
+                max_pattern_count,
+            } => write!(
+                f,
+                "simple_match_type {simple_match_type} expands to {pattern_count} ac patterns, exceeding the limit of {max_pattern_count}"
+            ),
+            MatcherError::DisabledProcessType {
+                simple_match_type,
+                str_conv_type,
+                feature,
+            } => write!(
+                f,
+                "simple_match_type {simple_match_type} requires the builtin {str_conv_type} mapping, which is compiled out, enable the `{feature}` feature or provide it via process_dict_override"
+            ),
+            MatcherError::DuplicateTableId { match_id, table_id } => {
+                write!(f, "match_id: {match_id}, table_id: {table_id}, duplicate table_id")
+            }
+            MatcherError::EmptyWord { match_id, table_id } => write!(
+                f,
+                "match_id: {match_id}, table_id: {table_id}, wordlist or exemption_wordlist contains an empty word"
+            ),
+            MatcherError::MultipleErrors { error_list } => {
+                write!(f, "{} errors: ", error_list.len())?;
+                for (index, error) in error_list.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, "; ")?;
                     }
+                    write!(f, "{error}")?;
                 }
+                Ok(())
+            }
+            MatcherError::DuplicateWordId { word_id } => {
+                write!(f, "internal word_id {word_id} is assigned to more than one word")
             }
+            MatcherError::InvalidOverlap {
+                overlap_bytes,
+                max_input_bytes,
+            } => write!(
+                f,
+                "overlap_bytes: {overlap_bytes} must be less than max_input_bytes: {max_input_bytes}"
+            ),
         }
+    }
+}
 
-        Matcher {
-            word_table_list,
-            simple_matcher: (!simple_wordlist_dict.is_empty())
-                .then(|| SimpleMatcher::new(&simple_wordlist_dict)),
-            regex_matcher: (!regex_table_list.is_empty())
-                .then(|| RegexMatcher::new(&regex_table_list)),
-            sim_matcher: (!sim_table_list.is_empty()).then(|| SimMatcher::new(&sim_table_list)),
+impl std::error::Error for MatcherError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MatcherError::InvalidRegex { source, .. } => Some(source),
+            _ => None,
         }
     }
+}
 
-    fn word_match_raw(&self, text: &str) -> AHashMap<&str, Vec<MatchResult>> {
-        if likely(!text.is_empty()) {
-            let mut match_result_dict: AHashMap<&str, ResultDict> = AHashMap::new();
+impl MatcherError {
+    // 没有错误时返回Ok，只有一个错误时原样返回，否则合并为MultipleErrors
+    fn from_error_list(mut error_list: Vec<MatcherError>) -> Result<(), MatcherError> {
+        match error_list.len() {
+            0 => Ok(()),
+            1 => Err(error_list.pop().unwrap()),
+            _ => Err(MatcherError::MultipleErrors { error_list }),
+        }
+    }
+}
 
-            if let Some(simple_matcher) = &self.simple_matcher {
-                for simple_result in simple_matcher.process(text) {
-                    let word_table_conf = unsafe {
-                        self.word_table_list
-                            .get_unchecked(simple_result.word_id as usize)
-                    };
+impl From<RegexBuildError> for MatcherError {
+    fn from(e: RegexBuildError) -> Self {
+        match e {
+            RegexBuildError::InvalidGeneratedPattern {
+                match_id,
+                table_id,
+                pattern,
+                error,
+            } => MatcherError::InvalidRegex {
+                match_id,
+                table_id,
+                pattern,
+                source: error,
+            },
+        }
+    }
+}
 
-                    let result_dict = match_result_dict
-                        .entry(&word_table_conf.match_id)
-                        .or_insert(ResultDict {
-                            result_list: Vec::new(),
-                            exemption_flag: false,
-                        });
+impl From<SimBuildError> for MatcherError {
+    fn from(e: SimBuildError) -> Self {
+        match e {
+            SimBuildError::InvalidThreshold {
+                match_id,
+                table_id,
+                threshold,
+            } => MatcherError::InvalidThreshold {
+                match_id,
+                table_id,
+                threshold,
+            },
+        }
+    }
+}
 
-                    if unlikely(word_table_conf.is_exemption) {
-                        result_dict.exemption_flag = true;
-                    }
+impl From<SimpleMatcherError> for MatcherError {
+    fn from(e: SimpleMatcherError) -> Self {
+        match e {
+            SimpleMatcherError::TooManyPatterns {
+                simple_match_type,
+                pattern_count,
+                max_pattern_count,
+            } => MatcherError::TooManyPatterns {
+                simple_match_type,
+                pattern_count,
+                max_pattern_count,
+            },
+            SimpleMatcherError::DisabledProcessType {
+                simple_match_type,
+                str_conv_type,
+                feature,
+            } => MatcherError::DisabledProcessType {
+                simple_match_type,
+                str_conv_type,
+                feature,
+            },
+            SimpleMatcherError::DuplicateWordId { word_id } => {
+                MatcherError::DuplicateWordId { word_id }
+            }
+        }
+    }
+}
 
-                    result_dict.result_list.push(MatchResult {
-                        table_id: word_table_conf.table_id,
-                        word: simple_result.word,
-                    });
-                }
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatcherWarning {
+    EmptyWordlist {
+        match_id: String,
+        table_id: u32,
+    }, // 词表为空，且不是只含豁免词的词表，或同match_id下没有其他非空词表
+    RedundantSimpleMatchType {
+        match_id: String,
+        table_id: u32,
+        covered_by_table_id: u32,
+    }, // 同match_id下，该词表的匹配类型是另一词表匹配类型的子集，会产生近似重复的变体与结果
+    RegexSetFallback {
+        match_id: String,
+        table_id: u32,
+        pattern_list: Vec<String>,
+    }, // 正则词表中存在regex crate不支持的正则，这些正则无法使用RegexSet而逐个匹配
+    ExemptionSimpleMatchTypeNotCovered {
+        match_id: String,
+        table_id: u32,
+        simple_match_type: SimpleMatchType,
+    }, // 词表匹配类型超出豁免词的繁简删除归一，eg. 拼音，豁免词无法命中对应变体而漏豁免
+    DuplicateWord {
+        match_id: String,
+        table_id: u32,
+        word: String,
+    }, // 同一词表内存在重复的词，默认策略下会重复命中
+}
+
+impl fmt::Display for MatcherWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatcherWarning::EmptyWordlist { match_id, table_id } => {
+                write!(f, "match_id: {match_id}, table_id: {table_id}, wordlist is empty")
             }
+            MatcherWarning::RedundantSimpleMatchType {
+                match_id,
+                table_id,
+                covered_by_table_id,
+            } => write!(
+                f,
+                "match_id: {match_id}, table_id: {table_id}, simple_match_type is a subset of table_id: {covered_by_table_id}"
+            ),
+            MatcherWarning::RegexSetFallback {
+                match_id,
+                table_id,
+                pattern_list,
+            } => write!(
+                f,
+                "match_id: {match_id}, table_id: {table_id}, patterns not supported by RegexSet: {pattern_list:?}"
+            ),
+            MatcherWarning::ExemptionSimpleMatchTypeNotCovered {
+                match_id,
+                table_id,
+                simple_match_type,
+            } => write!(
+                f,
+                "match_id: {match_id}, table_id: {table_id}, exemption words are not matched by simple_match_type: {simple_match_type}"
+            ),
+            MatcherWarning::DuplicateWord {
+                match_id,
+                table_id,
+                word,
+            } => write!(
+                f,
+                "match_id: {match_id}, table_id: {table_id}, duplicate word: {word}"
+            ),
+        }
+    }
+}
 
-            if let Some(regex_matcher) = &self.regex_matcher {
-                for regex_result in regex_matcher.process(text) {
-                    let result_dict =
-                        match_result_dict
-                            .entry(regex_result.match_id)
-                            .or_insert(ResultDict {
-                                result_list: Vec::new(),
-                                exemption_flag: false,
-                            });
+// lenient模式下被跳过的词表以及原因
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedTable {
+    pub match_id: String,
+    pub table_id: u32,
+    pub reason: MatcherError,
+}
 
-                    result_dict.result_list.push(MatchResult {
-                        table_id: regex_result.table_id,
-                        word: regex_result.word,
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BuildReport {
+    pub table_split_duration: Duration, // 词表拆分耗时，按类型分发到各匹配器
+    pub variant_expansion_duration: Duration, // simple词切分以及变体展开耗时
+    pub ac_build_duration: Duration,    // simple ac自动机构建耗时
+    pub regex_compile_duration: Duration, // 正则编译耗时
+    pub sim_table_prep_duration: Duration, // 相似度词表准备耗时
+    pub match_id_count: usize,          // match_id数
+    pub table_count: usize,             // 词表数
+    pub simple_word_count: usize,       // simple词数，不含豁免词
+    pub exemption_word_count: usize,    // 豁免词数
+    pub regex_table_count: usize,       // regex词表数
+    pub sim_table_count: usize,         // sim词表数
+    pub ac_pattern_count: usize,        // simple ac自动机的词数，即所有词切分并展开变体后的总数
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MatcherStats {
+    pub regex_matcher_stats: RegexMatcherStats, // regex匹配器统计信息，eg. 正则词表退化为逐个匹配的原因
+    pub ac_pattern_count: usize,                // simple以及豁免词ac自动机的总词数
+    pub max_table_ac_pattern_count: usize, // 单个ac自动机的最大词数，与MAX_AC_PATTERN_COUNT比较即为余量
+}
+
+// 已加载词表的概况，用于热加载词表后的健康检查
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TableInfo {
+    pub match_id: String,                 // 匹配ID
+    pub table_id: u32,                    // 词表ID
+    pub match_table_type: MatchTableType, // 词表类型
+    pub word_count: usize,                // 实际加载的词数
+    pub exemption_count: usize,           // 实际加载的豁免词数，含运行时已commit的豁免词
+}
+
+// 构建产物均由Arc持有，clone只增加引用计数；运行时增删豁免词时写时复制，不影响其他clone
+#[derive(Clone)]
+pub struct Matcher {
+    word_table_list: Arc<Vec<Arc<WordTableConf>>>, // 词ID对匹配ID，词表ID的映射关系，正常词与豁免词共用词ID空间，利用Arc指针共享数据，保证Matcher可跨线程共享
+    simple_matcher: Option<SimpleMatcher>, // simple匹配器，精准 / 繁简 / 归一 / 拼音 / 拼音字符 等匹配方式组合的快速实现
+    exemption_simple_matcher: Option<SimpleMatcher>, // 豁免词的simple匹配器，仅在存在带豁免词的match_id命中时才生成其文本变体并匹配
+    exemption_match_id_set: Arc<AHashSet<String>>,   // 构建时带有豁免词的match_id
+    exemption_regex_matcher: Option<RegexMatcher>, // 正则及通配符词表豁免词的regex匹配器，豁免词与词表中的词同样按正则匹配
+    exemption_regex_table_index_list: Arc<Vec<usize>>, // exemption_regex_matcher中各词表对应的词表下标
+    regex_matcher: Option<RegexMatcher>, // regex匹配器，邻近字 / 藏头诗 / 正则匹配的实现
+    sim_matcher: Option<SimMatcher>,     // sim匹配器，编辑距离匹配的实现
+    max_input_bytes: Option<usize>,      // 单条文本的最大字节数
+    over_limit_policy: OverLimitPolicy,  // 超长文本处理策略
+    treat_blank_as_empty: bool,          // 只含空白以及零宽字符的文本视为空文本
+    normalize_newlines: bool,            // 匹配前统一换行符
+    result_fingerprint: bool,            // 命中结果是否附带稳定指纹
+    report_span: bool,                   // 命中结果是否附带命中位置
+    report_table_type: bool,             // 命中结果是否附带命中词表类型
+    #[cfg(feature = "rayon")]
+    min_parallel_batch: usize, // 批量处理时并行的最小文本数
+    exemption_overlay: Arc<ExemptionOverlay>, // 运行时增删的豁免词
+    table_index_dict: Arc<AHashMap<(String, u32), Vec<usize>>>, // (匹配ID, 词表ID) 对词表下标的映射
+    regex_table_index_list: Arc<Vec<usize>>, // regex匹配器中各词表对应的词表下标
+    sim_table_index_list: Arc<Vec<usize>>, // sim匹配器中各词表对应的词表下标
+    table_count: usize,                  // 词表总数
+    table_conf_list: Arc<Vec<TableConf>>, // 按词表下标保存的词表构建参数
+}
+
+// 按词表下标预先构建的词表集合，匹配时直接按下标判断，省去每次调用对 (匹配ID, 词表ID) 的哈希
+#[derive(Clone, Debug, Default)]
+pub struct TableIdSet {
+    table_flag_list: Vec<bool>, // 按词表下标标记词表是否在集合中
+}
+
+impl TableIdSet {
+    #[inline]
+    fn contains(&self, table_index: usize) -> bool {
+        self.table_flag_list
+            .get(table_index)
+            .is_some_and(|&table_flag| table_flag)
+    }
+}
+
+impl Matcher {
+    pub fn new(match_table_dict: &MatchTableDict) -> Matcher {
+        Self::new_with_options(match_table_dict, MatcherOptions::default())
+    }
+
+    // 由完全拥有的词表构建，Matcher不借用词表，构建后词表以及其反序列化的输入均可释放
+    pub fn from_owned_dict(owned_match_table_dict: &OwnedMatchTableDict) -> Matcher {
+        Self::new(&MatchTableDict::from(owned_match_table_dict))
+    }
+
+    // 由rule bundle构建，bundle中的转换映射表覆盖并入构建选项
+    pub fn from_bundle(rule_bundle: &RuleBundle) -> Matcher {
+        Self::new_with_options(
+            &MatchTableDict::from(&rule_bundle.table_map),
+            rule_bundle.matcher_options(),
+        )
+    }
+
+    // 校验词表后构建，存在错误时返回错误，否则返回Matcher以及校验告警
+    pub fn try_new(
+        match_table_dict: &MatchTableDict,
+    ) -> Result<(Matcher, Vec<MatcherWarning>), MatcherError> {
+        Self::try_new_with_options(match_table_dict, MatcherOptions::default())
+    }
+
+    pub fn try_new_with_options(
+        match_table_dict: &MatchTableDict,
+        options: MatcherOptions,
+    ) -> Result<(Matcher, Vec<MatcherWarning>), MatcherError> {
+        let (matcher, warning_list, _) = Self::try_new_with_report(match_table_dict, options)?;
+
+        Ok((matcher, warning_list))
+    }
+
+    // strict模式同try_new_with_options，任一词表校验失败或含无法编译的正则时返回错误
+    // lenient模式跳过校验失败、table_id重复以及含无法编译正则的词表，与构建出的Matcher一同返回
+    pub fn try_new_with_mode(
+        match_table_dict: &MatchTableDict,
+        options: MatcherOptions,
+        build_mode: BuildMode,
+    ) -> Result<(Matcher, Vec<MatcherWarning>, Vec<SkippedTable>), MatcherError> {
+        if build_mode == BuildMode::Strict {
+            let (matcher, warning_list) = Self::try_new_with_options(match_table_dict, options)?;
+            return Ok((matcher, warning_list, Vec::new()));
+        }
+
+        let mut skipped_table_list: Vec<SkippedTable> = match_table_dict
+            .iter()
+            .flat_map(|(&match_id, table_list)| {
+                table_list.iter().filter_map(|table| {
+                    Self::check_table(match_id, table, &options)
+                        .err()
+                        .map(|reason| SkippedTable {
+                            match_id: match_id.to_owned(),
+                            table_id: table.table_id,
+                            reason,
+                        })
+                })
+            })
+            .collect();
+        // table_id重复时无法区分各词表，同一 (match_id, table_id) 的词表全部跳过
+        for (&match_id, table_list) in match_table_dict {
+            let mut table_id_set = AHashSet::new();
+            let mut duplicate_table_id_set = AHashSet::new();
+            for table in table_list {
+                if !table_id_set.insert(table.table_id)
+                    && duplicate_table_id_set.insert(table.table_id)
+                {
+                    skipped_table_list.push(SkippedTable {
+                        match_id: match_id.to_owned(),
+                        table_id: table.table_id,
+                        reason: MatcherError::DuplicateTableId {
+                            match_id: match_id.to_owned(),
+                            table_id: table.table_id,
+                        },
                     });
                 }
             }
+        }
 
-            if let Some(sim_matcher) = &self.sim_matcher {
-                for sim_result in sim_matcher.process(text) {
-                    let result_dict =
-                        match_result_dict
-                            .entry(sim_result.match_id)
-                            .or_insert(ResultDict {
-                                result_list: Vec::new(),
-                                exemption_flag: false,
-                            });
+        // 无法编译的正则只有在构建后才能得知，存在时跳过所在词表重新构建一次
+        let (mut matcher, mut warning_list, _) = Self::build_with_report(
+            &Self::without_skipped_tables(match_table_dict, &skipped_table_list),
+            options.clone(),
+        )?;
+        let invalid_regex_table_list = matcher.invalid_regex_table_list();
+        if !invalid_regex_table_list.is_empty() {
+            skipped_table_list.extend(invalid_regex_table_list);
+            (matcher, warning_list, _) = Self::build_with_report(
+                &Self::without_skipped_tables(match_table_dict, &skipped_table_list),
+                options,
+            )?;
+        }
 
-                    result_dict.result_list.push(MatchResult {
-                        table_id: sim_result.table_id,
-                        word: sim_result.word,
-                    });
-                }
+        Ok((matcher, warning_list, skipped_table_list))
+    }
+
+    // 单个词表在strict模式下导致构建失败的错误，与validate以及try_new_with_report中的判断一致
+    fn check_table(
+        match_id: &str,
+        table: &MatchTable,
+        options: &MatcherOptions,
+    ) -> Result<(), MatcherError> {
+        // 只含豁免词的词表只需校验豁免词
+        if table.wordlist.is_empty() && !Self::has_empty_word(table) {
+            return Ok(());
+        }
+
+        if Self::has_empty_word(table) {
+            return Err(MatcherError::EmptyWord {
+                match_id: match_id.to_owned(),
+                table_id: table.table_id,
+            });
+        }
+
+        if let Some(simple_match_type) = table
+            .simple_match_type_iter()
+            .filter(|_| matches!(table.match_table_type, MatchTableType::Simple))
+            .find(|simple_match_type| simple_match_type.is_conflicting())
+        {
+            return Err(MatcherError::ConflictingSimpleMatchType {
+                match_id: match_id.to_owned(),
+                table_id: table.table_id,
+                simple_match_type,
+            });
+        }
+
+        Self::check_process_type(table, options)?;
+
+        if options.dedup_words == DedupPolicy::Error {
+            let mut word_set = AHashSet::new();
+            if let Some(word) = table.wordlist.iter().find(|&word| !word_set.insert(word)) {
+                return Err(MatcherError::DuplicateWord {
+                    match_id: match_id.to_owned(),
+                    table_id: table.table_id,
+                    word: word.to_owned(),
+                });
             }
+        }
 
-            match_result_dict
-                .into_iter()
-                .filter_map(|(match_id, result_dict)| {
-                    likely(!result_dict.exemption_flag)
-                        .then_some((match_id, result_dict.result_list))
-                })
-                .collect()
-        } else {
-            AHashMap::new()
+        Ok(())
+    }
+
+    // simple词表使用simple_matcher_options中的覆盖映射表，similar词表只使用内置映射表
+    fn check_process_type(
+        table: &MatchTable,
+        options: &MatcherOptions,
+    ) -> Result<(), MatcherError> {
+        match table.match_table_type {
+            MatchTableType::Simple => {
+                for simple_match_type in table.simple_match_type_iter() {
+                    options
+                        .simple_matcher_options
+                        .check_simple_match_type(simple_match_type)?;
+                }
+            }
+            MatchTableType::SimilarTextLevenshtein => {
+                SimpleMatcherOptions::default().check_simple_match_type(table.simple_match_type)?
+            }
+            _ => {}
         }
+
+        Ok(())
     }
 
-    pub fn word_match(&self, text: &str) -> HashMap<&str, String> {
-        self.word_match_raw(text)
+    fn without_skipped_tables<'a>(
+        match_table_dict: &MatchTableDict<'a>,
+        skipped_table_list: &[SkippedTable],
+    ) -> MatchTableDict<'a> {
+        match_table_dict
+            .iter()
+            .map(|(&match_id, table_list)| {
+                (
+                    match_id,
+                    table_list
+                        .iter()
+                        .filter(|table| {
+                            !skipped_table_list.iter().any(|skipped_table| {
+                                skipped_table.match_id == match_id
+                                    && skipped_table.table_id == table.table_id
+                            })
+                        })
+                        .cloned()
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    // 含无法编译正则的正则词表，包括豁免词中无法编译的正则
+    fn invalid_regex_table_list(&self) -> Vec<SkippedTable> {
+        [&self.regex_matcher, &self.exemption_regex_matcher]
             .into_iter()
-            .map(|(match_id, result_list)| {
-                (match_id, unsafe {
-                    to_string(&result_list).unwrap_unchecked()
-                })
+            .flatten()
+            .flat_map(|regex_matcher| {
+                regex_matcher
+                    .stats()
+                    .table_stats_list
+                    .iter()
+                    .filter(|table_stats| !table_stats.invalid_pattern_list.is_empty())
+                    .map(|table_stats| SkippedTable {
+                        match_id: table_stats.match_id.clone(),
+                        table_id: table_stats.table_id,
+                        // 每个无法编译的正则一个InvalidRegex，多于一个时合并为MultipleErrors
+                        reason: MatcherError::from_error_list(
+                            table_stats
+                                .invalid_pattern_list
+                                .iter()
+                                .map(|(pattern, source)| MatcherError::InvalidRegex {
+                                    match_id: table_stats.match_id.clone(),
+                                    table_id: table_stats.table_id,
+                                    pattern: pattern.clone(),
+                                    source: source.clone(),
+                                })
+                                .collect(),
+                        )
+                        .unwrap_err(),
+                    })
+                    .collect::<Vec<SkippedTable>>()
             })
             .collect()
     }
 
-    pub fn word_match_as_string(&self, text: &str) -> String {
-        unsafe { to_string(&self.word_match(text)).unwrap_unchecked() }
+    // 只含豁免词的词表，其豁免词作用于同match_id下的所有词表，eg. 引用的新闻标题使整个match_id失效
+    // 同match_id下没有其他非空词表时豁免词无可豁免的对象，仍视为空词表
+    #[inline]
+    fn is_exemption_table(table: &MatchTable, table_list: &[MatchTable]) -> bool {
+        table.wordlist.is_empty()
+            && !table.exemption_wordlist.is_empty()
+            && table_list
+                .iter()
+                .any(|other_table| !other_table.wordlist.is_empty())
     }
-}
 
-impl<'a> TextMatcherTrait<'a, MatchResult<'a>> for Matcher {
-    fn is_match(&self, text: &str) -> bool {
-        if let Some(simple_matcher) = &self.simple_matcher {
-            if simple_matcher.is_match(text) {
-                return true;
+    // 邻近字词表的空分组不影响其他分组，不视为空词
+    #[inline]
+    fn has_empty_word(table: &MatchTable) -> bool {
+        (!matches!(table.match_table_type, MatchTableType::SimilarChar)
+            && table.wordlist.iter().any(str::is_empty))
+            || table.exemption_wordlist.iter().any(str::is_empty)
+    }
+
+    // 同try_new_with_options，额外返回构建各阶段的耗时以及计数，用于排查构建耗时异常
+    pub fn try_new_with_report(
+        match_table_dict: &MatchTableDict,
+        options: MatcherOptions,
+    ) -> Result<(Matcher, Vec<MatcherWarning>, BuildReport), MatcherError> {
+        let (matcher, warning_list, build_report) =
+            Self::build_with_report(match_table_dict, options)?;
+
+        // 无法编译的正则只有在构建后才能得知，每个正则各返回一个错误
+        MatcherError::from_error_list(
+            matcher
+                .invalid_regex_table_list()
+                .into_iter()
+                .flat_map(|skipped_table| match skipped_table.reason {
+                    MatcherError::MultipleErrors { error_list } => error_list,
+                    reason => vec![reason],
+                })
+                .collect(),
+        )?;
+
+        Ok((matcher, warning_list, build_report))
+    }
+
+    // 校验词表后构建，无法编译的正则被丢弃，所在词表的其余词正常构建
+    fn build_with_report(
+        match_table_dict: &MatchTableDict,
+        options: MatcherOptions,
+    ) -> Result<(Matcher, Vec<MatcherWarning>, BuildReport), MatcherError> {
+        let mut warning_list = Self::validate(match_table_dict)?;
+        // EmptyWordlistPolicy::Reject时，空词表由告警转为错误
+        if options.empty_wordlist_policy == EmptyWordlistPolicy::Reject {
+            if let Some(MatcherWarning::EmptyWordlist { match_id, table_id }) = warning_list
+                .iter()
+                .find(|warning| matches!(warning, MatcherWarning::EmptyWordlist { .. }))
+            {
+                return Err(MatcherError::EmptyWordlist {
+                    match_id: match_id.clone(),
+                    table_id: *table_id,
+                });
             }
         }
 
-        if let Some(regex_matcher) = &self.regex_matcher {
-            if regex_matcher.is_match(text) {
-                return true;
-            }
+        if options.inherit_simple_match_type_for_exemptions {
+            warning_list.retain(|warning| {
+                !matches!(
+                    warning,
+                    MatcherWarning::ExemptionSimpleMatchTypeNotCovered { .. }
+                )
+            });
         }
 
-        if let Some(sim_matcher) = &self.sim_matcher {
-            if sim_matcher.is_match(text) {
-                return true;
+        match options.dedup_words {
+            DedupPolicy::KeepAll => {}
+            DedupPolicy::KeepFirst => warning_list
+                .retain(|warning| !matches!(warning, MatcherWarning::DuplicateWord { .. })),
+            DedupPolicy::Error => {
+                if let Some(MatcherWarning::DuplicateWord {
+                    match_id,
+                    table_id,
+                    word,
+                }) = warning_list
+                    .iter()
+                    .find(|warning| matches!(warning, MatcherWarning::DuplicateWord { .. }))
+                {
+                    return Err(MatcherError::DuplicateWord {
+                        match_id: match_id.clone(),
+                        table_id: *table_id,
+                        word: word.clone(),
+                    });
+                }
             }
         }
 
-        false
+        let (matcher, build_report) = Self::build(match_table_dict, options)?;
+
+        if let Some(regex_matcher) = &matcher.regex_matcher {
+            warning_list.extend(
+                regex_matcher
+                    .stats()
+                    .table_stats_list
+                    .iter()
+                    .filter(|table_stats| !table_stats.fallback_pattern_list.is_empty())
+                    .map(|table_stats| MatcherWarning::RegexSetFallback {
+                        match_id: table_stats.match_id.clone(),
+                        table_id: table_stats.table_id,
+                        pattern_list: table_stats
+                            .fallback_pattern_list
+                            .iter()
+                            .map(|(pattern, _)| pattern.clone())
+                            .collect(),
+                    }),
+            );
+        }
+
+        Ok((matcher, warning_list, build_report))
+    }
+
+    pub fn validate(
+        match_table_dict: &MatchTableDict,
+    ) -> Result<Vec<MatcherWarning>, MatcherError> {
+        let mut warning_list = Vec::new();
+        let mut error_list = Vec::new();
+
+        for (&match_id, table_list) in match_table_dict {
+            let mut table_id_set = AHashSet::new();
+            for table in table_list {
+                if !table_id_set.insert(table.table_id) {
+                    error_list.push(MatcherError::DuplicateTableId {
+                        match_id: match_id.to_owned(),
+                        table_id: table.table_id,
+                    });
+                }
+
+                if Self::is_exemption_table(table, table_list) {
+                    if Self::has_empty_word(table) {
+                        error_list.push(MatcherError::EmptyWord {
+                            match_id: match_id.to_owned(),
+                            table_id: table.table_id,
+                        });
+                    }
+                    continue;
+                }
+
+                if table.wordlist.is_empty()
+                    || (matches!(table.match_table_type, MatchTableType::SimilarChar)
+                        && table.wordlist.iter().all(str::is_empty))
+                {
+                    warning_list.push(MatcherWarning::EmptyWordlist {
+                        match_id: match_id.to_owned(),
+                        table_id: table.table_id,
+                    });
+                    continue;
+                }
+
+                if Self::has_empty_word(table) {
+                    error_list.push(MatcherError::EmptyWord {
+                        match_id: match_id.to_owned(),
+                        table_id: table.table_id,
+                    });
+                }
+
+                let mut word_set = AHashSet::new();
+                for word in table.wordlist.iter() {
+                    if !word_set.insert(word) {
+                        warning_list.push(MatcherWarning::DuplicateWord {
+                            match_id: match_id.to_owned(),
+                            table_id: table.table_id,
+                            word: word.to_owned(),
+                        });
+                    }
+                }
+
+                if !matches!(table.match_table_type, MatchTableType::Simple) {
+                    continue;
+                }
+
+                if let Some(simple_match_type) = table
+                    .simple_match_type_iter()
+                    .find(|simple_match_type| simple_match_type.is_conflicting())
+                {
+                    error_list.push(MatcherError::ConflictingSimpleMatchType {
+                        match_id: match_id.to_owned(),
+                        table_id: table.table_id,
+                        simple_match_type,
+                    });
+                    continue;
+                }
+
+                if !table.exemption_wordlist.is_empty()
+                    && !SimpleMatchType::FanjianDeleteNormalize
+                        .contains(table.simple_match_type.canonicalize())
+                {
+                    warning_list.push(MatcherWarning::ExemptionSimpleMatchTypeNotCovered {
+                        match_id: match_id.to_owned(),
+                        table_id: table.table_id,
+                        simple_match_type: table.simple_match_type
+                            - SimpleMatchType::FanjianDeleteNormalize,
+                    });
+                }
+
+                let simple_match_type = table.simple_match_type.canonicalize();
+                if let Some(covered_by_table) = table_list.iter().find(|other_table| {
+                    matches!(other_table.match_table_type, MatchTableType::Simple)
+                        && !other_table.wordlist.is_empty()
+                        && other_table.table_id != table.table_id
+                        && {
+                            let other_simple_match_type =
+                                other_table.simple_match_type.canonicalize();
+                            other_simple_match_type != simple_match_type
+                                && other_simple_match_type.contains(simple_match_type)
+                        }
+                }) {
+                    warning_list.push(MatcherWarning::RedundantSimpleMatchType {
+                        match_id: match_id.to_owned(),
+                        table_id: table.table_id,
+                        covered_by_table_id: covered_by_table.table_id,
+                    });
+                }
+            }
+        }
+
+        MatcherError::from_error_list(error_list)?;
+        Ok(warning_list)
+    }
+
+    pub fn new_with_options(match_table_dict: &MatchTableDict, options: MatcherOptions) -> Matcher {
+        Self::build(match_table_dict, options)
+            .unwrap_or_else(|e| panic!("{e}"))
+            .0
+    }
+
+    // 去除词表内的重复词，只保留第一个，不存在重复词时返回None
+    fn dedup_wordlist(wordlist: &VarZeroVec<str>) -> Option<VarZeroVec<'static, str>> {
+        let mut word_set = AHashSet::new();
+        let dedup_word_list: Vec<&str> = wordlist
+            .iter()
+            .filter(|&word| word_set.insert(word))
+            .collect();
+
+        (dedup_word_list.len() < wordlist.len()).then(|| VarZeroVec::from(&dedup_word_list))
+    }
+
+    fn build(
+        match_table_dict: &MatchTableDict,
+        options: MatcherOptions,
+    ) -> Result<(Matcher, BuildReport), MatcherError> {
+        if let (Some(max_input_bytes), OverLimitPolicy::ChunkAndUnion { overlap_bytes }) =
+            (options.max_input_bytes, options.over_limit_policy)
+        {
+            if overlap_bytes >= max_input_bytes {
+                return Err(MatcherError::InvalidOverlap {
+                    overlap_bytes,
+                    max_input_bytes,
+                });
+            }
+        }
+
+        let mut build_report = BuildReport {
+            match_id_count: match_table_dict.len(),
+            ..Default::default()
+        };
+
+        let table_split_start = Instant::now();
+        let mut word_id: u64 = 0; // 词ID 全局唯一
+        let mut word_table_list: Vec<Arc<WordTableConf>> = Vec::new();
+        let mut exemption_overlay = ExemptionOverlay::default();
+
+        let mut simple_wordlist_dict: AHashMap<SimpleMatchType, Vec<SimpleWord>> = AHashMap::new();
+        // 豁免词单独构建匹配器，豁免词独有的匹配类型不会为每条文本生成变体
+        let mut exemption_wordlist_dict: AHashMap<SimpleMatchType, Vec<SimpleWord>> =
+            AHashMap::new();
+        let mut exemption_match_id_set = AHashSet::new();
+        let mut exemption_regex_table_list = Vec::new();
+        let mut exemption_regex_table_index_list = Vec::new();
+
+        // 按dedup_words去重后的词表，与match_table_dict的遍历顺序一一对应，不存在重复词的词表为None，直接使用原词表
+        let dedup_wordlist_list: Vec<Vec<Option<VarZeroVec<str>>>> = match_table_dict
+            .values()
+            .map(|table_list| {
+                table_list
+                    .iter()
+                    .map(|table| match options.dedup_words {
+                        DedupPolicy::KeepAll => None,
+                        DedupPolicy::KeepFirst | DedupPolicy::Error => {
+                            Self::dedup_wordlist(&table.wordlist)
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut regex_table_list: Vec<RegexTable> = Vec::new();
+        let mut sim_table_list: Vec<SimTable> = Vec::new();
+
+        let mut table_index: usize = 0; // 词表下标，跳过的match_id下的词表不占用下标
+        let mut table_index_dict: AHashMap<(String, u32), Vec<usize>> = AHashMap::new();
+        let mut regex_table_index_list = Vec::new();
+        let mut sim_table_index_list = Vec::new();
+        let mut table_conf_list = Vec::new();
+
+        for ((&match_id, table_list), dedup_wordlist_list) in
+            match_table_dict.iter().zip(&dedup_wordlist_list)
+        {
+            // 豁免是match_id级别的，当match_id下所有词表均为空时，豁免词无可豁免的对象，直接跳过，避免占用词ID以及ac自动机空间
+            if table_list.iter().all(|table| table.wordlist.is_empty()) {
+                continue;
+            }
+
+            build_report.table_count += table_list.len();
+
+            for (table, dedup_wordlist) in table_list.iter().zip(dedup_wordlist_list) {
+                let table_id = table.table_id;
+                table_index_dict
+                    .entry((match_id.to_owned(), table_id))
+                    .or_default()
+                    .push(table_index);
+                let match_table_type = &table.match_table_type;
+                let wordlist = dedup_wordlist.as_ref().unwrap_or(&table.wordlist);
+                let exemption_wordlist = &table.exemption_wordlist;
+
+                if !wordlist.is_empty() {
+                    Self::check_process_type(table, &options)?;
+                    match match_table_type {
+                        MatchTableType::Simple => {
+                            let word_table_conf = Arc::new(WordTableConf {
+                                match_id: match_id.to_owned(),
+                                table_id,
+                                table_index,
+                                first_word_id: word_id,
+                            });
+                            // 单独指定匹配类型的词放入对应匹配类型的词表，命中结果仍归属同一table_id
+                            for word in wordlist.iter() {
+                                let simple_match_type = table
+                                    .simple_match_type_override
+                                    .get(word)
+                                    .copied()
+                                    .unwrap_or(table.simple_match_type);
+                                word_table_list.push(Arc::clone(&word_table_conf));
+                                simple_wordlist_dict
+                                    .entry(simple_match_type)
+                                    .or_default()
+                                    .push(SimpleWord { word_id, word });
+                                word_id += 1;
+                            }
+                            build_report.simple_word_count += wordlist.len();
+                        }
+                        MatchTableType::SimilarTextLevenshtein => {
+                            sim_table_list.push(SimTable {
+                                table_id,
+                                match_id,
+                                wordlist,
+                                threshold: DEFAULT_SIM_THRESHOLD,
+                                simple_match_type: table.simple_match_type,
+                                process_words: true,
+                                sim_match_type: table.sim_match_type,
+                                top_k: None,
+                            });
+                            sim_table_index_list.push(table_index);
+                        }
+                        _ => {
+                            regex_table_list.push(RegexTable {
+                                table_id,
+                                match_id,
+                                match_table_type,
+                                wordlist,
+                                acrostic_position: table.acrostic_position,
+                                prefer_matched_text: table.prefer_matched_text,
+                                regex_flags: table.regex_flags,
+                                acrostic_separator: table.acrostic_separator,
+                            });
+                            regex_table_index_list.push(table_index);
+                        }
+                    }
+                }
+
+                if !exemption_wordlist.is_empty()
+                    && matches!(
+                        match_table_type,
+                        MatchTableType::Regex | MatchTableType::Wildcard
+                    )
+                {
+                    // 正则及通配符词表的豁免词同样是正则 / 通配符，由单独的regex匹配器匹配
+                    exemption_match_id_set.insert(match_id.to_owned());
+                    exemption_regex_table_list.push(RegexTable {
+                        table_id,
+                        match_id,
+                        match_table_type,
+                        wordlist: exemption_wordlist,
+                        acrostic_position: table.acrostic_position,
+                        prefer_matched_text: false,
+                        regex_flags: table.regex_flags,
+                        acrostic_separator: table.acrostic_separator,
+                    });
+                    exemption_regex_table_index_list.push(table_index);
+                    exemption_overlay.base_regex_exemption_set.extend(
+                        exemption_wordlist
+                            .iter()
+                            .map(|word| (match_id.to_owned(), table_id, word.to_owned())),
+                    );
+                    build_report.exemption_word_count += exemption_wordlist.len();
+                } else if !exemption_wordlist.is_empty() {
+                    let word_table_conf = Arc::new(WordTableConf {
+                        match_id: match_id.to_owned(),
+                        table_id,
+                        table_index,
+                        first_word_id: word_id,
+                    });
+
+                    // 豁免词默认的繁简删除归一不可用时退化为不做该转换
+                    let default_exemption_simple_match_type = options
+                        .simple_matcher_options
+                        .available_simple_match_type(SimpleMatchType::FanjianDeleteNormalize);
+                    let exemption_simple_match_type = match (
+                        options.inherit_simple_match_type_for_exemptions,
+                        match_table_type,
+                    ) {
+                        (true, MatchTableType::Simple) => {
+                            default_exemption_simple_match_type | table.simple_match_type
+                        }
+                        _ => default_exemption_simple_match_type,
+                    };
+                    let simple_word_list = exemption_wordlist_dict
+                        .entry(exemption_simple_match_type)
+                        .or_default();
+                    exemption_match_id_set.insert(match_id.to_owned());
+
+                    for exemption_word in exemption_wordlist.iter() {
+                        exemption_overlay
+                            .base_word_id_dict
+                            .entry((match_id.to_owned(), table_id, exemption_word.to_owned()))
+                            .or_default()
+                            .push(word_id);
+                        word_table_list.push(Arc::clone(&word_table_conf));
+                        simple_word_list.push(SimpleWord {
+                            word_id,
+                            word: exemption_word,
+                        });
+                        word_id += 1;
+                    }
+                    build_report.exemption_word_count += exemption_wordlist.len();
+                }
+
+                table_conf_list.push(TableConf {
+                    match_id: match_id.to_owned(),
+                    table_id,
+                    match_table_type: table.match_table_type,
+                    simple_match_type: table.simple_match_type,
+                    acrostic_position: table.acrostic_position,
+                    prefer_matched_text: table.prefer_matched_text,
+                    sim_match_type: table.sim_match_type,
+                    regex_flags: table.regex_flags,
+                    acrostic_separator: table.acrostic_separator,
+                    simple_match_type_override: match match_table_type {
+                        MatchTableType::Simple => wordlist
+                            .iter()
+                            .filter_map(|word| {
+                                table
+                                    .simple_match_type_override
+                                    .get(word)
+                                    .map(|&simple_match_type| (word.to_owned(), simple_match_type))
+                            })
+                            .collect(),
+                        _ => AHashMap::new(),
+                    },
+                });
+                table_index += 1;
+            }
+            // 词ID同时是word_table_list的下标，simple词与豁免词共用同一个计数器，不会互相冲突
+            debug_assert_eq!(word_id as usize, word_table_list.len());
+        }
+        build_report.table_split_duration = table_split_start.elapsed();
+        build_report.regex_table_count = regex_table_list.len();
+        build_report.sim_table_count = sim_table_list.len();
+
+        let exemption_simple_matcher = (!exemption_wordlist_dict.is_empty())
+            .then(|| {
+                SimpleMatcher::try_new_with_options(
+                    &exemption_wordlist_dict,
+                    options.simple_matcher_options.clone(),
+                )
+            })
+            .transpose()?;
+        let simple_matcher = (!simple_wordlist_dict.is_empty())
+            .then(|| {
+                SimpleMatcher::try_new_with_options(
+                    &simple_wordlist_dict,
+                    options.simple_matcher_options,
+                )
+            })
+            .transpose()?;
+        for simple_matcher in [&simple_matcher, &exemption_simple_matcher]
+            .into_iter()
+            .flatten()
+        {
+            let simple_build_stats = simple_matcher.build_stats();
+            build_report.variant_expansion_duration +=
+                simple_build_stats.variant_expansion_duration;
+            build_report.ac_build_duration += simple_build_stats.ac_build_duration;
+            build_report.ac_pattern_count += simple_build_stats.ac_pattern_count;
+        }
+
+        let regex_compile_start = Instant::now();
+        let exemption_regex_matcher = (!exemption_regex_table_list.is_empty())
+            .then(|| {
+                RegexMatcher::try_new_with_options(
+                    &exemption_regex_table_list,
+                    options.regex_matcher_options.clone(),
+                )
+                .map(|(regex_matcher, _)| regex_matcher)
+            })
+            .transpose()?;
+        let regex_matcher = (!regex_table_list.is_empty())
+            .then(|| {
+                RegexMatcher::try_new_with_options(&regex_table_list, options.regex_matcher_options)
+                    .map(|(regex_matcher, _)| regex_matcher)
+            })
+            .transpose()?;
+        build_report.regex_compile_duration = regex_compile_start.elapsed();
+
+        let sim_table_prep_start = Instant::now();
+        let sim_matcher = (!sim_table_list.is_empty())
+            .then(|| SimMatcher::try_new_with_options(&sim_table_list, options.sim_matcher_options))
+            .transpose()?;
+        build_report.sim_table_prep_duration = sim_table_prep_start.elapsed();
+
+        Ok((
+            Matcher {
+                word_table_list: Arc::new(word_table_list),
+                simple_matcher,
+                exemption_simple_matcher,
+                exemption_match_id_set: Arc::new(exemption_match_id_set),
+                exemption_regex_matcher,
+                exemption_regex_table_index_list: Arc::new(exemption_regex_table_index_list),
+                regex_matcher,
+                sim_matcher,
+                max_input_bytes: options.max_input_bytes,
+                over_limit_policy: options.over_limit_policy,
+                treat_blank_as_empty: options.treat_blank_as_empty,
+                normalize_newlines: options.normalize_newlines,
+                result_fingerprint: options.result_fingerprint,
+                report_span: options.report_span,
+                report_table_type: options.report_table_type,
+                #[cfg(feature = "rayon")]
+                min_parallel_batch: options.min_parallel_batch,
+                exemption_overlay: Arc::new(exemption_overlay),
+                table_index_dict: Arc::new(table_index_dict),
+                regex_table_index_list: Arc::new(regex_table_index_list),
+                sim_table_index_list: Arc::new(sim_table_index_list),
+                table_count: table_index,
+                table_conf_list: Arc::new(table_conf_list),
+            },
+            build_report,
+        ))
+    }
+
+    // 由内部状态还原当前实际加载的词表，用于审计，以其重新构建的Matcher匹配结果一致
+    // 构建时跳过的match_id，去重去除的重复词以及无法编译的正则均未加载，不会导出；运行时已commit的豁免词并入所属词表
+    pub fn export_table_dict(&self) -> OwnedMatchTableDict {
+        let mut owned_table_list: Vec<OwnedMatchTable> = self
+            .table_conf_list
+            .iter()
+            .map(|table_conf| OwnedMatchTable {
+                table_id: table_conf.table_id,
+                match_table_type: table_conf.match_table_type,
+                wordlist: Vec::new(),
+                exemption_wordlist: Vec::new(),
+                simple_match_type: table_conf.simple_match_type,
+                acrostic_position: table_conf.acrostic_position,
+                prefer_matched_text: table_conf.prefer_matched_text,
+                sim_match_type: table_conf.sim_match_type,
+                regex_flags: table_conf.regex_flags,
+                acrostic_separator: table_conf.acrostic_separator,
+                simple_match_type_override: table_conf.simple_match_type_override.clone(),
+            })
+            .collect();
+
+        // 正常词与豁免词共用词ID空间，分别存在于两个simple匹配器中
+        for (word_id, word_table_conf) in self.word_table_list.iter().enumerate() {
+            let word_id = word_id as u64;
+            let owned_table = &mut owned_table_list[word_table_conf.table_index];
+            if let Some(word) = self
+                .simple_matcher
+                .as_ref()
+                .and_then(|simple_matcher| simple_matcher.get_word(word_id))
+            {
+                owned_table.wordlist.push(word.to_owned());
+            } else if let Some(exemption_word) = self
+                .exemption_simple_matcher
+                .as_ref()
+                .filter(|_| {
+                    !self
+                        .exemption_overlay
+                        .removed_word_id_set
+                        .contains(&word_id)
+                })
+                .and_then(|exemption_simple_matcher| exemption_simple_matcher.get_word(word_id))
+            {
+                owned_table
+                    .exemption_wordlist
+                    .push(exemption_word.to_owned());
+            }
+        }
+
+        if let Some(regex_matcher) = &self.regex_matcher {
+            for (regex_table_index, wordlist) in regex_matcher.table_wordlist_dict() {
+                owned_table_list[self.regex_table_index_list[regex_table_index]].wordlist =
+                    wordlist.into_iter().map(str::to_owned).collect();
+            }
+        }
+
+        if let Some(exemption_regex_matcher) = &self.exemption_regex_matcher {
+            for (regex_table_index, wordlist) in exemption_regex_matcher.table_wordlist_dict() {
+                let table_index = self.exemption_regex_table_index_list[regex_table_index];
+                let table_conf = &self.table_conf_list[table_index];
+                owned_table_list[table_index].exemption_wordlist = wordlist
+                    .into_iter()
+                    .filter(|&word| {
+                        !self.is_removed_regex_exemption(
+                            &table_conf.match_id,
+                            table_conf.table_id,
+                            word,
+                        )
+                    })
+                    .map(str::to_owned)
+                    .collect();
+            }
+        }
+
+        if let Some(sim_matcher) = &self.sim_matcher {
+            for (sim_table_index, &table_index) in self.sim_table_index_list.iter().enumerate() {
+                owned_table_list[table_index].wordlist =
+                    sim_matcher.table_wordlist(sim_table_index).to_vec();
+            }
+        }
+
+        let mut owned_match_table_dict: AHashMap<String, Vec<OwnedMatchTable>> = AHashMap::new();
+        for (table_conf, owned_table) in self.table_conf_list.iter().zip(owned_table_list) {
+            owned_match_table_dict
+                .entry(table_conf.match_id.clone())
+                .or_default()
+                .push(owned_table);
+        }
+
+        // 运行时添加的豁免词所属词表不存在时，单独导出为只含豁免词的simple词表
+        for (word_table_conf, exemption_word) in &self.exemption_overlay.word_list {
+            let owned_table_list = owned_match_table_dict
+                .entry(word_table_conf.match_id.clone())
+                .or_default();
+            match owned_table_list
+                .iter_mut()
+                .find(|owned_table| owned_table.table_id == word_table_conf.table_id)
+            {
+                Some(owned_table) => owned_table.exemption_wordlist.push(exemption_word.clone()),
+                None => owned_table_list.push(OwnedMatchTable {
+                    table_id: word_table_conf.table_id,
+                    match_table_type: MatchTableType::Simple,
+                    wordlist: Vec::new(),
+                    exemption_wordlist: vec![exemption_word.clone()],
+                    simple_match_type: SimpleMatchType::None,
+                    acrostic_position: AcrosticPosition::default(),
+                    prefer_matched_text: false,
+                    sim_match_type: SimMatchType::default(),
+                    regex_flags: RegexFlags::default(),
+                    acrostic_separator: AcrosticSeparator::default(),
+                    simple_match_type_override: AHashMap::new(),
+                }),
+            }
+        }
+
+        OwnedMatchTableDict(owned_match_table_dict)
+    }
+
+    // 两个Matcher是否共享同一份构建产物，即其中一个由另一个clone而来；运行时增删豁免词不影响该判断
+    pub fn ptr_eq(&self, other: &Matcher) -> bool {
+        Arc::ptr_eq(&self.word_table_list, &other.word_table_list)
+    }
+
+    pub fn stats(&self) -> MatcherStats {
+        let mut matcher_stats = MatcherStats {
+            regex_matcher_stats: self
+                .regex_matcher
+                .as_ref()
+                .map(|regex_matcher| regex_matcher.stats().clone())
+                .unwrap_or_default(),
+            ..Default::default()
+        };
+        for simple_matcher in [&self.simple_matcher, &self.exemption_simple_matcher]
+            .into_iter()
+            .flatten()
+        {
+            let simple_build_stats = simple_matcher.build_stats();
+            matcher_stats.ac_pattern_count += simple_build_stats.ac_pattern_count;
+            matcher_stats.max_table_ac_pattern_count = matcher_stats
+                .max_table_ac_pattern_count
+                .max(simple_build_stats.max_table_ac_pattern_count);
+        }
+
+        matcher_stats
+    }
+
+    // 已加载的match_id，升序；构建时跳过的match_id不含在内，运行时已commit的豁免词所属的match_id含在内
+    pub fn match_ids(&self) -> Vec<&str> {
+        let mut match_id_list: Vec<&str> = self
+            .table_conf_list
+            .iter()
+            .map(|table_conf| table_conf.match_id.as_str())
+            .chain(
+                self.exemption_overlay
+                    .word_list
+                    .iter()
+                    .map(|(word_table_conf, _)| word_table_conf.match_id.as_str()),
+            )
+            .collect();
+        match_id_list.sort_unstable();
+        match_id_list.dedup();
+        match_id_list
+    }
+
+    // 已加载词表的概况，按 (match_id, table_id) 升序；词数与export_table_dict导出的词表一致
+    // 直接按词表下标从各匹配器已加载的词计数，不复制词
+    pub fn tables(&self) -> Vec<TableInfo> {
+        let mut count_list = vec![(0, 0); self.table_conf_list.len()]; // 按词表下标的 (词数, 豁免词数)
+
+        if let Some(simple_matcher) = &self.simple_matcher {
+            for (word_id, ..) in simple_matcher.words() {
+                count_list[self.word_table_list[word_id as usize].table_index].0 += 1;
+            }
+        }
+        if let Some(exemption_simple_matcher) = &self.exemption_simple_matcher {
+            for (word_id, ..) in exemption_simple_matcher.words() {
+                if !self
+                    .exemption_overlay
+                    .removed_word_id_set
+                    .contains(&word_id)
+                {
+                    count_list[self.word_table_list[word_id as usize].table_index].1 += 1;
+                }
+            }
+        }
+
+        if let Some(regex_matcher) = &self.regex_matcher {
+            for (regex_table_index, _) in regex_matcher.table_word_iter() {
+                count_list[self.regex_table_index_list[regex_table_index]].0 += 1;
+            }
+        }
+        if let Some(exemption_regex_matcher) = &self.exemption_regex_matcher {
+            for (regex_table_index, word) in exemption_regex_matcher.table_word_iter() {
+                let table_index = self.exemption_regex_table_index_list[regex_table_index];
+                let table_conf = &self.table_conf_list[table_index];
+                if !self.is_removed_regex_exemption(&table_conf.match_id, table_conf.table_id, word)
+                {
+                    count_list[table_index].1 += 1;
+                }
+            }
+        }
+
+        if let Some(sim_matcher) = &self.sim_matcher {
+            for (sim_table_index, &table_index) in self.sim_table_index_list.iter().enumerate() {
+                count_list[table_index].0 += sim_matcher.table_wordlist(sim_table_index).len();
+            }
+        }
+
+        let mut table_info_list: Vec<TableInfo> = self
+            .table_conf_list
+            .iter()
+            .zip(count_list)
+            .map(|(table_conf, (word_count, exemption_count))| TableInfo {
+                match_id: table_conf.match_id.clone(),
+                table_id: table_conf.table_id,
+                match_table_type: table_conf.match_table_type,
+                word_count,
+                exemption_count,
+            })
+            .collect();
+
+        // 运行时添加的豁免词计入同一 (match_id, table_id) 的第一个词表，词表不存在时单独列为只含豁免词的simple词表
+        let mut overlay_table_index_dict: AHashMap<(&str, u32), usize> = AHashMap::new();
+        for (word_table_conf, _) in &self.exemption_overlay.word_list {
+            let match_id = word_table_conf.match_id.as_str();
+            let table_id = word_table_conf.table_id;
+            let table_index = match self
+                .table_index_dict
+                .get(&(match_id.to_owned(), table_id))
+                .and_then(|table_index_list| table_index_list.first())
+            {
+                Some(&table_index) => table_index,
+                None => *overlay_table_index_dict
+                    .entry((match_id, table_id))
+                    .or_insert_with(|| {
+                        table_info_list.push(TableInfo {
+                            match_id: match_id.to_owned(),
+                            table_id,
+                            match_table_type: MatchTableType::Simple,
+                            word_count: 0,
+                            exemption_count: 0,
+                        });
+                        table_info_list.len() - 1
+                    }),
+            };
+            table_info_list[table_index].exemption_count += 1;
+        }
+
+        table_info_list
+            .sort_unstable_by(|a, b| (&a.match_id, a.table_id).cmp(&(&b.match_id, b.table_id)));
+        table_info_list
+    }
+
+    // 同tables，序列化为json，eg. [{"match_id": "1", "table_id": 1, "match_table_type": "simple", "word_count": 2, "exemption_count": 0}]
+    pub fn tables_as_string(&self) -> String {
+        unsafe { to_string(&self.tables()).unwrap_unchecked() }
+    }
+
+    // 添加豁免词，commit后生效，按繁简删除归一匹配（恢复被移除的构建时正则豁免词时仍按正则匹配），只作用于豁免词所在的match_id
+    pub fn add_exemption(&mut self, match_id: &str, table_id: u32, word: &str) {
+        Arc::make_mut(&mut self.exemption_overlay)
+            .pending_update_list
+            .push(ExemptionUpdate::Add {
+                match_id: match_id.to_owned(),
+                table_id,
+                word: word.to_owned(),
+            });
+    }
+
+    // 移除豁免词，commit后生效，构建时的豁免词以及运行时添加的豁免词均可移除，不存在时忽略
+    pub fn remove_exemption(&mut self, match_id: &str, table_id: u32, word: &str) {
+        Arc::make_mut(&mut self.exemption_overlay)
+            .pending_update_list
+            .push(ExemptionUpdate::Remove {
+                match_id: match_id.to_owned(),
+                table_id,
+                word: word.to_owned(),
+            });
+    }
+
+    // 按顺序应用待生效的豁免词变更，只重建运行时豁免词的匹配器，正常词及其词ID不受影响
+    pub fn commit(&mut self) {
+        if self.exemption_overlay.pending_update_list.is_empty() {
+            return;
+        }
+        // 与其他clone共享时先复制一份，变更只作用于当前Matcher
+        let exemption_overlay = Arc::make_mut(&mut self.exemption_overlay);
+
+        for exemption_update in exemption_overlay.pending_update_list.drain(..) {
+            match exemption_update {
+                ExemptionUpdate::Add {
+                    match_id,
+                    table_id,
+                    word,
+                } => {
+                    let key = (match_id, table_id, word);
+                    // 重新添加被移除的构建时豁免词，直接恢复
+                    if exemption_overlay.removed_regex_exemption_set.remove(&key) {
+                        continue;
+                    }
+                    if let Some(word_id_list) = exemption_overlay.base_word_id_dict.get(&key) {
+                        if word_id_list
+                            .iter()
+                            .any(|word_id| exemption_overlay.removed_word_id_set.remove(word_id))
+                        {
+                            continue;
+                        }
+                    }
+                    let (match_id, table_id, word) = key;
+                    exemption_overlay.word_list.push((
+                        Arc::new(WordTableConf {
+                            match_id,
+                            table_id,
+                            table_index: usize::MAX, // 豁免词不受TableIdSet影响
+                            first_word_id: exemption_overlay.word_list.len() as u64,
+                        }),
+                        word,
+                    ));
+                }
+                ExemptionUpdate::Remove {
+                    match_id,
+                    table_id,
+                    word,
+                } => {
+                    exemption_overlay
+                        .word_list
+                        .retain(|(word_table_conf, exemption_word)| {
+                            !(word_table_conf.match_id == match_id
+                                && word_table_conf.table_id == table_id
+                                && *exemption_word == word)
+                        });
+                    let key = (match_id, table_id, word);
+                    if exemption_overlay.base_regex_exemption_set.contains(&key) {
+                        exemption_overlay
+                            .removed_regex_exemption_set
+                            .insert(key.clone());
+                    }
+                    if let Some(word_id_list) = exemption_overlay.base_word_id_dict.get(&key) {
+                        exemption_overlay
+                            .removed_word_id_set
+                            .extend(word_id_list.iter().copied());
+                    }
+                }
+            }
+        }
+
+        exemption_overlay.simple_matcher = (!exemption_overlay.word_list.is_empty()).then(|| {
+            SimpleMatcher::new(&AHashMap::from([(
+                SimpleMatcherOptions::default()
+                    .available_simple_match_type(SimpleMatchType::FanjianDeleteNormalize),
+                exemption_overlay
+                    .word_list
+                    .iter()
+                    .enumerate()
+                    .map(|(word_id, (_, word))| SimpleWord {
+                        word_id: word_id as u64,
+                        word,
+                    })
+                    .collect(),
+            )]))
+        });
+    }
+
+    fn _word_match<F>(
+        &self,
+        text: &str,
+        pred: F,
+        ignore_exemption: bool,
+    ) -> Result<AHashMap<&str, Vec<MatchResult>>, MatcherError>
+    where
+        F: Fn(&str, u32, u64) -> bool,
+    {
+        if likely(!text.is_empty()) {
+            let text = self.normalize_input(text);
+            Ok(self._word_match_processed(&self.preprocess(&text)?, pred, ignore_exemption))
+        } else {
+            Ok(AHashMap::new())
+        }
+    }
+
+    fn _word_match_processed<F>(
+        &self,
+        processed_text_list: &[ProcessedText],
+        pred: F,
+        ignore_exemption: bool,
+    ) -> AHashMap<&str, Vec<MatchResult>>
+    where
+        F: Fn(&str, u32, u64) -> bool,
+    {
+        self._word_match_processed_until(processed_text_list, pred, ignore_exemption, None, None)
+            .0
+    }
+
+    // 超过截止时间后停止匹配，返回已匹配的结果以及是否超时；simple匹配不可中断，截止时间在窗口之间以及regex / sim匹配内部检查
+    // disabled_table_set中的词表不参与匹配，豁免词不受影响
+    fn _word_match_processed_until<F>(
+        &self,
+        processed_text_list: &[ProcessedText],
+        pred: F,
+        ignore_exemption: bool,
+        deadline: Option<Instant>,
+        disabled_table_set: Option<&TableIdSet>,
+    ) -> (AHashMap<&str, Vec<MatchResult>>, bool)
+    where
+        F: Fn(&str, u32, u64) -> bool,
+    {
+        let (match_result_dict, deadline_exceeded) = self._result_dict_until(
+            processed_text_list,
+            pred,
+            ignore_exemption,
+            false,
+            deadline,
+            disabled_table_set,
+        );
+
+        (
+            match_result_dict
+                .into_iter()
+                .filter_map(|(match_id, result_dict)| {
+                    likely(
+                        (ignore_exemption || result_dict.exemption_hit_list.is_empty())
+                            && !result_dict.result_list.is_empty(),
+                    )
+                    .then_some((match_id, result_dict.result_list))
+                })
+                .collect(),
+            deadline_exceeded,
+        )
+    }
+
+    // 各match_id的命中结果以及命中的豁免词，ignore_exemption为true时不匹配豁免词
+    // keep_exempted为false时，带豁免词的match_id在regex / sim匹配前先判断是否被豁免，被豁免的词表直接跳过，省去无用的匹配
+    // 有截止时间时未必匹配全部窗口，而豁免词作用于已匹配的窗口，此时不提前判断
+    fn _result_dict_until<F>(
+        &self,
+        processed_text_list: &[ProcessedText],
+        pred: F,
+        ignore_exemption: bool,
+        keep_exempted: bool,
+        deadline: Option<Instant>,
+        disabled_table_set: Option<&TableIdSet>,
+    ) -> (AHashMap<&str, ResultDict<'_>>, bool)
+    where
+        F: Fn(&str, u32, u64) -> bool,
+    {
+        let mut match_result_dict: AHashMap<&str, ResultDict> = AHashMap::new();
+        let mut deadline_exceeded = false;
+        let is_disabled = |table_index: usize| {
+            disabled_table_set
+                .is_some_and(|disabled_table_set| disabled_table_set.contains(table_index))
+        };
+        let skip_exempted = !ignore_exemption && !keep_exempted && deadline.is_none();
+        let exempted_match_id_set = OnceCell::new();
+        let is_skipped = |table_index: usize| {
+            is_disabled(table_index)
+                || (skip_exempted && {
+                    let match_id = self.table_conf_list[table_index].match_id.as_str();
+                    self.has_exemption(match_id)
+                        && exempted_match_id_set
+                            .get_or_init(|| self.exempted_match_id_set(processed_text_list))
+                            .contains(match_id)
+                })
+        };
+
+        let mut processed_window_count = 0;
+        let mut chunk_seen_set: Option<AHashSet<ChunkResultKey>> =
+            (processed_text_list.len() > 1).then(AHashSet::new);
+        let mut is_chunk_duplicate = |key| {
+            chunk_seen_set
+                .as_mut()
+                .is_some_and(|chunk_seen_set| !chunk_seen_set.insert(key))
+        };
+
+        for (index, processed_text) in processed_text_list.iter().enumerate() {
+            // 至少完成第一个窗口的simple匹配
+            if unlikely(index > 0 && deadline.is_some_and(|deadline| Instant::now() >= deadline)) {
+                deadline_exceeded = true;
+                break;
+            }
+            processed_window_count += 1;
+
+            let text = processed_text.text();
+
+            // 分块匹配时各窗口均为输入文本的子串，原文本中的位置需加上窗口的起始位置
+            let window_start =
+                text.as_ptr() as usize - processed_text_list[0].text().as_ptr() as usize;
+            let result_span = |span: Option<MatchSpan>| {
+                span.filter(|_| self.report_span)
+                    .map(|span| match span.variant {
+                        0 => MatchSpan {
+                            start: span.start + window_start,
+                            end: span.end + window_start,
+                            variant: 0,
+                        },
+                        _ => span,
+                    })
+            };
+
+            if let Some(simple_matcher) = &self.simple_matcher {
+                for simple_result in simple_matcher.process_processed(processed_text) {
+                    let word_table_conf = unsafe {
+                        self.word_table_list
+                            .get_unchecked(simple_result.word_id as usize)
+                    };
+
+                    if is_disabled(word_table_conf.table_index)
+                        || !pred(
+                            &word_table_conf.match_id,
+                            word_table_conf.table_id,
+                            simple_result.word_id - word_table_conf.first_word_id,
+                        )
+                        || is_chunk_duplicate(ChunkResultKey::Simple(simple_result.word_id))
+                    {
+                        continue;
+                    }
+
+                    match_result_dict
+                        .entry(&word_table_conf.match_id)
+                        .or_default()
+                        .result_list
+                        .push(self.simple_match_result(
+                            word_table_conf,
+                            simple_result,
+                            result_span,
+                        ));
+                }
+            }
+
+            if let Some(regex_matcher) = &self.regex_matcher {
+                let (regex_result_list, regex_deadline_exceeded) = regex_matcher
+                    .process_until_skipping(
+                        text,
+                        deadline,
+                        self.report_span,
+                        false,
+                        |regex_table_index| {
+                            is_skipped(self.regex_table_index_list[regex_table_index])
+                        },
+                    );
+                deadline_exceeded |= regex_deadline_exceeded;
+
+                for regex_result in regex_result_list {
+                    if !pred(
+                        regex_result.match_id,
+                        regex_result.table_id,
+                        regex_result.word_id,
+                    ) || is_chunk_duplicate(ChunkResultKey::Regex(
+                        regex_result.match_id,
+                        regex_result.table_id,
+                        regex_result.word_id,
+                        regex_result
+                            .span
+                            .filter(|_| regex_result.matched_text.is_some())
+                            .map(|span| (span.start + window_start, span.end + window_start)),
+                    )) {
+                        continue;
+                    }
+
+                    match_result_dict
+                        .entry(regex_result.match_id)
+                        .or_default()
+                        .result_list
+                        .push(self.regex_match_result(regex_result, result_span));
+                }
+            }
+
+            if let Some(sim_matcher) = &self.sim_matcher {
+                let (sim_result_list, sim_deadline_exceeded) =
+                    sim_matcher.process_until_skipping(text, deadline, false, |sim_table_index| {
+                        is_skipped(self.sim_table_index_list[sim_table_index])
+                    });
+                deadline_exceeded |= sim_deadline_exceeded;
+
+                for sim_result in sim_result_list {
+                    if !pred(sim_result.match_id, sim_result.table_id, sim_result.word_id)
+                        || is_chunk_duplicate(ChunkResultKey::Sim(
+                            sim_result.match_id,
+                            sim_result.table_id,
+                            sim_result.word_id,
+                        ))
+                    {
+                        continue;
+                    }
+
+                    match_result_dict
+                        .entry(sim_result.match_id)
+                        .or_default()
+                        .result_list
+                        .push(self.sim_match_result(sim_result));
+                }
+            }
+
+            if unlikely(deadline_exceeded) {
+                break;
+            }
+        }
+
+        // 豁免词在任一已匹配的窗口命中都会作用于整条文本，只有带豁免词的match_id存在命中时才需要匹配豁免词
+        if !ignore_exemption
+            && unlikely(
+                match_result_dict
+                    .keys()
+                    .any(|&match_id| self.has_exemption(match_id)),
+            )
+        {
+            for processed_text in &processed_text_list[..processed_window_count] {
+                self.mark_exemption(processed_text, &mut match_result_dict);
+            }
+        }
+
+        if unlikely(processed_text_list.len() > 1) {
+            // 重叠区内的豁免词会在相邻窗口重复命中，命中结果已在匹配时去重
+            for result_dict in match_result_dict.values_mut() {
+                let mut seen_set = AHashSet::new();
+                result_dict.exemption_hit_list.retain(|exemption_hit| {
+                    seen_set.insert((exemption_hit.table_id, exemption_hit.word.clone()))
+                });
+            }
+        }
+
+        (match_result_dict, deadline_exceeded)
+    }
+
+    // 按窗口、simple / regex / sim的顺序返回第一个未被豁免的命中结果，得到结果后即停止匹配
+    // 豁免词在任一窗口命中都会作用于整条文本，只在首次遇到带豁免词的match_id时匹配豁免词
+    fn _find_first<'b>(&'b self, processed_text_list: &[ProcessedText]) -> Option<MatchResult<'b>> {
+        let exempted_match_id_set = OnceCell::new();
+        let is_exempted = |match_id: &str| {
+            self.has_exemption(match_id)
+                && exempted_match_id_set
+                    .get_or_init(|| self.exempted_match_id_set(processed_text_list))
+                    .contains(match_id)
+        };
+
+        for processed_text in processed_text_list {
+            let text = processed_text.text();
+
+            let window_start =
+                text.as_ptr() as usize - processed_text_list[0].text().as_ptr() as usize;
+            let result_span = |span: Option<MatchSpan>| {
+                span.filter(|_| self.report_span)
+                    .map(|span| match span.variant {
+                        0 => MatchSpan {
+                            start: span.start + window_start,
+                            end: span.end + window_start,
+                            variant: 0,
+                        },
+                        _ => span,
+                    })
+            };
+
+            if let Some(simple_matcher) = &self.simple_matcher {
+                for simple_result in simple_matcher.process_processed_iter(processed_text) {
+                    let word_table_conf = unsafe {
+                        self.word_table_list
+                            .get_unchecked(simple_result.word_id as usize)
+                    };
+
+                    if is_exempted(&word_table_conf.match_id) {
+                        continue;
+                    }
+
+                    return Some(self.simple_match_result(
+                        word_table_conf,
+                        simple_result,
+                        result_span,
+                    ));
+                }
+            }
+
+            if let Some(regex_matcher) = &self.regex_matcher {
+                if let Some(regex_result) = regex_matcher
+                    .process_until_skipping(
+                        text,
+                        None,
+                        self.report_span,
+                        true,
+                        |regex_table_index| {
+                            is_exempted(
+                                &self.table_conf_list
+                                    [self.regex_table_index_list[regex_table_index]]
+                                    .match_id,
+                            )
+                        },
+                    )
+                    .0
+                    .pop()
+                {
+                    return Some(self.regex_match_result(regex_result, result_span));
+                }
+            }
+
+            if let Some(sim_matcher) = &self.sim_matcher {
+                if let Some(sim_result) = sim_matcher
+                    .process_until_skipping(text, None, true, |sim_table_index| {
+                        is_exempted(
+                            &self.table_conf_list[self.sim_table_index_list[sim_table_index]]
+                                .match_id,
+                        )
+                    })
+                    .0
+                    .pop()
+                {
+                    return Some(self.sim_match_result(sim_result));
+                }
+            }
+        }
+
+        None
+    }
+
+    // 由各匹配器的结果构建MatchResult，result_span将窗口内的位置换算为原文本中的位置
+    #[inline]
+    fn simple_match_result<'b, F>(
+        &self,
+        word_table_conf: &WordTableConf,
+        simple_result: SimpleResult<'b>,
+        result_span: F,
+    ) -> MatchResult<'b>
+    where
+        F: Fn(Option<MatchSpan>) -> Option<MatchSpan>,
+    {
+        MatchResult {
+            table_id: word_table_conf.table_id,
+            table_type: self.report_table_type.then_some(TableType::Simple),
+            fingerprint: self.result_fingerprint.then(|| {
+                match_fingerprint(
+                    &word_table_conf.match_id,
+                    word_table_conf.table_id,
+                    &simple_result.word,
+                )
+            }),
+            word: simple_result.word,
+            parts: simple_result.parts,
+            span: result_span(simple_result.span),
+            captures: Vec::new(),
+        }
+    }
+
+    #[inline]
+    fn regex_match_result<'b, F>(
+        &self,
+        regex_result: RegexResult<'b>,
+        result_span: F,
+    ) -> MatchResult<'b>
+    where
+        F: Fn(Option<MatchSpan>) -> Option<MatchSpan>,
+    {
+        MatchResult {
+            table_id: regex_result.table_id,
+            table_type: self.report_table_type.then_some(TableType::Regex),
+            fingerprint: self.result_fingerprint.then(|| {
+                match_fingerprint(
+                    regex_result.match_id,
+                    regex_result.table_id,
+                    &regex_result.word,
+                )
+            }),
+            word: regex_result.word,
+            parts: None,
+            span: result_span(regex_result.span),
+            captures: regex_result.captures,
+        }
+    }
+
+    #[inline]
+    fn sim_match_result<'b>(&self, sim_result: SimResult<'b>) -> MatchResult<'b> {
+        MatchResult {
+            table_id: sim_result.table_id,
+            table_type: self.report_table_type.then_some(TableType::Similar),
+            fingerprint: self.result_fingerprint.then(|| {
+                match_fingerprint(sim_result.match_id, sim_result.table_id, &sim_result.word)
+            }),
+            word: sim_result.word,
+            parts: None,
+            span: None,
+            captures: Vec::new(),
+        }
+    }
+
+    #[inline]
+    fn has_exemption(&self, match_id: &str) -> bool {
+        self.exemption_match_id_set.contains(match_id)
+            || self
+                .exemption_overlay
+                .word_list
+                .iter()
+                .any(|(word_table_conf, _)| word_table_conf.match_id == match_id)
+    }
+
+    #[inline]
+    fn is_removed_regex_exemption(&self, match_id: &str, table_id: u32, word: &str) -> bool {
+        !self
+            .exemption_overlay
+            .removed_regex_exemption_set
+            .is_empty()
+            && self
+                .exemption_overlay
+                .removed_regex_exemption_set
+                .contains(&(match_id.to_owned(), table_id, word.to_owned()))
+    }
+
+    // 标记已命中的match_id是否命中豁免词，豁免词匹配器的转换树与正常词不同时，在此才生成其文本变体
+    fn mark_exemption<'b>(
+        &'b self,
+        processed_text: &ProcessedText,
+        match_result_dict: &mut AHashMap<&'b str, ResultDict<'b>>,
+    ) {
+        if let Some(exemption_simple_matcher) = &self.exemption_simple_matcher {
+            for simple_result in exemption_simple_matcher.process_processed(processed_text) {
+                let word_table_conf = unsafe {
+                    self.word_table_list
+                        .get_unchecked(simple_result.word_id as usize)
+                };
+
+                if let Some(result_dict) =
+                    match_result_dict.get_mut(word_table_conf.match_id.as_str())
+                {
+                    if !self
+                        .exemption_overlay
+                        .removed_word_id_set
+                        .contains(&simple_result.word_id)
+                    {
+                        result_dict.exemption_hit_list.push(ExemptionHit {
+                            table_id: word_table_conf.table_id,
+                            word: simple_result.word,
+                            similarity: 1.0,
+                        });
+                    }
+                }
+            }
+        }
+
+        if let Some(exemption_regex_matcher) = &self.exemption_regex_matcher {
+            // 只匹配已命中的match_id下的豁免正则
+            let (regex_result_list, _) = exemption_regex_matcher.process_until_skipping(
+                processed_text.text(),
+                None,
+                false,
+                false,
+                |regex_table_index| {
+                    !match_result_dict.contains_key(
+                        self.table_conf_list
+                            [self.exemption_regex_table_index_list[regex_table_index]]
+                            .match_id
+                            .as_str(),
+                    )
+                },
+            );
+
+            for regex_result in regex_result_list {
+                if self.is_removed_regex_exemption(
+                    regex_result.match_id,
+                    regex_result.table_id,
+                    &regex_result.word,
+                ) {
+                    continue;
+                }
+                if let Some(result_dict) = match_result_dict.get_mut(regex_result.match_id) {
+                    result_dict.exemption_hit_list.push(ExemptionHit {
+                        table_id: regex_result.table_id,
+                        word: regex_result.word,
+                        similarity: 1.0,
+                    });
+                }
+            }
+        }
+
+        if let Some(overlay_simple_matcher) = &self.exemption_overlay.simple_matcher {
+            for simple_result in overlay_simple_matcher.process_processed(processed_text) {
+                let (word_table_conf, _) = unsafe {
+                    self.exemption_overlay
+                        .word_list
+                        .get_unchecked(simple_result.word_id as usize)
+                };
+
+                if let Some(result_dict) =
+                    match_result_dict.get_mut(word_table_conf.match_id.as_str())
+                {
+                    result_dict.exemption_hit_list.push(ExemptionHit {
+                        table_id: word_table_conf.table_id,
+                        word: simple_result.word,
+                        similarity: 1.0,
+                    });
+                }
+            }
+        }
+    }
+
+    // 在所有窗口中命中任一豁免词的match_id
+    fn exempted_match_id_set<'b>(
+        &'b self,
+        processed_text_list: &[ProcessedText],
+    ) -> AHashSet<&'b str> {
+        let mut match_result_dict: AHashMap<&str, ResultDict> = self
+            .exemption_match_id_set
+            .iter()
+            .map(String::as_str)
+            .chain(
+                self.exemption_overlay
+                    .word_list
+                    .iter()
+                    .map(|(word_table_conf, _)| word_table_conf.match_id.as_str()),
+            )
+            .map(|match_id| (match_id, ResultDict::default()))
+            .collect();
+
+        for processed_text in processed_text_list {
+            self.mark_exemption(processed_text, &mut match_result_dict);
+        }
+
+        match_result_dict
+            .into_iter()
+            .filter(|(_, result_dict)| !result_dict.exemption_hit_list.is_empty())
+            .map(|(match_id, _)| match_id)
+            .collect()
+    }
+
+    // 按超长文本策略切分文本，并为每个窗口生成simple匹配所需的文本变体，结果可在多次匹配之间复用；不统一换行符，需要时先调用normalize_input
+    pub fn preprocess<'b>(&self, text: &'b str) -> Result<Vec<ProcessedText<'b>>, MatcherError> {
+        Ok(self
+            .split_input(text)?
+            .into_iter()
+            .map(|text| match &self.simple_matcher {
+                // 不含触发字符的窗口不生成变体，simple匹配时直接跳过
+                Some(simple_matcher) if simple_matcher.is_candidate(text) => {
+                    simple_matcher.preprocess(text)
+                }
+                _ => ProcessedText::from_text(text),
+            })
+            .collect())
+    }
+
+    pub fn is_match_processed(&self, processed_text_list: &[ProcessedText]) -> bool {
+        processed_text_list.iter().any(|processed_text| {
+            self.simple_matcher
+                .as_ref()
+                .is_some_and(|simple_matcher| simple_matcher.is_match_processed(processed_text))
+                || self
+                    .regex_matcher
+                    .as_ref()
+                    .is_some_and(|regex_matcher| regex_matcher.is_match(processed_text.text()))
+                || self
+                    .sim_matcher
+                    .as_ref()
+                    .is_some_and(|sim_matcher| sim_matcher.is_match(processed_text.text()))
+        })
+    }
+
+    // 同word_match，复用preprocess的结果
+    pub fn word_match_processed(
+        &self,
+        processed_text_list: &[ProcessedText],
+    ) -> HashMap<&str, String> {
+        Self::to_string_dict(self._word_match_processed(processed_text_list, |_, _, _| true, false))
+    }
+
+    pub fn word_match(&self, text: &str) -> HashMap<&str, String> {
+        self.word_match_filtered(text, |_, _, _| true)
+    }
+
+    // 各match_id的命中结果数，与word_match中各match_id的结果数一致，不构建MatchResult以及结果json
+    pub fn word_match_count(&self, text: &str) -> HashMap<&str, usize> {
+        if unlikely(text.is_empty()) {
+            return HashMap::new();
+        }
+        let text = self.normalize_input(text);
+        let Ok(processed_text_list) = self.preprocess(&text) else {
+            return HashMap::new();
+        };
+
+        // 多个窗口时重叠区内在相邻窗口重复产生的命中需去重，退化为完整匹配后计数
+        if unlikely(processed_text_list.len() > 1) {
+            return self
+                ._word_match_processed(&processed_text_list, |_, _, _| true, false)
+                .into_iter()
+                .map(|(match_id, result_list)| (match_id, result_list.len()))
+                .collect();
+        }
+
+        let mut match_count_dict: HashMap<&str, usize> = HashMap::new();
+        for processed_text in &processed_text_list {
+            if let Some(simple_matcher) = &self.simple_matcher {
+                // detailed模式不影响命中与否，不必退化为process_processed
+                for simple_result in simple_matcher.simple_process_iter_processed(processed_text) {
+                    let word_table_conf = unsafe {
+                        self.word_table_list
+                            .get_unchecked(simple_result.word_id as usize)
+                    };
+                    *match_count_dict
+                        .entry(&word_table_conf.match_id)
+                        .or_default() += 1;
+                }
+            }
+
+            if let Some(regex_matcher) = &self.regex_matcher {
+                for regex_result in regex_matcher
+                    .process_until_skipping(processed_text.text(), None, false, false, |_| false)
+                    .0
+                {
+                    *match_count_dict.entry(regex_result.match_id).or_default() += 1;
+                }
+            }
+
+            if let Some(sim_matcher) = &self.sim_matcher {
+                for sim_result in sim_matcher.process_until(processed_text.text(), None).0 {
+                    *match_count_dict.entry(sim_result.match_id).or_default() += 1;
+                }
+            }
+        }
+
+        // 只有带豁免词的match_id存在命中时才需要匹配豁免词
+        if unlikely(
+            match_count_dict
+                .keys()
+                .any(|&match_id| self.has_exemption(match_id)),
+        ) {
+            let exempted_match_id_set = self.exempted_match_id_set(&processed_text_list);
+            match_count_dict.retain(|match_id, _| !exempted_match_id_set.contains(match_id));
+        }
+
+        match_count_dict
+    }
+
+    // 同word_match(text).values()中各结果列表的长度之和
+    pub fn match_count(&self, text: &str) -> usize {
+        self.word_match_count(text).values().sum()
+    }
+
+    // 批量处理文本，结果与输入顺序一致；开启rayon（或parallel）且文本数不少于min_parallel_batch时各文本并行处理
+    // 各文本的匹配缓冲（文本变体、命中计数）由匹配过程内部持有，每条文本各自分配，不在线程间复用
+    pub fn process_batch(&self, text_list: &[&str]) -> Vec<Vec<MatchResult<'_>>> {
+        #[cfg(feature = "rayon")]
+        if self.is_parallel_batch(text_list.len()) {
+            return text_list
+                .par_iter()
+                .map(|&text| self.process(text))
+                .collect();
+        }
+
+        text_list.iter().map(|&text| self.process(text)).collect()
+    }
+
+    // 同process_batch，批量判断文本是否命中
+    pub fn is_match_batch(&self, text_list: &[&str]) -> Vec<bool> {
+        #[cfg(feature = "rayon")]
+        if self.is_parallel_batch(text_list.len()) {
+            return text_list
+                .par_iter()
+                .map(|&text| self.is_match(text))
+                .collect();
+        }
+
+        text_list.iter().map(|&text| self.is_match(text)).collect()
+    }
+
+    // 线程池只有一个线程时并行调度只有开销
+    #[cfg(feature = "rayon")]
+    #[inline]
+    fn is_parallel_batch(&self, text_count: usize) -> bool {
+        text_count >= self.min_parallel_batch && rayon::current_num_threads() > 1
+    }
+
+    // 将未被豁免的命中词在文本中的位置替换为mask，每个字符替换为一个mask
+    // simple词各部分在原文本中的所有出现位置都会被替换，只出现在转换后文本变体中的部分无法对应回原文本，不替换
+    // regex词替换其命中位置；sim词为整段文本相似，不替换
+    // 开启normalize_newlines且文本含需统一的换行符时，返回统一换行后的文本
+    pub fn redact<'b>(&self, text: &'b str, mask: char) -> Cow<'b, str> {
+        let normalized_text = self.normalize_input(text);
+        let span_list = self
+            .hit_span_list(&normalized_text)
+            .into_iter()
+            .map(|(span, _)| span)
+            .collect();
+        match normalized_text {
+            Cow::Borrowed(text) => mask_span_list(text, span_list, mask),
+            Cow::Owned(text) => Cow::Owned(mask_span_list(&text, span_list, mask).into_owned()),
+        }
+    }
+
+    // 将原文本切分为命中片段与未命中片段，按顺序拼接即为原文本，用于渲染高亮
+    // 命中位置同redact；重叠的命中合并为一个片段，取其中位置最长的命中结果，相邻的命中不合并
+    // 开启normalize_newlines时在统一换行后的文本上匹配，命中位置换算回原文本
+    pub fn highlight<'a>(&'a self, text: &'a str) -> Vec<Segment<'a>> {
+        let normalized_text = self.normalize_input(text);
+        let mut hit_span_list = self.hit_span_list(&normalized_text);
+
+        if let Cow::Owned(normalized_text) = &normalized_text {
+            // 统一换行后文本中各字节所在字符在原文本中的 (起始, 结束) 位置，规则同normalize_input
+            let mut offset_list = Vec::with_capacity(normalized_text.len());
+            let mut char_iter = text.char_indices().peekable();
+            while let Some((index, c)) = char_iter.next() {
+                let offset = (index, index + c.len_utf8());
+                match c {
+                    '\r' if char_iter.peek().is_some_and(|&(_, c)| c == '\n') => {}
+                    '\r' | '\u{2028}' | '\u{2029}' => offset_list.push(offset),
+                    _ => offset_list.extend(std::iter::repeat_n(offset, c.len_utf8())),
+                }
+            }
+            for ((start, end), _) in hit_span_list.iter_mut() {
+                (*start, *end) = (offset_list[*start].0, offset_list[*end - 1].1);
+            }
+        }
+
+        // 起始位置相同时较长的命中在前
+        hit_span_list.sort_by_key(|&((start, end), _)| (start, std::cmp::Reverse(end)));
+
+        let mut segment_list = Vec::new();
+        let mut last_end = 0;
+        let mut hit_span_iter = hit_span_list.into_iter().peekable();
+        while let Some(((start, mut end), mut match_result)) = hit_span_iter.next() {
+            let mut longest_len = end - start;
+            while let Some(((next_start, next_end), next_match_result)) =
+                hit_span_iter.next_if(|&((next_start, _), _)| next_start < end)
+            {
+                if next_end - next_start > longest_len {
+                    longest_len = next_end - next_start;
+                    match_result = next_match_result;
+                }
+                end = end.max(next_end);
+            }
+
+            if start > last_end {
+                segment_list.push(Segment {
+                    text: &text[last_end..start],
+                    hit: None,
+                });
+            }
+            segment_list.push(Segment {
+                text: &text[start..end],
+                hit: Some(match_result),
+            });
+            last_end = end;
+        }
+        if last_end < text.len() {
+            segment_list.push(Segment {
+                text: &text[last_end..],
+                hit: None,
+            });
+        }
+
+        segment_list
+    }
+
+    // 未被豁免的命中结果在文本中的位置，simple词为其各部分在原文本中的所有出现位置，不含空位置
+    fn hit_span_list<'a>(&'a self, text: &str) -> Vec<((usize, usize), MatchResult<'a>)> {
+        let mut hit_span_list = Vec::new();
+        let Ok(processed_text_list) = self.preprocess(text) else {
+            return hit_span_list;
+        };
+
+        let mut simple_result_list = Vec::new();
+        let mut regex_result_list = Vec::new();
+        for (index, processed_text) in processed_text_list.iter().enumerate() {
+            if let Some(simple_matcher) = &self.simple_matcher {
+                simple_result_list.extend(
+                    simple_matcher
+                        .process_processed_iter(processed_text)
+                        .map(|simple_result| (index, simple_result)),
+                );
+            }
+
+            if let Some(regex_matcher) = &self.regex_matcher {
+                regex_result_list.extend(
+                    regex_matcher
+                        .process_until_skipping(processed_text.text(), None, true, false, |_| false)
+                        .0
+                        .into_iter()
+                        .map(|regex_result| (index, regex_result)),
+                );
+            }
+        }
+
+        // 只有带豁免词的match_id存在命中时才需要匹配豁免词
+        let mut hit_match_id_iter = simple_result_list
+            .iter()
+            .map(|(_, simple_result)| unsafe {
+                self.word_table_list
+                    .get_unchecked(simple_result.word_id as usize)
+                    .match_id
+                    .as_str()
+            })
+            .chain(
+                regex_result_list
+                    .iter()
+                    .map(|(_, regex_result)| regex_result.match_id),
+            );
+        let exempted_match_id_set =
+            if unlikely(hit_match_id_iter.any(|match_id| self.has_exemption(match_id))) {
+                self.exempted_match_id_set(&processed_text_list)
+            } else {
+                AHashSet::new()
+            };
+
+        let window_start_list: Vec<usize> = processed_text_list
+            .iter()
+            .map(|processed_text| processed_text.text().as_ptr() as usize - text.as_ptr() as usize)
+            .collect();
+        let result_span = |index: usize| {
+            let window_start = window_start_list[index];
+            move |span: Option<MatchSpan>| {
+                span.filter(|_| self.report_span)
+                    .map(|span| match span.variant {
+                        0 => MatchSpan {
+                            start: span.start + window_start,
+                            end: span.end + window_start,
+                            variant: 0,
+                        },
+                        _ => span,
+                    })
+            }
+        };
+
+        if let Some(simple_matcher) = &self.simple_matcher {
+            // 各窗口内命中的词ID对其结果的映射，每个窗口只需扫描一次原文本
+            let mut window_result_dict_list: Vec<IntMap<u64, MatchResult>> = (0
+                ..processed_text_list.len())
+                .map(|_| IntMap::default())
+                .collect();
+            for (index, simple_result) in simple_result_list {
+                let word_table_conf = unsafe {
+                    self.word_table_list
+                        .get_unchecked(simple_result.word_id as usize)
+                };
+                if !exempted_match_id_set.contains(word_table_conf.match_id.as_str()) {
+                    window_result_dict_list[index].insert(
+                        simple_result.word_id,
+                        self.simple_match_result(
+                            word_table_conf,
+                            simple_result,
+                            result_span(index),
+                        ),
+                    );
+                }
+            }
+
+            for (index, window_result_dict) in window_result_dict_list.iter().enumerate() {
+                if window_result_dict.is_empty() {
+                    continue;
+                }
+                for (word_id, (start, end)) in simple_matcher
+                    .part_span_list(processed_text_list[index].text(), |word_id| {
+                        window_result_dict.contains_key(&word_id)
+                    })
+                {
+                    hit_span_list.push((
+                        (
+                            start + window_start_list[index],
+                            end + window_start_list[index],
+                        ),
+                        window_result_dict[&word_id].clone(),
+                    ));
+                }
+            }
+        }
+
+        for (index, regex_result) in regex_result_list {
+            if exempted_match_id_set.contains(regex_result.match_id) {
+                continue;
+            }
+            if let Some(span) = regex_result.span.filter(|span| span.start < span.end) {
+                hit_span_list.push((
+                    (
+                        span.start + window_start_list[index],
+                        span.end + window_start_list[index],
+                    ),
+                    self.regex_match_result(regex_result, result_span(index)),
+                ));
+            }
+        }
+
+        hit_span_list
+    }
+
+    // 按 (match_id, table_id, word_id) 过滤命中结果，在构建MatchResult之前生效，豁免词不受影响
+    // 各类词表的word_id均为词在词表（dedup_words去重后）中的下标，邻近字词表整体为一个正则，恒为0
+    pub fn word_match_filtered<F>(&self, text: &str, pred: F) -> HashMap<&str, String>
+    where
+        F: Fn(&str, u32, u64) -> bool,
+    {
+        Self::to_string_dict(self._word_match(text, pred, false).unwrap_or_default())
+    }
+
+    // 由 (match_id, table_id) 列表构建词表集合，不存在的词表忽略；集合只对构建它的Matcher有效
+    pub fn table_id_set(&self, table_list: &[(&str, u32)]) -> TableIdSet {
+        let mut table_flag_list = vec![false; self.table_count];
+        for &(match_id, table_id) in table_list {
+            if let Some(table_index_list) =
+                self.table_index_dict.get(&(match_id.to_owned(), table_id))
+            {
+                for &table_index in table_index_list {
+                    table_flag_list[table_index] = true;
+                }
+            }
+        }
+
+        TableIdSet { table_flag_list }
+    }
+
+    // 同word_match，disabled_table_set中的词表完全不参与匹配，regex / sim词表直接跳过，豁免词不受影响
+    pub fn word_match_with_disabled_tables(
+        &self,
+        text: &str,
+        disabled_table_set: &TableIdSet,
+    ) -> HashMap<&str, String> {
+        let text = self.normalize_input(text);
+        match self.preprocess(&text) {
+            Ok(processed_text_list) if likely(!text.is_empty()) => Self::to_string_dict(
+                self._word_match_processed_until(
+                    &processed_text_list,
+                    |_, _, _| true,
+                    false,
+                    None,
+                    Some(disabled_table_set),
+                )
+                .0,
+            ),
+            _ => HashMap::new(),
+        }
+    }
+
+    // 同word_match，超长文本策略为Reject时返回InputTooLong错误，而不是空结果
+    pub fn try_word_match(&self, text: &str) -> Result<HashMap<&str, String>, MatcherError> {
+        Ok(Self::to_string_dict(self._word_match(
+            text,
+            |_, _, _| true,
+            false,
+        )?))
+    }
+
+    // 同word_match，超过截止时间后停止匹配，返回已匹配的部分结果以及是否超时
+    pub fn word_match_until(&self, text: &str, deadline: Instant) -> (HashMap<&str, String>, bool) {
+        let text = self.normalize_input(text);
+        match self.preprocess(&text) {
+            Ok(processed_text_list) => {
+                let (match_result_dict, deadline_exceeded) = self._word_match_processed_until(
+                    &processed_text_list,
+                    |_, _, _| true,
+                    false,
+                    Some(deadline),
+                    None,
+                );
+                (Self::to_string_dict(match_result_dict), deadline_exceeded)
+            }
+            Err(_) => (HashMap::new(), false),
+        }
+    }
+
+    // 同word_match_until，超时时返回DeadlineExceeded错误，而不是部分结果
+    pub fn try_word_match_until(
+        &self,
+        text: &str,
+        deadline: Instant,
+    ) -> Result<HashMap<&str, String>, MatcherError> {
+        let text = self.normalize_input(text);
+        let (match_result_dict, deadline_exceeded) = self._word_match_processed_until(
+            &self.preprocess(&text)?,
+            |_, _, _| true,
+            false,
+            Some(deadline),
+            None,
+        );
+
+        if deadline_exceeded {
+            Err(MatcherError::DeadlineExceeded)
+        } else {
+            Ok(Self::to_string_dict(match_result_dict))
+        }
+    }
+
+    // 校验文本长度，仅当策略为Reject且文本超长时返回错误
+    pub fn check_input(&self, text: &str) -> Result<(), MatcherError> {
+        match self.max_input_bytes {
+            Some(max_input_bytes)
+                if text.len() > max_input_bytes
+                    && self.over_limit_policy == OverLimitPolicy::Reject =>
+            {
+                Err(MatcherError::InputTooLong {
+                    text_bytes: text.len(),
+                    max_input_bytes,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // 开启normalize_newlines时统一换行符，所有匹配入口在preprocess之前调用；直接使用preprocess时需自行调用
+    pub fn normalize_input<'b>(&self, text: &'b str) -> Cow<'b, str> {
+        if likely(!self.normalize_newlines || !text.contains(['\r', '\u{2028}', '\u{2029}'])) {
+            return Cow::Borrowed(text);
+        }
+
+        let mut normalized_text = String::with_capacity(text.len());
+        let mut char_iter = text.chars().peekable();
+        while let Some(c) = char_iter.next() {
+            match c {
+                '\r' if char_iter.peek() == Some(&'\n') => {} // \r\n中的\n原样保留
+                '\r' | '\u{2028}' | '\u{2029}' => normalized_text.push('\n'),
+                _ => normalized_text.push(c),
+            }
+        }
+        Cow::Owned(normalized_text)
+    }
+
+    // 按超长文本策略处理文本，未设置上限或未超长时原样返回
+    fn split_input<'b>(&self, text: &'b str) -> Result<TinyVec<[&'b str; 1]>, MatcherError> {
+        let mut text_list = TinyVec::new();
+
+        // 空文本不生成任何窗口，所有匹配方式均直接返回空结果
+        if unlikely(text.is_empty() || (self.treat_blank_as_empty && is_blank(text))) {
+            return Ok(text_list);
+        }
+
+        let max_input_bytes = match self.max_input_bytes {
+            Some(max_input_bytes) if unlikely(text.len() > max_input_bytes) => max_input_bytes,
+            _ => {
+                text_list.push(text);
+                return Ok(text_list);
+            }
+        };
+
+        match self.over_limit_policy {
+            OverLimitPolicy::Truncate => {
+                text_list.push(&text[..Self::floor_char_boundary(text, max_input_bytes)]);
+            }
+            OverLimitPolicy::ChunkAndUnion { overlap_bytes } => {
+                let mut start = 0;
+                loop {
+                    let mut end = Self::floor_char_boundary(text, start + max_input_bytes);
+                    if end <= start {
+                        // 窗口小于单个字符时，至少包含一个字符
+                        end = Self::ceil_char_boundary(text, start + 1);
+                    }
+                    text_list.push(&text[start..end]);
+                    if end == text.len() {
+                        break;
+                    }
+                    start = Self::ceil_char_boundary(
+                        text,
+                        end.saturating_sub(overlap_bytes).max(start + 1),
+                    );
+                }
+            }
+            OverLimitPolicy::Reject => {
+                return Err(MatcherError::InputTooLong {
+                    text_bytes: text.len(),
+                    max_input_bytes,
+                });
+            }
+        }
+
+        Ok(text_list)
+    }
+
+    #[inline]
+    fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+        if index >= text.len() {
+            return text.len();
+        }
+        while !text.is_char_boundary(index) {
+            index -= 1;
+        }
+        Self::floor_grapheme_boundary(text, index)
+    }
+
+    #[inline]
+    fn ceil_char_boundary(text: &str, mut index: usize) -> usize {
+        if index >= text.len() {
+            return text.len();
+        }
+        while !text.is_char_boundary(index) {
+            index += 1;
+        }
+        Self::ceil_grapheme_boundary(text, index)
+    }
+
+    // 截断及分块时不拆开字素簇（emoji ZWJ序列、韩文字母等），index须已在字符边界上
+    #[cfg(feature = "unicode-segmentation")]
+    fn floor_grapheme_boundary(text: &str, index: usize) -> usize {
+        let mut cursor = GraphemeCursor::new(index, text.len(), true);
+        match cursor.is_boundary(text, 0) {
+            Ok(false) => cursor.prev_boundary(text, 0).ok().flatten().unwrap_or(0),
+            _ => index,
+        }
+    }
+
+    #[cfg(feature = "unicode-segmentation")]
+    fn ceil_grapheme_boundary(text: &str, index: usize) -> usize {
+        let mut cursor = GraphemeCursor::new(index, text.len(), true);
+        match cursor.is_boundary(text, 0) {
+            Ok(false) => cursor
+                .next_boundary(text, 0)
+                .ok()
+                .flatten()
+                .unwrap_or(text.len()),
+            _ => index,
+        }
+    }
+
+    #[cfg(not(feature = "unicode-segmentation"))]
+    #[inline(always)]
+    fn floor_grapheme_boundary(_text: &str, index: usize) -> usize {
+        index
+    }
+
+    #[cfg(not(feature = "unicode-segmentation"))]
+    #[inline(always)]
+    fn ceil_grapheme_boundary(_text: &str, index: usize) -> usize {
+        index
+    }
+
+    // 忽略豁免词的匹配结果，用于离线评估豁免词的效果，豁免词本身不会出现在结果中
+    pub fn word_match_raw(&self, text: &str) -> HashMap<&str, String> {
+        Self::to_string_dict(
+            self._word_match(text, |_, _, _| true, true)
+                .unwrap_or_default(),
+        )
+    }
+
+    // 同word_match，额外返回被豁免的match_id及其命中结果与命中的豁免词，豁免判断不变
+    pub fn word_match_full(&self, text: &str) -> FullMatchResult<'_> {
+        let text = self.normalize_input(text);
+        let Ok(processed_text_list) = self.preprocess(&text) else {
+            return FullMatchResult::default();
+        };
+        let (match_result_dict, _) = self._result_dict_until(
+            &processed_text_list,
+            |_, _, _| true,
+            false,
+            true,
+            None,
+            None,
+        );
+
+        let mut full_match_result = FullMatchResult {
+            variants_truncated: processed_text_list
+                .iter()
+                .any(ProcessedText::variants_truncated),
+            ..Default::default()
+        };
+        for (match_id, result_dict) in match_result_dict {
+            if result_dict.result_list.is_empty() {
+                continue;
+            }
+            if result_dict.exemption_hit_list.is_empty() {
+                full_match_result
+                    .matched
+                    .insert(match_id, result_dict.result_list);
+            } else {
+                full_match_result.exempted.insert(
+                    match_id,
+                    ExemptedResult {
+                        result_list: result_dict.result_list,
+                        exemption_hit_list: result_dict.exemption_hit_list,
+                    },
+                );
+            }
+        }
+
+        full_match_result
+    }
+
+    // 同word_match_full，序列化为json，eg. {"matched": {...}, "exempted": {"test": {"result_list": [...], "exemption_hit_list": [...]}}, "variants_truncated": false}
+    pub fn word_match_full_as_string(&self, text: &str) -> String {
+        unsafe { to_string(&self.word_match_full(text)).unwrap_unchecked() }
+    }
+
+    // 同word_match，命中结果按词表分组，match_id -> table_id -> 命中结果，组内保持word_match中的顺序
+    pub fn word_match_by_table(
+        &self,
+        text: &str,
+    ) -> HashMap<&str, HashMap<u32, Vec<MatchResult<'_>>>> {
+        self._word_match(text, |_, _, _| true, false)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(match_id, result_list)| {
+                let mut table_result_dict: HashMap<u32, Vec<MatchResult>> = HashMap::new();
+                for match_result in result_list {
+                    table_result_dict
+                        .entry(match_result.table_id)
+                        .or_default()
+                        .push(match_result);
+                }
+                (match_id, table_result_dict)
+            })
+            .collect()
+    }
+
+    pub fn word_match_by_table_as_string(&self, text: &str) -> String {
+        unsafe { to_string(&self.word_match_by_table(text)).unwrap_unchecked() }
+    }
+
+    fn to_string_dict<'a>(
+        match_result_dict: AHashMap<&'a str, Vec<MatchResult>>,
+    ) -> HashMap<&'a str, String> {
+        match_result_dict
+            .into_iter()
+            .map(|(match_id, result_list)| {
+                (match_id, unsafe {
+                    to_string(&result_list).unwrap_unchecked()
+                })
+            })
+            .collect()
+    }
+
+    pub fn word_match_as_string(&self, text: &str) -> String {
+        let mut buf = Vec::new();
+        unsafe {
+            self.word_match_to_writer(text, &mut buf).unwrap_unchecked();
+            String::from_utf8_unchecked(buf)
+        }
+    }
+
+    // 同word_match_as_string，直接序列化到writer，省去中间的String，适用于批量写文件
+    pub fn word_match_to_writer<W: Write>(&self, text: &str, writer: &mut W) -> io::Result<()> {
+        serde_json::to_writer(writer, &self.word_match(text))?;
+        Ok(())
+    }
+}
+
+impl<'a> TextMatcherTrait<'a, MatchResult<'a>> for Matcher {
+    fn is_match(&self, text: &str) -> bool {
+        let text = self.normalize_input(text);
+        let Ok(text_list) = self.split_input(&text) else {
+            return false;
+        };
+
+        for &text in &text_list {
+            if let Some(simple_matcher) = &self.simple_matcher {
+                if simple_matcher.is_match(text) {
+                    return true;
+                }
+            }
+
+            if let Some(regex_matcher) = &self.regex_matcher {
+                if regex_matcher.is_match(text) {
+                    return true;
+                }
+            }
+
+            if let Some(sim_matcher) = &self.sim_matcher {
+                if sim_matcher.is_match(text) {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    fn batch_process(&'a self, text_array: &[&str]) -> Vec<Vec<MatchResult<'a>>> {
+        self.process_batch(text_array)
+    }
+
+    fn find_first(&'a self, text: &str) -> Option<MatchResult<'a>> {
+        if unlikely(text.is_empty()) {
+            return None;
+        }
+        let text = self.normalize_input(text);
+        self._find_first(&self.preprocess(&text).ok()?)
     }
 
     fn process(&'a self, text: &str) -> Vec<MatchResult<'a>> {
-        self.word_match_raw(text)
+        self._word_match(text, |_, _, _| true, false)
+            .unwrap_or_default()
             .into_iter()
             .flat_map(|(_, result_list)| result_list)
             .collect()
@@ -0,0 +1,273 @@
+use std::borrow::Cow;
+
+use ahash::AHashMap;
+use serde_json::to_string;
+use zerovec::VarZeroVec;
+
+use crate::matcher::{
+    MatchTable, MatchTableDict, MatchTableType, Matcher, MatcherError, MatcherOptions,
+    MatcherWarning,
+};
+use crate::regex_matcher::{AcrosticPosition, AcrosticSeparator, RegexFlags};
+use crate::sim_matcher::SimMatchType;
+use crate::simple_matcher::SimpleMatchType;
+
+struct TableConf {
+    table_id: u32,
+    match_table_type: MatchTableType,
+    wordlist: Vec<String>,
+    exemption_wordlist: Vec<String>,
+    simple_match_type: SimpleMatchType,
+    acrostic_position: AcrosticPosition,
+    prefer_matched_text: bool,
+    sim_match_type: SimMatchType,
+    regex_flags: RegexFlags,
+    acrostic_separator: AcrosticSeparator,
+    simple_match_type_override: AHashMap<String, SimpleMatchType>,
+}
+
+#[derive(Default)]
+pub struct MatchIdBuilder {
+    table_conf_list: Vec<TableConf>, // 该match_id下的词表，按添加顺序
+}
+
+impl MatchIdBuilder {
+    fn table<I, S>(
+        mut self,
+        table_id: u32,
+        match_table_type: MatchTableType,
+        simple_match_type: SimpleMatchType,
+        wordlist: I,
+    ) -> MatchIdBuilder
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.table_conf_list.push(TableConf {
+            table_id,
+            match_table_type,
+            wordlist: wordlist.into_iter().map(Into::into).collect(),
+            exemption_wordlist: Vec::new(),
+            simple_match_type,
+            acrostic_position: AcrosticPosition::Initial,
+            prefer_matched_text: false,
+            sim_match_type: SimMatchType::default(),
+            regex_flags: RegexFlags::default(),
+            acrostic_separator: AcrosticSeparator::default(),
+            simple_match_type_override: AHashMap::new(),
+        });
+        self
+    }
+
+    fn last_table_conf(&mut self) -> &mut TableConf {
+        self.table_conf_list
+            .last_mut()
+            .expect("exemptions / acrostic_position / acrostic_separator / prefer_matched_text / sim_match_type / regex_flags / simple_match_type_override must follow a table")
+    }
+
+    pub fn simple_table<I, S>(
+        self,
+        table_id: u32,
+        simple_match_type: SimpleMatchType,
+        wordlist: I,
+    ) -> MatchIdBuilder
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.table(
+            table_id,
+            MatchTableType::Simple,
+            simple_match_type,
+            wordlist,
+        )
+    }
+
+    // 邻近字 / 藏头诗 / 正则词表
+    pub fn regex_table<I, S>(
+        self,
+        table_id: u32,
+        match_table_type: MatchTableType,
+        wordlist: I,
+    ) -> MatchIdBuilder
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        debug_assert!(matches!(
+            match_table_type,
+            MatchTableType::SimilarChar
+                | MatchTableType::Acrostic
+                | MatchTableType::Regex
+                | MatchTableType::Wildcard
+        ));
+        self.table(table_id, match_table_type, SimpleMatchType::None, wordlist)
+    }
+
+    pub fn sim_table<I, S>(self, table_id: u32, wordlist: I) -> MatchIdBuilder
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.table(
+            table_id,
+            MatchTableType::SimilarTextLevenshtein,
+            SimpleMatchType::None,
+            wordlist,
+        )
+    }
+
+    // 只含豁免词的词表，任一豁免词命中时该match_id下所有词表的结果均被豁免
+    pub fn exemption_table<I, S>(self, table_id: u32, exemption_wordlist: I) -> MatchIdBuilder
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.table(
+            table_id,
+            MatchTableType::Simple,
+            SimpleMatchType::None,
+            Vec::<String>::new(),
+        )
+        .exemptions(exemption_wordlist)
+    }
+
+    // 为最近添加的词表设置豁免词
+    pub fn exemptions<I, S>(mut self, exemption_wordlist: I) -> MatchIdBuilder
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.last_table_conf()
+            .exemption_wordlist
+            .extend(exemption_wordlist.into_iter().map(Into::into));
+        self
+    }
+
+    // 为最近添加的藏头诗词表设置藏头位置
+    pub fn acrostic_position(mut self, acrostic_position: AcrosticPosition) -> MatchIdBuilder {
+        self.last_table_conf().acrostic_position = acrostic_position;
+        self
+    }
+
+    // 为最近添加的藏头诗词表设置藏头字之间的分隔方式
+    pub fn acrostic_separator(mut self, acrostic_separator: AcrosticSeparator) -> MatchIdBuilder {
+        self.last_table_conf().acrostic_separator = acrostic_separator;
+        self
+    }
+
+    // 最近添加的邻近字词表的命中词使用文本中实际命中的子串
+    pub fn prefer_matched_text(mut self, prefer_matched_text: bool) -> MatchIdBuilder {
+        self.last_table_conf().prefer_matched_text = prefer_matched_text;
+        self
+    }
+
+    // 为最近添加的相似文本词表设置相似度算法
+    pub fn sim_match_type(mut self, sim_match_type: SimMatchType) -> MatchIdBuilder {
+        self.last_table_conf().sim_match_type = sim_match_type;
+        self
+    }
+
+    // 为最近添加的正则词表设置编译标志
+    pub fn regex_flags(mut self, regex_flags: RegexFlags) -> MatchIdBuilder {
+        self.last_table_conf().regex_flags = regex_flags;
+        self
+    }
+
+    // 为最近添加的simple词表中的词单独指定匹配类型
+    pub fn simple_match_type_override(
+        mut self,
+        word: impl Into<String>,
+        simple_match_type: SimpleMatchType,
+    ) -> MatchIdBuilder {
+        self.last_table_conf()
+            .simple_match_type_override
+            .insert(word.into(), simple_match_type);
+        self
+    }
+}
+
+#[derive(Default)]
+pub struct MatcherBuilder {
+    match_id_list: Vec<(String, MatchIdBuilder)>, // 匹配ID对其词表的映射，按添加顺序
+    options: MatcherOptions,
+}
+
+impl MatcherBuilder {
+    pub fn new() -> MatcherBuilder {
+        MatcherBuilder::default()
+    }
+
+    // 同一match_id多次调用时，词表依次追加
+    pub fn match_id<F>(mut self, match_id: impl ToString, build_fn: F) -> MatcherBuilder
+    where
+        F: FnOnce(MatchIdBuilder) -> MatchIdBuilder,
+    {
+        let match_id = match_id.to_string();
+
+        match self
+            .match_id_list
+            .iter_mut()
+            .find(|(existing_match_id, _)| *existing_match_id == match_id)
+        {
+            Some((_, match_id_builder)) => {
+                let table_conf_list = std::mem::take(&mut match_id_builder.table_conf_list);
+                *match_id_builder = build_fn(MatchIdBuilder { table_conf_list });
+            }
+            None => self
+                .match_id_list
+                .push((match_id, build_fn(MatchIdBuilder::default()))),
+        }
+        self
+    }
+
+    pub fn options(mut self, options: MatcherOptions) -> MatcherBuilder {
+        self.options = options;
+        self
+    }
+
+    // 词表借用builder中的字符串，调用方无需关心生命周期
+    pub fn match_table_dict(&self) -> MatchTableDict<'_> {
+        self.match_id_list
+            .iter()
+            .map(|(match_id, match_id_builder)| {
+                (
+                    match_id.as_str(),
+                    match_id_builder
+                        .table_conf_list
+                        .iter()
+                        .map(|table_conf| MatchTable {
+                            table_id: table_conf.table_id,
+                            match_table_type: table_conf.match_table_type,
+                            wordlist: VarZeroVec::from(&table_conf.wordlist),
+                            exemption_wordlist: VarZeroVec::from(&table_conf.exemption_wordlist),
+                            simple_match_type: table_conf.simple_match_type,
+                            acrostic_position: table_conf.acrostic_position,
+                            prefer_matched_text: table_conf.prefer_matched_text,
+                            sim_match_type: table_conf.sim_match_type,
+                            regex_flags: table_conf.regex_flags,
+                            acrostic_separator: table_conf.acrostic_separator,
+                            simple_match_type_override: table_conf
+                                .simple_match_type_override
+                                .iter()
+                                .map(|(word, &simple_match_type)| {
+                                    (Cow::Borrowed(word.as_str()), simple_match_type)
+                                })
+                                .collect(),
+                        })
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    // 校验后构建，同Matcher::try_new_with_options
+    pub fn build(&self) -> Result<(Matcher, Vec<MatcherWarning>), MatcherError> {
+        Matcher::try_new_with_options(&self.match_table_dict(), self.options.clone())
+    }
+
+    // 序列化为json，可交由python / c等其他语言端构建
+    pub fn to_json(&self) -> String {
+        unsafe { to_string(&self.match_table_dict()).unwrap_unchecked() }
+    }
+}
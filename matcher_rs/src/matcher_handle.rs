@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use arc_swap::{ArcSwap, Guard};
+
+use crate::matcher::{MatchTableDict, Matcher, MatcherError};
+
+pub struct MatcherHandle {
+    matcher: ArcSwap<Matcher>, // 当前生效的Matcher，读取无锁，替换为原子操作
+}
+
+impl MatcherHandle {
+    pub fn new(matcher: Matcher) -> MatcherHandle {
+        MatcherHandle {
+            matcher: ArcSwap::from_pointee(matcher),
+        }
+    }
+
+    // 获取当前Matcher，请求处理期间持有guard即可，替换不会影响进行中的请求
+    pub fn load(&self) -> Guard<Arc<Matcher>> {
+        self.matcher.load()
+    }
+
+    // 原子替换Matcher，返回旧的Matcher，旧Matcher在最后一个持有者释放后回收
+    pub fn swap(&self, matcher: Matcher) -> Arc<Matcher> {
+        self.matcher.swap(Arc::new(matcher))
+    }
+
+    // 构建新Matcher后原子替换，构建期间读取方始终使用旧Matcher，调用方阻塞至替换完成
+    // 构建失败时返回错误，继续使用旧Matcher，校验告警不影响替换
+    pub fn rebuild_from(&self, match_table_dict: &MatchTableDict) -> Result<(), MatcherError> {
+        let (matcher, _) = Matcher::try_new(match_table_dict)?;
+        self.swap(matcher);
+
+        Ok(())
+    }
+}
@@ -1,23 +1,376 @@
 use std::borrow::Cow;
+use std::fmt;
+use std::intrinsics::unlikely;
+use std::sync::{Arc, OnceLock};
+use std::time::Instant;
 
-use fancy_regex::{escape, Regex};
+use ahash::{AHashMap, AHashSet};
+use fancy_regex::{escape, Regex, RegexBuilder as FancyRegexBuilder};
+use regex::{RegexBuilder, RegexSet, RegexSetBuilder};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "unicode-segmentation")]
+use unicode_segmentation::UnicodeSegmentation;
 use zerovec::VarZeroVec;
 
-use super::{MatchTableType, TextMatcherTrait};
+use super::{MatchSpan, MatchTableType, TextMatcherTrait};
 
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AcrosticPosition {
+    #[default]
+    Initial, // 句首字，regex实现
+    Nth(usize), // 每个分句的第n个字（从1开始），按分句顺序逐字比对实现
+}
+
+// 句首藏头的各字之间的分隔方式
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AcrosticSeparator {
+    #[default]
+    Punctuation, // 每个字位于句首，即文本开头或空白、标点之后
+    Gap(usize), // 不要求空白或标点，相邻两字之间至多间隔n个任意字符，eg. 无标点的中文文本；n越大误报越多
+}
+
+#[derive(Clone)]
 pub struct RegexTable<'a> {
     pub table_id: u32,
     pub match_id: &'a str,
     pub match_table_type: &'a MatchTableType,
     pub wordlist: &'a VarZeroVec<'a, str>,
+    pub acrostic_position: AcrosticPosition,
+    pub acrostic_separator: AcrosticSeparator, // 藏头字之间的分隔方式，仅对句首藏头生效
+    pub prefer_matched_text: bool,             // 命中词使用文本中实际命中的子串，仅对邻近字词表生效
+    pub regex_flags: RegexFlags,               // 正则的编译标志，仅对正则及通配符词表生效
+}
+
+// 正则词表的编译标志，与在每个正则前加内联标志等价，默认值与不设置标志时一致
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct RegexFlags {
+    pub case_insensitive: bool,     // i，忽略大小写
+    pub multi_line: bool,           // m，^ $ 匹配每行的行首行尾
+    pub dot_matches_new_line: bool, // s，. 匹配换行符
+    pub unicode: bool, // u，\w \d 等按unicode匹配，默认开启；fancy_regex不支持关闭，逐个匹配的正则忽略该项
+}
+
+impl Default for RegexFlags {
+    fn default() -> RegexFlags {
+        RegexFlags {
+            case_insensitive: false,
+            multi_line: false,
+            dot_matches_new_line: false,
+            unicode: true,
+        }
+    }
+}
+
+impl RegexFlags {
+    // fancy_regex的内联标志前缀，eg. "(?is)"，未开启任何标志时为空
+    fn inline_prefix(&self) -> String {
+        let flag_str: String = [
+            (self.case_insensitive, 'i'),
+            (self.multi_line, 'm'),
+            (self.dot_matches_new_line, 's'),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, flag)| enabled.then_some(flag))
+        .collect();
+
+        if flag_str.is_empty() {
+            flag_str
+        } else {
+            format!("(?{flag_str})")
+        }
+    }
+
+    fn fancy_regex(
+        &self,
+        pattern: &str,
+        regex_limit: RegexLimit,
+    ) -> Result<Regex, fancy_regex::Error> {
+        build_fancy_regex(&(self.inline_prefix() + pattern), regex_limit)
+    }
+
+    fn regex(&self, pattern: &str, regex_limit: RegexLimit) -> Result<regex::Regex, regex::Error> {
+        let mut regex_builder = RegexBuilder::new(pattern);
+        regex_builder
+            .case_insensitive(self.case_insensitive)
+            .multi_line(self.multi_line)
+            .dot_matches_new_line(self.dot_matches_new_line)
+            .unicode(self.unicode);
+        if let Some(size_limit) = regex_limit.size_limit {
+            regex_builder.size_limit(size_limit);
+        }
+        if let Some(dfa_size_limit) = regex_limit.dfa_size_limit {
+            regex_builder.dfa_size_limit(dfa_size_limit);
+        }
+        regex_builder.build()
+    }
+
+    fn regex_set<I, S>(
+        &self,
+        pattern_list: I,
+        regex_limit: RegexLimit,
+    ) -> Result<RegexSet, regex::Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut regex_set_builder = RegexSetBuilder::new(pattern_list);
+        regex_set_builder
+            .case_insensitive(self.case_insensitive)
+            .multi_line(self.multi_line)
+            .dot_matches_new_line(self.dot_matches_new_line)
+            .unicode(self.unicode);
+        if let Some(size_limit) = regex_limit.size_limit {
+            regex_set_builder.size_limit(size_limit);
+        }
+        if let Some(dfa_size_limit) = regex_limit.dfa_size_limit {
+            regex_set_builder.dfa_size_limit(dfa_size_limit);
+        }
+        regex_set_builder.build()
+    }
+}
+
+// 正则的编译及匹配上限，取自RegexMatcherOptions
+#[derive(Clone, Copy, Debug)]
+struct RegexLimit {
+    backtrack_limit: Option<usize>,
+    size_limit: Option<usize>,
+    dfa_size_limit: Option<usize>,
+}
+
+// 通配符转换为正则，*匹配任意个字符，?匹配一个字符，均可匹配换行符，其余字符按字面匹配
+fn wildcard_pattern(word: &str) -> String {
+    let mut pattern = String::with_capacity(word.len());
+    let mut literal_start = 0;
+    for (index, c) in word.char_indices() {
+        let wildcard = match c {
+            '*' => "(?s:.*?)",
+            '?' => "(?s:.)",
+            _ => continue,
+        };
+        pattern.push_str(&escape(&word[literal_start..index]));
+        pattern.push_str(wildcard);
+        literal_start = index + c.len_utf8();
+    }
+    pattern.push_str(&escape(&word[literal_start..]));
+    pattern
+}
+
+// RegexSet中的正则是否可能带有具名捕获组，RegexSet不支持环视，(?<只可能是具名捕获组；误判只会多编译一次单独的正则
+fn may_have_named_group(pattern: &str) -> bool {
+    pattern.contains("(?P<") || pattern.contains("(?<")
+}
+
+// 命中的具名捕获组 (组名, 捕获文本)，未参与命中的组不返回
+fn named_capture_list<'n, 't, F>(
+    capture_names: impl Iterator<Item = Option<&'n str>>,
+    get_capture: F,
+) -> Vec<(String, String)>
+where
+    F: Fn(usize) -> Option<&'t str>,
+{
+    capture_names
+        .enumerate()
+        .filter_map(|(index, name)| Some((name?.to_owned(), get_capture(index)?.to_owned())))
+        .collect()
+}
+
+// 大小上限作用于fancy_regex委托给regex crate的每个子正则，纯字面量的正则不受限制
+fn build_fancy_regex(pattern: &str, regex_limit: RegexLimit) -> Result<Regex, fancy_regex::Error> {
+    let mut regex_builder = FancyRegexBuilder::new(pattern);
+    if let Some(backtrack_limit) = regex_limit.backtrack_limit {
+        regex_builder.backtrack_limit(backtrack_limit);
+    }
+    if let Some(size_limit) = regex_limit.size_limit {
+        regex_builder.delegate_size_limit(size_limit);
+    }
+    if let Some(dfa_size_limit) = regex_limit.dfa_size_limit {
+        regex_builder.delegate_dfa_size_limit(dfa_size_limit);
+    }
+    regex_builder.build()
+}
+
+// 匹配时的告警回调，eg. 正则超出回溯上限而视为未命中
+#[derive(Clone)]
+pub struct RegexWarningCallback(pub Arc<dyn Fn(&PatternWarning) + Send + Sync>);
+
+impl fmt::Debug for RegexWarningCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RegexWarningCallback")
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct RegexMatcherOptions {
+    pub split_regex_table: bool, // regex词表中存在regex crate不支持的正则时，将兼容的正则拆分到RegexSet，其余逐个匹配（默认）；false时整表逐个匹配
+    pub backtrack_limit: Option<usize>, // fancy_regex单次匹配的回溯上限，超出时视为未命中；None时使用fancy_regex的默认上限（100万次）
+    pub size_limit: Option<usize>, // 单个正则（及RegexSet整体）编译后的大小上限（字节），超出时编译失败；None时使用regex crate的默认上限（10MiB）
+    pub dfa_size_limit: Option<usize>, // 惰性DFA缓存的大小上限（字节），超出时回退到较慢的匹配方式而不会失败；None时使用regex crate的默认上限（2MiB）
+    #[serde(skip)]
+    pub warning_callback: Option<RegexWarningCallback>, // 匹配时超出回溯上限的告警回调，不参与序列化
+}
+
+impl Default for RegexMatcherOptions {
+    fn default() -> RegexMatcherOptions {
+        RegexMatcherOptions {
+            split_regex_table: true,
+            backtrack_limit: None,
+            size_limit: None,
+            dfa_size_limit: None,
+            warning_callback: None,
+        }
+    }
+}
+
+impl RegexMatcherOptions {
+    fn regex_limit(&self) -> RegexLimit {
+        RegexLimit {
+            backtrack_limit: self.backtrack_limit,
+            size_limit: self.size_limit,
+            dfa_size_limit: self.dfa_size_limit,
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct RegexTableStats {
+    pub match_id: String,
+    pub table_id: u32,
+    pub set_pattern_count: usize,  // RegexSet中一次性匹配的正则数
+    pub list_pattern_count: usize, // 逐个匹配的正则数
+    pub fallback_pattern_list: Vec<(String, String)>, // regex crate不支持而只能逐个匹配的正则，及其不支持的原因
+    pub invalid_pattern_list: Vec<(String, RegexCompileError)>, // 无法编译而被丢弃的正则，及fancy_regex的编译错误
+}
+
+// fancy_regex的编译错误，Arc共享原始错误以便clone；比较以及序列化均按错误信息
+#[derive(Clone, Debug)]
+pub struct RegexCompileError(Arc<fancy_regex::Error>);
+
+impl RegexCompileError {
+    pub fn get_ref(&self) -> &fancy_regex::Error {
+        &self.0
+    }
+}
+
+impl From<fancy_regex::Error> for RegexCompileError {
+    fn from(e: fancy_regex::Error) -> Self {
+        RegexCompileError(Arc::new(e))
+    }
+}
+
+impl fmt::Display for RegexCompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl PartialEq for RegexCompileError {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0) || self.to_string() == other.to_string()
+    }
+}
+
+impl Eq for RegexCompileError {}
+
+impl Serialize for RegexCompileError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+// 透明包装，错误信息以及source均与fancy_regex的错误一致
+impl std::error::Error for RegexCompileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        std::error::Error::source(self.get_ref())
+    }
+}
+
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct RegexMatcherStats {
+    pub table_stats_list: Vec<RegexTableStats>, // regex词表的统计信息
+    pub empty_table_list: Vec<(String, u32)>,   // 没有有效词而被跳过的词表 (match_id, table_id)
+    pub duplicate_word_list: Vec<(String, u32, String)>, // 词表中重复而被忽略的词 (match_id, table_id, 词)，只保留第一次出现的
+}
+
+// 正则词表中无法编译而被丢弃的正则
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct PatternWarning {
+    pub match_id: String,
+    pub table_id: u32,
+    pub pattern: String,
+    pub error: String, // fancy_regex的编译错误
+}
+
+impl fmt::Display for PatternWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "match_id: {}, table_id: {}, invalid regex pattern {:?}: {}",
+            self.match_id, self.table_id, self.pattern, self.error
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegexBuildError {
+    InvalidGeneratedPattern {
+        match_id: String,
+        table_id: u32,
+        pattern: String,
+        error: RegexCompileError,
+    }, // 邻近字 / 藏头诗词表生成的正则无法编译，eg. 超出大小限制
+}
+
+impl fmt::Display for RegexBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegexBuildError::InvalidGeneratedPattern {
+                match_id,
+                table_id,
+                pattern,
+                error,
+            } => write!(
+                f,
+                "match_id: {match_id}, table_id: {table_id}, generated regex pattern {pattern:?} failed to compile: {error}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RegexBuildError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RegexBuildError::InvalidGeneratedPattern { error, .. } => Some(error),
+        }
+    }
 }
 
 enum RegexType {
     StandardRegex {
         regex: Regex,
+        prefer_matched_text: bool, // 命中词使用实际命中的子串，而不是各组命中字符的拼接
+        wordlist: Vec<String>,     // 构建正则的邻近字词表，仅用于导出
+    },
+    SetRegex {
+        regex_set: RegexSet,
+        word_id_list: Vec<u64>, // 与regex_set中的正则一一对应
+        wordlist: Vec<String>,
+        locate_regex_list: Vec<OnceLock<regex::Regex>>, // RegexSet不返回命中位置，需要位置时按需单独编译命中的正则
+        regex_flags: RegexFlags,
+        regex_limit: RegexLimit, // 与构建RegexSet时一致，保证单独编译必定成功
     },
     ListRegex {
         regex_list: Vec<Regex>,
+        word_id_list: Vec<u64>, // 与regex_list一一对应
+        wordlist: Vec<String>,
+    },
+    AcrosticNth {
+        nth: usize,                       // 分句中的字位置（从1开始）
+        split_wordlist: Vec<Vec<String>>, // 词按','切分后的各部分，依次对应连续分句
+        word_id_list: Vec<u64>,           // 与split_wordlist一一对应
         wordlist: Vec<String>,
     },
 }
@@ -30,27 +383,75 @@ struct RegexPatternTable {
 
 #[derive(Debug)]
 pub struct RegexResult<'a> {
+    pub word_id: u64, // 词在词表中的下标，邻近字表整体为一个正则，恒为0
     pub word: Cow<'a, str>,
     pub table_id: u32,
     pub match_id: &'a str,
+    pub matched_text: Option<String>, // 文本中实际命中的子串，仅邻近字词表返回
+    pub span: Option<MatchSpan>, // 文本中的命中位置，variant恒为0；正则词表为每个正则首个命中的位置
+    pub captures: Vec<(String, String)>, // 具名捕获组 (组名, 捕获文本)，正则词表为每个正则首个命中中的捕获，没有具名捕获组时为空
 }
 
+// 编译后的正则表由Arc持有，clone只增加引用计数
+#[derive(Clone)]
 pub struct RegexMatcher {
-    regex_pattern_table_list: Vec<RegexPatternTable>,
+    clause_split_pattern: Regex, // 分句符号，空白及标点
+    regex_pattern_table_list: Arc<Vec<RegexPatternTable>>,
+    table_index_list: Arc<Vec<usize>>, // 与regex_pattern_table_list一一对应，所属词表在构建输入中的下标
+    stats: Arc<RegexMatcherStats>,     // 构建统计信息，eg. 退化为逐个匹配的原因
+    warning_callback: Option<RegexWarningCallback>,
 }
 
 impl RegexMatcher {
+    // 无法编译的正则被丢弃，记录在stats中；邻近字 / 藏头诗词表生成的正则无法编译时panic
     pub fn new(regex_table_list: &Vec<RegexTable>) -> RegexMatcher {
+        Self::new_with_options(regex_table_list, RegexMatcherOptions::default())
+    }
+
+    pub fn new_with_options(
+        regex_table_list: &Vec<RegexTable>,
+        options: RegexMatcherOptions,
+    ) -> RegexMatcher {
+        Self::try_new_with_options(regex_table_list, options)
+            .unwrap_or_else(|e| panic!("{e}"))
+            .0
+    }
+
+    // 同new，生成的正则无法编译时返回错误，并返回正则词表中被丢弃的正则，由调用方决定报错还是记录日志
+    pub fn try_new(
+        regex_table_list: &[RegexTable],
+    ) -> Result<(RegexMatcher, Vec<PatternWarning>), RegexBuildError> {
+        Self::try_new_with_options(regex_table_list, RegexMatcherOptions::default())
+    }
+
+    pub fn try_new_with_options(
+        regex_table_list: &[RegexTable],
+        options: RegexMatcherOptions,
+    ) -> Result<(RegexMatcher, Vec<PatternWarning>), RegexBuildError> {
         let mut regex_pattern_table_list = Vec::with_capacity(regex_table_list.len());
+        let mut table_index_list = Vec::with_capacity(regex_table_list.len());
+        let mut stats = RegexMatcherStats::default();
 
-        for regex_table in regex_table_list {
+        for (table_index, regex_table) in regex_table_list.iter().enumerate() {
             let size = regex_table.wordlist.len();
 
-            match regex_table.match_table_type {
-                MatchTableType::SimilarChar => {
+            // 空词表不构建，邻近字词表的空正则会命中任意文本；邻近字词表中的空词同理，全部为空词时视为空词表
+            if size == 0
+                || (matches!(regex_table.match_table_type, MatchTableType::SimilarChar)
+                    && regex_table.wordlist.iter().all(str::is_empty))
+            {
+                stats
+                    .empty_table_list
+                    .push((regex_table.match_id.to_owned(), regex_table.table_id));
+                continue;
+            }
+
+            match (regex_table.match_table_type, regex_table.acrostic_position) {
+                (MatchTableType::SimilarChar, _) => {
                     let pattern = regex_table
                         .wordlist
                         .iter()
+                        .filter(|charstr| !charstr.is_empty())
                         .map(|charstr| format!("({})", escape(charstr).replace(',', "|")))
                         .collect::<Vec<String>>()
                         .join(".?");
@@ -59,22 +460,69 @@ impl RegexMatcher {
                         table_id: regex_table.table_id,
                         match_id: regex_table.match_id.to_owned(),
                         table_match_type: RegexType::StandardRegex {
-                            regex: Regex::new(&pattern).unwrap(),
+                            regex: Self::compile_generated_pattern(
+                                regex_table,
+                                &pattern,
+                                &options,
+                            )?,
+                            prefer_matched_text: regex_table.prefer_matched_text,
+                            wordlist: regex_table
+                                .wordlist
+                                .iter()
+                                .map(|word| word.to_owned())
+                                .collect(),
                         },
                     });
                 }
-                MatchTableType::Acrostic => {
+                (MatchTableType::Acrostic, AcrosticPosition::Nth(nth)) => {
+                    let (word_id_list, wordlist): (Vec<u64>, Vec<String>) =
+                        Self::dedup_wordlist(regex_table, &mut stats)
+                            .into_iter()
+                            .map(|(word_id, word)| (word_id, word.to_owned()))
+                            .unzip();
+
+                    regex_pattern_table_list.push(RegexPatternTable {
+                        table_id: regex_table.table_id,
+                        match_id: regex_table.match_id.to_owned(),
+                        table_match_type: RegexType::AcrosticNth {
+                            nth: nth.max(1),
+                            split_wordlist: wordlist
+                                .iter()
+                                .map(|word| {
+                                    word.split(',')
+                                        .filter(|part| !part.is_empty())
+                                        .map(|part| part.to_owned())
+                                        .collect()
+                                })
+                                .collect(),
+                            word_id_list,
+                            wordlist,
+                        },
+                    });
+                }
+                (MatchTableType::Acrostic, AcrosticPosition::Initial) => {
+                    let mut word_id_list = Vec::with_capacity(size);
                     let mut wordlist = Vec::with_capacity(size);
                     let mut regex_list = Vec::with_capacity(size);
 
-                    for word in regex_table.wordlist.iter() {
-                        let pattern = format!(
-                            r"(?:^|[\s\pP]+?){}",
-                            escape(word).replace(',', r".*?[\s\pP]+?")
-                        );
+                    for (word_id, word) in Self::dedup_wordlist(regex_table, &mut stats) {
+                        let pattern = match regex_table.acrostic_separator {
+                            AcrosticSeparator::Punctuation => format!(
+                                r"(?:^|[\s\pP]+?){}",
+                                escape(word).replace(',', r".*?[\s\pP]+?")
+                            ),
+                            AcrosticSeparator::Gap(max_gap) => {
+                                escape(word).replace(',', &format!(r"(?s:.{{0,{max_gap}}}?)"))
+                            }
+                        };
 
+                        word_id_list.push(word_id);
                         wordlist.push(word.to_owned());
-                        regex_list.push(Regex::new(&pattern).unwrap());
+                        regex_list.push(Self::compile_generated_pattern(
+                            regex_table,
+                            &pattern,
+                            &options,
+                        )?);
                     }
 
                     regex_pattern_table_list.push(RegexPatternTable {
@@ -82,96 +530,705 @@ impl RegexMatcher {
                         match_id: regex_table.match_id.to_owned(),
                         table_match_type: RegexType::ListRegex {
                             regex_list,
+                            word_id_list,
                             wordlist,
                         },
                     });
                 }
-                MatchTableType::Regex => {
-                    let wordlist = regex_table
-                        .wordlist
-                        .iter()
-                        .map(|word| word.to_owned())
-                        .collect::<Vec<String>>();
+                (MatchTableType::Regex | MatchTableType::Wildcard, _) => {
+                    let word_list = Self::dedup_wordlist(regex_table, &mut stats);
+                    let table_stats = Self::build_regex_table(
+                        regex_table,
+                        &word_list,
+                        &options,
+                        &mut regex_pattern_table_list,
+                    );
+                    stats.table_stats_list.push(table_stats);
+                }
+                _ => unreachable!(),
+            };
+            // 一个词表可能拆分为多个正则表
+            table_index_list.resize(regex_pattern_table_list.len(), table_index);
+        }
+
+        let warning_list = stats
+            .table_stats_list
+            .iter()
+            .flat_map(|table_stats| {
+                table_stats
+                    .invalid_pattern_list
+                    .iter()
+                    .map(|(pattern, error)| PatternWarning {
+                        match_id: table_stats.match_id.clone(),
+                        table_id: table_stats.table_id,
+                        pattern: pattern.clone(),
+                        error: error.to_string(),
+                    })
+            })
+            .collect();
+
+        Ok((
+            RegexMatcher {
+                clause_split_pattern: Regex::new(r"[\s\pP]+").unwrap(),
+                regex_pattern_table_list: Arc::new(regex_pattern_table_list),
+                table_index_list: Arc::new(table_index_list),
+                stats: Arc::new(stats),
+                warning_callback: options.warning_callback,
+            },
+            warning_list,
+        ))
+    }
+
+    // 词表中重复的词只保留第一次出现的，以其下标作为词ID，返回 (词ID, 词)
+    fn dedup_wordlist<'a>(
+        regex_table: &RegexTable<'a>,
+        stats: &mut RegexMatcherStats,
+    ) -> Vec<(u64, &'a str)> {
+        let wordlist: &'a VarZeroVec<'a, str> = regex_table.wordlist;
+        let mut word_set = AHashSet::with_capacity(wordlist.len());
+        wordlist
+            .iter()
+            .enumerate()
+            .filter_map(|(word_id, word)| {
+                if word_set.insert(word) {
+                    Some((word_id as u64, word))
+                } else {
+                    stats.duplicate_word_list.push((
+                        regex_table.match_id.to_owned(),
+                        regex_table.table_id,
+                        word.to_owned(),
+                    ));
+                    None
+                }
+            })
+            .collect()
+    }
+
+    // 生成的正则中的词均已转义，只有超出大小限制等情况下才会编译失败
+    fn compile_generated_pattern(
+        regex_table: &RegexTable,
+        pattern: &str,
+        options: &RegexMatcherOptions,
+    ) -> Result<Regex, RegexBuildError> {
+        build_fancy_regex(pattern, options.regex_limit()).map_err(|e| {
+            RegexBuildError::InvalidGeneratedPattern {
+                match_id: regex_table.match_id.to_owned(),
+                table_id: regex_table.table_id,
+                pattern: pattern.to_owned(),
+                error: e.into(),
+            }
+        })
+    }
+
+    // fancy_regex匹配出错时（eg. 超出回溯上限）视为未命中，并通过告警回调上报
+    fn fancy_result<T: Default>(
+        &self,
+        regex_table: &RegexPatternTable,
+        regex: &Regex,
+        result: Result<T, fancy_regex::Error>,
+    ) -> T {
+        result.unwrap_or_else(|e| {
+            if let Some(warning_callback) = &self.warning_callback {
+                (warning_callback.0)(&PatternWarning {
+                    match_id: regex_table.match_id.clone(),
+                    table_id: regex_table.table_id,
+                    pattern: regex.as_str().to_owned(),
+                    error: e.to_string(),
+                });
+            }
+            T::default()
+        })
+    }
 
+    pub fn stats(&self) -> &RegexMatcherStats {
+        &self.stats
+    }
+
+    // 构建输入中各词表下标对实际加载的词的映射，词按词ID排序，无法编译的正则不在其中
+    pub(crate) fn table_wordlist_dict(&self) -> AHashMap<usize, Vec<&str>> {
+        let mut table_word_dict: AHashMap<usize, Vec<(u64, &str)>> = AHashMap::new();
+
+        for (regex_table, &table_index) in self
+            .regex_pattern_table_list
+            .iter()
+            .zip(self.table_index_list.iter())
+        {
+            let table_word_list = table_word_dict.entry(table_index).or_default();
+            match &regex_table.table_match_type {
+                RegexType::StandardRegex { wordlist, .. } => {
+                    table_word_list.extend(
+                        wordlist
+                            .iter()
+                            .enumerate()
+                            .map(|(word_id, word)| (word_id as u64, word.as_str())),
+                    );
+                }
+                RegexType::SetRegex {
+                    word_id_list,
+                    wordlist,
+                    ..
+                }
+                | RegexType::AcrosticNth {
+                    word_id_list,
+                    wordlist,
+                    ..
+                }
+                | RegexType::ListRegex {
+                    word_id_list,
+                    wordlist,
+                    ..
+                } => {
+                    table_word_list.extend(
+                        word_id_list
+                            .iter()
+                            .copied()
+                            .zip(wordlist.iter().map(String::as_str)),
+                    );
+                }
+            }
+        }
+
+        table_word_dict
+            .into_iter()
+            .map(|(table_index, mut table_word_list)| {
+                table_word_list.sort_by_key(|(word_id, _)| *word_id);
+                (
+                    table_index,
+                    table_word_list.into_iter().map(|(_, word)| word).collect(),
+                )
+            })
+            .collect()
+    }
+
+    // 构建输入中的词表下标及其实际加载的词，顺序不确定，无法编译的正则不在其中；只计数时省去table_wordlist_dict的排序与分组
+    pub(crate) fn table_word_iter(&self) -> impl Iterator<Item = (usize, &str)> + '_ {
+        self.regex_pattern_table_list
+            .iter()
+            .zip(self.table_index_list.iter())
+            .flat_map(|(regex_table, &table_index)| {
+                let (RegexType::StandardRegex { wordlist, .. }
+                | RegexType::SetRegex { wordlist, .. }
+                | RegexType::AcrosticNth { wordlist, .. }
+                | RegexType::ListRegex { wordlist, .. }) = &regex_table.table_match_type;
+                wordlist
+                    .iter()
+                    .map(move |word| (table_index, word.as_str()))
+            })
+    }
+
+    // 两个匹配器是否共享同一份编译后的正则表，即其中一个由另一个clone而来
+    pub fn ptr_eq(&self, other: &RegexMatcher) -> bool {
+        Arc::ptr_eq(
+            &self.regex_pattern_table_list,
+            &other.regex_pattern_table_list,
+        )
+    }
+
+    // 正则词表优先整表构建RegexSet一次性匹配，存在regex crate不支持的正则（eg. 环视，反向引用）时，记录原因并退化为fancy_regex逐个匹配
+    fn build_regex_table(
+        regex_table: &RegexTable,
+        word_list: &[(u64, &str)],
+        options: &RegexMatcherOptions,
+        regex_pattern_table_list: &mut Vec<RegexPatternTable>,
+    ) -> RegexTableStats {
+        let mut table_stats = RegexTableStats {
+            match_id: regex_table.match_id.to_owned(),
+            table_id: regex_table.table_id,
+            ..Default::default()
+        };
+
+        let regex_flags = regex_table.regex_flags;
+        let regex_limit = options.regex_limit();
+        // 通配符词表的词转换为正则后复用正则词表的匹配方式，结果仍返回原词
+        let pattern_list: Vec<Cow<str>> = word_list
+            .iter()
+            .map(|&(_, word)| match regex_table.match_table_type {
+                MatchTableType::Wildcard => Cow::Owned(wildcard_pattern(word)),
+                _ => Cow::Borrowed(word),
+            })
+            .collect();
+
+        if let Ok(regex_set) = regex_flags.regex_set(&pattern_list, regex_limit) {
+            table_stats.set_pattern_count = regex_set.len();
+            regex_pattern_table_list.push(RegexPatternTable {
+                table_id: regex_table.table_id,
+                match_id: regex_table.match_id.to_owned(),
+                table_match_type: RegexType::SetRegex {
+                    regex_set,
+                    word_id_list: word_list.iter().map(|&(word_id, _)| word_id).collect(),
+                    wordlist: word_list.iter().map(|&(_, word)| word.to_owned()).collect(),
+                    locate_regex_list: word_list.iter().map(|_| OnceLock::new()).collect(),
+                    regex_flags,
+                    regex_limit,
+                },
+            });
+            return table_stats;
+        }
+
+        let mut set_word_list = Vec::new(); // regex crate兼容的正则
+        let mut list_word_list = Vec::new(); // 需要逐个匹配的正则
+
+        for (&(word_id, word), pattern) in word_list.iter().zip(&pattern_list) {
+            match regex_flags.fancy_regex(pattern, regex_limit) {
+                Ok(regex) => match regex_flags.regex(pattern, regex_limit) {
+                    Ok(_) => set_word_list.push((word_id, word, pattern, regex)),
+                    Err(e) => {
+                        table_stats
+                            .fallback_pattern_list
+                            .push((word.to_owned(), e.to_string()));
+                        list_word_list.push((word_id, word, pattern, regex));
+                    }
+                },
+                Err(e) => table_stats
+                    .invalid_pattern_list
+                    .push((word.to_owned(), e.into())),
+            }
+        }
+
+        if !list_word_list.is_empty() && !options.split_regex_table {
+            // 不拆分时整表退化为逐个匹配
+            list_word_list.append(&mut set_word_list);
+            list_word_list.sort_by_key(|(word_id, _, _, _)| *word_id);
+        }
+
+        if !set_word_list.is_empty() {
+            match regex_flags.regex_set(
+                set_word_list.iter().map(|(_, _, pattern, _)| pattern),
+                regex_limit,
+            ) {
+                Ok(regex_set) => {
+                    table_stats.set_pattern_count = set_word_list.len();
                     regex_pattern_table_list.push(RegexPatternTable {
                         table_id: regex_table.table_id,
                         match_id: regex_table.match_id.to_owned(),
-                        table_match_type: RegexType::ListRegex {
-                            regex_list: wordlist
+                        table_match_type: RegexType::SetRegex {
+                            regex_set,
+                            word_id_list: set_word_list
                                 .iter()
-                                .filter_map(|word| Regex::new(&word).ok())
+                                .map(|(word_id, _, _, _)| *word_id)
                                 .collect(),
-                            wordlist,
+                            wordlist: set_word_list
+                                .iter()
+                                .map(|(_, word, _, _)| word.to_string())
+                                .collect(),
+                            locate_regex_list: set_word_list
+                                .iter()
+                                .map(|_| OnceLock::new())
+                                .collect(),
+                            regex_flags,
+                            regex_limit,
                         },
                     });
                 }
-                _ => unreachable!(),
-            };
+                Err(e) => {
+                    // 单个正则均兼容但组合后构建失败，eg. 超出大小限制
+                    table_stats
+                        .fallback_pattern_list
+                        .push((String::from("<regex set>"), e.to_string()));
+                    list_word_list.append(&mut set_word_list);
+                    list_word_list.sort_by_key(|(word_id, _, _, _)| *word_id);
+                }
+            }
         }
 
-        RegexMatcher {
-            regex_pattern_table_list,
+        if !list_word_list.is_empty() {
+            table_stats.list_pattern_count = list_word_list.len();
+
+            let mut regex_list = Vec::with_capacity(list_word_list.len());
+            let mut word_id_list = Vec::with_capacity(list_word_list.len());
+            let mut wordlist = Vec::with_capacity(list_word_list.len());
+            for (word_id, word, _, regex) in list_word_list {
+                regex_list.push(regex);
+                word_id_list.push(word_id);
+                wordlist.push(word.to_owned());
+            }
+
+            regex_pattern_table_list.push(RegexPatternTable {
+                table_id: regex_table.table_id,
+                match_id: regex_table.match_id.to_owned(),
+                table_match_type: RegexType::ListRegex {
+                    regex_list,
+                    word_id_list,
+                    wordlist,
+                },
+            });
         }
+
+        table_stats
     }
-}
 
-impl<'a> TextMatcherTrait<'a, RegexResult<'a>> for RegexMatcher {
-    fn is_match(&self, text: &str) -> bool {
-        for regex_table in &self.regex_pattern_table_list {
-            match &regex_table.table_match_type {
-                RegexType::StandardRegex { regex } => {
-                    if regex.is_match(text).unwrap() {
-                        return true;
-                    }
-                }
-                RegexType::ListRegex { regex_list, .. } => {
-                    if regex_list.iter().any(|regex| regex.is_match(text).unwrap()) {
-                        return true;
-                    }
-                }
-            }
+    // 按空白及标点切分分句，返回每个分句从第nth个字开始的剩余部分，分句长度不足时为None
+    fn nth_clause_tail_list<'b>(&self, text: &'b str, nth: usize) -> Vec<Option<&'b str>> {
+        let mut clause_list = Vec::new();
+        let mut last_end = 0;
+
+        for mat in self
+            .clause_split_pattern
+            .find_iter(text)
+            .filter_map(|mat| mat.ok())
+        {
+            clause_list.push(&text[last_end..mat.start()]);
+            last_end = mat.end();
         }
+        clause_list.push(&text[last_end..]);
 
-        false
+        clause_list
+            .into_iter()
+            .filter(|clause| !clause.is_empty())
+            .map(|clause| Self::nth_unit_index(clause, nth).map(|index| &clause[index..]))
+            .collect()
     }
 
-    fn process(&'a self, text: &str) -> Vec<RegexResult<'a>> {
+    // 第nth个字的字节下标，开启unicode-segmentation时按字素簇计数，避免拆开emoji序列及韩文字母
+    #[cfg(feature = "unicode-segmentation")]
+    fn nth_unit_index(clause: &str, nth: usize) -> Option<usize> {
+        clause
+            .grapheme_indices(true)
+            .nth(nth - 1)
+            .map(|(index, _)| index)
+    }
+
+    #[cfg(not(feature = "unicode-segmentation"))]
+    fn nth_unit_index(clause: &str, nth: usize) -> Option<usize> {
+        clause.char_indices().nth(nth - 1).map(|(index, _)| index)
+    }
+
+    // 命中的分句尾部在文本中的位置，分句尾部均为text的子串
+    fn acrostic_span(
+        text: &str,
+        clause_tail_list: &[Option<&str>],
+        start: usize,
+        split_word: &[String],
+    ) -> Option<MatchSpan> {
+        let offset = |clause_tail: &str| clause_tail.as_ptr() as usize - text.as_ptr() as usize;
+        let first_tail = clause_tail_list[start]?;
+        let last_tail = clause_tail_list[start + split_word.len() - 1]?;
+        Some(MatchSpan {
+            start: offset(first_tail),
+            end: offset(last_tail) + split_word.last()?.len(),
+            variant: 0,
+        })
+    }
+
+    // 在连续分句中依次比对词的各部分，返回命中的起始分句下标
+    fn match_acrostic_nth(
+        clause_tail_list: &[Option<&str>],
+        split_word: &[String],
+    ) -> Option<usize> {
+        if split_word.is_empty() || split_word.len() > clause_tail_list.len() {
+            return None;
+        }
+
+        (0..=clause_tail_list.len() - split_word.len()).find(|&start| {
+            split_word
+                .iter()
+                .zip(&clause_tail_list[start..])
+                .all(|(part, clause_tail)| {
+                    clause_tail.is_some_and(|clause_tail| clause_tail.starts_with(part.as_str()))
+                })
+        })
+    }
+
+    // 同process，每个词表匹配前检查截止时间，超时后停止匹配，返回已匹配的结果以及是否超时
+    pub fn process_until(
+        &self,
+        text: &str,
+        deadline: Option<Instant>,
+    ) -> (Vec<RegexResult<'_>>, bool) {
+        self.process_until_skipping(text, deadline, true, false, |_| false)
+    }
+
+    // 同process_until，跳过is_skipped返回true的词表，参数为词表在构建输入中的下标
+    // report_span为false时不获取命中位置，RegexSet以及逐个匹配的正则只需判断是否命中
+    // first_only为true时得到第一个结果后立即返回
+    pub(crate) fn process_until_skipping<F>(
+        &self,
+        text: &str,
+        deadline: Option<Instant>,
+        report_span: bool,
+        first_only: bool,
+        is_skipped: F,
+    ) -> (Vec<RegexResult<'_>>, bool)
+    where
+        F: Fn(usize) -> bool,
+    {
         let mut result_list = Vec::new();
+        if unlikely(text.is_empty()) {
+            return (result_list, false);
+        }
+
+        for (regex_table, &table_index) in self
+            .regex_pattern_table_list
+            .iter()
+            .zip(self.table_index_list.iter())
+        {
+            if is_skipped(table_index) {
+                continue;
+            }
+
+            if unlikely(deadline.is_some_and(|deadline| Instant::now() >= deadline)) {
+                return (result_list, true);
+            }
 
-        for regex_table in &self.regex_pattern_table_list {
             match &regex_table.table_match_type {
-                RegexType::StandardRegex { regex } => {
-                    for caps in regex.captures_iter(text).map(|caps| caps.unwrap()) {
+                RegexType::StandardRegex {
+                    regex,
+                    prefer_matched_text,
+                    ..
+                } => {
+                    for caps in regex
+                        .captures_iter(text)
+                        .map_while(|caps| self.fancy_result(regex_table, regex, caps.map(Some)))
+                    {
+                        // 各组命中字符之间可能夹杂其他字符，拼接结果不一定是文本中的子串
+                        let whole_match = unsafe { caps.get(0).unwrap_unchecked() };
+                        let matched_text = whole_match.as_str();
+                        let word = if *prefer_matched_text {
+                            matched_text.to_owned()
+                        } else {
+                            let group_text = caps
+                                .iter()
+                                .skip(1)
+                                .filter_map(|m| m.map(|match_char| match_char.as_str()))
+                                .collect::<Vec<&str>>()
+                                .join("");
+                            // 正则没有捕获组或各组均未参与命中时，使用整个命中子串，避免返回空词
+                            if group_text.is_empty() {
+                                matched_text.to_owned()
+                            } else {
+                                group_text
+                            }
+                        };
+
                         result_list.push(RegexResult {
-                            word: Cow::Owned(
-                                caps.iter()
-                                    .skip(1)
-                                    .filter_map(|m| m.map(|match_char| match_char.as_str()))
-                                    .collect::<Vec<&str>>()
-                                    .join(""),
-                            ),
+                            word_id: 0,
+                            word: Cow::Owned(word),
+                            table_id: regex_table.table_id,
+                            match_id: &regex_table.match_id,
+                            matched_text: Some(matched_text.to_owned()),
+                            span: Some(MatchSpan {
+                                start: whole_match.start(),
+                                end: whole_match.end(),
+                                variant: 0,
+                            }),
+                            captures: named_capture_list(regex.capture_names(), |index| {
+                                caps.get(index).map(|m| m.as_str())
+                            }),
+                        });
+                        if first_only {
+                            return (result_list, false);
+                        }
+                    }
+                }
+                RegexType::SetRegex {
+                    regex_set,
+                    word_id_list,
+                    wordlist,
+                    locate_regex_list,
+                    regex_flags,
+                    regex_limit,
+                } => {
+                    for index in regex_set.matches(text).into_iter() {
+                        let pattern = &regex_set.patterns()[index];
+                        // 构建RegexSet时已校验过，单独编译必定成功
+                        let get_locate_regex = || {
+                            locate_regex_list[index]
+                                .get_or_init(|| regex_flags.regex(pattern, *regex_limit).unwrap())
+                        };
+                        let (span, captures) = if may_have_named_group(pattern) {
+                            let locate_regex = get_locate_regex();
+                            let caps = locate_regex.captures(text).unwrap();
+                            let mat = caps.get(0).unwrap();
+                            (
+                                report_span.then(|| MatchSpan {
+                                    start: mat.start(),
+                                    end: mat.end(),
+                                    variant: 0,
+                                }),
+                                named_capture_list(locate_regex.capture_names(), |index| {
+                                    caps.get(index).map(|m| m.as_str())
+                                }),
+                            )
+                        } else if report_span {
+                            let mat = get_locate_regex().find(text).unwrap();
+                            (
+                                Some(MatchSpan {
+                                    start: mat.start(),
+                                    end: mat.end(),
+                                    variant: 0,
+                                }),
+                                Vec::new(),
+                            )
+                        } else {
+                            (None, Vec::new())
+                        };
+                        result_list.push(RegexResult {
+                            word_id: word_id_list[index],
+                            word: Cow::Borrowed(&wordlist[index]),
                             table_id: regex_table.table_id,
                             match_id: &regex_table.match_id,
+                            matched_text: None,
+                            span,
+                            captures,
                         });
+                        if first_only {
+                            return (result_list, false);
+                        }
                     }
                 }
                 RegexType::ListRegex {
                     regex_list,
+                    word_id_list,
                     wordlist,
                 } => {
                     for (index, regex) in regex_list.iter().enumerate() {
-                        if regex.is_match(text).unwrap() {
+                        let has_named_group = regex.captures_len() > 1
+                            && regex.capture_names().any(|name| name.is_some());
+                        let (span, captures) = if has_named_group {
+                            match self.fancy_result(regex_table, regex, regex.captures(text)) {
+                                Some(caps) => {
+                                    let mat = caps.get(0).unwrap();
+                                    (
+                                        report_span.then(|| MatchSpan {
+                                            start: mat.start(),
+                                            end: mat.end(),
+                                            variant: 0,
+                                        }),
+                                        named_capture_list(regex.capture_names(), |index| {
+                                            caps.get(index).map(|m| m.as_str())
+                                        }),
+                                    )
+                                }
+                                None => continue,
+                            }
+                        } else if report_span {
+                            match self.fancy_result(regex_table, regex, regex.find(text)) {
+                                Some(mat) => (
+                                    Some(MatchSpan {
+                                        start: mat.start(),
+                                        end: mat.end(),
+                                        variant: 0,
+                                    }),
+                                    Vec::new(),
+                                ),
+                                None => continue,
+                            }
+                        } else if self.fancy_result(regex_table, regex, regex.is_match(text)) {
+                            (None, Vec::new())
+                        } else {
+                            continue;
+                        };
+                        result_list.push(RegexResult {
+                            word_id: word_id_list[index],
+                            word: Cow::Borrowed(&wordlist[index]),
+                            table_id: regex_table.table_id,
+                            match_id: &regex_table.match_id,
+                            matched_text: None,
+                            span,
+                            captures,
+                        });
+                        if first_only {
+                            return (result_list, false);
+                        }
+                    }
+                }
+                RegexType::AcrosticNth {
+                    nth,
+                    split_wordlist,
+                    word_id_list,
+                    wordlist,
+                } => {
+                    let clause_tail_list = self.nth_clause_tail_list(text, *nth);
+
+                    for (index, split_word) in split_wordlist.iter().enumerate() {
+                        if let Some(start) = Self::match_acrostic_nth(&clause_tail_list, split_word)
+                        {
+                            // 命中词后附上贡献的分句序号（从1开始），eg. "你,好[2,3]"
                             result_list.push(RegexResult {
-                                word: Cow::Borrowed(&wordlist[index]),
+                                word_id: word_id_list[index],
+                                word: Cow::Owned(format!(
+                                    "{}[{}]",
+                                    wordlist[index],
+                                    (start + 1..=start + split_word.len())
+                                        .map(|clause_index| clause_index.to_string())
+                                        .collect::<Vec<String>>()
+                                        .join(",")
+                                )),
                                 table_id: regex_table.table_id,
                                 match_id: &regex_table.match_id,
+                                matched_text: None,
+                                span: Self::acrostic_span(
+                                    text,
+                                    &clause_tail_list,
+                                    start,
+                                    split_word,
+                                ),
+                                captures: Vec::new(),
                             });
+                            if first_only {
+                                return (result_list, false);
+                            }
                         }
                     }
                 }
             }
         }
 
-        result_list
+        (result_list, false)
+    }
+}
+
+impl<'a> TextMatcherTrait<'a, RegexResult<'a>> for RegexMatcher {
+    fn is_match(&self, text: &str) -> bool {
+        if unlikely(text.is_empty()) {
+            return false;
+        }
+
+        for regex_table in self.regex_pattern_table_list.iter() {
+            match &regex_table.table_match_type {
+                RegexType::StandardRegex { regex, .. } => {
+                    if self.fancy_result(regex_table, regex, regex.is_match(text)) {
+                        return true;
+                    }
+                }
+                RegexType::SetRegex { regex_set, .. } => {
+                    if regex_set.is_match(text) {
+                        return true;
+                    }
+                }
+                RegexType::ListRegex { regex_list, .. } => {
+                    if regex_list
+                        .iter()
+                        .any(|regex| self.fancy_result(regex_table, regex, regex.is_match(text)))
+                    {
+                        return true;
+                    }
+                }
+                RegexType::AcrosticNth {
+                    nth,
+                    split_wordlist,
+                    ..
+                } => {
+                    let clause_tail_list = self.nth_clause_tail_list(text, *nth);
+                    if split_wordlist.iter().any(|split_word| {
+                        Self::match_acrostic_nth(&clause_tail_list, split_word).is_some()
+                    }) {
+                        return true;
+                    }
+                }
+            }
+        }
+
+        false
+    }
+
+    fn process(&'a self, text: &str) -> Vec<RegexResult<'a>> {
+        self.process_until(text, None).0
+    }
+
+    fn find_first(&'a self, text: &str) -> Option<RegexResult<'a>> {
+        self.process_until_skipping(text, None, true, true, |_| false)
+            .0
+            .pop()
     }
 }
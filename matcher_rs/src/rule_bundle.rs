@@ -0,0 +1,266 @@
+use std::fmt;
+
+use ahash::AHashMap;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::matcher::{MatcherOptions, OwnedMatchTable, OwnedMatchTableDict};
+use crate::simple_matcher::SimpleMatchType;
+
+// 不兼容的格式变更时递增，新增带默认值的字段不需要递增，旧版本读取时忽略未知字段
+// 2: 词表拆分为独立的分段，追加在其余字段之后
+pub const RULE_BUNDLE_FORMAT_VERSION: u32 = 2;
+
+const RULE_BUNDLE_MAGIC: &[u8; 4] = b"MRBD";
+const RULE_BUNDLE_HEADER_LEN: usize = RULE_BUNDLE_MAGIC.len() + 1; // magic + 压缩方式
+const RULE_BUNDLE_SEGMENT_WORD_COUNT: usize = 1 << 16; // 每个词表分段的目标词数，分段越多并行度越高，但每段都有固定的调度开销
+
+// 词表分段，每段为若干match_id的词表
+type TableSegment<'a> = Vec<(&'a String, &'a Vec<OwnedMatchTable>)>;
+type OwnedTableSegment = Vec<(String, Vec<OwnedMatchTable>)>;
+
+// RuleBundle中除词表以外的字段，字段名与RuleBundle一致，读取时缺失的table_map由分段补齐
+#[derive(Serialize)]
+struct RuleBundleHead<'a> {
+    format_version: u32,
+    options: &'a MatcherOptions,
+    process_overrides: &'a AHashMap<SimpleMatchType, Vec<(String, String)>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BundleCompression {
+    #[default]
+    None,
+    Zstd, // 需要开启zstd feature
+}
+
+impl BundleCompression {
+    fn to_byte(self) -> u8 {
+        match self {
+            BundleCompression::None => 0,
+            BundleCompression::Zstd => 1,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<BundleCompression> {
+        match byte {
+            0 => Some(BundleCompression::None),
+            1 => Some(BundleCompression::Zstd),
+            _ => None,
+        }
+    }
+}
+
+// 词表、转换映射表覆盖以及构建选项打包为一个文件，保证三者版本一致
+// msgpack按字段名编码，旧版本读取新版本的bundle时忽略未知字段，缺失的字段使用默认值
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct RuleBundle {
+    pub format_version: u32, // 格式版本，读取时大于RULE_BUNDLE_FORMAT_VERSION则拒绝
+    pub options: MatcherOptions, // 构建选项
+    // 转换映射表覆盖，构建时并入options中的process_dict_override，同一转换类型以此为准
+    pub process_overrides: AHashMap<SimpleMatchType, Vec<(String, String)>>,
+    pub table_map: OwnedMatchTableDict, // 词表
+}
+
+impl Default for RuleBundle {
+    fn default() -> Self {
+        RuleBundle {
+            format_version: RULE_BUNDLE_FORMAT_VERSION,
+            options: MatcherOptions::default(),
+            process_overrides: AHashMap::new(),
+            table_map: OwnedMatchTableDict::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleBundleError {
+    InvalidHeader,              // 不是rule bundle，或已截断
+    UnsupportedCompression(u8), // 未知的压缩方式，或未开启对应feature
+    UnsupportedFormatVersion {
+        format_version: u32,
+        max_format_version: u32,
+    }, // bundle由不兼容的新版本生成
+    Encode(String),
+    Decode(String),
+}
+
+impl fmt::Display for RuleBundleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleBundleError::InvalidHeader => write!(f, "invalid rule bundle header"),
+            RuleBundleError::UnsupportedCompression(compression) => {
+                write!(f, "unsupported rule bundle compression: {compression}")
+            }
+            RuleBundleError::UnsupportedFormatVersion {
+                format_version,
+                max_format_version,
+            } => write!(
+                f,
+                "rule bundle format_version {format_version} is newer than the supported {max_format_version}"
+            ),
+            RuleBundleError::Encode(e) => write!(f, "encode rule bundle failed: {e}"),
+            RuleBundleError::Decode(e) => write!(f, "decode rule bundle failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for RuleBundleError {}
+
+impl RuleBundle {
+    pub fn new(table_map: OwnedMatchTableDict, options: MatcherOptions) -> RuleBundle {
+        RuleBundle {
+            table_map,
+            options,
+            ..Default::default()
+        }
+    }
+
+    // 构建时实际使用的选项
+    pub fn matcher_options(&self) -> MatcherOptions {
+        let mut options = self.options.clone();
+        options
+            .simple_matcher_options
+            .process_dict_override
+            .extend(self.process_overrides.clone());
+        options
+    }
+
+    // 格式为 magic + 压缩方式 + payload，payload为 不含词表的msgpack + 若干 (小端u64长度 + 词表分段的msgpack)
+    // 大词表的读取主要耗时在词表的反序列化，分段之间互不依赖，开启rayon时并行反序列化
+    pub fn to_bytes(&self, compression: BundleCompression) -> Result<Vec<u8>, RuleBundleError> {
+        let mut payload = rmp_serde::to_vec_named(&RuleBundleHead {
+            format_version: self.format_version,
+            options: &self.options,
+            process_overrides: &self.process_overrides,
+        })
+        .map_err(|e| RuleBundleError::Encode(e.to_string()))?;
+
+        for segment in self.table_segment_list() {
+            let segment_bytes = rmp_serde::to_vec_named(&segment)
+                .map_err(|e| RuleBundleError::Encode(e.to_string()))?;
+            payload.extend_from_slice(&(segment_bytes.len() as u64).to_le_bytes());
+            payload.extend_from_slice(&segment_bytes);
+        }
+
+        let mut bytes = Vec::with_capacity(RULE_BUNDLE_HEADER_LEN + payload.len());
+        bytes.extend_from_slice(RULE_BUNDLE_MAGIC);
+        bytes.push(compression.to_byte());
+        match compression {
+            BundleCompression::None => bytes.extend_from_slice(&payload),
+            #[cfg(feature = "zstd")]
+            BundleCompression::Zstd => bytes.extend_from_slice(
+                &zstd::encode_all(payload.as_slice(), 0)
+                    .map_err(|e| RuleBundleError::Encode(e.to_string()))?,
+            ),
+            #[cfg(not(feature = "zstd"))]
+            BundleCompression::Zstd => {
+                return Err(RuleBundleError::UnsupportedCompression(
+                    compression.to_byte(),
+                ))
+            }
+        }
+
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<RuleBundle, RuleBundleError> {
+        if bytes.len() < RULE_BUNDLE_HEADER_LEN || !bytes.starts_with(RULE_BUNDLE_MAGIC) {
+            return Err(RuleBundleError::InvalidHeader);
+        }
+        let compression_byte = bytes[RULE_BUNDLE_MAGIC.len()];
+        let payload = &bytes[RULE_BUNDLE_HEADER_LEN..];
+
+        match BundleCompression::from_byte(compression_byte) {
+            Some(BundleCompression::None) => Self::from_payload(payload),
+            #[cfg(feature = "zstd")]
+            Some(BundleCompression::Zstd) => Self::from_payload(
+                &zstd::decode_all(payload).map_err(|e| RuleBundleError::Decode(e.to_string()))?,
+            ),
+            _ => Err(RuleBundleError::UnsupportedCompression(compression_byte)),
+        }
+    }
+
+    // 按词数将词表切分为分段，单个match_id的词表不会跨段
+    fn table_segment_list(&self) -> Vec<TableSegment<'_>> {
+        let mut segment_list = Vec::new();
+        let mut segment = Vec::new();
+        let mut segment_word_count = 0;
+
+        for (match_id, table_list) in &self.table_map.0 {
+            segment_word_count += table_list
+                .iter()
+                .map(|table| table.wordlist.len() + table.exemption_wordlist.len())
+                .sum::<usize>();
+            segment.push((match_id, table_list));
+            if segment_word_count >= RULE_BUNDLE_SEGMENT_WORD_COUNT {
+                segment_list.push(std::mem::take(&mut segment));
+                segment_word_count = 0;
+            }
+        }
+        if !segment.is_empty() {
+            segment_list.push(segment);
+        }
+
+        segment_list
+    }
+
+    // 开启rayon且线程池不止一个线程时并行反序列化各分段，单线程时并行调度只有开销
+    fn decode_segment_list(
+        segment_bytes_list: Vec<&[u8]>,
+    ) -> Result<Vec<OwnedTableSegment>, rmp_serde::decode::Error> {
+        #[cfg(feature = "rayon")]
+        if segment_bytes_list.len() > 1 && rayon::current_num_threads() > 1 {
+            return segment_bytes_list
+                .into_par_iter()
+                .map(rmp_serde::from_slice)
+                .collect();
+        }
+
+        segment_bytes_list
+            .into_iter()
+            .map(rmp_serde::from_slice)
+            .collect()
+    }
+
+    fn from_payload(payload: &[u8]) -> Result<RuleBundle, RuleBundleError> {
+        // 版本1的payload只有完整的RuleBundle，读取后没有剩余字节
+        let mut rest = payload;
+        let mut rule_bundle: RuleBundle =
+            rmp_serde::from_read(&mut rest).map_err(|e| RuleBundleError::Decode(e.to_string()))?;
+
+        if rule_bundle.format_version > RULE_BUNDLE_FORMAT_VERSION {
+            return Err(RuleBundleError::UnsupportedFormatVersion {
+                format_version: rule_bundle.format_version,
+                max_format_version: RULE_BUNDLE_FORMAT_VERSION,
+            });
+        }
+
+        let mut segment_bytes_list = Vec::new();
+        while !rest.is_empty() {
+            let (len_bytes, segment_rest) = rest
+                .split_first_chunk::<8>()
+                .ok_or(RuleBundleError::InvalidHeader)?;
+            let segment_len = usize::try_from(u64::from_le_bytes(*len_bytes))
+                .map_err(|_| RuleBundleError::InvalidHeader)?;
+            if segment_len > segment_rest.len() {
+                return Err(RuleBundleError::InvalidHeader);
+            }
+            let (segment_bytes, segment_rest) = segment_rest.split_at(segment_len);
+            segment_bytes_list.push(segment_bytes);
+            rest = segment_rest;
+        }
+
+        let segment_list = Self::decode_segment_list(segment_bytes_list)
+            .map_err(|e| RuleBundleError::Decode(e.to_string()))?;
+        rule_bundle
+            .table_map
+            .0
+            .extend(segment_list.into_iter().flatten());
+
+        Ok(rule_bundle)
+    }
+}
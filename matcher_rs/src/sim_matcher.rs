@@ -1,66 +1,605 @@
 use std::borrow::Cow;
+use std::fmt;
 use std::intrinsics::unlikely;
+use std::sync::Arc;
+use std::time::Instant;
 
+use ahash::AHashMap;
 use fancy_regex::Regex;
-use strsim::normalized_levenshtein;
+use serde::{Deserialize, Serialize};
+use strsim::{jaro_winkler, normalized_damerau_levenshtein, normalized_levenshtein};
 use zerovec::VarZeroVec;
 
 use super::TextMatcherTrait;
+use crate::simple_matcher::{SimpleMatchType, TextConverter};
 
+pub const DEFAULT_SIM_THRESHOLD: f64 = 0.8; // 默认相似度阈值
+
+const CALIBRATE_GRID_SIZE: usize = 100; // 阈值校准的网格数，即步长0.01
+const DEADLINE_CHECK_INTERVAL: usize = 64; // 每匹配多少个词检查一次截止时间
+
+// 相似度算法，相似度均归一化到 0~1，与阈值的比较方式一致
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SimMatchType {
+    #[default]
+    Levenshtein, // 编辑距离，按较长字符串的长度归一化
+    JaroWinkler,          // Jaro-Winkler，公共前缀加权，适合用户名、品牌名等短文本
+    DamerauLevenshtein,   // 编辑距离，相邻字符交换计为一次编辑，eg. "hlelo" 与 "hello"
+    LevenshteinSubstring, // 编辑距离，在文本上滑动与词长度相近的窗口，取相似度最高的窗口，长文本中的近似词也能命中
+    NgramCosine {
+        n: usize,
+    }, // 字符n-gram频数向量的余弦相似度，与词序无关，长词表下比编辑距离快，eg. {"ngram_cosine": {"n": 3}}
+}
+
+// 文本按字符切分的字节位置，末尾附带文本长度，不会切开多字节字符
+fn char_index_list(text: &str) -> Vec<usize> {
+    text.char_indices()
+        .map(|(index, _)| index)
+        .chain(std::iter::once(text.len()))
+        .collect()
+}
+
+// 字符n-gram的频数向量，不足n个字符的文本整体作为一个n-gram
+struct NgramVector<K> {
+    count_map: AHashMap<K, u32>,
+    norm: f64,
+}
+
+impl<'t> NgramVector<&'t str> {
+    fn new(text: &'t str, n: usize) -> NgramVector<&'t str> {
+        let char_index_list = char_index_list(text);
+        let char_count = char_index_list.len() - 1;
+
+        let mut count_map = AHashMap::new();
+        if char_count > 0 {
+            let n = n.clamp(1, char_count);
+            for start in 0..=char_count - n {
+                *count_map
+                    .entry(&text[char_index_list[start]..char_index_list[start + n]])
+                    .or_insert(0) += 1;
+            }
+        }
+        let norm = count_map
+            .values()
+            .map(|&count| (count as f64).powi(2))
+            .sum::<f64>()
+            .sqrt();
+
+        NgramVector { count_map, norm }
+    }
+
+    fn into_owned(self) -> NgramVector<String> {
+        NgramVector {
+            count_map: self
+                .count_map
+                .into_iter()
+                .map(|(gram, count)| (gram.to_owned(), count))
+                .collect(),
+            norm: self.norm,
+        }
+    }
+}
+
+impl NgramVector<String> {
+    // 与文本向量的余弦相似度，两者均为空时视为相同
+    fn cosine(&self, text_vector: &NgramVector<&str>) -> f64 {
+        if self.norm == 0.0 || text_vector.norm == 0.0 {
+            return if self.norm == text_vector.norm {
+                1.0
+            } else {
+                0.0
+            };
+        }
+        let dot = self
+            .count_map
+            .iter()
+            .map(|(gram, &count)| {
+                count as f64
+                    * text_vector
+                        .count_map
+                        .get(gram.as_str())
+                        .copied()
+                        .unwrap_or(0) as f64
+            })
+            .sum::<f64>();
+        (dot / (self.norm * text_vector.norm)).min(1.0)
+    }
+}
+
+// 文本在某个词表下的打分输入，n-gram模式下文本只向量化一次，供词表中所有词复用
+struct TableText<'t> {
+    text: &'t str,
+    ngram_vector: Option<NgramVector<&'t str>>,
+}
+
+// 命中结果的排序方式，同一match_id下的结果在Matcher中保持该顺序
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SimSort {
+    #[default]
+    Similarity, // 按相似度降序，相似度相同时按词ID升序
+    WordId, // 按词ID升序
+    None,   // 不排序，按词表以及词的顺序返回，省去排序开销
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct SimMatcherOptions {
+    pub sort: SimSort, // 命中结果的排序方式
+}
+
+#[derive(Clone)]
 pub struct SimTable<'a> {
     pub table_id: u32,
     pub match_id: &'a str,
     pub wordlist: &'a VarZeroVec<'a, str>,
+    pub threshold: f64,                     // 相似度阈值，相似度不低于该值即命中
+    pub simple_match_type: SimpleMatchType, // 计算相似度前词与文本的转换类型，eg. 拼音字符，词按去除文本删除的类型转换，文本按去除词删除的类型转换
+    pub process_words: bool, // 是否按simple_match_type转换词与文本，关闭时忽略simple_match_type，原词与文本直接比较
+    pub sim_match_type: SimMatchType, // 相似度算法
+    pub top_k: Option<usize>, // 每个词表只保留相似度最高的k个结果，相似度相同时按词ID升序，None时保留所有达到阈值的结果；不影响is_match
 }
 
 struct SimProcessedTable {
     table_id: u32,
     match_id: String,
-    wordlist: Vec<String>,
+    wordlist: Vec<String>,                 // 原词，作为结果返回
+    scoring_wordlist: Option<Vec<String>>, // 转换后的词，用于计算相似度，不需要转换时为None，直接使用原词
+    text_converter: Option<TextConverter>, // 文本的转换器，不需要转换时为None
+    threshold: f64,
+    sim_match_type: SimMatchType,
+    top_k: Option<usize>,
+    ngram_vector_list: Vec<NgramVector<String>>, // n-gram模式下与scoring_wordlist一一对应的词向量，构建时计算，其余模式为空
+}
+
+impl SimProcessedTable {
+    #[inline]
+    fn scoring_wordlist(&self) -> &[String] {
+        self.scoring_wordlist.as_deref().unwrap_or(&self.wordlist)
+    }
+
+    fn build_ngram_vector_list(&mut self) {
+        if let SimMatchType::NgramCosine { n } = self.sim_match_type {
+            self.ngram_vector_list = self
+                .scoring_wordlist()
+                .iter()
+                .map(|word| NgramVector::new(word, n).into_owned())
+                .collect();
+        }
+    }
+
+    #[inline]
+    fn table_text<'t>(&self, processed_text: &'t str) -> TableText<'t> {
+        TableText {
+            text: processed_text,
+            ngram_vector: match self.sim_match_type {
+                SimMatchType::NgramCosine { n } => Some(NgramVector::new(processed_text, n)),
+                _ => None,
+            },
+        }
+    }
+
+    #[inline]
+    fn similarity(&self, word_index: usize, table_text: &TableText) -> f64 {
+        self.score(word_index, table_text).0
+    }
+
+    // 第word_index个词的相似度以及滑动窗口模式下最佳窗口在文本中的字节位置
+    fn score(&self, word_index: usize, table_text: &TableText) -> (f64, Option<(usize, usize)>) {
+        let word = self.scoring_wordlist()[word_index].as_str();
+        let processed_text = table_text.text;
+        match self.sim_match_type {
+            SimMatchType::Levenshtein => (normalized_levenshtein(word, processed_text), None),
+            SimMatchType::JaroWinkler => (jaro_winkler(word, processed_text), None),
+            SimMatchType::DamerauLevenshtein => {
+                (normalized_damerau_levenshtein(word, processed_text), None)
+            }
+            SimMatchType::LevenshteinSubstring => {
+                let (similarity, window) = self.best_window(word, processed_text);
+                (similarity, Some(window))
+            }
+            SimMatchType::NgramCosine { .. } => match &table_text.ngram_vector {
+                Some(text_vector) => (self.ngram_vector_list[word_index].cosine(text_vector), None),
+                None => (0.0, None),
+            },
+        }
+    }
+
+    // 窗口长度为 词长±允许的编辑数，按字符切分，不会切开多字节字符；相似度相同时取靠前且较短的窗口
+    // 文本不长于词时整个文本即为窗口
+    fn best_window(&self, word: &str, processed_text: &str) -> (f64, (usize, usize)) {
+        let word_len = word.chars().count();
+        let char_index_list = char_index_list(processed_text);
+        let text_len = char_index_list.len() - 1;
+
+        if text_len <= word_len || word_len == 0 {
+            return (
+                normalized_levenshtein(word, processed_text),
+                (0, processed_text.len()),
+            );
+        }
+
+        // 窗口与词的长度差至少需要同样多的编辑，超出允许编辑数的窗口不可能达到阈值
+        let edit_allowance = ((1.0 - self.threshold).max(0.0) * word_len as f64).ceil() as usize;
+        let min_window_len = word_len.saturating_sub(edit_allowance).max(1);
+        let max_window_len = (word_len + edit_allowance).min(text_len);
+
+        let mut best_similarity = -1.0;
+        let mut best_window = (0, 0);
+        for start in 0..text_len {
+            for window_len in min_window_len..=max_window_len.min(text_len - start) {
+                let window = (char_index_list[start], char_index_list[start + window_len]);
+                let similarity = normalized_levenshtein(word, &processed_text[window.0..window.1]);
+                if similarity > best_similarity {
+                    best_similarity = similarity;
+                    best_window = window;
+                }
+            }
+        }
+
+        (best_similarity, best_window)
+    }
 }
 
 #[derive(Debug)]
 pub struct SimResult<'a> {
+    pub word_id: u64, // 词在词表中的下标
     pub word: Cow<'a, str>,
     pub table_id: u32,
     pub match_id: &'a str,
     pub similarity: f64,
+    pub window: Option<(usize, usize)>, // 滑动窗口模式下最佳窗口在预处理（及转换）后文本中的字节位置，其余模式为None
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimBuildError {
+    InvalidThreshold {
+        match_id: String,
+        table_id: u32,
+        threshold: f64,
+    }, // 相似度阈值不在 0~1 之间，含NaN
+}
+
+impl fmt::Display for SimBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimBuildError::InvalidThreshold {
+                match_id,
+                table_id,
+                threshold,
+            } => write!(
+                f,
+                "match_id: {match_id}, table_id: {table_id}, threshold {threshold} is not within 0..=1"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SimBuildError {}
+
+// 词表由Arc持有，clone只增加引用计数
+#[derive(Clone)]
 pub struct SimMatcher {
     remove_special_pattern: Regex,
-    sim_processed_table_list: Vec<SimProcessedTable>,
+    sim_processed_table_list: Arc<Vec<SimProcessedTable>>,
+    options: SimMatcherOptions,
 }
 
 impl SimMatcher {
+    // 阈值不在 0~1 之间时panic
     pub fn new(sim_table_list: &Vec<SimTable>) -> SimMatcher {
-        SimMatcher {
+        Self::new_with_options(sim_table_list, SimMatcherOptions::default())
+    }
+
+    pub fn new_with_options(
+        sim_table_list: &Vec<SimTable>,
+        options: SimMatcherOptions,
+    ) -> SimMatcher {
+        Self::try_new_with_options(sim_table_list, options).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    // 同new，阈值不在 0~1 之间时返回错误
+    pub fn try_new(sim_table_list: &Vec<SimTable>) -> Result<SimMatcher, SimBuildError> {
+        Self::try_new_with_options(sim_table_list, SimMatcherOptions::default())
+    }
+
+    pub fn try_new_with_options(
+        sim_table_list: &Vec<SimTable>,
+        options: SimMatcherOptions,
+    ) -> Result<SimMatcher, SimBuildError> {
+        if let Some(sim_table) = sim_table_list
+            .iter()
+            .find(|sim_table| !(0.0..=1.0).contains(&sim_table.threshold))
+        {
+            return Err(SimBuildError::InvalidThreshold {
+                match_id: sim_table.match_id.to_owned(),
+                table_id: sim_table.table_id,
+                threshold: sim_table.threshold,
+            });
+        }
+
+        Ok(SimMatcher {
             remove_special_pattern: Regex::new(r"\W+").unwrap(),
             sim_processed_table_list: sim_table_list
                 .iter()
-                .map(|sim_table| SimProcessedTable {
-                    table_id: sim_table.table_id,
-                    match_id: sim_table.match_id.to_owned(),
-                    wordlist: sim_table
+                .map(|sim_table| {
+                    let wordlist = sim_table
                         .wordlist
                         .iter()
                         .map(|word| word.to_owned())
-                        .collect::<Vec<String>>(),
+                        .collect::<Vec<String>>();
+                    let simple_match_type = sim_table.simple_match_type.canonicalize();
+
+                    if !sim_table.process_words || simple_match_type.is_empty() {
+                        return SimProcessedTable {
+                            table_id: sim_table.table_id,
+                            match_id: sim_table.match_id.to_owned(),
+                            wordlist,
+                            scoring_wordlist: None,
+                            text_converter: None,
+                            threshold: sim_table.threshold,
+                            sim_match_type: sim_table.sim_match_type,
+                            top_k: sim_table.top_k,
+                            ngram_vector_list: Vec::new(),
+                        };
+                    }
+
+                    // 与simple匹配器一致，词不做文本删除，文本不做词删除
+                    let word_converter =
+                        TextConverter::new(simple_match_type - SimpleMatchType::TextDelete);
+                    SimProcessedTable {
+                        table_id: sim_table.table_id,
+                        match_id: sim_table.match_id.to_owned(),
+                        scoring_wordlist: Some(
+                            wordlist
+                                .iter()
+                                .map(|word| word_converter.convert(word).into_owned())
+                                .collect(),
+                        ),
+                        wordlist,
+                        text_converter: Some(TextConverter::new(
+                            simple_match_type - SimpleMatchType::WordDelete,
+                        )),
+                        threshold: sim_table.threshold,
+                        sim_match_type: sim_table.sim_match_type,
+                        top_k: sim_table.top_k,
+                        ngram_vector_list: Vec::new(),
+                    }
                 })
-                .collect(),
+                .map(|mut sim_processed_table| {
+                    sim_processed_table.build_ngram_vector_list();
+                    sim_processed_table
+                })
+                .collect::<Vec<SimProcessedTable>>()
+                .into(),
+            options,
+        })
+    }
+
+    // 构建输入中第table_index个词表的词
+    pub(crate) fn table_wordlist(&self, table_index: usize) -> &[String] {
+        &self.sim_processed_table_list[table_index].wordlist
+    }
+
+    // 两个匹配器是否共享同一份词表，即其中一个由另一个clone而来
+    pub fn ptr_eq(&self, other: &SimMatcher) -> bool {
+        Arc::ptr_eq(
+            &self.sim_processed_table_list,
+            &other.sim_processed_table_list,
+        )
+    }
+
+    // 匹配前对文本的预处理，去除非单词字符
+    #[inline]
+    fn preprocess<'b>(&self, text: &'b str) -> Cow<'b, str> {
+        self.remove_special_pattern.replace_all(text, "")
+    }
+
+    // 按词表的匹配类型转换后再预处理文本，词表不需要转换时返回None，直接使用公共的预处理结果
+    #[inline]
+    fn convert_text(&self, sim_table: &SimProcessedTable, text: &str) -> Option<String> {
+        sim_table
+            .text_converter
+            .as_ref()
+            .map(|text_converter| self.preprocess(&text_converter.convert(text)).into_owned())
+    }
+
+    // 按sort选项排序，均为稳定排序，排序键相同的结果保持词表顺序
+    fn sort_result_list(&self, result_list: &mut [SimResult]) {
+        match self.options.sort {
+            SimSort::Similarity => result_list.sort_by(|a, b| {
+                b.similarity
+                    .total_cmp(&a.similarity)
+                    .then(a.word_id.cmp(&b.word_id))
+            }),
+            SimSort::WordId => result_list.sort_by_key(|sim_result| sim_result.word_id),
+            SimSort::None => {}
+        }
+    }
+
+    // 词表的结果从table_start开始，只保留相似度最高的top_k个，相似度相同时按词ID升序
+    fn keep_top_k(result_list: &mut Vec<SimResult>, table_start: usize, top_k: Option<usize>) {
+        if let Some(top_k) = top_k {
+            result_list[table_start..].sort_by(|a, b| {
+                b.similarity
+                    .total_cmp(&a.similarity)
+                    .then(a.word_id.cmp(&b.word_id))
+            });
+            result_list.truncate(table_start + top_k);
+        }
+    }
+
+    // 离线校准工具：按负样本（不应命中的文本）在阈值网格上的误报率，返回误报率不超过max_fp_rate的最低阈值，即召回最高的阈值
+    // 负样本与词表的打分与匹配时完全一致；任何阈值都无法满足时返回1.0
+    pub fn calibrate(table: &SimTable, negatives: &[&str], max_fp_rate: f64) -> f64 {
+        if negatives.is_empty() {
+            return 0.0;
+        }
+
+        let sim_matcher = SimMatcher::new(&vec![SimTable {
+            table_id: table.table_id,
+            match_id: table.match_id,
+            wordlist: table.wordlist,
+            threshold: table.threshold,
+            simple_match_type: table.simple_match_type,
+            process_words: table.process_words,
+            sim_match_type: table.sim_match_type,
+            top_k: table.top_k,
+        }]);
+        let sim_table = &sim_matcher.sim_processed_table_list[0];
+
+        // 每条负样本与词表的最高相似度，相似度不低于阈值即为误报
+        let max_similarity_list: Vec<f64> = negatives
+            .iter()
+            .map(|text| {
+                let processed_text = sim_matcher.preprocess(text);
+                let converted_text = sim_matcher.convert_text(sim_table, text);
+                let table_text =
+                    sim_table.table_text(converted_text.as_deref().unwrap_or(&processed_text));
+                (0..sim_table.scoring_wordlist().len())
+                    .map(|index| sim_table.similarity(index, &table_text))
+                    .fold(0.0, f64::max)
+            })
+            .collect();
+
+        (0..=CALIBRATE_GRID_SIZE)
+            .map(|index| index as f64 / CALIBRATE_GRID_SIZE as f64)
+            .find(|&threshold| {
+                let fp_count = max_similarity_list
+                    .iter()
+                    .filter(|&&similarity| similarity >= threshold)
+                    .count();
+                fp_count as f64 / negatives.len() as f64 <= max_fp_rate
+            })
+            .unwrap_or(1.0)
+    }
+
+    // 离线查询工具：返回与query最相似的k个词，忽略阈值，跨词表按相似度降序、词ID升序排列
+    pub fn nearest(&self, query: &str, k: usize) -> Vec<SimResult<'_>> {
+        let processed_query = self.preprocess(query);
+
+        let mut result_list: Vec<SimResult> = self
+            .sim_processed_table_list
+            .iter()
+            .flat_map(|sim_table| {
+                let converted_query = self.convert_text(sim_table, query);
+                let table_query =
+                    sim_table.table_text(converted_query.as_deref().unwrap_or(&processed_query));
+                sim_table
+                    .wordlist
+                    .iter()
+                    .enumerate()
+                    .map(|(index, word)| {
+                        let (similarity, window) = sim_table.score(index, &table_query);
+                        SimResult {
+                            word_id: index as u64,
+                            word: Cow::Borrowed(word),
+                            table_id: sim_table.table_id,
+                            match_id: &sim_table.match_id,
+                            similarity,
+                            window,
+                        }
+                    })
+                    .collect::<Vec<SimResult>>()
+            })
+            .collect();
+
+        result_list.sort_by(|a, b| {
+            b.similarity
+                .total_cmp(&a.similarity)
+                .then(a.word_id.cmp(&b.word_id))
+        });
+        result_list.truncate(k);
+        result_list
+    }
+
+    // 同process，超过截止时间后停止匹配，返回已匹配的结果以及是否超时
+    pub fn process_until(
+        &self,
+        text: &str,
+        deadline: Option<Instant>,
+    ) -> (Vec<SimResult<'_>>, bool) {
+        self.process_until_skipping(text, deadline, false, |_| false)
+    }
+
+    // 同process_until，跳过is_skipped返回true的词表，参数为词表在构建输入中的下标
+    // first_only为true时得到第一个达到阈值的结果后立即返回，不保证是相似度最高的结果
+    pub(crate) fn process_until_skipping<F>(
+        &self,
+        text: &str,
+        deadline: Option<Instant>,
+        first_only: bool,
+        is_skipped: F,
+    ) -> (Vec<SimResult<'_>>, bool)
+    where
+        F: Fn(usize) -> bool,
+    {
+        let processed_text = self.preprocess(text);
+
+        let mut result_list = Vec::new();
+        // 去除非单词字符后为空的文本不参与匹配
+        if unlikely(processed_text.is_empty()) {
+            return (result_list, false);
         }
+
+        for (table_index, sim_table) in self.sim_processed_table_list.iter().enumerate() {
+            if is_skipped(table_index) {
+                continue;
+            }
+            let converted_text = self.convert_text(sim_table, text);
+            let table_text =
+                sim_table.table_text(converted_text.as_deref().unwrap_or(&processed_text));
+            let table_start = result_list.len();
+
+            for (index, word) in sim_table.wordlist.iter().enumerate() {
+                if unlikely(
+                    index % DEADLINE_CHECK_INTERVAL == 0
+                        && deadline.is_some_and(|deadline| Instant::now() >= deadline),
+                ) {
+                    Self::keep_top_k(&mut result_list, table_start, sim_table.top_k);
+                    self.sort_result_list(&mut result_list);
+                    return (result_list, true);
+                }
+
+                let (similarity, window) = sim_table.score(index, &table_text);
+
+                if unlikely(similarity >= sim_table.threshold) {
+                    result_list.push(SimResult {
+                        word_id: index as u64,
+                        word: Cow::Borrowed(word),
+                        table_id: sim_table.table_id,
+                        match_id: &sim_table.match_id,
+                        similarity,
+                        window,
+                    });
+                    if first_only {
+                        return (result_list, false);
+                    }
+                }
+            }
+
+            Self::keep_top_k(&mut result_list, table_start, sim_table.top_k);
+        }
+
+        self.sort_result_list(&mut result_list);
+        (result_list, false)
     }
 }
 
 impl<'a> TextMatcherTrait<'a, SimResult<'a>> for SimMatcher {
     fn is_match(&self, text: &str) -> bool {
-        let processed_text = self.remove_special_pattern.replace_all(text, "");
+        let processed_text = self.preprocess(text);
+        if unlikely(processed_text.is_empty()) {
+            return false;
+        }
 
-        for sim_table in &self.sim_processed_table_list {
-            if sim_table
-                .wordlist
-                .iter()
-                .any(|text| normalized_levenshtein(text, &processed_text) >= 0.8)
+        for sim_table in self.sim_processed_table_list.iter() {
+            let converted_text = self.convert_text(sim_table, text);
+            let table_text =
+                sim_table.table_text(converted_text.as_deref().unwrap_or(&processed_text));
+            if (0..sim_table.scoring_wordlist().len())
+                .any(|index| sim_table.similarity(index, &table_text) >= sim_table.threshold)
             {
                 return true;
             }
@@ -70,23 +609,13 @@ impl<'a> TextMatcherTrait<'a, SimResult<'a>> for SimMatcher {
     }
 
     fn process(&'a self, text: &str) -> Vec<SimResult<'a>> {
-        let processed_text = self.remove_special_pattern.replace_all(text, "");
-
-        let mut result_list = Vec::new();
-
-        for sim_table in &self.sim_processed_table_list {
-            result_list.extend(sim_table.wordlist.iter().filter_map(|text| {
-                let similarity = normalized_levenshtein(text, &processed_text);
-
-                unlikely(similarity >= 0.8).then(|| SimResult {
-                    word: Cow::Borrowed(text),
-                    table_id: sim_table.table_id,
-                    match_id: &sim_table.match_id,
-                    similarity,
-                })
-            }));
-        }
+        self.process_until(text, None).0
+    }
 
-        result_list
+    // 按词表以及词的顺序返回第一个达到阈值的结果，而非相似度最高的结果
+    fn find_first(&'a self, text: &str) -> Option<SimResult<'a>> {
+        self.process_until_skipping(text, None, true, |_| false)
+            .0
+            .pop()
     }
 }
@@ -1,26 +1,60 @@
 use std::borrow::Cow;
+use std::fmt;
+#[cfg(feature = "mmap")]
+use std::fs::File;
+use std::hash::{Hash, Hasher};
 use std::intrinsics::{likely, unlikely};
+#[cfg(feature = "serde")]
+use std::io::{self, Write};
+#[cfg(feature = "mmap")]
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use ahash::{AHashMap, AHashSet};
-use aho_corasick::{AhoCorasick, AhoCorasickBuilder, AhoCorasickKind::DFA, MatchKind};
+use ahash::{AHashMap, AHashSet, AHasher};
+use aho_corasick::{
+    automaton::OverlappingState, AhoCorasick, AhoCorasickBuilder, AhoCorasickKind::DFA, MatchKind,
+    PatternID,
+};
 use bitflags::bitflags;
 use nohash_hasher::{IntMap, IntSet};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+#[cfg(feature = "serde")]
+use serde::de::{self, Visitor};
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use tinyvec::{ArrayVec, TinyVec};
 
 use super::TextMatcherTrait;
 
+// 内置映射表按转换类型由feature控制是否编译进产物，关闭的转换类型只使用process_dict_override中的映射表
+#[cfg(feature = "fanjian")]
 const FANJIAN: &str = include_str!("../str_conv_dat/RASEMAT-FANJIAN.txt"); // 繁简
+#[cfg(feature = "delete")]
 const CN_SPECIAL: &str = include_str!("../str_conv_dat/RASEMAT-CN-SPECIAL.txt"); // 中文特殊字符
+#[cfg(feature = "delete")]
 const EN_SPECIAL: &str = include_str!("../str_conv_dat/RASEMAT-EN-SPECIAL.txt"); // 英文特殊字符
+#[cfg(feature = "delete")]
 const PUNCTUATION_SPECIAL: &str = include_str!("../str_conv_dat/RASEMAT-PUNCTUATION-SPECIAL.txt"); // 特殊符号
+#[cfg(feature = "normalize")]
 const EN_VARIATION: &str = include_str!("../str_conv_dat/RASEMAT-EN-VARIATION.txt"); // 英文变体
+#[cfg(feature = "fanjian")]
 const UNICODE: &str = include_str!("../str_conv_dat/RASEMAT-UNICODE.txt"); // UNICODE变体
+#[cfg(feature = "normalize")]
 const NUM_NORM: &str = include_str!("../str_conv_dat/RASEMAT-NUM-NORM.txt"); // 数字变体
+#[cfg(feature = "normalize")]
 const UPPER_LOWER: &str = include_str!("../str_conv_dat/RASEMAT-UPPER-LOWER.txt"); // 大小写
+#[cfg(feature = "pinyin")]
 const PINYIN: &str = include_str!("../str_conv_dat/RASEMAT-PINYIN.txt"); // 中文拼音
+#[cfg(feature = "pinyin")]
 const PINYIN_CHAR: &str = include_str!("../str_conv_dat/RASEMAT-PINYIN-CHAR.txt"); // 中文拼音
 
+const MAX_EXPANSION_RATIO: f32 = 4.0; // 预分配时膨胀比例的上限，个别映射膨胀比例极大时避免过度分配，超出部分由Vec自行扩容
+const TRUNCATED_TEXT_INDEX: usize = usize::MAX; // 因变体数超出上限而跳过的节点的文本下标
+
+#[cfg(feature = "delete")]
 const WHITE_SPACE: &[&str] = &[
     // 不可见字符
     "\u{0009}", "\u{000A}", "\u{000B}", "\u{000C}", "\u{000D}", "\u{0020}", "\u{0085}", "\u{00A0}",
@@ -29,7 +63,195 @@ const WHITE_SPACE: &[&str] = &[
     "\u{3000}",
 ];
 
-#[derive(Serialize, Deserialize)]
+// 词按'~'切分为组合词部分以及否定部分，eg. "你好,世界~坏人~骗子" -> ("你好,世界", ["坏人", "骗子"])
+// 优先级 '|' > '>' > ',' > '~'，eg. "刷单|兼职,微信~招聘|广告" 即 (刷单 或 兼职) 且 微信，且不含 (招聘 或 广告)
+// "转账>二维码|收款码,微信" 即 转账 之后出现 (二维码 或 收款码)，且 微信
+// 组合词部分末尾的 "@n" 要求各部分在同一文本变体中 n 字节的窗口内命中，eg. "转账,二维码@50~测试"
+#[inline]
+fn split_not_part(word: &str) -> (&str, impl Iterator<Item = &str>) {
+    let mut part_iter = split_unescaped(word, '~');
+    (
+        part_iter.next().unwrap_or_default(),
+        part_iter.filter(|part| !is_empty_part(part)),
+    )
+}
+
+// 词切分为去重后的各部分，按词表中首次出现的顺序，否定部分排在最后，以及组合词部分的重复次数和窗口约束
+// eg. "无,法,无,天~坏人" -> (["无", "法", "天", "坏人"], [2, 1, 1], None)，重复次数列表的长度即否定部分的偏移量
+fn split_word_part_list(word: &str) -> (Vec<&str>, Vec<u8>, Option<usize>) {
+    let (and_word, not_part_iter) = split_not_part(word);
+    let (and_word, window) = split_window(and_word);
+
+    let mut split_word_list: Vec<&str> = Vec::new();
+    let mut repeat_count_list: Vec<u8> = Vec::new(); // 计算重复词的个数
+    for split_word in split_unescaped(and_word, ',').filter(|&x| !is_empty_part(x)) {
+        match split_word_list.iter().position(|&x| x == split_word) {
+            Some(offset) => repeat_count_list[offset] = repeat_count_list[offset].saturating_add(1),
+            None => {
+                split_word_list.push(split_word);
+                repeat_count_list.push(1);
+            }
+        }
+    }
+
+    let not_offset = split_word_list.len();
+    for not_part in not_part_iter {
+        if !split_word_list[not_offset..].contains(&not_part) {
+            split_word_list.push(not_part);
+        }
+    }
+
+    (split_word_list, repeat_count_list, window)
+}
+
+// 组合词部分按'>'切分后的各步骤均为空，eg. "" / "|" / ">"，这样的部分不参与匹配
+#[inline]
+fn is_empty_part(part: &str) -> bool {
+    split_unescaped(part, '>').all(is_empty_step)
+}
+
+// 有序部分的步骤按'|'切分后的备选词均为空
+#[inline]
+fn is_empty_step(step: &str) -> bool {
+    split_unescaped(step, '|').all(str::is_empty)
+}
+
+// 组合词部分末尾未转义的 "@n" 为窗口约束，eg. "你好,世界@50" -> ("你好,世界", Some(50))，"a@b.com" 不变
+#[inline]
+fn split_window(and_word: &str) -> (&str, Option<usize>) {
+    let Some(at_index) = and_word.rfind('@') else {
+        return (and_word, None);
+    };
+    let window = &and_word[at_index + 1..];
+    let is_escaped = and_word[..at_index]
+        .bytes()
+        .rev()
+        .take_while(|&b| b == b'\\')
+        .count()
+        % 2
+        == 1;
+    if is_escaped || window.is_empty() || !window.bytes().all(|b| b.is_ascii_digit()) {
+        return (and_word, None);
+    }
+    (&and_word[..at_index], window.parse().ok())
+}
+
+const ESCAPED_CHAR_LIST: [char; 6] = ['\\', ',', '~', '|', '>', '@']; // 词中可以用'\'转义的字符
+
+// 按未转义的separator切分，各部分保留转义字符，eg. r"1\,000,元" -> [r"1\,000", "元"]
+#[inline]
+fn split_unescaped(text: &str, separator: char) -> impl Iterator<Item = &str> {
+    let mut escaped = false;
+    text.split(move |c: char| {
+        let is_separator = !escaped && c == separator;
+        escaped = !escaped && c == '\\';
+        is_separator
+    })
+}
+
+// 还原备选词中的转义字符，只有ESCAPED_CHAR_LIST中的字符视为转义，其余反斜杠原样保留，eg. r"1\,000" -> "1,000"，r"/\/\y" 不变
+fn unescape_part(part: &str) -> Cow<'_, str> {
+    if !part.contains('\\') {
+        return Cow::Borrowed(part);
+    }
+
+    let mut unescaped = String::with_capacity(part.len());
+    let mut char_iter = part.chars().peekable();
+    while let Some(c) = char_iter.next() {
+        match char_iter.peek() {
+            Some(&next) if c == '\\' && ESCAPED_CHAR_LIST.contains(&next) => {
+                unescaped.push(next);
+                char_iter.next();
+            }
+            _ => unescaped.push(c),
+        }
+    }
+    Cow::Owned(unescaped)
+}
+
+// 转义词中的 '\' ',' '~' '|' '>' '@'，使其按字面匹配，eg. "AT,T" -> r"AT\,T"，可以与其他部分拼接为组合词
+pub fn escape_simple_word(word: &str) -> Cow<'_, str> {
+    if !word.contains(ESCAPED_CHAR_LIST) {
+        return Cow::Borrowed(word);
+    }
+
+    let mut escaped = String::with_capacity(word.len() + 4);
+    for c in word.chars() {
+        if ESCAPED_CHAR_LIST.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    Cow::Owned(escaped)
+}
+
+// utf8的后续字节形如 0b10xxxxxx，转为i8后小于-0x40
+#[inline]
+fn is_char_boundary(text_bytes: &[u8], index: usize) -> bool {
+    index == text_bytes.len() || text_bytes.get(index).is_some_and(|&b| (b as i8) >= -0x40)
+}
+
+// 转换词与文本均为合法utf8，且utf8自同步，ac命中的起止位置必然落在字符边界上，包括4字节的辅助平面字符
+// 开启safe feature时改为带检查的切片，位置不在字符边界上时直接panic，而不是产生非法utf8
+#[inline]
+fn slice_text_bytes(text_bytes: &[u8], start: usize, end: usize) -> &[u8] {
+    debug_assert!(is_char_boundary(text_bytes, start) && is_char_boundary(text_bytes, end));
+
+    #[cfg(feature = "safe")]
+    {
+        assert!(
+            is_char_boundary(text_bytes, start) && is_char_boundary(text_bytes, end),
+            "match {start}..{end} is not on a char boundary"
+        );
+        &text_bytes[start..end]
+    }
+    #[cfg(not(feature = "safe"))]
+    unsafe {
+        text_bytes.get_unchecked(start..end)
+    }
+}
+
+// 文本是否只含空白以及零宽字符，eg. "\u{3000}\u{200B}"
+#[cfg(feature = "serde")]
+#[inline]
+pub(crate) fn is_blank(text: &str) -> bool {
+    text.chars().all(|c| {
+        c.is_whitespace()
+            || matches!(
+                c,
+                '\u{180E}' | '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}'
+            )
+    })
+}
+
+// 将文本中的各个位置替换为mask，每个字符替换为一个mask，位置可以重叠，eg. ("你好世界", [(0, 6), (3, 9)], '*') -> "***界"
+pub(crate) fn mask_span_list(
+    text: &str,
+    mut span_list: Vec<(usize, usize)>,
+    mask: char,
+) -> Cow<'_, str> {
+    if span_list.is_empty() {
+        return Cow::Borrowed(text);
+    }
+    span_list.sort_unstable();
+
+    let mut masked_text = String::with_capacity(text.len());
+    let mut last_end = 0;
+    for (start, end) in span_list {
+        let start = start.max(last_end);
+        if start >= end {
+            continue;
+        }
+        masked_text.push_str(&text[last_end..start]);
+        masked_text.extend(std::iter::repeat_n(mask, text[start..end].chars().count()));
+        last_end = end;
+    }
+    masked_text.push_str(&text[last_end..]);
+
+    Cow::Owned(masked_text)
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SimpleWord<'a> {
     pub word_id: u64,  // 词ID
     pub word: &'a str, // 敏感词
@@ -53,6 +275,51 @@ bitflags! {
 
 pub type SimpleMatchType = StrConvType;
 
+impl StrConvType {
+    // 规范化匹配类型：去除未定义的bit；词删除与文本删除任一存在时补齐为删除归一，保证词与文本的删除方式一致
+    pub fn canonicalize(self) -> StrConvType {
+        let mut canonical = self & StrConvType::all();
+        if canonical.intersects(StrConvType::Delete) {
+            canonical |= StrConvType::Delete;
+        }
+        canonical
+    }
+
+    // 拼音 与 拼音字符 同时存在时，会生成带空格与不带空格两种相互冲突的拼音变体
+    pub fn is_conflicting(self) -> bool {
+        self.contains(StrConvType::PinYin | StrConvType::PinYinChar)
+    }
+
+    // 未开启对应feature、没有内置映射表的转换类型
+    pub fn builtin_unavailable() -> StrConvType {
+        let mut str_conv_type = StrConvType::None;
+        if !cfg!(feature = "fanjian") {
+            str_conv_type |= StrConvType::Fanjian;
+        }
+        if !cfg!(feature = "delete") {
+            str_conv_type |= StrConvType::Delete;
+        }
+        if !cfg!(feature = "normalize") {
+            str_conv_type |= StrConvType::Normalize;
+        }
+        if !cfg!(feature = "pinyin") {
+            str_conv_type |= StrConvType::PinYin | StrConvType::PinYinChar;
+        }
+        str_conv_type
+    }
+
+    // 提供单个转换类型内置映射表的feature
+    fn builtin_feature(self) -> &'static str {
+        match self {
+            StrConvType::Fanjian => "fanjian",
+            StrConvType::WordDelete | StrConvType::TextDelete => "delete",
+            StrConvType::Normalize => "normalize",
+            _ => "pinyin",
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
 impl Serialize for StrConvType {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -62,221 +329,1663 @@ impl Serialize for StrConvType {
     }
 }
 
+#[cfg(feature = "serde")]
 impl<'de> Deserialize<'de> for StrConvType {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let bits: u8 = u8::deserialize(deserializer)?;
-        Ok(StrConvType::from_bits_retain(bits))
+        if !deserializer.is_human_readable() {
+            let bits: u8 = u8::deserialize(deserializer)?;
+            return Ok(StrConvType::from_bits_retain(bits));
+        }
+
+        // json等可读格式下同时接受数字与可读字符串，eg. 15 / "15" / "fanjian_delete_normalize"
+        struct StrConvTypeVisitor;
+
+        impl<'de> Visitor<'de> for StrConvTypeVisitor {
+            type Value = StrConvType;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("u8 bits or readable name of StrConvType")
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<StrConvType, E> {
+                u8::try_from(value)
+                    .map(StrConvType::from_bits_retain)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(value), &self))
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<StrConvType, E> {
+                u8::try_from(value)
+                    .map(StrConvType::from_bits_retain)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Signed(value), &self))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<StrConvType, E> {
+                match value.parse::<u8>() {
+                    Ok(bits) => Ok(StrConvType::from_bits_retain(bits)),
+                    Err(_) => value.parse().map_err(E::custom),
+                }
+            }
+        }
+
+        deserializer.deserialize_any(StrConvTypeVisitor)
     }
 }
 
-pub type SimpleWordlistDict<'a> = AHashMap<SimpleMatchType, Vec<SimpleWord<'a>>>;
+impl fmt::Display for StrConvType {
+    // 可读名称，eg. "fanjian_delete_normalize"，与FromStr互逆
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut name_list: ArrayVec<[&str; 6]> = ArrayVec::new();
 
-struct WordConf {
-    word: String,                  // 词
-    split_bit: TinyVec<[u64; 64]>, // 词的命中bit列表，eg. "你好" -> [1]，“你好,你真棒” -> [1, 1]，“无,法,无,天” -> [2, 1, 1]，这里 "无" 出现了2次，对应bit为 1 << (2 - 1) = 2
-}
+        if self.contains(StrConvType::Fanjian) {
+            name_list.push("fanjian");
+        }
+        if self.contains(StrConvType::Delete) {
+            name_list.push("delete");
+        } else if self.contains(StrConvType::WordDelete) {
+            name_list.push("word_delete");
+        } else if self.contains(StrConvType::TextDelete) {
+            name_list.push("text_delete");
+        }
+        if self.contains(StrConvType::Normalize) {
+            name_list.push("normalize");
+        }
+        if self.contains(StrConvType::PinYin) {
+            name_list.push("pinyin");
+        }
+        if self.contains(StrConvType::PinYinChar) {
+            name_list.push("pinyin_char");
+        }
 
-struct SimpleAcTable {
-    ac_matcher: AhoCorasick,              // ac自动机
-    ac_word_conf_list: Vec<(u64, usize)>, // ac词ID对 词ID 以及 偏移量（上述split_bit的索引）的映射
+        if name_list.is_empty() {
+            write!(f, "none")
+        } else {
+            write!(f, "{}", name_list.join("_"))
+        }
+    }
 }
 
-#[derive(Debug, Serialize)]
-pub struct SimpleResult<'a> {
-    pub word_id: u64,       // 命中词ID
-    pub word: Cow<'a, str>, // 命中词
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseSimpleMatchTypeError {
+    pub token: String, // 无法识别的名称片段
 }
 
-pub struct SimpleMatcher {
-    str_conv_process_dict: AHashMap<StrConvType, (Vec<&'static str>, AhoCorasick)>, // 转换方式对替换词表，替换词ac自动机的映射
-    simple_ac_table_dict: AHashMap<SimpleMatchType, SimpleAcTable>,                 // simple ac词表
-    simple_word_map: IntMap<u64, WordConf>, // 词ID对 词以及词命中bit列表的映射
-    min_text_len: usize, // 要求的文本最小长度，小于该长度直接返回空命中列表，在最小词长度相对较长时，可高效过滤短文本
+impl fmt::Display for ParseSimpleMatchTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown simple_match_type token: {:?}, expected none / fanjian / delete / word_delete / text_delete / normalize / pinyin / pinyin_char joined by '_'",
+            self.token
+        )
+    }
 }
 
-impl SimpleMatcher {
-    pub fn new(simple_wordlist_dict: &SimpleWordlistDict) -> SimpleMatcher {
-        let mut simple_matcher = SimpleMatcher {
-            str_conv_process_dict: AHashMap::new(),
-            simple_ac_table_dict: AHashMap::new(),
-            simple_word_map: IntMap::default(),
-            min_text_len: 255,
-        };
+impl std::error::Error for ParseSimpleMatchTypeError {}
 
-        for (simple_match_type, simple_wordlist) in simple_wordlist_dict {
-            for str_conv_type in simple_match_type.iter() {
-                simple_matcher
-                    .str_conv_process_dict
-                    .entry(str_conv_type)
-                    .or_insert_with(|| Self::_get_process_matcher(str_conv_type));
+// 单个ac自动机的词数上限，由ac自动机的PatternID决定
+pub const MAX_AC_PATTERN_COUNT: usize = PatternID::LIMIT;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimpleMatcherError {
+    TooManyPatterns {
+        simple_match_type: SimpleMatchType,
+        pattern_count: usize,
+        max_pattern_count: usize,
+    }, // 同一匹配类型下所有词切分并展开变体后的ac词数超过上限
+    DisabledProcessType {
+        simple_match_type: SimpleMatchType,
+        str_conv_type: StrConvType,
+        feature: &'static str,
+    }, // 转换类型未开启对应feature，且未通过process_dict_override提供映射表
+    DuplicateWordId {
+        word_id: u64,
+    }, // 同一词ID出现在多个词中，含不同匹配类型下的词
+}
+
+impl fmt::Display for SimpleMatcherError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimpleMatcherError::TooManyPatterns {
+                simple_match_type,
+                pattern_count,
+                max_pattern_count,
+            } => write!(
+                f,
+                "simple_match_type {simple_match_type} expands to {pattern_count} ac patterns, exceeding the limit of {max_pattern_count}"
+            ),
+            SimpleMatcherError::DisabledProcessType {
+                simple_match_type,
+                str_conv_type,
+                feature,
+            } => write!(
+                f,
+                "simple_match_type {simple_match_type} requires the builtin {str_conv_type} mapping, which is compiled out, enable the `{feature}` feature or provide it via process_dict_override"
+            ),
+            SimpleMatcherError::DuplicateWordId { word_id } => {
+                write!(f, "word_id {word_id} is used by more than one word")
             }
+        }
+    }
+}
 
-            let word_str_conv_list = *simple_match_type - StrConvType::TextDelete;
+impl std::error::Error for SimpleMatcherError {}
 
-            let simple_ac_table =
-                simple_matcher.build_simple_ac_table(&word_str_conv_list, simple_wordlist);
+impl FromStr for StrConvType {
+    type Err = ParseSimpleMatchTypeError;
 
-            simple_matcher.simple_ac_table_dict.insert(
-                *simple_match_type - StrConvType::WordDelete,
-                simple_ac_table,
-            );
+    // 接受单个名称以及'_'连接的组合，eg. "none" / "pinyin_char" / "fanjian_delete_normalize"
+    fn from_str(s: &str) -> Result<StrConvType, ParseSimpleMatchTypeError> {
+        let mut str_conv_type = StrConvType::None;
+        let mut token_iter = s.trim().split('_').peekable();
+
+        while let Some(token) = token_iter.next() {
+            str_conv_type |= match token.to_ascii_lowercase().as_str() {
+                "none" => StrConvType::None,
+                "fanjian" => StrConvType::Fanjian,
+                "delete" => StrConvType::Delete,
+                "normalize" => StrConvType::Normalize,
+                "word" | "text" if token_iter.peek() == Some(&"delete") => {
+                    token_iter.next();
+                    if token.eq_ignore_ascii_case("word") {
+                        StrConvType::WordDelete
+                    } else {
+                        StrConvType::TextDelete
+                    }
+                }
+                "pinyin" if token_iter.peek() == Some(&"char") => {
+                    token_iter.next();
+                    StrConvType::PinYinChar
+                }
+                "pinyin" => StrConvType::PinYin,
+                _ => {
+                    return Err(ParseSimpleMatchTypeError {
+                        token: token.to_owned(),
+                    })
+                }
+            };
         }
 
-        simple_matcher
+        Ok(str_conv_type)
     }
+}
 
-    fn _get_process_matcher(str_conv_type: StrConvType) -> (Vec<&'static str>, AhoCorasick) {
-        let mut process_dict = AHashMap::new();
+pub type SimpleWordlistDict<'a> = AHashMap<SimpleMatchType, Vec<SimpleWord<'a>>>;
 
-        match str_conv_type {
-            StrConvType::Fanjian => {
-                for str_conv_dat in [FANJIAN, UNICODE] {
-                    process_dict.extend(str_conv_dat.trim().split('\n').map(|pair_str| {
-                        let mut pair_str_split = pair_str.split('\t');
-                        (
-                            pair_str_split.next().unwrap(),
-                            pair_str_split.next().unwrap(),
-                        )
-                    }));
-                }
-            }
-            StrConvType::WordDelete => {
-                process_dict.extend(
-                    PUNCTUATION_SPECIAL
-                        .trim()
-                        .split('\n')
-                        .map(|pair_str| (pair_str, "")),
-                );
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum VariantStrategy {
+    #[default]
+    AllStages, // 文本链式转换的每一步结果都参与匹配，召回最全
+    FinalOnly, // 仅用链式转换后的最终文本匹配，变体更少更快，但依赖中间形态才能命中的词会漏召回，eg. 词 "a,b" 中 "a" 只出现在删除归一前的文本里
+}
 
-                process_dict.extend(WHITE_SPACE.iter().map(|&c| (c, "")));
-            }
-            StrConvType::TextDelete => {
-                for str_conv_dat in [PUNCTUATION_SPECIAL, CN_SPECIAL, EN_SPECIAL] {
-                    process_dict.extend(
-                        str_conv_dat
-                            .trim()
-                            .split('\n')
-                            .map(|pair_str| (pair_str, "")),
-                    );
-                }
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum CaseMode {
+    Lower, // 结果词统一转为小写
+    Upper, // 结果词统一转为大写
+}
 
-                process_dict.extend(WHITE_SPACE.iter().map(|&c| (c, "")));
-            }
-            StrConvType::Normalize => {
-                for str_conv_dat in [UPPER_LOWER, EN_VARIATION, NUM_NORM] {
-                    process_dict.extend(str_conv_dat.trim().split('\n').map(|pair_str| {
-                        let mut pair_str_split = pair_str.split('\t');
-                        (
-                            pair_str_split.next().unwrap(),
-                            pair_str_split.next().unwrap(),
-                        )
-                    }));
-                }
-            }
-            StrConvType::PinYin => {
-                process_dict.extend(PINYIN.trim().split('\n').map(|pair_str| {
-                    let mut pair_str_split = pair_str.split('\t');
-                    (
-                        pair_str_split.next().unwrap(),
-                        pair_str_split.next().unwrap(),
-                    )
-                }));
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SimpleMatcherOptions {
+    pub variant_strategy: VariantStrategy, // 查询时文本变体的生成策略
+    pub detailed: bool, // 是否在结果中返回组合词各部分的命中次数，会额外保存切分词并统计命中
+    pub normalize_result_case: Option<CaseMode>, // 匹配大小写不敏感，结果词默认保留词表中的写法，设置后按统一的大小写返回，eg. 词表 "FREE" 命中文本 "free"
+    pub prefilter: bool, // 构建字符级前置过滤器，原文本中不含任何可能产生命中的字符时，跳过文本转换以及ac匹配，适用于绝大多数文本不命中的场景
+    pub dedup_words: bool, // 同一匹配类型下重复的词只保留第一个word_id，避免重复命中
+    pub process_dict_override: AHashMap<StrConvType, Vec<(String, String)>>, // 按转换类型覆盖内置的映射表，存在覆盖的转换类型在构建时由覆盖的映射表生成，其余沿用内置映射表
+    pub display_separator: Option<String>, // 设置后组合词的结果词按各部分以该分隔符拼接返回，eg. "无,法,无,天" -> "无 法 无 天"，原始写法见SimpleResult.raw_word
+    pub max_variants: Option<usize>, // 单条文本最多生成的文本变体数，含原文本，超出后按树的节点顺序跳过后续产生新变体的节点及其子树，默认不限制
+    pub max_ac_pattern_count: Option<usize>, // 单个匹配类型ac自动机的词数上限，超出时构建返回TooManyPatterns错误，不超过MAX_AC_PATTERN_COUNT，默认即为MAX_AC_PATTERN_COUNT
+    pub report_negated: bool, // 诊断模式，process_full额外返回组合词部分均命中、但因'~'否定部分命中而被丢弃的词，以及命中的否定部分
+}
+
+impl SimpleMatcherOptions {
+    // 匹配类型中既没有内置映射表、也没有覆盖映射表的转换类型会报错
+    pub(crate) fn check_simple_match_type(
+        &self,
+        simple_match_type: SimpleMatchType,
+    ) -> Result<(), SimpleMatcherError> {
+        match (simple_match_type & StrConvType::builtin_unavailable())
+            .iter()
+            .find(|str_conv_type| !self.process_dict_override.contains_key(str_conv_type))
+        {
+            Some(str_conv_type) => Err(SimpleMatcherError::DisabledProcessType {
+                simple_match_type,
+                str_conv_type,
+                feature: str_conv_type.builtin_feature(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    // 去除不可用的转换类型，用于豁免词等隐式的匹配类型，不可用时退化为不做该转换
+    #[cfg(feature = "serde")]
+    pub(crate) fn available_simple_match_type(
+        &self,
+        simple_match_type: SimpleMatchType,
+    ) -> SimpleMatchType {
+        (simple_match_type & StrConvType::builtin_unavailable())
+            .iter()
+            .filter(|str_conv_type| !self.process_dict_override.contains_key(str_conv_type))
+            .fold(simple_match_type, |simple_match_type, str_conv_type| {
+                simple_match_type - str_conv_type
+            })
+    }
+}
+
+enum WordStorage {
+    Packed(String), // 所有词拼接存储在一段连续内存中，省去每个词单独分配的开销
+    #[cfg(feature = "mmap")]
+    Mmap(memmap2::Mmap), // 内存映射的词表文件，词直接引用文件内容，不占用堆内存
+}
+
+impl WordStorage {
+    #[inline]
+    fn get(&self, word_start: usize, word_end: usize) -> &str {
+        match self {
+            WordStorage::Packed(buffer) => unsafe { buffer.get_unchecked(word_start..word_end) },
+            #[cfg(feature = "mmap")]
+            WordStorage::Mmap(mmap) => unsafe {
+                std::str::from_utf8_unchecked(mmap.get_unchecked(word_start..word_end))
+            },
+        }
+    }
+
+    // 保存词并返回其在存储中的偏移量，mmap模式下词本身就来自映射内存，只需计算偏移量
+    fn store(&mut self, word: &str) -> (usize, usize) {
+        match self {
+            WordStorage::Packed(buffer) => {
+                let word_start = buffer.len();
+                buffer.push_str(word);
+                (word_start, buffer.len())
             }
-            StrConvType::PinYinChar => {
-                process_dict.extend(PINYIN_CHAR.trim().split('\n').map(|pair_str| {
-                    let mut pair_str_split = pair_str.split('\t');
-                    (
-                        pair_str_split.next().unwrap(),
-                        pair_str_split.next().unwrap(),
-                    )
-                }));
+            #[cfg(feature = "mmap")]
+            WordStorage::Mmap(mmap) => {
+                let word_start = word.as_ptr() as usize - mmap.as_ptr() as usize;
+                debug_assert!(word_start + word.len() <= mmap.len());
+                (word_start, word_start + word.len())
             }
-            _ => {}
         }
+    }
+}
 
-        process_dict
-            .retain(|&key, &mut value| (key == "#" || !key.starts_with('#')) && key != value); // 剔除注释词以及无效映射关系
+#[derive(Clone)]
+struct WordConf {
+    simple_match_type: SimpleMatchType, // 词所属的匹配类型，增删词后据此重建对应的ac自动机
+    word_start: usize,                  // 词在词存储中的起始偏移量
+    word_end: usize,                    // 词在词存储中的结束偏移量
+    split_word_list: Vec<String>, // 词按','切分去重后的各部分，含'|'的部分整体作为一部分，否定部分排在最后，与split_bit一一对应，仅detailed或report_negated模式下保存
+    split_bit: TinyVec<[u64; 64]>, // 词的命中bit列表，eg. "你好" -> [1]，“你好,你真棒” -> [1, 1]，“无,法,无,天” -> [2, 1, 1]，这里 "无" 出现了2次，对应bit为 1 << (2 - 1) = 2，否定部分恒为1
+    not_offset: usize, // 否定部分在split_bit中的起始下标，eg. "你好~坏人" -> 1，没有否定部分时等于split_bit的长度
+    sequence_list: Vec<(usize, usize)>, // 有序部分的 (在split_bit中的下标, 步骤数)，eg. "转账>二维码,微信" -> [(0, 2)]
+    step_list: Vec<(usize, usize)>, // 有序部分各步骤的 (在sequence_list中的下标, 步骤序号)，其ac词的偏移量为 split_bit的长度 + 在step_list中的下标
+    window: Option<usize>, // 窗口约束，组合词各部分须在同一文本变体中该字节数的窗口内命中，eg. "你好,世界@50" -> Some(50)
+}
 
-        let process_matcher = AhoCorasickBuilder::new()
-            .kind(Some(DFA)) // dfa更快但更占内存
-            .match_kind(MatchKind::LeftmostLongest) // 转换词之间可能会有重叠，eg."A","Ą̴̡̣̠̮̓̋", 此时以bytes最长的为准
-            .build(
-                process_dict
-                    .iter()
-                    .map(|(&key, _)| key)
-                    .collect::<Vec<&str>>(),
-            )
-            .unwrap();
-        let process_replace_list = process_dict.iter().map(|(_, &val)| val).collect();
+impl WordConf {
+    #[inline]
+    fn has_not_part(&self) -> bool {
+        self.not_offset < self.split_bit.len()
+    }
 
-        (process_replace_list, process_matcher)
+    // ac词偏移量是否属于组合词部分，有序部分的步骤同样属于其所在的部分
+    #[inline]
+    fn is_and_offset(&self, offset: usize) -> bool {
+        match offset.checked_sub(self.split_bit.len()) {
+            Some(step_index) => {
+                self.sequence_list[self.step_list[step_index].0].0 < self.not_offset
+            }
+            None => offset < self.not_offset,
+        }
     }
+}
 
-    fn build_simple_ac_table(
-        &mut self,
-        str_conv_type_list: &StrConvType,
-        simple_wordlist: &Vec<SimpleWord>,
-    ) -> SimpleAcTable {
-        let mut ac_wordlist = Vec::with_capacity(simple_wordlist.len());
-        let mut ac_word_conf_list = Vec::with_capacity(simple_wordlist.len());
+#[derive(Clone)]
+struct SimpleAcTable {
+    ac_matcher: AhoCorasick,              // ac自动机
+    ac_word_conf_list: Vec<(u64, usize)>, // ac词ID对 词ID 以及 偏移量（上述split_bit的索引）的映射
+    min_text_len: usize,                  // 该匹配类型的词要求的文本最小长度
+}
 
-        for simple_word in simple_wordlist {
-            let char_unique_cnt = simple_word
-                .word
-                .chars()
-                .filter(|&c| c != ',')
-                .collect::<AHashSet<char>>()
-                .len();
+// 逐个ac命中更新的匹配状态，process与process_iter共用
+#[derive(Default)]
+struct MatchState {
+    word_id_set: IntSet<u64>,                // 已完成命中的词ID
+    not_word_id_list: Vec<(u64, MatchSpan)>, // 组合词部分均已命中，但含否定部分的词ID，否定部分可能在后续轮次中命中，所有轮次结束后再判定
+    // 词ID对其命中轮次以及命中bit的映射，eg.“无,法,无,天” 繁简+删除归一+替换归一 3轮匹配，1 -> [[2，2，2], [1, 1, 1], [1, 1, 1]]
+    // 当且仅当 所有内部数组都至少有一个0时 代表命中
+    word_id_split_bit_map: IntMap<u64, TinyVec<[ArrayVec<[u64; 4]>; 64]>>,
+    word_id_hit_count_map: IntMap<u64, Vec<[u32; 4]>>, // detailed模式下，词ID对其各部分在各轮次中命中次数的映射
+    word_id_sequence_map: IntMap<u64, Vec<ArrayVec<[SequenceProgress; 4]>>>, // 词ID对其各有序部分在各轮次中进度的映射
+    word_id_window_hit_map: IntMap<u64, Vec<Vec<(usize, usize)>>>, // 窗口约束的词ID对其在各轮次中窗口内的 (部分下标, 命中开始位置) 列表
+}
 
-            if self.min_text_len > char_unique_cnt {
-                self.min_text_len = char_unique_cnt; // 计算最小长度文本
-            }
+// 有序部分的进度，(下一个待命中的步骤序号, 上一步骤命中的结束位置, 第一步骤命中的开始位置)，下一步骤须在上一步骤结束之后开始
+type SequenceProgress = (usize, usize, usize);
 
-            let mut ac_split_word_counter: AHashMap<&str, u8> = AHashMap::new(); // 计算重复词的个数
-            for ac_split_word in simple_word.word.split(',').filter(|&x| !x.is_empty()) {
-                ac_split_word_counter
-                    .entry(ac_split_word)
-                    .and_modify(|cnt| *cnt += 1)
-                    .or_insert(1);
-            }
+const MAX_VARIANT_COUNT: usize = 4; // 单一匹配类型的最大文本变体数，即各轮次相关定长数组的容量
 
-            let split_bit = ac_split_word_counter
-                .values()
-                .map(|&x| if x < 64 { 1 << (x - 1) } else { 1 << 63 }) // 最多重复64次
-                .collect();
+type VariantIndexList = ArrayVec<[(usize, usize); MAX_VARIANT_COUNT]>; // (全局变体下标, 文本变体列表下标) 列表
 
-            self.simple_word_map.insert(
-                simple_word.word_id,
-                WordConf {
-                    word: simple_word.word.to_owned(),
-                    split_bit,
-                },
-            );
+// SimpleMatcher::process_iter返回的惰性迭代器，按需驱动ac自动机的重叠匹配，词完成命中即返回
+// 调用方提前停止时，剩余的文本以及文本变体不再扫描；含否定部分的词需要扫描完所有变体才能判定，最后返回
+// detailed模式下不使用该迭代器，process_iter / find_first 退化为一次性的process
+// 'a为匹配器的生命周期，结果只借用匹配器；'t为文本的生命周期
+pub struct SimpleProcessIter<'a, 't> {
+    simple_matcher: &'a SimpleMatcher,
+    processed_text_bytes_list: Vec<Cow<'t, [u8]>>, // 文本变体
+    type_variant_list: Vec<(SimpleMatchType, VariantIndexList)>, // 匹配类型对其文本变体的下标列表
+    pending_str_conv_type: StrConvType, // 单一匹配类型时尚未进行的链式转换，上一个变体扫描完后才转换出下一个变体
+    type_index: usize,                  // 当前扫描的匹配类型
+    index: usize,                       // 当前扫描的文本变体在该匹配类型变体列表中的轮次
+    overlapping_state: OverlappingState, // 当前文本变体的ac重叠匹配进度
+    match_state: MatchState,
+    not_result_iter: Option<std::vec::IntoIter<SimpleResult<'a>>>, // 扫描结束后判定通过的含否定部分的词
+}
 
-            for (offset, split_word) in ac_split_word_counter.keys().enumerate() {
-                for ac_word in self.reduce_text_process(str_conv_type_list, split_word.as_bytes()) {
-                    ac_wordlist.push(ac_word.into_owned());
-                    ac_word_conf_list.push((simple_word.word_id, offset));
-                }
-            }
+impl<'a, 't> SimpleProcessIter<'a, 't> {
+    // 没有待扫描变体的迭代器
+    fn new(simple_matcher: &'a SimpleMatcher) -> SimpleProcessIter<'a, 't> {
+        SimpleProcessIter {
+            simple_matcher,
+            processed_text_bytes_list: Vec::new(),
+            type_variant_list: Vec::new(),
+            pending_str_conv_type: StrConvType::None,
+            type_index: 0,
+            index: 0,
+            overlapping_state: OverlappingState::start(),
+            match_state: MatchState::default(),
+            not_result_iter: None,
         }
+    }
 
-        SimpleAcTable {
-            ac_matcher: AhoCorasickBuilder::new()
-                .kind(Some(DFA))
-                .ascii_case_insensitive(true) // 大小写不敏感
-                .build(&ac_wordlist)
-                .unwrap(),
-            ac_word_conf_list,
+    // 对最后一个变体依次进行尚未进行的转换，直到产生新的变体或转换全部完成
+    fn convert_next_variant(&mut self) {
+        while let Some(str_conv_type) = self.pending_str_conv_type.iter().next() {
+            self.pending_str_conv_type -= str_conv_type;
+
+            let last_processed_text =
+                unsafe { self.processed_text_bytes_list.last().unwrap_unchecked() };
+            if let Some(processed_text) = self
+                .simple_matcher
+                ._convert_stage(str_conv_type, last_processed_text)
+            {
+                let variant_list = unsafe { &mut self.type_variant_list.get_unchecked_mut(0).1 };
+                let variant_offset = unsafe { variant_list.get_unchecked(0).0 };
+                let text_index = self.processed_text_bytes_list.len();
+                variant_list.push((text_index + variant_offset, text_index));
+                self.processed_text_bytes_list
+                    .push(Cow::Owned(processed_text));
+                return;
+            }
         }
     }
+}
 
-    #[inline]
-    fn reduce_text_process<'a>(
-        &self,
+impl<'a> Iterator for SimpleProcessIter<'a, '_> {
+    type Item = SimpleResult<'a>;
+
+    fn next(&mut self) -> Option<SimpleResult<'a>> {
+        loop {
+            if let Some(not_result_iter) = &mut self.not_result_iter {
+                return not_result_iter.next();
+            }
+
+            let Some((simple_match_type, variant_list)) =
+                self.type_variant_list.get(self.type_index)
+            else {
+                let mut result_list = Vec::new();
+                self.simple_matcher._finish_match_state(
+                    &std::mem::take(&mut self.match_state),
+                    &mut result_list,
+                    None,
+                );
+                self.not_result_iter = Some(result_list.into_iter());
+                continue;
+            };
+            let Some(&(variant, text_index)) = variant_list.get(self.index) else {
+                if !self.pending_str_conv_type.is_empty() {
+                    self.convert_next_variant();
+                } else {
+                    self.type_index += 1;
+                    self.index = 0;
+                }
+                continue;
+            };
+
+            let simple_ac_table = unsafe {
+                self.simple_matcher
+                    .simple_ac_table_dict
+                    .get(simple_match_type)
+                    .unwrap_unchecked()
+            };
+            let processed_text =
+                unsafe { self.processed_text_bytes_list.get_unchecked(text_index) };
+            simple_ac_table
+                .ac_matcher
+                .find_overlapping(processed_text.as_ref(), &mut self.overlapping_state);
+
+            match self.overlapping_state.get_match() {
+                Some(ac_result) => {
+                    if let Some(simple_result) = self.simple_matcher._record_ac_hit(
+                        &mut self.match_state,
+                        simple_ac_table,
+                        &ac_result,
+                        (self.index, variant),
+                        MAX_VARIANT_COUNT, // 变体可能尚未全部生成，按最大变体数分配，未生成的变体不会产生命中
+                    ) {
+                        return Some(simple_result);
+                    }
+                }
+                None => {
+                    self.index += 1;
+                    self.overlapping_state = OverlappingState::start();
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct PartHit<'a> {
+    pub part: Cow<'a, str>, // 组合词的一部分
+    pub count: u32,         // 命中次数，取各文本变体中的最大值
+}
+
+// 命中位置，start..end为字节位置
+// variant为0时位置在原文本中；否则位置在转换后的文本变体中，变体可由ProcessedText::variant获取
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct MatchSpan {
+    pub start: usize,   // 起始字节位置
+    pub end: usize,     // 结束字节位置，不含
+    pub variant: usize, // 命中所在的文本变体下标，0为原文本
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct SimpleResult<'a> {
+    pub word_id: u64,       // 命中词ID
+    pub word: Cow<'a, str>, // 命中词
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub raw_word: &'a str, // 词表中的原始写法，不受display_separator以及normalize_result_case影响，用于按词精确查找规则
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub parts: Option<Vec<PartHit<'a>>>, // 组合词各部分的命中次数，仅detailed模式下返回
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub span: Option<MatchSpan>, // 使词完成命中的那次ac命中的位置，组合词即最后命中的部分；par_process不返回
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct NegatedResult<'a> {
+    pub result: SimpleResult<'a>, // 组合词部分均命中，但被否定部分丢弃的词
+    pub not_part_list: Vec<Cow<'a, str>>, // 文本中命中的否定部分，按词表中的顺序
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct PartDetail<'a> {
+    pub part: Cow<'a, str>,     // 组合词的一部分或否定部分
+    pub negated: bool,          // 是否为否定部分
+    pub matched: bool,          // 是否命中，重复的部分须在同一文本变体中命中对应次数
+    pub variant: Option<usize>, // 首个使该部分命中的文本变体下标，0为原文本
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct DetailedResult<'a> {
+    pub word_id: u64,               // 词ID
+    pub word: Cow<'a, str>,         // 词
+    pub matched: bool,              // 是否在process的结果中
+    pub parts: Vec<PartDetail<'a>>, // 各部分的命中情况，组合词部分按词表中首次出现的顺序，否定部分排在最后
+}
+
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct SimpleFullResult<'a> {
+    pub matched: Vec<SimpleResult<'a>>,  // 与process的结果一致
+    pub negated: Vec<NegatedResult<'a>>, // 仅report_negated模式下返回
+}
+
+impl<'a> SimpleResult<'a> {
+    // 组合词的各部分，按词表中的顺序，不去重，不含否定部分以及窗口约束，'|'备选词不拆分，eg. "无,法,无,天" -> ["无", "法", "无", "天"]，"刷单|兼职,微信" -> ["刷单|兼职", "微信"]
+    pub fn word_parts(&self) -> Vec<&'a str> {
+        split_unescaped(split_window(split_not_part(self.raw_word).0).0, ',')
+            .filter(|part| !is_empty_part(part))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct SimpleBuildStats {
+    pub(crate) variant_expansion_duration: Duration, // 词切分以及变体展开耗时
+    pub(crate) ac_build_duration: Duration,          // ac自动机构建耗时
+    pub(crate) ac_pattern_count: usize,              // ac自动机的词数
+    pub(crate) max_table_ac_pattern_count: usize, // 单个匹配类型ac自动机的最大词数，与MAX_AC_PATTERN_COUNT比较即为余量
+}
+
+struct ProcessTypeTreeNode {
+    str_conv_type: StrConvType, // 该节点对父节点文本做的转换，根节点为None
+    parent: usize,              // 父节点下标
+    children: Vec<usize>,       // 子节点下标
+}
+
+pub struct ProcessTypeTree {
+    node_list: Vec<ProcessTypeTreeNode>, // 转换节点列表，下标0为根节点即原文本，父节点下标总小于子节点
+    simple_match_type_path_list: Vec<(SimpleMatchType, Vec<usize>)>, // 匹配类型对其转换链在树上的节点路径（不含根节点）
+    signature: u64, // 树结构的签名，签名相同的树对同一文本生成的变体完全一致，可以复用
+}
+
+impl ProcessTypeTree {
+    // 按匹配类型的转换链构建前缀树，共享前缀的匹配类型只需对文本做一次相同的转换，eg. 繁简 与 繁简+删除归一 共享繁简转换
+    pub fn new(simple_match_type_list: &[SimpleMatchType]) -> ProcessTypeTree {
+        let mut node_list = vec![ProcessTypeTreeNode {
+            str_conv_type: StrConvType::None,
+            parent: 0,
+            children: Vec::new(),
+        }];
+        let mut simple_match_type_path_list = Vec::with_capacity(simple_match_type_list.len());
+
+        for &simple_match_type in simple_match_type_list {
+            let mut current_node_index = 0;
+            let mut path = Vec::new();
+
+            for str_conv_type in simple_match_type.iter() {
+                let child_node_index = node_list[current_node_index]
+                    .children
+                    .iter()
+                    .copied()
+                    .find(|&child| node_list[child].str_conv_type == str_conv_type);
+
+                current_node_index = match child_node_index {
+                    Some(child) => child,
+                    None => {
+                        node_list.push(ProcessTypeTreeNode {
+                            str_conv_type,
+                            parent: current_node_index,
+                            children: Vec::new(),
+                        });
+                        let child = node_list.len() - 1;
+                        node_list[current_node_index].children.push(child);
+                        child
+                    }
+                };
+                path.push(current_node_index);
+            }
+
+            simple_match_type_path_list.push((simple_match_type, path));
+        }
+
+        let mut hasher = AHasher::default();
+        for node in &node_list {
+            (node.str_conv_type.bits(), node.parent).hash(&mut hasher);
+        }
+
+        ProcessTypeTree {
+            node_list,
+            simple_match_type_path_list,
+            signature: hasher.finish(),
+        }
+    }
+
+    pub fn simple_match_type_list(&self) -> impl Iterator<Item = SimpleMatchType> + '_ {
+        self.simple_match_type_path_list
+            .iter()
+            .map(|(simple_match_type, _)| *simple_match_type)
+    }
+
+    // 每条文本最多需要做的转换次数
+    pub fn process_count(&self) -> usize {
+        self.node_list.len() - 1
+    }
+
+    // 按节点路径还原匹配类型的文本变体下标列表，与 reduce_text_process 的结果一一对应
+    #[inline]
+    fn variant_index_list(
+        &self,
+        path: &[usize],
+        node_text_index_list: &[usize],
+    ) -> ArrayVec<[usize; MAX_VARIANT_COUNT]> {
+        let mut variant_index_list: ArrayVec<[usize; MAX_VARIANT_COUNT]> = ArrayVec::new();
+        variant_index_list.push(0);
+
+        let mut parent_node_index = 0;
+        for &node_index in path {
+            let text_index = unsafe { *node_text_index_list.get_unchecked(node_index) };
+            if unlikely(text_index == TRUNCATED_TEXT_INDEX) {
+                // 转换链上的节点因变体数超出上限被跳过，该匹配类型不参与匹配
+                variant_index_list.clear();
+                return variant_index_list;
+            }
+
+            if text_index != unsafe { *node_text_index_list.get_unchecked(parent_node_index) } {
+                if unsafe { self.node_list.get_unchecked(node_index) }.str_conv_type
+                    == StrConvType::Fanjian
+                {
+                    // 繁简直接覆盖原文本
+                    *unsafe { variant_index_list.last_mut().unwrap_unchecked() } = text_index;
+                } else {
+                    variant_index_list.push(text_index);
+                }
+            }
+            parent_node_index = node_index;
+        }
+
+        variant_index_list
+    }
+}
+
+struct Prefilter {
+    bmp_bit_list: Vec<u64>,          // 基本平面字符的bit集合
+    astral_char_set: AHashSet<char>, // 辅助平面字符集合
+    pass_all: bool,                  // 存在空ac词时任意文本都可能命中，不做过滤
+}
+
+impl Prefilter {
+    // 每个ac词至少有一个字符需要出现在转换后的文本中，而转换后文本中的字符，要么原样来自原文本，要么来自某个映射的替换结果
+    // 沿映射反向求出能产生该字符的所有原文本字符（映射键取首字符），每个ac词取反向闭包最小的字符，所有闭包的并集即为触发字符集
+    // 反向闭包忽略转换顺序，只会放大触发字符集，不会漏召回
+    fn new(
+        str_conv_type_list: &[StrConvType],
+        process_dict_override: &AHashMap<StrConvType, Vec<(String, String)>>,
+        ac_wordlist: &[Vec<u8>],
+    ) -> Prefilter {
+        let mut prefilter = Prefilter {
+            bmp_bit_list: vec![0; 0x10000 / 64],
+            astral_char_set: AHashSet::new(),
+            pass_all: ac_wordlist.iter().any(|ac_word| ac_word.is_empty()),
+        };
+        if prefilter.pass_all {
+            return prefilter;
+        }
+
+        let mut reverse_dict: AHashMap<char, AHashSet<char>> = AHashMap::new();
+        for &str_conv_type in str_conv_type_list {
+            for (key, value) in
+                SimpleMatcher::_get_process_pair_list(str_conv_type, process_dict_override)
+            {
+                let Some(key_first_char) = key.chars().next() else {
+                    continue;
+                };
+                for value_char in value.chars() {
+                    reverse_dict
+                        .entry(value_char)
+                        .or_default()
+                        .insert(key_first_char);
+                }
+            }
+        }
+
+        let mut closure_dict: AHashMap<char, AHashSet<char>> = AHashMap::new();
+        let mut closure = |c: char| -> AHashSet<char> {
+            closure_dict
+                .entry(c)
+                .or_insert_with(|| {
+                    // ac自动机对ascii大小写不敏感，大小写任一形式出现在转换后的文本中都能命中
+                    let mut closure_set =
+                        AHashSet::from([c.to_ascii_lowercase(), c.to_ascii_uppercase()]);
+                    let mut stack: Vec<char> = closure_set.iter().copied().collect();
+                    while let Some(c) = stack.pop() {
+                        for &key_char in reverse_dict.get(&c).into_iter().flatten() {
+                            if closure_set.insert(key_char) {
+                                stack.push(key_char);
+                            }
+                        }
+                    }
+                    closure_set
+                })
+                .clone()
+        };
+
+        for ac_word in ac_wordlist {
+            let Some(trigger_char_set) = String::from_utf8_lossy(ac_word)
+                .chars()
+                .collect::<AHashSet<char>>()
+                .into_iter()
+                .map(&mut closure)
+                .min_by_key(|closure_set| closure_set.len())
+            else {
+                continue;
+            };
+            for c in trigger_char_set {
+                prefilter.insert(c);
+            }
+        }
+
+        prefilter
+    }
+
+    #[inline]
+    fn insert(&mut self, c: char) {
+        let code = c as usize;
+        if code < 0x10000 {
+            self.bmp_bit_list[code / 64] |= 1 << (code % 64);
+        } else {
+            self.astral_char_set.insert(c);
+        }
+    }
+
+    // 并入另一过滤器的触发字符集
+    fn merge(&mut self, other: &Prefilter) {
+        self.pass_all |= other.pass_all;
+        for (bit, other_bit) in self.bmp_bit_list.iter_mut().zip(&other.bmp_bit_list) {
+            *bit |= other_bit;
+        }
+        self.astral_char_set
+            .extend(other.astral_char_set.iter().copied());
+    }
+
+    #[inline]
+    fn contains(&self, c: char) -> bool {
+        let code = c as usize;
+        if likely(code < 0x10000) {
+            unsafe { *self.bmp_bit_list.get_unchecked(code / 64) & (1 << (code % 64)) != 0 }
+        } else {
+            self.astral_char_set.contains(&c)
+        }
+    }
+
+    // 原文本中是否存在触发字符，不存在时一定不会命中
+    #[inline]
+    fn is_candidate(&self, text: &str) -> bool {
+        self.pass_all || text.chars().any(|c| self.contains(c))
+    }
+}
+
+pub struct ProcessedText<'a> {
+    text: &'a str,                                 // 原文本
+    tree_signature: Option<u64>,                   // 生成变体所用转换树的签名，未生成变体时为None
+    processed_text_bytes_list: Vec<Cow<'a, [u8]>>, // 按转换树转换并去重后的文本变体
+    node_text_index_list: Vec<usize>,              // 转换树每个节点对应的文本变体下标
+    variants_truncated: bool,                      // 是否因变体数超出max_variants跳过了部分节点
+}
+
+impl<'a> ProcessedText<'a> {
+    // 不生成任何变体，仅包装原文本，供没有simple匹配器的场景使用
+    pub fn from_text(text: &'a str) -> ProcessedText<'a> {
+        ProcessedText {
+            text,
+            tree_signature: None,
+            processed_text_bytes_list: Vec::new(),
+            node_text_index_list: Vec::new(),
+            variants_truncated: false,
+        }
+    }
+
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    // 文本变体数，含原文本
+    pub fn variant_count(&self) -> usize {
+        self.processed_text_bytes_list.len()
+    }
+
+    // 下标对应的文本变体，与MatchSpan.variant对应，0为原文本
+    pub fn variant(&self, index: usize) -> Option<&str> {
+        if index == 0 {
+            return Some(self.text);
+        }
+        self.processed_text_bytes_list
+            .get(index)
+            .map(|processed_text_bytes| unsafe {
+                std::str::from_utf8_unchecked(processed_text_bytes)
+            })
+    }
+
+    // 是否因变体数超出max_variants跳过了部分转换，此时依赖被跳过转换的匹配类型不会产生命中
+    pub fn variants_truncated(&self) -> bool {
+        self.variants_truncated
+    }
+}
+
+type ProcessMatcher = (Vec<Cow<'static, str>>, AhoCorasick, f32); // 替换词表，替换词ac自动机，替换后的最大膨胀比例
+
+// 按匹配类型链式转换文本，只保留最终的转换结果，不生成中间变体，供sim匹配器转换词以及文本
+#[cfg(feature = "serde")]
+pub(crate) struct TextConverter {
+    process_matcher_list: Vec<(StrConvType, ProcessMatcher)>, // 按bit顺序排列的转换方式
+}
+
+#[cfg(feature = "serde")]
+impl TextConverter {
+    pub(crate) fn new(str_conv_type_list: StrConvType) -> TextConverter {
+        TextConverter {
+            process_matcher_list: str_conv_type_list
+                .iter()
+                .map(|str_conv_type| {
+                    (
+                        str_conv_type,
+                        SimpleMatcher::_get_process_matcher(str_conv_type, &AHashMap::new()),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    pub(crate) fn convert<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut processed_text_bytes: Cow<[u8]> = Cow::Borrowed(text.as_bytes());
+
+        for (str_conv_type, (process_replace_list, process_matcher, max_expansion_ratio)) in
+            &self.process_matcher_list
+        {
+            if !process_matcher.is_match(processed_text_bytes.as_ref()) {
+                continue;
+            }
+            processed_text_bytes = Cow::Owned(match *str_conv_type {
+                StrConvType::TextDelete | StrConvType::WordDelete => {
+                    SimpleMatcher::delete_all_bytes(process_matcher, &processed_text_bytes)
+                }
+                _ => SimpleMatcher::replace_all_bytes(
+                    process_matcher,
+                    process_replace_list,
+                    *max_expansion_ratio,
+                    &processed_text_bytes,
+                ),
+            });
+        }
+
+        // 映射表均为合法utf8，按ac命中位置替换以及删除后仍为合法utf8
+        match processed_text_bytes {
+            Cow::Borrowed(_) => Cow::Borrowed(text),
+            #[cfg(feature = "safe")]
+            Cow::Owned(processed_text_bytes) => Cow::Owned(
+                String::from_utf8(processed_text_bytes).expect("processed text is not valid utf8"),
+            ),
+            #[cfg(not(feature = "safe"))]
+            Cow::Owned(processed_text_bytes) => {
+                Cow::Owned(unsafe { String::from_utf8_unchecked(processed_text_bytes) })
+            }
+        }
+    }
+}
+
+// 构建产物均由Arc持有，clone只增加引用计数，不拷贝ac自动机以及词表
+#[derive(Clone)]
+pub struct SimpleMatcher {
+    str_conv_process_dict: Arc<AHashMap<StrConvType, ProcessMatcher>>, // 转换方式对替换词表，替换词ac自动机，替换后的最大膨胀比例的映射
+    simple_ac_table_dict: Arc<AHashMap<SimpleMatchType, SimpleAcTable>>, // simple ac词表
+    simple_word_map: Arc<IntMap<u64, WordConf>>, // 词ID对 词以及词命中bit列表的映射
+    min_text_len: usize, // 要求的文本最小长度，小于该长度直接返回空命中列表，在最小词长度相对较长时，可高效过滤短文本
+    word_storage: Arc<WordStorage>, // 词存储，WordConf中只保存偏移量
+    process_type_tree: Arc<ProcessTypeTree>, // 所有匹配类型的文本转换树
+    build_stats: SimpleBuildStats, // 构建耗时统计
+    prefilter: Option<Arc<Prefilter>>, // 字符级前置过滤器，仅在选项开启时构建
+    single_type: Option<SimpleMatchType>, // 仅有一种匹配类型且变体数不会被截断时，process直接链式转换文本，省去转换树的开销
+    options: SimpleMatcherOptions,        // 匹配选项
+    pending_word_list: Vec<(SimpleMatchType, u64, String)>, // add_word新增、调用rebuild后才生效的词
+    dirty_type_set: AHashSet<SimpleMatchType>, // 有词增删、调用rebuild时需要重建ac自动机的匹配类型
+}
+
+impl SimpleMatcher {
+    pub fn new(simple_wordlist_dict: &SimpleWordlistDict) -> SimpleMatcher {
+        Self::new_with_options(simple_wordlist_dict, SimpleMatcherOptions::default())
+    }
+
+    // ac词数超过上限或词ID重复时panic，需要处理错误时使用try_new_with_options
+    pub fn new_with_options(
+        simple_wordlist_dict: &SimpleWordlistDict,
+        options: SimpleMatcherOptions,
+    ) -> SimpleMatcher {
+        Self::try_new_with_options(simple_wordlist_dict, options).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    pub fn try_new(
+        simple_wordlist_dict: &SimpleWordlistDict,
+    ) -> Result<SimpleMatcher, SimpleMatcherError> {
+        Self::try_new_with_options(simple_wordlist_dict, SimpleMatcherOptions::default())
+    }
+
+    pub fn try_new_with_options(
+        simple_wordlist_dict: &SimpleWordlistDict,
+        options: SimpleMatcherOptions,
+    ) -> Result<SimpleMatcher, SimpleMatcherError> {
+        Self::build(
+            simple_wordlist_dict,
+            options,
+            WordStorage::Packed(String::new()),
+        )
+    }
+
+    // 从内存映射的json词表文件构建，格式同SimpleWordlistDict，词直接引用映射内存，不做拷贝
+    // 由于是零拷贝反序列化，词中不能包含json转义字符
+    #[cfg(feature = "mmap")]
+    pub fn open_mmap<P: AsRef<Path>>(
+        path: P,
+        options: SimpleMatcherOptions,
+    ) -> io::Result<SimpleMatcher> {
+        let file = File::open(path)?;
+        let mmap = unsafe { memmap2::Mmap::map(&file)? };
+
+        // 映射内存的地址不随Mmap的移动而改变，且Mmap会被SimpleMatcher持有，所以这里可以放开生命周期的限制
+        let mmap_bytes: &'static [u8] =
+            unsafe { std::slice::from_raw_parts(mmap.as_ptr(), mmap.len()) };
+        let simple_wordlist_dict: SimpleWordlistDict = serde_json::from_slice(mmap_bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        Self::build(&simple_wordlist_dict, options, WordStorage::Mmap(mmap))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn build(
+        simple_wordlist_dict: &SimpleWordlistDict,
+        options: SimpleMatcherOptions,
+        mut word_storage: WordStorage,
+    ) -> Result<SimpleMatcher, SimpleMatcherError> {
+        let mut simple_matcher = SimpleMatcher {
+            str_conv_process_dict: Arc::new(AHashMap::new()),
+            simple_ac_table_dict: Arc::new(AHashMap::new()),
+            simple_word_map: Arc::new(IntMap::default()),
+            min_text_len: 255,
+            word_storage: Arc::new(WordStorage::Packed(String::new())),
+            process_type_tree: Arc::new(ProcessTypeTree::new(&[])),
+            build_stats: SimpleBuildStats::default(),
+            prefilter: None,
+            single_type: None,
+            options,
+            pending_word_list: Vec::new(),
+            dirty_type_set: AHashSet::new(),
+        };
+        let mut simple_ac_table_dict = AHashMap::new();
+        let mut simple_word_map = IntMap::default();
+        let mut build_stats = SimpleBuildStats::default();
+        let mut prefilter_ac_wordlist = Vec::new();
+
+        // 词配置按词ID存储，重复的词ID会互相覆盖，先构建的词的ac词指向被覆盖的配置；存在多个时报告最小的词ID
+        let mut word_id_set = IntSet::default();
+        if let Some(word_id) = simple_wordlist_dict
+            .values()
+            .flatten()
+            .map(|simple_word| simple_word.word_id)
+            .filter(|&word_id| !word_id_set.insert(word_id))
+            .min()
+        {
+            return Err(SimpleMatcherError::DuplicateWordId { word_id });
+        }
+
+        for (simple_match_type, simple_wordlist) in simple_wordlist_dict {
+            simple_matcher.add_process_matcher(*simple_match_type)?;
+
+            let (simple_ac_table, ac_wordlist) = simple_matcher.build_simple_ac_table(
+                simple_match_type,
+                simple_wordlist,
+                &mut word_storage,
+                &mut simple_word_map,
+                &mut build_stats,
+            )?;
+            if simple_matcher.options.prefilter {
+                prefilter_ac_wordlist.extend(ac_wordlist);
+            }
+
+            simple_ac_table_dict.insert(
+                *simple_match_type - StrConvType::WordDelete,
+                simple_ac_table,
+            );
+        }
+
+        simple_matcher.min_text_len = simple_ac_table_dict
+            .values()
+            .map(|simple_ac_table| simple_ac_table.min_text_len)
+            .fold(255, usize::min);
+        simple_matcher.simple_ac_table_dict = Arc::new(simple_ac_table_dict);
+        simple_matcher.simple_word_map = Arc::new(simple_word_map);
+        simple_matcher.word_storage = Arc::new(word_storage);
+        simple_matcher.build_stats = build_stats;
+        simple_matcher.build_process_type_tree();
+
+        if simple_matcher.options.prefilter {
+            simple_matcher.prefilter = Some(Arc::new(
+                simple_matcher.build_prefilter(&prefilter_ac_wordlist),
+            ));
+        }
+
+        Ok(simple_matcher)
+    }
+
+    // 检查匹配类型是否可用，并补充其所需的转换
+    fn add_process_matcher(
+        &mut self,
+        simple_match_type: SimpleMatchType,
+    ) -> Result<(), SimpleMatcherError> {
+        self.options.check_simple_match_type(simple_match_type)?;
+        for str_conv_type in simple_match_type.iter() {
+            if !self.str_conv_process_dict.contains_key(&str_conv_type) {
+                let process_matcher =
+                    Self::_get_process_matcher(str_conv_type, &self.options.process_dict_override);
+                Arc::make_mut(&mut self.str_conv_process_dict)
+                    .insert(str_conv_type, process_matcher);
+            }
+        }
+        Ok(())
+    }
+
+    // 由ac词表的匹配类型生成转换树，匹配类型集合变化后需要重新生成
+    fn build_process_type_tree(&mut self) {
+        // 按bits排序，保证相同匹配类型集合构建出的转换树一致，ProcessedText才能在匹配器之间复用
+        let mut simple_match_type_list = self
+            .simple_ac_table_dict
+            .keys()
+            .copied()
+            .collect::<Vec<SimpleMatchType>>();
+        simple_match_type_list.sort_unstable_by_key(|simple_match_type| simple_match_type.bits());
+        let mut process_type_tree = ProcessTypeTree::new(&simple_match_type_list);
+
+        // 覆盖的映射表会改变文本变体，需要计入签名，避免与使用内置映射表的匹配器复用ProcessedText
+        let mut process_dict_override_list: Vec<(&StrConvType, &Vec<(String, String)>)> = self
+            .options
+            .process_dict_override
+            .iter()
+            .filter(|(str_conv_type, _)| self.str_conv_process_dict.contains_key(str_conv_type))
+            .collect();
+        if !process_dict_override_list.is_empty() {
+            process_dict_override_list
+                .sort_unstable_by_key(|(str_conv_type, _)| str_conv_type.bits());
+
+            let mut hasher = AHasher::default();
+            process_type_tree.signature.hash(&mut hasher);
+            for (str_conv_type, process_pair_list) in process_dict_override_list {
+                (str_conv_type.bits(), process_pair_list).hash(&mut hasher);
+            }
+            process_type_tree.signature = hasher.finish();
+        }
+        // 单一匹配类型的转换树是一条链，最多生成 链长+1 个变体，max_variants不小于该值时不会截断，结果与按树转换一致
+        self.single_type = None;
+        if let [simple_match_type] = simple_match_type_list[..] {
+            if self
+                .options
+                .max_variants
+                .is_none_or(|max_variants| max_variants > process_type_tree.process_count())
+            {
+                self.single_type = Some(simple_match_type);
+            }
+        }
+        self.process_type_tree = Arc::new(process_type_tree);
+    }
+
+    fn build_prefilter(&self, ac_wordlist: &[Vec<u8>]) -> Prefilter {
+        let str_conv_type_list: Vec<StrConvType> =
+            self.str_conv_process_dict.keys().copied().collect();
+        Prefilter::new(
+            &str_conv_type_list,
+            &self.options.process_dict_override,
+            ac_wordlist,
+        )
+    }
+
+    // 新增词，词ID已存在时替换原词，原词立即失效，新词在调用rebuild后生效
+    // 多次增删后只需调用一次rebuild
+    pub fn add_word(
+        &mut self,
+        simple_match_type: SimpleMatchType,
+        word_id: u64,
+        word: &str,
+    ) -> Result<(), SimpleMatcherError> {
+        self.options.check_simple_match_type(simple_match_type)?;
+        self.remove_word(word_id);
+        self.pending_word_list
+            .push((simple_match_type, word_id, word.to_owned()));
+        self.dirty_type_set.insert(simple_match_type);
+        Ok(())
+    }
+
+    // 删除词，立即生效，ac自动机中残留的模式在调用rebuild时清除；返回词是否存在，含尚未生效的新增词
+    pub fn remove_word(&mut self, word_id: u64) -> bool {
+        let pending_word_count = self.pending_word_list.len();
+        self.pending_word_list
+            .retain(|(_, pending_word_id, _)| *pending_word_id != word_id);
+        let is_pending = self.pending_word_list.len() < pending_word_count;
+
+        // 词不存在时不触发写时拷贝
+        if !self.simple_word_map.contains_key(&word_id) {
+            return is_pending;
+        }
+        let word_conf = Arc::make_mut(&mut self.simple_word_map)
+            .remove(&word_id)
+            .unwrap();
+        self.dirty_type_set.insert(word_conf.simple_match_type);
+        true
+    }
+
+    // 是否存在尚未rebuild的增删
+    pub fn has_pending_changes(&self) -> bool {
+        !self.dirty_type_set.is_empty()
+    }
+
+    // 使add_word / remove_word的修改生效，只重建有词增删的匹配类型的ac自动机，其余匹配类型沿用原ac自动机
+    // 词存储会重新整理为一份紧凑的拷贝，open_mmap构建的匹配器此后不再引用映射内存；出错时匹配器保持调用前的状态
+    pub fn rebuild(&mut self) -> Result<(), SimpleMatcherError> {
+        if self.dirty_type_set.is_empty() {
+            return Ok(());
+        }
+
+        let mut dirty_type_list: Vec<SimpleMatchType> =
+            self.dirty_type_set.iter().copied().collect();
+        dirty_type_list.sort_unstable_by_key(|simple_match_type| simple_match_type.bits());
+        for &simple_match_type in &dirty_type_list {
+            self.add_process_matcher(simple_match_type)?;
+        }
+
+        // 未改动匹配类型的词原样拷贝，改动匹配类型的词按词ID排序后连同新增词重新展开
+        let mut word_storage = WordStorage::Packed(String::new());
+        let mut simple_word_map = IntMap::default();
+        let mut dirty_wordlist_dict: AHashMap<SimpleMatchType, Vec<SimpleWord>> = AHashMap::new();
+        for (&word_id, word_conf) in self.simple_word_map.iter() {
+            let word = self
+                .word_storage
+                .get(word_conf.word_start, word_conf.word_end);
+            if self.dirty_type_set.contains(&word_conf.simple_match_type) {
+                dirty_wordlist_dict
+                    .entry(word_conf.simple_match_type)
+                    .or_default()
+                    .push(SimpleWord { word_id, word });
+            } else {
+                let (word_start, word_end) = word_storage.store(word);
+                simple_word_map.insert(
+                    word_id,
+                    WordConf {
+                        word_start,
+                        word_end,
+                        ..word_conf.clone()
+                    },
+                );
+            }
+        }
+        for simple_wordlist in dirty_wordlist_dict.values_mut() {
+            simple_wordlist.sort_unstable_by_key(|simple_word| simple_word.word_id);
+        }
+        for (simple_match_type, word_id, word) in &self.pending_word_list {
+            dirty_wordlist_dict
+                .entry(*simple_match_type)
+                .or_default()
+                .push(SimpleWord {
+                    word_id: *word_id,
+                    word,
+                });
+        }
+
+        let mut simple_ac_table_dict = (*self.simple_ac_table_dict).clone();
+        let mut build_stats = self.build_stats;
+        let mut prefilter_ac_wordlist = Vec::new();
+        for simple_match_type in dirty_type_list {
+            let table_key = simple_match_type - StrConvType::WordDelete;
+            match dirty_wordlist_dict.get(&simple_match_type) {
+                Some(simple_wordlist) => {
+                    let (simple_ac_table, ac_wordlist) = self.build_simple_ac_table(
+                        &simple_match_type,
+                        simple_wordlist,
+                        &mut word_storage,
+                        &mut simple_word_map,
+                        &mut build_stats,
+                    )?;
+                    prefilter_ac_wordlist.extend(ac_wordlist);
+                    simple_ac_table_dict.insert(table_key, simple_ac_table);
+                }
+                // 该匹配类型的词已全部删除
+                None => {
+                    simple_ac_table_dict.remove(&table_key);
+                }
+            }
+        }
+
+        build_stats.ac_pattern_count = simple_ac_table_dict
+            .values()
+            .map(|simple_ac_table| simple_ac_table.ac_word_conf_list.len())
+            .sum();
+        build_stats.max_table_ac_pattern_count = simple_ac_table_dict
+            .values()
+            .map(|simple_ac_table| simple_ac_table.ac_word_conf_list.len())
+            .max()
+            .unwrap_or(0);
+        self.min_text_len = simple_ac_table_dict
+            .values()
+            .map(|simple_ac_table| simple_ac_table.min_text_len)
+            .fold(255, usize::min);
+        self.simple_ac_table_dict = Arc::new(simple_ac_table_dict);
+        self.simple_word_map = Arc::new(simple_word_map);
+        self.word_storage = Arc::new(word_storage);
+        self.build_stats = build_stats;
+        self.build_process_type_tree();
+
+        // 删除词残留的触发字符只会放大触发字符集，不会漏召回，只需并入新增词的触发字符
+        if let Some(prefilter) = &self.prefilter {
+            let mut new_prefilter = self.build_prefilter(&prefilter_ac_wordlist);
+            new_prefilter.merge(prefilter);
+            self.prefilter = Some(Arc::new(new_prefilter));
+        }
+
+        self.pending_word_list.clear();
+        self.dirty_type_set.clear();
+        Ok(())
+    }
+
+    // 开启前置过滤时，原文本中不含任何触发字符则一定不会命中，未开启时恒为true
+    #[inline]
+    pub fn is_candidate(&self, text: &str) -> bool {
+        match &self.prefilter {
+            Some(prefilter) => prefilter.is_candidate(text),
+            None => true,
+        }
+    }
+
+    pub fn process_type_tree(&self) -> &ProcessTypeTree {
+        &self.process_type_tree
+    }
+
+    // 两个匹配器是否共享同一份构建产物，即其中一个由另一个clone而来
+    pub fn ptr_eq(&self, other: &SimpleMatcher) -> bool {
+        Arc::ptr_eq(&self.simple_ac_table_dict, &other.simple_ac_table_dict)
+            && Arc::ptr_eq(&self.simple_word_map, &other.simple_word_map)
+            && Arc::ptr_eq(&self.word_storage, &other.word_storage)
+    }
+
+    // 按转换树生成文本变体，转换树相同（即匹配类型集合相同）的匹配器之间可以共享，省去重复转换
+    pub fn preprocess<'b>(&self, text: &'b str) -> ProcessedText<'b> {
+        let (processed_text_bytes_list, node_text_index_list, variants_truncated) =
+            self.reduce_text_process_with_tree(text.as_bytes());
+
+        ProcessedText {
+            text,
+            tree_signature: Some(self.process_type_tree.signature),
+            processed_text_bytes_list,
+            node_text_index_list,
+            variants_truncated,
+        }
+    }
+
+    // 按词ID获取构建时的原词，dedup_words去除的重复词、已删除以及尚未rebuild生效的词不存在
+    pub fn get_word(&self, word_id: u64) -> Option<&str> {
+        self.simple_word_map.get(&word_id).map(|word_conf| {
+            self.word_storage
+                .get(word_conf.word_start, word_conf.word_end)
+        })
+    }
+
+    // 已生效的词数，同words的个数
+    pub fn word_count(&self) -> usize {
+        self.simple_word_map.len()
+    }
+
+    // 遍历已生效的 (词ID, 原词, 匹配类型)，顺序不确定；已生效的匹配类型集合见simple_match_types
+    pub fn words(&self) -> impl Iterator<Item = (u64, &str, SimpleMatchType)> + '_ {
+        self.simple_word_map.iter().map(|(&word_id, word_conf)| {
+            (
+                word_id,
+                self.word_storage
+                    .get(word_conf.word_start, word_conf.word_end),
+                word_conf.simple_match_type,
+            )
+        })
+    }
+
+    // 已生效的词所用的匹配类型，去重后按bits排序，与words一致；process_type_tree()还包含构建时词已全部删除的匹配类型
+    pub fn simple_match_types(&self) -> Vec<SimpleMatchType> {
+        let mut simple_match_type_list: Vec<SimpleMatchType> = self
+            .simple_word_map
+            .values()
+            .map(|word_conf| word_conf.simple_match_type)
+            .collect();
+        simple_match_type_list.sort_unstable_by_key(|simple_match_type| simple_match_type.bits());
+        simple_match_type_list.dedup();
+        simple_match_type_list
+    }
+
+    // 文本所需的最小字符数，字符数更少的文本直接返回空结果；存在会使文本变长的转换时为1
+    pub fn min_text_len(&self) -> usize {
+        self.min_text_len
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn build_stats(&self) -> &SimpleBuildStats {
+        &self.build_stats
+    }
+
+    // 将process的结果直接序列化为json写入writer，省去中间的String
+    #[cfg(feature = "serde")]
+    pub fn process_to_writer<W: Write>(&self, text: &str, writer: &mut W) -> io::Result<()> {
+        serde_json::to_writer(writer, &self.process(text))?;
+        Ok(())
+    }
+
+    fn _get_process_dict(str_conv_type: StrConvType) -> AHashMap<&'static str, &'static str> {
+        let mut process_dict: AHashMap<&'static str, &'static str> = AHashMap::new();
+
+        match str_conv_type {
+            #[cfg(feature = "fanjian")]
+            StrConvType::Fanjian => {
+                for str_conv_dat in [FANJIAN, UNICODE] {
+                    process_dict.extend(str_conv_dat.trim().split('\n').map(|pair_str| {
+                        let mut pair_str_split = pair_str.split('\t');
+                        (
+                            pair_str_split.next().unwrap(),
+                            pair_str_split.next().unwrap(),
+                        )
+                    }));
+                }
+            }
+            #[cfg(feature = "delete")]
+            StrConvType::WordDelete => {
+                process_dict.extend(
+                    PUNCTUATION_SPECIAL
+                        .trim()
+                        .split('\n')
+                        .map(|pair_str| (pair_str, "")),
+                );
+
+                process_dict.extend(WHITE_SPACE.iter().map(|&c| (c, "")));
+            }
+            #[cfg(feature = "delete")]
+            StrConvType::TextDelete => {
+                for str_conv_dat in [PUNCTUATION_SPECIAL, CN_SPECIAL, EN_SPECIAL] {
+                    process_dict.extend(
+                        str_conv_dat
+                            .trim()
+                            .split('\n')
+                            .map(|pair_str| (pair_str, "")),
+                    );
+                }
+
+                process_dict.extend(WHITE_SPACE.iter().map(|&c| (c, "")));
+            }
+            #[cfg(feature = "normalize")]
+            StrConvType::Normalize => {
+                for str_conv_dat in [UPPER_LOWER, EN_VARIATION, NUM_NORM] {
+                    process_dict.extend(str_conv_dat.trim().split('\n').map(|pair_str| {
+                        let mut pair_str_split = pair_str.split('\t');
+                        (
+                            pair_str_split.next().unwrap(),
+                            pair_str_split.next().unwrap(),
+                        )
+                    }));
+                }
+            }
+            #[cfg(feature = "pinyin")]
+            StrConvType::PinYin => {
+                process_dict.extend(PINYIN.trim().split('\n').map(|pair_str| {
+                    let mut pair_str_split = pair_str.split('\t');
+                    (
+                        pair_str_split.next().unwrap(),
+                        pair_str_split.next().unwrap(),
+                    )
+                }));
+            }
+            #[cfg(feature = "pinyin")]
+            StrConvType::PinYinChar => {
+                process_dict.extend(PINYIN_CHAR.trim().split('\n').map(|pair_str| {
+                    let mut pair_str_split = pair_str.split('\t');
+                    (
+                        pair_str_split.next().unwrap(),
+                        pair_str_split.next().unwrap(),
+                    )
+                }));
+            }
+            _ => {}
+        }
+
+        process_dict
+            .retain(|&key, &mut value| (key == "#" || !key.starts_with('#')) && key != value); // 剔除注释词以及无效映射关系
+
+        process_dict
+    }
+
+    // 内置的转换映射表，可在此基础上修改后通过SimpleMatcherOptions::process_dict_override覆盖
+    pub fn builtin_process_dict(
+        str_conv_type: StrConvType,
+    ) -> AHashMap<&'static str, &'static str> {
+        Self::_get_process_dict(str_conv_type)
+    }
+
+    // 按键排序的内置映射关系，即转换ac自动机的模式顺序，与哈希表的迭代顺序无关，相同数据在任意环境下构建结果一致
+    pub fn builtin_process_pair_list(
+        str_conv_type: StrConvType,
+    ) -> Vec<(&'static str, &'static str)> {
+        let mut process_pair_list: Vec<(&'static str, &'static str)> =
+            Self::_get_process_dict(str_conv_type).into_iter().collect();
+        process_pair_list.sort_unstable_by_key(|&(key, _)| key);
+        process_pair_list
+    }
+
+    // 转换类型实际使用的映射关系，存在覆盖时使用覆盖的映射表，剔除空键以及无效映射关系，否则使用内置映射表
+    fn _get_process_pair_list(
+        str_conv_type: StrConvType,
+        process_dict_override: &AHashMap<StrConvType, Vec<(String, String)>>,
+    ) -> Vec<(&str, Cow<'static, str>)> {
+        match process_dict_override.get(&str_conv_type) {
+            Some(process_pair_list) => process_pair_list
+                .iter()
+                .filter(|(key, value)| !key.is_empty() && key != value)
+                .map(|(key, value)| (key.as_str(), Cow::Owned(value.to_owned())))
+                .collect(),
+            None => Self::builtin_process_pair_list(str_conv_type)
+                .into_iter()
+                .map(|(key, value)| (key, Cow::Borrowed(value)))
+                .collect(),
+        }
+    }
+
+    fn _get_process_matcher(
+        str_conv_type: StrConvType,
+        process_dict_override: &AHashMap<StrConvType, Vec<(String, String)>>,
+    ) -> ProcessMatcher {
+        let process_pair_list = Self::_get_process_pair_list(str_conv_type, process_dict_override);
+
+        let process_matcher = AhoCorasickBuilder::new()
+            .kind(Some(DFA)) // dfa更快但更占内存
+            .match_kind(MatchKind::LeftmostLongest) // 转换词之间可能会有重叠，eg."A","Ą̴̡̣̠̮̓̋", 此时以bytes最长的为准
+            .build(
+                process_pair_list
+                    .iter()
+                    .map(|&(key, _)| key)
+                    .collect::<Vec<&str>>(),
+            )
+            .unwrap();
+        let max_expansion_ratio = process_pair_list
+            .iter()
+            .map(|(key, value)| value.len() as f32 / key.len() as f32)
+            .fold(1.0, f32::max)
+            .min(MAX_EXPANSION_RATIO);
+        let process_replace_list = process_pair_list
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect();
+
+        (process_replace_list, process_matcher, max_expansion_ratio)
+    }
+
+    // 展开词的各个变体并构建ac自动机，词存入word_storage以及simple_word_map，不改动匹配器本身，出错时可以直接丢弃
+    fn build_simple_ac_table(
+        &self,
+        simple_match_type: &SimpleMatchType,
+        simple_wordlist: &[SimpleWord],
+        word_storage: &mut WordStorage,
+        simple_word_map: &mut IntMap<u64, WordConf>,
+        build_stats: &mut SimpleBuildStats,
+    ) -> Result<(SimpleAcTable, Vec<Vec<u8>>), SimpleMatcherError> {
+        let str_conv_type_list = &(*simple_match_type - StrConvType::TextDelete);
+        let mut min_text_len = 255;
+        let mut ac_wordlist = Vec::with_capacity(simple_wordlist.len());
+        let mut prefilter_ac_wordlist = Vec::new();
+        let mut ac_word_conf_list = Vec::with_capacity(simple_wordlist.len());
+
+        // 文本的各个转换均不使文本变长时，文本变体的字符数不超过原文本，词变体的字符又都出现在文本变体中，可由词推出原文本的最小长度
+        // eg. 繁简及删除归一；归一 "⑩" -> "10"、拼音等转换会使文本变长，此时不限制最小长度；存在覆盖的映射表时按覆盖的映射关系判断
+        let is_len_bounded = simple_match_type
+            .iter()
+            .filter(|&str_conv_type| str_conv_type != StrConvType::WordDelete)
+            .all(|str_conv_type| {
+                Self::_get_process_pair_list(str_conv_type, &self.options.process_dict_override)
+                    .iter()
+                    .all(|(key, value)| value.chars().count() <= key.chars().count())
+            });
+
+        let variant_expansion_start = Instant::now();
+        let mut word_set: AHashSet<&str> = AHashSet::new();
+        for simple_word in simple_wordlist {
+            if self.options.dedup_words && !word_set.insert(simple_word.word) {
+                continue;
+            }
+
+            let (split_word_list, repeat_count_list, window) =
+                split_word_part_list(simple_word.word);
+            let not_offset = repeat_count_list.len();
+
+            let mut and_char_set: Option<AHashSet<char>> = is_len_bounded.then(AHashSet::new);
+
+            // 否定部分命中一次即可丢弃该词
+            let split_bit = repeat_count_list
+                .iter()
+                .map(|&x| if x < 64 { 1 << (x - 1) } else { 1 << 63 }) // 最多重复64次
+                .chain(std::iter::repeat_n(1, split_word_list.len() - not_offset))
+                .collect();
+
+            // 含'>'的部分为有序部分，各步骤的ac词映射到split_bit之后的步骤偏移量，所有步骤依次命中后才计为该部分命中一次
+            let mut sequence_list = Vec::new();
+            let mut step_list = Vec::new();
+            for (offset, &split_word) in split_word_list.iter().enumerate() {
+                let split_step_list: Vec<&str> = split_unescaped(split_word, '>')
+                    .filter(|&x| !is_empty_step(x))
+                    .collect();
+                let step_offset_list: Vec<(usize, &str)> = if split_step_list.len() > 1 {
+                    let sequence_index = sequence_list.len();
+                    sequence_list.push((offset, split_step_list.len()));
+                    split_step_list
+                        .into_iter()
+                        .enumerate()
+                        .map(|(step_index, step)| {
+                            step_list.push((sequence_index, step_index));
+                            (split_word_list.len() + step_list.len() - 1, step)
+                        })
+                        .collect()
+                } else {
+                    vec![(offset, split_word)]
+                };
+
+                for (ac_offset, step) in step_offset_list {
+                    // '|'分隔的各个备选词共用同一偏移量，任一备选词命中即该部分命中
+                    // 删除归一后为空的变体是空模式，会在任意文本的每个位置命中，只保留非空的变体
+                    // ac自动机大小写不敏感，仅大小写不同的变体在同一位置会重复计数，eg. "Ab" 与归一后的 "ab"，只保留第一个
+                    let alternative_list: Vec<Cow<str>> = split_unescaped(step, '|')
+                        .filter(|&x| !x.is_empty())
+                        .map(unescape_part)
+                        .collect();
+                    let mut ac_word_list: Vec<Cow<[u8]>> = Vec::new();
+                    for alternative in &alternative_list {
+                        for ac_word in
+                            self.reduce_text_process(str_conv_type_list, alternative.as_bytes())
+                        {
+                            if !ac_word.is_empty()
+                                && !ac_word_list
+                                    .iter()
+                                    .any(|existing| existing.eq_ignore_ascii_case(&ac_word))
+                            {
+                                ac_word_list.push(ac_word);
+                            }
+                        }
+                    }
+
+                    // 否定部分不影响命中所需的最小长度；含多个备选词的部分无法确定命中哪个，同样不计入；ac自动机大小写不敏感，按小写计算
+                    if let (Some(and_char_set), Some(ac_word)) =
+                        (&mut and_char_set, ac_word_list.last())
+                    {
+                        if offset < not_offset && alternative_list.len() == 1 {
+                            and_char_set.extend(
+                                String::from_utf8_lossy(ac_word)
+                                    .chars()
+                                    .map(|c| c.to_ascii_lowercase()),
+                            );
+                        }
+                    }
+
+                    for ac_word in ac_word_list {
+                        // 仅命中否定部分的文本不会产生命中，前置过滤器不需要其字符
+                        if self.options.prefilter && offset < not_offset {
+                            prefilter_ac_wordlist.push(ac_word.to_vec());
+                        }
+                        ac_wordlist.push(ac_word.into_owned());
+                        ac_word_conf_list.push((simple_word.word_id, ac_offset));
+                    }
+                }
+            }
+
+            let (word_start, word_end) = word_storage.store(simple_word.word);
+
+            simple_word_map.insert(
+                simple_word.word_id,
+                WordConf {
+                    simple_match_type: *simple_match_type,
+                    word_start,
+                    word_end,
+                    split_word_list: if self.options.detailed || self.options.report_negated {
+                        split_word_list
+                            .iter()
+                            .map(|&split_word| split_word.to_owned())
+                            .collect()
+                    } else {
+                        Vec::new()
+                    },
+                    split_bit,
+                    not_offset,
+                    sequence_list,
+                    step_list,
+                    window,
+                },
+            );
+
+            let char_unique_cnt = and_char_set.map_or(1, |and_char_set| and_char_set.len());
+            if min_text_len > char_unique_cnt {
+                min_text_len = char_unique_cnt; // 计算最小长度文本
+            }
+        }
+
+        build_stats.variant_expansion_duration += variant_expansion_start.elapsed();
+
+        // 超出上限后ac自动机无法构建，或ac词ID越界后命中错误的词
+        let max_pattern_count = self
+            .options
+            .max_ac_pattern_count
+            .map_or(MAX_AC_PATTERN_COUNT, |max_ac_pattern_count| {
+                max_ac_pattern_count.min(MAX_AC_PATTERN_COUNT)
+            });
+        if ac_wordlist.len() > max_pattern_count {
+            return Err(SimpleMatcherError::TooManyPatterns {
+                simple_match_type: *simple_match_type,
+                pattern_count: ac_wordlist.len(),
+                max_pattern_count,
+            });
+        }
+
+        let ac_build_start = Instant::now();
+        let ac_matcher = AhoCorasickBuilder::new()
+            .kind(Some(DFA))
+            .ascii_case_insensitive(true) // 大小写不敏感
+            .build(&ac_wordlist)
+            .unwrap();
+        build_stats.ac_build_duration += ac_build_start.elapsed();
+        build_stats.ac_pattern_count += ac_wordlist.len();
+        build_stats.max_table_ac_pattern_count = build_stats
+            .max_table_ac_pattern_count
+            .max(ac_wordlist.len());
+
+        Ok((
+            SimpleAcTable {
+                ac_matcher,
+                ac_word_conf_list,
+                min_text_len,
+            },
+            prefilter_ac_wordlist,
+        ))
+    }
+
+    #[inline]
+    fn reduce_text_process<'a>(
+        &self,
         str_conv_type_list: &StrConvType,
         text_bytes: &'a [u8],
     ) -> ArrayVec<[Cow<'a, [u8]>; 4]> {
@@ -285,122 +1994,1047 @@ impl SimpleMatcher {
         processed_text_bytes_list.push(Cow::Borrowed(text_bytes));
 
         for str_conv_type in str_conv_type_list.iter() {
-            let (process_replace_list, process_matcher) = unsafe {
-                self.str_conv_process_dict
-                    .get(&str_conv_type)
-                    .unwrap_unchecked()
-            };
             let tmp_processed_text_bytes =
                 unsafe { processed_text_bytes_list.last_mut().unwrap_unchecked() };
 
-            if likely(process_matcher.is_match(tmp_processed_text_bytes.as_ref())) {
-                // 按先验信息，删除归一 与 替换归一 是大概率命中的
-                match str_conv_type {
-                    StrConvType::Fanjian => {
-                        // 由于词和文本都做了相同的繁简变换，那么原文本是没必要的，直接匹配繁简转换后的文本即可
-                        *tmp_processed_text_bytes = Cow::Owned(
-                            process_matcher.replace_all_bytes(text_bytes, process_replace_list),
-                        );
-                    }
-                    StrConvType::TextDelete | StrConvType::WordDelete => {
-                        // 省去n次 string.push('')的操作
-                        let mut processed_text = Vec::with_capacity(tmp_processed_text_bytes.len());
-                        let mut last_match = 0;
+            if let Some(processed_text) =
+                self._convert_stage(str_conv_type, tmp_processed_text_bytes.as_ref())
+            {
+                if str_conv_type == StrConvType::Fanjian {
+                    // 由于词和文本都做了相同的繁简变换，那么原文本是没必要的，直接匹配繁简转换后的文本即可
+                    *tmp_processed_text_bytes = Cow::Owned(processed_text);
+                } else {
+                    processed_text_bytes_list.push(Cow::Owned(processed_text));
+                }
+            }
+        }
 
-                        for mat in process_matcher.find_iter(tmp_processed_text_bytes.as_ref()) {
-                            processed_text.extend(unsafe {
-                                tmp_processed_text_bytes.get_unchecked(last_match..mat.start())
-                            });
-                            last_match = mat.end();
-                        }
-                        processed_text.extend(unsafe {
-                            tmp_processed_text_bytes.get_unchecked(last_match..)
-                        });
+        processed_text_bytes_list
+    }
 
-                        processed_text_bytes_list.push(Cow::Owned(processed_text));
-                    }
-                    _ => {
-                        let processed_text = process_matcher
-                            .replace_all_bytes(tmp_processed_text_bytes, process_replace_list);
-                        processed_text_bytes_list.push(Cow::Owned(processed_text));
-                    }
+    // 对文本做单个转换，文本中没有可转换的内容时返回None
+    #[inline]
+    fn _convert_stage(&self, str_conv_type: StrConvType, text_bytes: &[u8]) -> Option<Vec<u8>> {
+        let (process_replace_list, process_matcher, max_expansion_ratio) = unsafe {
+            self.str_conv_process_dict
+                .get(&str_conv_type)
+                .unwrap_unchecked()
+        };
+
+        // 按先验信息，删除归一 与 替换归一 是大概率命中的
+        if unlikely(!process_matcher.is_match(text_bytes)) {
+            return None;
+        }
+        Some(match str_conv_type {
+            StrConvType::TextDelete | StrConvType::WordDelete => {
+                Self::delete_all_bytes(process_matcher, text_bytes)
+            }
+            _ => Self::replace_all_bytes(
+                process_matcher,
+                process_replace_list,
+                *max_expansion_ratio,
+                text_bytes,
+            ),
+        })
+    }
+
+    #[inline]
+    fn reduce_text_process_with_tree<'a>(
+        &self,
+        text_bytes: &'a [u8],
+    ) -> (Vec<Cow<'a, [u8]>>, Vec<usize>, bool) {
+        // 按转换树对文本做转换，每个节点只转换一次，返回去重后的文本列表 以及 每个节点对应的文本下标（未发生转换的节点沿用父节点文本）以及 是否发生截断
+        // 变体数达到max_variants后，按节点顺序跳过后续会产生新变体的节点，其子树一并跳过，节点顺序固定，所以跳过的节点对同一文本总是一致的
+        let node_list = &self.process_type_tree.node_list;
+        let max_variants = self.options.max_variants.unwrap_or(usize::MAX).max(1);
+        let mut processed_text_bytes_list: Vec<Cow<'a, [u8]>> = Vec::with_capacity(node_list.len());
+        let mut node_text_index_list = Vec::with_capacity(node_list.len());
+        let mut variants_truncated = false;
+
+        processed_text_bytes_list.push(Cow::Borrowed(text_bytes));
+        node_text_index_list.push(0);
+
+        for node in node_list.iter().skip(1) {
+            let parent_text_index = unsafe { *node_text_index_list.get_unchecked(node.parent) };
+            if unlikely(parent_text_index == TRUNCATED_TEXT_INDEX) {
+                node_text_index_list.push(TRUNCATED_TEXT_INDEX);
+                continue;
+            }
+            let parent_text_bytes: &[u8] =
+                unsafe { processed_text_bytes_list.get_unchecked(parent_text_index) }.as_ref();
+            let (process_replace_list, process_matcher, max_expansion_ratio) = unsafe {
+                self.str_conv_process_dict
+                    .get(&node.str_conv_type)
+                    .unwrap_unchecked()
+            };
+
+            if likely(process_matcher.is_match(parent_text_bytes)) {
+                if unlikely(processed_text_bytes_list.len() >= max_variants) {
+                    node_text_index_list.push(TRUNCATED_TEXT_INDEX);
+                    variants_truncated = true;
+                    continue;
                 }
+                let processed_text = match node.str_conv_type {
+                    StrConvType::TextDelete | StrConvType::WordDelete => {
+                        Self::delete_all_bytes(process_matcher, parent_text_bytes)
+                    }
+                    _ => Self::replace_all_bytes(
+                        process_matcher,
+                        process_replace_list,
+                        *max_expansion_ratio,
+                        parent_text_bytes,
+                    ),
+                };
+                processed_text_bytes_list.push(Cow::Owned(processed_text));
+                node_text_index_list.push(processed_text_bytes_list.len() - 1);
+            } else {
+                node_text_index_list.push(parent_text_index);
             }
         }
 
-        processed_text_bytes_list
+        (
+            processed_text_bytes_list,
+            node_text_index_list,
+            variants_truncated,
+        )
     }
-}
 
-impl<'a> TextMatcherTrait<'a, SimpleResult<'a>> for SimpleMatcher {
-    fn is_match(&self, text: &str) -> bool {
-        // 后续再优化
-        !self.process(text).is_empty()
+    #[inline]
+    fn _variant_index_list(
+        &self,
+        path: &[usize],
+        node_text_index_list: &[usize],
+    ) -> ArrayVec<[usize; MAX_VARIANT_COUNT]> {
+        let mut variant_index_list = self
+            .process_type_tree
+            .variant_index_list(path, node_text_index_list);
+        if self.options.variant_strategy == VariantStrategy::FinalOnly
+            && !variant_index_list.is_empty()
+        {
+            // 仅保留链式转换后的最终文本
+            let final_index = unsafe { *variant_index_list.last().unwrap_unchecked() };
+            variant_index_list.clear();
+            variant_index_list.push(final_index);
+        }
+        variant_index_list
     }
 
-    fn process(&'a self, text: &str) -> Vec<SimpleResult<'a>> {
+    // 同process，按 (匹配类型, 文本变体) 并行扫描ac自动机，各任务只统计局部的命中次数，全部结束后按固定顺序合并判定
+    // 命中结果与process一致，顺序按匹配类型以及词ID排列；适用于变体较多的长文本，短文本并行调度的开销大于收益
+    #[cfg(feature = "rayon")]
+    pub fn par_process(&self, text: &str) -> Vec<SimpleResult> {
         let text_bytes = text.as_bytes();
         let mut result_list = Vec::new();
 
-        if unlikely(bytecount::num_chars(text_bytes) < self.min_text_len) {
+        if unlikely(self.is_short_text(text_bytes)) {
             // 过滤短文本
             return result_list;
         }
 
-        let mut word_id_set = IntSet::default();
+        let (processed_text_bytes_list, node_text_index_list, _) =
+            self.reduce_text_process_with_tree(text_bytes);
+
+        let variant_index_list_list: Vec<ArrayVec<[usize; MAX_VARIANT_COUNT]>> = self
+            .process_type_tree
+            .simple_match_type_path_list
+            .iter()
+            .map(|(_, path)| self._variant_index_list(path, &node_text_index_list))
+            .collect();
+        let task_list: Vec<(usize, usize)> = variant_index_list_list
+            .iter()
+            .enumerate()
+            .flat_map(|(type_index, variant_index_list)| {
+                variant_index_list
+                    .iter()
+                    .map(move |&text_index| (type_index, text_index))
+            })
+            .collect();
 
-        // 词ID对其命中轮次以及命中bit的映射，eg.“无,法,无,天” 繁简+删除归一+替换归一 3轮匹配，1 -> [[2，2，2], [1, 1, 1], [1, 1, 1]]
-        // 当且仅当 所有内部数组都至少有一个0时 代表命中
-        let mut word_id_split_bit_map = IntMap::default();
+        // 每个任务得到 词ID对其各部分命中次数 的局部映射，任务之间互不影响
+        // 窗口约束的词另外返回在该变体中满足窗口约束的词ID
+        let (hit_count_map_list, window_word_id_set_list): (Vec<_>, Vec<_>) = task_list
+            .par_iter()
+            .map(|&(type_index, text_index)| {
+                let simple_match_type = unsafe {
+                    &self
+                        .process_type_tree
+                        .simple_match_type_path_list
+                        .get_unchecked(type_index)
+                        .0
+                };
+                let simple_ac_table = unsafe {
+                    self.simple_ac_table_dict
+                        .get(simple_match_type)
+                        .unwrap_unchecked()
+                };
+                let processed_text: &[u8] =
+                    unsafe { processed_text_bytes_list.get_unchecked(text_index) }.as_ref();
 
-        for (simple_match_type, simple_ac_table) in &self.simple_ac_table_dict {
-            let processed_text_bytes_list = self.reduce_text_process(simple_match_type, text_bytes);
-            for (index, processed_text) in processed_text_bytes_list.iter().enumerate() {
+                let mut hit_count_map: IntMap<u64, TinyVec<[u32; 64]>> = IntMap::default();
+                let mut sequence_progress_map: IntMap<u64, Vec<ArrayVec<[SequenceProgress; 4]>>> =
+                    IntMap::default();
+                let mut window_hit_map: IntMap<u64, Vec<(usize, usize)>> = IntMap::default();
+                let mut window_word_id_set: IntSet<u64> = IntSet::default();
                 for ac_result in simple_ac_table
                     .ac_matcher
                     .find_overlapping_iter(processed_text)
-                // ac词会重复，需要遍历所有的ac命中词
                 {
-                    let ac_word_id = ac_result.pattern().as_usize();
-                    let ac_word_conf =
-                        unsafe { simple_ac_table.ac_word_conf_list.get_unchecked(ac_word_id) };
-                    let word_id = ac_word_conf.0;
-                    let word_conf =
-                        unsafe { self.simple_word_map.get(&word_id).unwrap_unchecked() };
-
-                    let split_bit = word_id_split_bit_map.entry(word_id).or_insert_with(|| {
-                        word_conf
-                            .split_bit
-                            .iter()
-                            .map(|&x| {
-                                processed_text_bytes_list
+                    let ac_word_conf = unsafe {
+                        simple_ac_table
+                            .ac_word_conf_list
+                            .get_unchecked(ac_result.pattern().as_usize())
+                    };
+                    let Some(word_conf) = self.simple_word_map.get(&ac_word_conf.0) else {
+                        continue;
+                    };
+                    // 每个任务只扫描一个变体，有序部分的进度只有一个轮次
+                    let mut offset = ac_word_conf.1;
+                    let mut start = ac_result.start();
+                    if unlikely(offset >= word_conf.split_bit.len()) {
+                        let sequence_progress_list = sequence_progress_map
+                            .entry(ac_word_conf.0)
+                            .or_insert_with(|| {
+                                word_conf
+                                    .sequence_list
                                     .iter()
-                                    .map(|_| x)
-                                    .collect::<ArrayVec<[u64; 4]>>()
-                            })
-                            .collect::<TinyVec<[_; 64]>>()
-                    });
-
-                    *unsafe {
-                        split_bit
-                            .get_unchecked_mut(ac_word_conf.1)
-                            .get_unchecked_mut(index)
-                    } >>= 1; // 右移一位，不用 -1 是因为不能确定命中次数，u64 - 1 最后可能会越界
-
-                    if unlikely(
-                        split_bit.iter().all(|bit| bit.iter().any(|&b| b == 0))
-                            && !word_id_set.contains(&word_id),
+                                    .map(|_| std::iter::once((0, 0, 0)).collect())
+                                    .collect()
+                            });
+                        let Some(sequence_hit) = Self::_advance_sequence(
+                            word_conf,
+                            offset,
+                            &mut sequence_progress_list[..],
+                            0,
+                            &ac_result,
+                        ) else {
+                            continue;
+                        };
+                        (offset, start) = sequence_hit;
+                    }
+                    let hit_count_list = hit_count_map
+                        .entry(ac_word_conf.0)
+                        .or_insert_with(|| word_conf.split_bit.iter().map(|_| 0).collect());
+                    *unsafe { hit_count_list.get_unchecked_mut(offset) } += 1;
+
+                    if let Some(window) = word_conf.window {
+                        if offset < word_conf.not_offset
+                            && Self::_check_window(
+                                word_conf,
+                                window,
+                                window_hit_map.entry(ac_word_conf.0).or_default(),
+                                offset,
+                                (start, ac_result.end()),
+                            )
+                        {
+                            window_word_id_set.insert(ac_word_conf.0);
+                        }
+                    }
+                }
+                (hit_count_map, window_word_id_set)
+            })
+            .unzip();
+
+        let mut task_offset = 0;
+        for variant_index_list in &variant_index_list_list {
+            let variant_hit_count_map_list =
+                &hit_count_map_list[task_offset..task_offset + variant_index_list.len()];
+            let variant_window_word_id_set_list =
+                &window_word_id_set_list[task_offset..task_offset + variant_index_list.len()];
+            task_offset += variant_index_list.len();
+
+            let mut word_id_list: Vec<u64> = variant_hit_count_map_list
+                .iter()
+                .flat_map(|hit_count_map| hit_count_map.keys().copied())
+                .collect::<IntSet<u64>>()
+                .into_iter()
+                .collect();
+            word_id_list.sort_unstable();
+
+            for word_id in word_id_list {
+                let word_conf = unsafe { self.simple_word_map.get(&word_id).unwrap_unchecked() };
+
+                // 与process的右移判定等价：每个部分至少在一个变体中命中 bit位数 次，且否定部分在所有变体中均未命中
+                // 窗口约束的词，组合词部分改为至少在一个变体中满足窗口约束
+                let is_part_hit = |offset: usize| {
+                    let required_count = u64::BITS - word_conf.split_bit[offset].leading_zeros();
+                    variant_hit_count_map_list.iter().any(|hit_count_map| {
+                        hit_count_map
+                            .get(&word_id)
+                            .is_some_and(|hit_count_list| hit_count_list[offset] >= required_count)
+                    })
+                };
+                let is_hit = match word_conf.window {
+                    Some(_) => variant_window_word_id_set_list
+                        .iter()
+                        .any(|window_word_id_set| window_word_id_set.contains(&word_id)),
+                    None => (0..word_conf.not_offset).all(is_part_hit),
+                } && !(word_conf.not_offset..word_conf.split_bit.len())
+                    .any(is_part_hit);
+                if !is_hit {
+                    continue;
+                }
+
+                let parts = self.options.detailed.then(|| {
+                    word_conf
+                        .split_word_list
+                        .iter()
+                        .take(word_conf.not_offset)
+                        .enumerate()
+                        .map(|(offset, split_word)| PartHit {
+                            part: self.result_word(split_word),
+                            count: variant_hit_count_map_list
+                                .iter()
+                                .filter_map(|hit_count_map| hit_count_map.get(&word_id))
+                                .map(|hit_count_list| hit_count_list[offset])
+                                .max()
+                                .unwrap_or(0),
+                        })
+                        .collect()
+                });
+
+                let raw_word = self
+                    .word_storage
+                    .get(word_conf.word_start, word_conf.word_end);
+                result_list.push(SimpleResult {
+                    word_id,
+                    word: self.result_word(raw_word),
+                    raw_word,
+                    parts,
+                    span: None,
+                });
+            }
+        }
+
+        result_list
+    }
+
+    // 与process一致，report_negated模式下额外返回被否定部分丢弃的词
+    pub fn process_full(&self, text: &str) -> SimpleFullResult<'_> {
+        let mut simple_full_result = SimpleFullResult::default();
+        if unlikely(text.is_empty() || self.is_short_text(text.as_bytes()))
+            || !self.is_candidate(text)
+        {
+            return simple_full_result;
+        }
+
+        let processed_text = self.preprocess(text);
+        simple_full_result.matched = self._process_processed(
+            &processed_text,
+            self.options
+                .report_negated
+                .then_some(&mut simple_full_result.negated),
+            None,
+        );
+        simple_full_result
+    }
+
+    // 诊断用，返回至少命中一个部分的词的各部分命中情况，包括因部分缺失或否定部分命中而未命中的词，按word_id升序，process的结果不受影响
+    // 文本中不含前置过滤器中的任何字符时，不会命中任何组合词部分，直接返回空
+    pub fn process_detailed(&self, text: &str) -> Vec<DetailedResult<'_>> {
+        let mut detail_list = Vec::new();
+        if unlikely(text.is_empty()) || !self.is_candidate(text) {
+            return detail_list;
+        }
+
+        let processed_text = self.preprocess(text);
+        self._process_processed(&processed_text, None, Some(&mut detail_list));
+        detail_list
+    }
+
+    // negated_list不为None时，收集被否定部分丢弃的词；detail_list不为None时，收集各部分的命中情况
+    fn _process_processed<'a>(
+        &'a self,
+        processed_text: &ProcessedText,
+        negated_list: Option<&mut Vec<NegatedResult<'a>>>,
+        detail_list: Option<&mut Vec<DetailedResult<'a>>>,
+    ) -> Vec<SimpleResult<'a>> {
+        if unlikely(detail_list.is_none() && self.is_short_text(processed_text.text.as_bytes())) {
+            // 过滤短文本，短文本仍可能命中组合词的部分，收集命中情况时不过滤
+            return Vec::new();
+        }
+
+        // 变体由其他转换树生成时无法复用，重新生成
+        let reprocessed_text;
+        let processed_text =
+            if likely(processed_text.tree_signature == Some(self.process_type_tree.signature)) {
+                processed_text
+            } else if self.is_candidate(processed_text.text) {
+                reprocessed_text = self.preprocess(processed_text.text);
+                &reprocessed_text
+            } else {
+                return Vec::new();
+            };
+        let processed_text_bytes_list = &processed_text.processed_text_bytes_list;
+        let node_text_index_list = &processed_text.node_text_index_list;
+
+        self._process_variant_list(
+            self.process_type_tree
+                .simple_match_type_path_list
+                .iter()
+                .map(|(simple_match_type, path)| {
+                    let variant_list = self
+                        ._variant_index_list(path, node_text_index_list)
+                        .iter()
+                        .map(|&text_index| {
+                            (
+                                text_index,
+                                unsafe { processed_text_bytes_list.get_unchecked(text_index) }
+                                    .as_ref(),
+                            )
+                        })
+                        .collect();
+                    (simple_match_type, variant_list)
+                }),
+            negated_list,
+            detail_list,
+        )
+    }
+
+    // 文本字符数是否小于min_text_len，utf8字符最长4字节，字节数足够长时不必逐字符计数
+    #[inline]
+    fn is_short_text(&self, text_bytes: &[u8]) -> bool {
+        text_bytes.len() < self.min_text_len * 4
+            && bytecount::num_chars(text_bytes) < self.min_text_len
+    }
+
+    // 文本变体与process一致，ac匹配推迟到迭代时进行
+    fn simple_process_iter<'a, 't>(&'a self, text: &'t str) -> SimpleProcessIter<'a, 't> {
+        let mut simple_process_iter = SimpleProcessIter::new(self);
+        if unlikely(text.is_empty() || self.is_short_text(text.as_bytes()))
+            || !self.is_candidate(text)
+        {
+            return simple_process_iter;
+        }
+
+        match self.single_type {
+            // 链式转换的各个变体按需生成，前面的变体已产生命中时不必转换后续变体
+            Some(simple_match_type)
+                if self.options.variant_strategy == VariantStrategy::AllStages =>
+            {
+                let text_bytes = text.as_bytes();
+                // 同_process_single_type，繁简转换直接替换原文本时变体下标从1开始
+                let (first_processed_text, variant_offset) = match simple_match_type
+                    .contains(StrConvType::Fanjian)
+                    .then(|| self._convert_stage(StrConvType::Fanjian, text_bytes))
+                    .flatten()
+                {
+                    Some(processed_text) => (Cow::Owned(processed_text), 1),
+                    None => (Cow::Borrowed(text_bytes), 0),
+                };
+                simple_process_iter.processed_text_bytes_list = vec![first_processed_text];
+                simple_process_iter.type_variant_list = vec![(
+                    simple_match_type,
+                    ArrayVec::from_iter([(variant_offset, 0)]),
+                )];
+                simple_process_iter.pending_str_conv_type =
+                    simple_match_type - StrConvType::Fanjian;
+            }
+            // 仅用链式转换后的最终文本匹配
+            Some(simple_match_type) => {
+                let processed_text_bytes_list =
+                    self.reduce_text_process(&simple_match_type, text.as_bytes());
+                let variant_offset = matches!(processed_text_bytes_list[0], Cow::Owned(_)) as usize;
+                let final_index = processed_text_bytes_list.len() - 1;
+                simple_process_iter.processed_text_bytes_list =
+                    processed_text_bytes_list.into_iter().collect();
+                simple_process_iter.type_variant_list = vec![(
+                    simple_match_type,
+                    ArrayVec::from_iter([(final_index + variant_offset, final_index)]),
+                )];
+            }
+            None => {
+                let processed_text = self.preprocess(text);
+                simple_process_iter.type_variant_list =
+                    self._tree_type_variant_list(&processed_text.node_text_index_list);
+                simple_process_iter.processed_text_bytes_list =
+                    processed_text.processed_text_bytes_list;
+            }
+        }
+        simple_process_iter
+    }
+
+    // 同process(text).len()，逐个计数命中的词，不收集结果；detailed模式不影响命中与否，同样逐个计数
+    pub fn match_count(&self, text: &str) -> usize {
+        self.simple_process_iter(text).count()
+    }
+
+    // 将命中词在文本中的出现位置替换为mask，每个字符替换为一个mask
+    // 命中词各部分在原文本中的所有出现位置都会被替换；只出现在转换后文本变体中的部分（eg. 繁体字、夹杂删除字符、拼音）无法对应回原文本，不替换
+    pub fn redact<'b>(&self, text: &'b str, mask: char) -> Cow<'b, str> {
+        let word_id_set: IntSet<u64> = self
+            .simple_process_iter(text)
+            .map(|simple_result| simple_result.word_id)
+            .collect();
+        if word_id_set.is_empty() {
+            return Cow::Borrowed(text);
+        }
+
+        mask_span_list(
+            text,
+            self.part_span_list(text, |word_id| word_id_set.contains(&word_id))
+                .into_iter()
+                .map(|(_, span)| span)
+                .collect(),
+            mask,
+        )
+    }
+
+    // 用ac自动机直接扫描原文本，is_selected返回true的词的各组合词部分在原文本中的所有出现位置，不含否定部分
+    pub(crate) fn part_span_list<F>(&self, text: &str, is_selected: F) -> Vec<(u64, (usize, usize))>
+    where
+        F: Fn(u64) -> bool,
+    {
+        let mut span_list = Vec::new();
+        for simple_ac_table in self.simple_ac_table_dict.values() {
+            for ac_result in simple_ac_table
+                .ac_matcher
+                .find_overlapping_iter(text.as_bytes())
+            {
+                let (word_id, offset) = unsafe {
+                    *simple_ac_table
+                        .ac_word_conf_list
+                        .get_unchecked(ac_result.pattern().as_usize())
+                };
+                if self.simple_word_map.get(&word_id).is_some_and(|word_conf| {
+                    word_conf.is_and_offset(offset) && is_selected(word_id)
+                }) {
+                    span_list.push((word_id, (ac_result.start(), ac_result.end())));
+                }
+            }
+        }
+        span_list
+    }
+
+    // 同simple_process_iter，复用已生成的文本变体
+    pub(crate) fn simple_process_iter_processed<'a, 't>(
+        &'a self,
+        processed_text: &'t ProcessedText,
+    ) -> SimpleProcessIter<'a, 't> {
+        // 变体由其他转换树生成时无法复用，重新生成
+        if unlikely(processed_text.tree_signature != Some(self.process_type_tree.signature)) {
+            return self.simple_process_iter(processed_text.text);
+        }
+
+        let mut simple_process_iter = SimpleProcessIter::new(self);
+        if unlikely(self.is_short_text(processed_text.text.as_bytes())) {
+            return simple_process_iter;
+        }
+        simple_process_iter.type_variant_list =
+            self._tree_type_variant_list(&processed_text.node_text_index_list);
+        simple_process_iter.processed_text_bytes_list = processed_text
+            .processed_text_bytes_list
+            .iter()
+            .map(|processed_text_bytes| Cow::Borrowed(processed_text_bytes.as_ref()))
+            .collect();
+        simple_process_iter
+    }
+
+    // 同process_iter，复用已生成的文本变体，detailed模式下退化为process_processed
+    #[cfg(feature = "serde")]
+    pub(crate) fn process_processed_iter<'a: 't, 't>(
+        &'a self,
+        processed_text: &'t ProcessedText,
+    ) -> Box<dyn Iterator<Item = SimpleResult<'a>> + 't> {
+        if unlikely(self.options.detailed) {
+            return Box::new(self.process_processed(processed_text).into_iter());
+        }
+        Box::new(self.simple_process_iter_processed(processed_text))
+    }
+
+    // 转换树生成的变体下标即全局变体下标
+    fn _tree_type_variant_list(
+        &self,
+        node_text_index_list: &[usize],
+    ) -> Vec<(SimpleMatchType, VariantIndexList)> {
+        self.process_type_tree
+            .simple_match_type_path_list
+            .iter()
+            .map(|(simple_match_type, path)| {
+                let variant_list = self
+                    ._variant_index_list(path, node_text_index_list)
+                    .iter()
+                    .map(|&text_index| (text_index, text_index))
+                    .collect();
+                (*simple_match_type, variant_list)
+            })
+            .collect()
+    }
+
+    // 单一匹配类型时，直接链式转换文本后匹配，与按转换树生成变体的结果一致
+    fn _process_single_type<'a>(
+        &'a self,
+        simple_match_type: &SimpleMatchType,
+        text: &str,
+    ) -> Vec<SimpleResult<'a>> {
+        let processed_text_bytes_list =
+            self.reduce_text_process(simple_match_type, text.as_bytes());
+        // 繁简转换直接替换原文本，此时所有变体都不是原文本，变体下标从1开始
+        let variant_offset = matches!(processed_text_bytes_list[0], Cow::Owned(_)) as usize;
+        let mut variant_list: ArrayVec<[(usize, &[u8]); 4]> = processed_text_bytes_list
+            .iter()
+            .enumerate()
+            .map(|(index, processed_text_bytes)| {
+                (index + variant_offset, processed_text_bytes.as_ref())
+            })
+            .collect();
+        if self.options.variant_strategy == VariantStrategy::FinalOnly {
+            // 仅保留链式转换后的最终文本
+            let final_variant = unsafe { *variant_list.last().unwrap_unchecked() };
+            variant_list.clear();
+            variant_list.push(final_variant);
+        }
+
+        self._process_variant_list([(simple_match_type, variant_list)], None, None)
+    }
+
+    // 按 (匹配类型, 该类型的 (文本变体下标, 文本变体) 列表) 依次扫描ac自动机并判定命中
+    fn _process_variant_list<'a, 'b>(
+        &'a self,
+        type_variant_list: impl IntoIterator<
+            Item = (&'b SimpleMatchType, ArrayVec<[(usize, &'b [u8]); 4]>),
+        >,
+        negated_list: Option<&mut Vec<NegatedResult<'a>>>,
+        detail_list: Option<&mut Vec<DetailedResult<'a>>>,
+    ) -> Vec<SimpleResult<'a>> {
+        let mut result_list = Vec::new();
+        let mut match_state = MatchState::default();
+        let mut type_variant_map: AHashMap<SimpleMatchType, ArrayVec<[usize; MAX_VARIANT_COUNT]>> =
+            AHashMap::new(); // 收集命中情况时，匹配类型对其各轮次全局变体下标的映射
+
+        for (simple_match_type, variant_list) in type_variant_list {
+            let simple_ac_table = unsafe {
+                self.simple_ac_table_dict
+                    .get(simple_match_type)
+                    .unwrap_unchecked()
+            };
+            if unlikely(detail_list.is_some()) {
+                type_variant_map.insert(
+                    *simple_match_type,
+                    variant_list.iter().map(|&(variant, _)| variant).collect(),
+                );
+            }
+
+            for (index, &(variant, processed_text)) in variant_list.iter().enumerate() {
+                for ac_result in simple_ac_table
+                    .ac_matcher
+                    .find_overlapping_iter(processed_text)
+                // ac词会重复，需要遍历所有的ac命中词
+                {
+                    if let Some(simple_result) = self._record_ac_hit(
+                        &mut match_state,
+                        simple_ac_table,
+                        &ac_result,
+                        (index, variant),
+                        variant_list.len(),
                     ) {
-                        word_id_set.insert(word_id);
-                        result_list.push(SimpleResult {
-                            word_id,
-                            word: Cow::Borrowed(&word_conf.word),
-                        });
+                        result_list.push(simple_result);
                     }
                 }
             }
         }
 
+        self._finish_match_state(&match_state, &mut result_list, negated_list);
+        if let Some(detail_list) = detail_list {
+            self._collect_detail(&match_state, &type_variant_map, &result_list, detail_list);
+        }
         result_list
     }
+
+    // 记录一次ac命中，不含否定部分的词在此完成命中时返回结果，含否定部分的词暂存到所有轮次结束后判定
+    // (index, variant) 为命中文本变体在该匹配类型变体列表中的轮次，以及其全局变体下标
+    #[inline(always)]
+    fn _record_ac_hit<'a>(
+        &'a self,
+        match_state: &mut MatchState,
+        simple_ac_table: &SimpleAcTable,
+        ac_result: &aho_corasick::Match,
+        (index, variant): (usize, usize),
+        variant_count: usize,
+    ) -> Option<SimpleResult<'a>> {
+        let ac_word_id = ac_result.pattern().as_usize();
+        let ac_word_conf = unsafe { simple_ac_table.ac_word_conf_list.get_unchecked(ac_word_id) };
+        let word_id = ac_word_conf.0;
+        // remove_word删除的词在rebuild前仍残留在ac自动机中
+        let word_conf = self.simple_word_map.get(&word_id)?;
+
+        let mut offset = ac_word_conf.1;
+        let mut start = ac_result.start();
+        if unlikely(offset >= word_conf.split_bit.len()) {
+            let sequence_progress_list = match_state
+                .word_id_sequence_map
+                .entry(word_id)
+                .or_insert_with(|| {
+                    word_conf
+                        .sequence_list
+                        .iter()
+                        .map(|_| std::iter::repeat_n((0, 0, 0), variant_count).collect())
+                        .collect()
+                });
+            (offset, start) = Self::_advance_sequence(
+                word_conf,
+                offset,
+                &mut sequence_progress_list[..],
+                index,
+                ac_result,
+            )?;
+        }
+
+        let split_bit = match_state
+            .word_id_split_bit_map
+            .entry(word_id)
+            .or_insert_with(|| {
+                word_conf
+                    .split_bit
+                    .iter()
+                    .map(|&x| std::iter::repeat_n(x, variant_count).collect())
+                    .collect()
+            });
+
+        *unsafe { split_bit.get_unchecked_mut(offset).get_unchecked_mut(index) } >>= 1; // 右移一位，不用 -1 是因为不能确定命中次数，u64 - 1 最后可能会越界
+
+        if unlikely(self.options.detailed) {
+            match_state
+                .word_id_hit_count_map
+                .entry(word_id)
+                .or_insert_with(|| vec![[0; 4]; word_conf.split_word_list.len()])[offset][index] +=
+                1;
+        }
+
+        if likely(
+            match_state.word_id_set.contains(&word_id)
+                || !match word_conf.window {
+                    None => split_bit[..word_conf.not_offset]
+                        .iter()
+                        .all(|bit| bit.iter().any(|&b| b == 0)),
+                    Some(window) => {
+                        offset < word_conf.not_offset
+                            && Self::_check_window(
+                                word_conf,
+                                window,
+                                &mut match_state
+                                    .word_id_window_hit_map
+                                    .entry(word_id)
+                                    .or_insert_with(|| vec![Vec::new(); variant_count])[index],
+                                offset,
+                                (start, ac_result.end()),
+                            )
+                    }
+                },
+        ) {
+            return None;
+        }
+
+        match_state.word_id_set.insert(word_id);
+        let span = MatchSpan {
+            start: ac_result.start(),
+            end: ac_result.end(),
+            variant,
+        };
+        if unlikely(word_conf.has_not_part()) {
+            match_state.not_word_id_list.push((word_id, span));
+            return None;
+        }
+        let raw_word = self
+            .word_storage
+            .get(word_conf.word_start, word_conf.word_end);
+        Some(SimpleResult {
+            word_id,
+            word: self.result_word(raw_word),
+            raw_word,
+            parts: None,
+            span: Some(span),
+        })
+    }
+
+    // 有序部分的步骤命中，推进该轮次中所在有序部分的进度；所有步骤依次命中时返回 (有序部分在split_bit中的下标, 第一步骤命中的开始位置)，并从头开始下一次命中
+    // ac重叠匹配按结束位置依次返回命中，每一步骤取最早结束且在上一步骤结束之后开始的命中，存在合法的顺序时必然能找到
+    #[inline]
+    fn _advance_sequence(
+        word_conf: &WordConf,
+        offset: usize,
+        sequence_progress_list: &mut [ArrayVec<[SequenceProgress; 4]>],
+        index: usize,
+        ac_result: &aho_corasick::Match,
+    ) -> Option<(usize, usize)> {
+        let (sequence_index, step_index) = word_conf.step_list[offset - word_conf.split_bit.len()];
+        let (part_offset, step_count) = word_conf.sequence_list[sequence_index];
+        let (next_step_index, last_end, first_start) =
+            &mut sequence_progress_list[sequence_index][index];
+
+        if step_index != *next_step_index || ac_result.start() < *last_end {
+            return None;
+        }
+        if step_index == 0 {
+            *first_start = ac_result.start();
+        }
+        *last_end = ac_result.end();
+        *next_step_index = (step_index + 1) % step_count;
+
+        (*next_step_index == 0).then_some((part_offset, *first_start))
+    }
+
+    // 记录窗口约束的词在该轮次中组合词部分的命中，判断是否存在不超过window字节、且包含各部分所需命中次数的窗口
+    // ac重叠匹配按结束位置依次返回命中，只需检查以当前命中结束的窗口；开始位置早于该窗口的命中之后也不会再用到，直接丢弃
+    #[inline]
+    fn _check_window(
+        word_conf: &WordConf,
+        window: usize,
+        window_hit_list: &mut Vec<(usize, usize)>,
+        offset: usize,
+        (start, end): (usize, usize),
+    ) -> bool {
+        window_hit_list.retain(|&(_, hit_start)| hit_start + window >= end);
+        if start + window < end {
+            return false;
+        }
+        window_hit_list.push((offset, start));
+
+        word_conf.split_bit[..word_conf.not_offset]
+            .iter()
+            .enumerate()
+            .all(|(part_offset, &bit)| {
+                let required_count = (u64::BITS - bit.leading_zeros()) as usize;
+                window_hit_list
+                    .iter()
+                    .filter(|&&(hit_offset, _)| hit_offset == part_offset)
+                    .count()
+                    >= required_count
+            })
+    }
+
+    // 所有轮次结束后，判定含否定部分的词，并在detailed模式下统计各部分的命中次数
+    fn _finish_match_state<'a>(
+        &'a self,
+        match_state: &MatchState,
+        result_list: &mut Vec<SimpleResult<'a>>,
+        mut negated_list: Option<&mut Vec<NegatedResult<'a>>>,
+    ) {
+        // 任一文本变体中命中任一否定部分即丢弃该词
+        for &(word_id, span) in &match_state.not_word_id_list {
+            let word_conf = unsafe { self.simple_word_map.get(&word_id).unwrap_unchecked() };
+            let split_bit = unsafe {
+                match_state
+                    .word_id_split_bit_map
+                    .get(&word_id)
+                    .unwrap_unchecked()
+            };
+            let mut hit_not_offset_iter = (word_conf.not_offset..split_bit.len())
+                .filter(|&offset| split_bit[offset].contains(&0))
+                .peekable();
+
+            let raw_word = self
+                .word_storage
+                .get(word_conf.word_start, word_conf.word_end);
+            let simple_result = SimpleResult {
+                word_id,
+                word: self.result_word(raw_word),
+                raw_word,
+                parts: None,
+                span: Some(span),
+            };
+            if hit_not_offset_iter.peek().is_none() {
+                result_list.push(simple_result);
+            } else if let Some(negated_list) = negated_list.as_deref_mut() {
+                negated_list.push(NegatedResult {
+                    result: simple_result,
+                    not_part_list: hit_not_offset_iter
+                        .map(|offset| self.result_word(&word_conf.split_word_list[offset]))
+                        .collect(),
+                });
+            }
+        }
+
+        if unlikely(self.options.detailed) {
+            // 命中后的词仍可能继续命中，所以在所有轮次结束后再统计各部分的命中次数
+            for simple_result in result_list.iter_mut() {
+                let word_conf = unsafe {
+                    self.simple_word_map
+                        .get(&simple_result.word_id)
+                        .unwrap_unchecked()
+                };
+                let hit_count_list = unsafe {
+                    match_state
+                        .word_id_hit_count_map
+                        .get(&simple_result.word_id)
+                        .unwrap_unchecked()
+                };
+
+                simple_result.parts = Some(
+                    word_conf
+                        .split_word_list
+                        .iter()
+                        .take(word_conf.not_offset)
+                        .zip(hit_count_list)
+                        .map(|(split_word, hit_count)| PartHit {
+                            part: self.result_word(split_word),
+                            count: hit_count.iter().copied().max().unwrap_or(0),
+                        })
+                        .collect(),
+                );
+            }
+        }
+    }
+
+    // 所有轮次结束后，由各部分在各轮次中的命中bit生成命中情况，命中bit归0的轮次即该部分命中的变体
+    // 有序部分在所有步骤依次命中后才记录命中，仅命中部分步骤的词不返回
+    fn _collect_detail<'a>(
+        &'a self,
+        match_state: &MatchState,
+        type_variant_map: &AHashMap<SimpleMatchType, ArrayVec<[usize; MAX_VARIANT_COUNT]>>,
+        result_list: &[SimpleResult<'a>],
+        detail_list: &mut Vec<DetailedResult<'a>>,
+    ) {
+        let matched_word_id_set: IntSet<u64> = result_list
+            .iter()
+            .map(|simple_result| simple_result.word_id)
+            .collect();
+
+        for (&word_id, split_bit) in &match_state.word_id_split_bit_map {
+            let word_conf = unsafe { self.simple_word_map.get(&word_id).unwrap_unchecked() };
+            let variant_list = unsafe {
+                type_variant_map
+                    .get(&word_conf.simple_match_type)
+                    .unwrap_unchecked()
+            };
+            let raw_word = self
+                .word_storage
+                .get(word_conf.word_start, word_conf.word_end);
+
+            detail_list.push(DetailedResult {
+                word_id,
+                word: self.result_word(raw_word),
+                matched: matched_word_id_set.contains(&word_id),
+                parts: split_word_part_list(raw_word)
+                    .0
+                    .into_iter()
+                    .zip(split_bit)
+                    .enumerate()
+                    .map(|(offset, (split_word, bit))| {
+                        let variant = bit
+                            .iter()
+                            .position(|&b| b == 0)
+                            .map(|index| variant_list[index]);
+                        PartDetail {
+                            part: self.result_word(split_word),
+                            negated: offset >= word_conf.not_offset,
+                            matched: variant.is_some(),
+                            variant,
+                        }
+                    })
+                    .collect(),
+            });
+        }
+        detail_list.sort_unstable_by_key(|detailed_result| detailed_result.word_id);
+    }
+
+    // ac自动机只对ascii大小写不敏感，所以只需转换ascii字符，且仅在大小写确实不一致时才分配
+    // 组合词仅在设置display_separator时转为展示形式
+    #[inline]
+    fn result_word<'b>(&self, word: &'b str) -> Cow<'b, str> {
+        let word = match &self.options.display_separator {
+            Some(separator) if unlikely(word.contains(',')) => Cow::Owned(
+                split_unescaped(word, ',')
+                    .filter(|part| !part.is_empty())
+                    .collect::<Vec<&str>>()
+                    .join(separator),
+            ),
+            _ => Cow::Borrowed(word),
+        };
+
+        match self.options.normalize_result_case {
+            Some(CaseMode::Lower) if unlikely(word.bytes().any(|b| b.is_ascii_uppercase())) => {
+                Cow::Owned(word.to_ascii_lowercase())
+            }
+            Some(CaseMode::Upper) if unlikely(word.bytes().any(|b| b.is_ascii_lowercase())) => {
+                Cow::Owned(word.to_ascii_uppercase())
+            }
+            _ => word,
+        }
+    }
+
+    #[inline]
+    fn replace_all_bytes(
+        process_matcher: &AhoCorasick,
+        process_replace_list: &[Cow<'static, str>],
+        max_expansion_ratio: f32,
+        text_bytes: &[u8],
+    ) -> Vec<u8> {
+        // aho_corasick自带的replace_all_bytes按原文本长度预分配，拼音等膨胀型转换在密集替换时会反复扩容拷贝，这里按最大膨胀比例预分配
+        let mut processed_text =
+            Vec::with_capacity((text_bytes.len() as f32 * max_expansion_ratio) as usize);
+        let mut last_match = 0;
+
+        for mat in process_matcher.find_iter(text_bytes) {
+            processed_text.extend(slice_text_bytes(text_bytes, last_match, mat.start()));
+            processed_text.extend(
+                unsafe { process_replace_list.get_unchecked(mat.pattern().as_usize()) }.as_bytes(),
+            );
+            last_match = mat.end();
+        }
+        processed_text.extend(slice_text_bytes(text_bytes, last_match, text_bytes.len()));
+
+        processed_text
+    }
+
+    #[inline]
+    fn delete_all_bytes(process_matcher: &AhoCorasick, text_bytes: &[u8]) -> Vec<u8> {
+        // 省去n次 string.push('')的操作
+        let mut processed_text = Vec::with_capacity(text_bytes.len());
+        let mut last_match = 0;
+
+        for mat in process_matcher.find_iter(text_bytes) {
+            processed_text.extend(slice_text_bytes(text_bytes, last_match, mat.start()));
+            last_match = mat.end();
+        }
+        processed_text.extend(slice_text_bytes(text_bytes, last_match, text_bytes.len()));
+
+        processed_text
+    }
+}
+
+impl<'a> TextMatcherTrait<'a, SimpleResult<'a>> for SimpleMatcher {
+    fn is_match(&self, text: &str) -> bool {
+        // 任一不含否定部分的词完成命中即返回，不再扫描剩余的文本以及变体；含否定部分的词在所有变体扫描结束后判定
+        self.simple_process_iter(text).next().is_some()
+    }
+
+    fn process(&'a self, text: &str) -> Vec<SimpleResult<'a>> {
+        if unlikely(text.is_empty() || self.is_short_text(text.as_bytes()))
+            || !self.is_candidate(text)
+        {
+            // 过滤空文本，短文本以及不含触发字符的文本，省去文本转换
+            return Vec::new();
+        }
+
+        match &self.single_type {
+            Some(simple_match_type) => self._process_single_type(simple_match_type, text),
+            None => self.process_processed(&self.preprocess(text)),
+        }
+    }
+
+    fn is_match_processed(&self, processed_text: &ProcessedText) -> bool {
+        self.simple_process_iter_processed(processed_text)
+            .next()
+            .is_some()
+    }
+
+    fn process_processed(&'a self, processed_text: &ProcessedText) -> Vec<SimpleResult<'a>> {
+        self._process_processed(processed_text, None, None)
+    }
+
+    fn process_iter(&'a self, text: &'a str) -> Box<dyn Iterator<Item = SimpleResult<'a>> + 'a> {
+        // detailed模式下各部分的命中次数需要扫描完所有变体才能确定，退化为process
+        if unlikely(self.options.detailed) {
+            return Box::new(self.process(text).into_iter());
+        }
+        Box::new(self.simple_process_iter(text))
+    }
+
+    fn find_first(&'a self, text: &str) -> Option<SimpleResult<'a>> {
+        // detailed模式下各部分的命中次数需要扫描完所有变体才能确定
+        if unlikely(self.options.detailed) {
+            return self.process(text).into_iter().next();
+        }
+        self.simple_process_iter(text).next()
+    }
 }
@@ -0,0 +1,83 @@
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+use serde_json::Value;
+
+fn fixture(name: &str) -> String {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn run(args: &[&str]) -> (Vec<Value>, Value) {
+    let output = Command::cargo_bin("matcher_rs")
+        .unwrap()
+        .args(args)
+        .pipe_stdin(fixture("texts.txt"))
+        .unwrap()
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+
+    let result_list = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let timing = serde_json::from_str(String::from_utf8(output.stderr).unwrap().trim()).unwrap();
+
+    (result_list, timing)
+}
+
+#[test]
+fn cli_word_match() {
+    let (result_list, timing) = run(&[&fixture("table_map.json")]);
+
+    assert_eq!(4, result_list.len());
+    assert_eq!(r#"[{"table_id":1,"word":"你好"}]"#, result_list[0]["test"]);
+    assert_eq!(
+        r#"[{"table_id":1,"word":"无,法,无,天"}]"#,
+        result_list[1]["test"]
+    );
+    assert_eq!(
+        r#"[{"table_id":2,"word":"1[3-9]\\d{9}"}]"#,
+        result_list[2]["test"]
+    );
+    assert_eq!(serde_json::json!({}), result_list[3]);
+
+    assert_eq!(4, timing["text_count"]);
+    assert_eq!(1, timing["build_report"]["regex_table_count"]);
+    assert!(timing["match_duration"].is_object());
+}
+
+#[test]
+fn cli_simple_table() {
+    let (result_list, timing) = run(&["--simple-table", &fixture("simple_wordlist.json")]);
+
+    assert_eq!(4, result_list.len());
+    assert_eq!(1, result_list[0][0]["word_id"]);
+    assert_eq!(2, result_list[1][0]["word_id"]);
+    assert_eq!(serde_json::json!([]), result_list[2]);
+    assert_eq!(serde_json::json!([]), result_list[3]);
+
+    assert_eq!(4, timing["text_count"]);
+    assert!(timing.get("build_report").is_none());
+}
+
+#[test]
+fn cli_usage_error() {
+    Command::cargo_bin("matcher_rs").unwrap().assert().code(2);
+    Command::cargo_bin("matcher_rs")
+        .unwrap()
+        .args(["--unknown", &fixture("table_map.json")])
+        .assert()
+        .code(2);
+    Command::cargo_bin("matcher_rs")
+        .unwrap()
+        .arg(fixture("not_exist.json"))
+        .assert()
+        .failure();
+}
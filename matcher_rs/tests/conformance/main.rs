@@ -0,0 +1,192 @@
+mod reference;
+
+use std::collections::BTreeMap;
+
+use proptest::prelude::*;
+use proptest::sample::{select, Index};
+use serde_json::Value;
+
+use matcher_rs::*;
+
+const CASE_COUNT: u32 = 64; // 随机词表数，每个词表都需要构建一次Matcher
+const MAX_TEXT_COUNT_PER_CASE: usize = 48; // 每个随机词表匹配的随机文本数上限
+
+// 覆盖 繁简 / 删除 / 替换归一 映射以及ascii大小写的小字母表，字符越少，随机词与文本越容易产生命中
+const ALPHABET: &[&str] = &[
+    "a", "b", "A", "B", "1", "0", "⑩", "Ａ", "國", "国", "你", "妳", " ", "*", "-",
+];
+
+// 拼音映射表过大，逐用例构建过慢，不参与随机
+const SIMPLE_MATCH_TYPE_LIST: &[SimpleMatchType] = &[
+    SimpleMatchType::None,
+    SimpleMatchType::Fanjian,
+    SimpleMatchType::Delete,
+    SimpleMatchType::Normalize,
+    SimpleMatchType::DeleteNormalize,
+    SimpleMatchType::FanjianDeleteNormalize,
+];
+
+fn part_strategy() -> impl Strategy<Value = String> {
+    prop::collection::vec(select(ALPHABET), 1..=2).prop_map(|char_list| char_list.concat())
+}
+
+// 1~3个组合部分，部分可能重复以要求多次出现，eg. "a,a,国"，可能带一个否定部分
+fn word_strategy() -> impl Strategy<Value = String> {
+    (
+        prop::collection::vec(
+            (
+                part_strategy(),
+                prop::option::weighted(0.25, any::<Index>()),
+            ),
+            1..=3,
+        ),
+        prop::option::weighted(0.25, part_strategy()),
+    )
+        .prop_map(|(part_conf_list, not_part)| {
+            let mut part_list: Vec<String> = Vec::new();
+            for (part, repeat_index) in part_conf_list {
+                match repeat_index {
+                    Some(repeat_index) if !part_list.is_empty() => {
+                        let repeated_part = part_list[repeat_index.index(part_list.len())].clone();
+                        part_list.push(repeated_part);
+                    }
+                    _ => part_list.push(part),
+                }
+            }
+
+            let mut word = part_list.join(",");
+            if let Some(not_part) = not_part {
+                word.push('~');
+                word.push_str(&not_part);
+            }
+            word
+        })
+}
+
+type TableSpec = (Vec<String>, Vec<String>, SimpleMatchType); // (词表, 豁免词表, 匹配类型)
+
+fn table_spec_strategy() -> impl Strategy<Value = TableSpec> {
+    let simple_match_type_list: Vec<SimpleMatchType> = SIMPLE_MATCH_TYPE_LIST
+        .iter()
+        .copied()
+        .filter(|simple_match_type| {
+            !simple_match_type.intersects(SimpleMatchType::builtin_unavailable())
+        })
+        .collect();
+
+    (
+        prop::collection::vec(word_strategy(), 1..=4),
+        prop::collection::vec(word_strategy(), 0..=1),
+        select(simple_match_type_list),
+    )
+}
+
+// 随机字符与词表中出现过的片段交替拼接，保证相当比例的文本能产生命中
+fn text_strategy(fragment_list: Vec<String>) -> impl Strategy<Value = String> {
+    prop::collection::vec(
+        prop_oneof![
+            select(fragment_list),
+            select(ALPHABET).prop_map(str::to_owned)
+        ],
+        0..8,
+    )
+    .prop_map(|fragment_list| fragment_list.concat())
+}
+
+// 1~3个match_id，每个1~2个词表，以及若干由词表片段拼接的文本
+fn case_strategy() -> impl Strategy<Value = (Vec<Vec<TableSpec>>, Vec<String>)> {
+    prop::collection::vec(prop::collection::vec(table_spec_strategy(), 1..=2), 1..=3).prop_flat_map(
+        |match_id_list| {
+            let fragment_list: Vec<String> = match_id_list
+                .iter()
+                .flatten()
+                .flat_map(|(wordlist, exemption_wordlist, _)| {
+                    wordlist.iter().chain(exemption_wordlist)
+                })
+                .flat_map(|word| word.split([',', '~']))
+                .filter(|fragment| !fragment.is_empty())
+                .map(str::to_owned)
+                .collect();
+            (
+                Just(match_id_list),
+                prop::collection::vec(text_strategy(fragment_list), 1..=MAX_TEXT_COUNT_PER_CASE),
+            )
+        },
+    )
+}
+
+// table_id按顺序分配
+fn build_table_map(match_id_list: Vec<Vec<TableSpec>>) -> BTreeMap<String, Vec<OwnedMatchTable>> {
+    let mut table_id = 0;
+    match_id_list
+        .into_iter()
+        .enumerate()
+        .map(|(match_id_index, table_spec_list)| {
+            let table_list = table_spec_list
+                .into_iter()
+                .map(|(wordlist, exemption_wordlist, simple_match_type)| {
+                    table_id += 1;
+                    OwnedMatchTable {
+                        table_id,
+                        match_table_type: MatchTableType::Simple,
+                        wordlist,
+                        exemption_wordlist,
+                        simple_match_type,
+                        ..Default::default()
+                    }
+                })
+                .collect();
+            (format!("match_id_{match_id_index}"), table_list)
+        })
+        .collect()
+}
+
+fn word_match(matcher: &Matcher, text: &str) -> BTreeMap<String, Vec<(u32, String)>> {
+    matcher
+        .word_match(text)
+        .into_iter()
+        .map(|(match_id, result)| {
+            let result_list: Vec<Value> = serde_json::from_str(&result).unwrap();
+            let mut result_list: Vec<(u32, String)> = result_list
+                .iter()
+                .map(|match_result| {
+                    (
+                        match_result["table_id"].as_u64().unwrap() as u32,
+                        match_result["word"].as_str().unwrap().to_owned(),
+                    )
+                })
+                .collect();
+            result_list.sort_unstable();
+            (match_id.to_owned(), result_list)
+        })
+        .collect()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(CASE_COUNT))]
+
+    // 失败时proptest会缩减词表与文本，输出最小的反例
+    #[test]
+    fn conformance_word_match((match_id_list, text_list) in case_strategy()) {
+        let table_map = build_table_map(match_id_list);
+        let match_table_dict: MatchTableDict = table_map
+            .iter()
+            .map(|(match_id, table_list)| {
+                (
+                    match_id.as_str(),
+                    table_list.iter().map(MatchTable::from).collect(),
+                )
+            })
+            .collect();
+        let matcher = Matcher::new(&match_table_dict);
+
+        for text in &text_list {
+            prop_assert_eq!(
+                reference::naive_word_match(&table_map, text),
+                word_match(&matcher, text),
+                "text: {:?}",
+                text
+            );
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::OnceLock;
+
+use matcher_rs::{MatchTableType, OwnedMatchTable, SimpleMatchType, SimpleMatcher};
+
+// 慢但直观的参考实现，只用于一致性测试，不追求性能：
+// 1. 按内置映射表逐字符做最左最长替换，链式生成文本与词的各个变体
+// 2. 在每个文本变体中朴素地查找词各部分的所有变体，按部分统计命中次数
+// 3. 组合词所有部分在某个文本变体中达到出现次数，且否定部分在任何文本变体中都未出现时命中
+// 4. match_id的任一豁免词命中时丢弃该match_id的全部结果
+// 5. 同MatcherOptions的默认值，只含空白以及零宽字符的文本视为空文本
+// 只支持simple词表，其余词表类型由各自的匹配器单独测试
+
+// 链式转换的顺序，与SimpleMatchType的bit顺序一致
+const STAGE_LIST: [SimpleMatchType; 6] = [
+    SimpleMatchType::Fanjian,
+    SimpleMatchType::WordDelete,
+    SimpleMatchType::TextDelete,
+    SimpleMatchType::Normalize,
+    SimpleMatchType::PinYin,
+    SimpleMatchType::PinYinChar,
+];
+
+struct ProcessDict {
+    replace_dict: HashMap<&'static str, &'static str>,
+    max_key_len: usize, // 最长键的字节数
+}
+
+fn process_dict(str_conv_type: SimpleMatchType) -> &'static ProcessDict {
+    static PROCESS_DICT_LIST: OnceLock<Vec<ProcessDict>> = OnceLock::new();
+
+    let process_dict_list = PROCESS_DICT_LIST.get_or_init(|| {
+        STAGE_LIST
+            .iter()
+            .map(|&str_conv_type| {
+                let process_pair_list = SimpleMatcher::builtin_process_pair_list(str_conv_type);
+                ProcessDict {
+                    max_key_len: process_pair_list
+                        .iter()
+                        .map(|(key, _)| key.len())
+                        .max()
+                        .unwrap_or(0),
+                    replace_dict: process_pair_list.into_iter().collect(),
+                }
+            })
+            .collect()
+    });
+
+    let stage_index = STAGE_LIST
+        .iter()
+        .position(|&stage| stage == str_conv_type)
+        .unwrap();
+    &process_dict_list[stage_index]
+}
+
+// 从左到右，每个位置尝试以该位置开头的最长键，命中则替换并跳过整个键，否则原样保留当前字符
+fn convert(text: &str, str_conv_type: SimpleMatchType) -> String {
+    let process_dict = process_dict(str_conv_type);
+    let mut converted_text = String::with_capacity(text.len());
+    let mut start = 0;
+
+    while start < text.len() {
+        let longest_match = (start + 1..=text.len().min(start + process_dict.max_key_len))
+            .rev()
+            .filter(|&end| text.is_char_boundary(end))
+            .find_map(|end| {
+                process_dict
+                    .replace_dict
+                    .get(&text[start..end])
+                    .map(|&value| (end, value))
+            });
+
+        match longest_match {
+            Some((end, value)) => {
+                converted_text.push_str(value);
+                start = end;
+            }
+            None => {
+                let c = text[start..].chars().next().unwrap();
+                converted_text.push(c);
+                start += c.len_utf8();
+            }
+        }
+    }
+
+    converted_text
+}
+
+// 原文本 以及 每一步转换后的结果；繁简转换直接替换原文本，不保留繁体形态
+fn variant_list(text: &str, str_conv_type: SimpleMatchType) -> Vec<String> {
+    let mut variant_list = vec![text.to_owned()];
+
+    for stage in STAGE_LIST {
+        if !str_conv_type.contains(stage) {
+            continue;
+        }
+        let last_variant = variant_list.last().unwrap();
+        let converted_text = convert(last_variant, stage);
+        if stage == SimpleMatchType::Fanjian {
+            *variant_list.last_mut().unwrap() = converted_text;
+        } else {
+            variant_list.push(converted_text);
+        }
+    }
+
+    variant_list
+}
+
+// 部分的各个变体在文本中所有（可重叠的）出现位置数，ascii大小写不敏感
+fn count_occurrence(part_variant_list: &[String], text: &str) -> usize {
+    let text = text.to_ascii_lowercase();
+    let mut pattern_list: Vec<String> = part_variant_list
+        .iter()
+        .map(|part_variant| part_variant.to_ascii_lowercase())
+        .filter(|pattern| !pattern.is_empty())
+        .collect();
+    pattern_list.sort_unstable();
+    pattern_list.dedup();
+
+    pattern_list
+        .iter()
+        .map(|pattern| {
+            (0..text.len())
+                .filter(|&start| text.as_bytes()[start..].starts_with(pattern.as_bytes()))
+                .count()
+        })
+        .sum()
+}
+
+// 词在文本中是否命中，eg. "a,a,b~c" 需要某个文本变体中出现两次a、某个文本变体中出现b，且任何文本变体中都不出现c
+pub fn word_hit(word: &str, simple_match_type: SimpleMatchType, text: &str) -> bool {
+    let text_variant_list = variant_list(text, simple_match_type - SimpleMatchType::WordDelete);
+    let part_hit_count = |part: &str| {
+        let part_variant_list = variant_list(part, simple_match_type - SimpleMatchType::TextDelete);
+        text_variant_list
+            .iter()
+            .map(|text_variant| count_occurrence(&part_variant_list, text_variant))
+            .max()
+            .unwrap_or(0)
+    };
+
+    let mut part_iter = word.split('~');
+    let and_word = part_iter.next().unwrap_or_default();
+
+    let mut required_count_dict: BTreeMap<&str, usize> = BTreeMap::new();
+    for part in and_word.split(',').filter(|part| !part.is_empty()) {
+        *required_count_dict.entry(part).or_default() += 1;
+    }
+
+    !required_count_dict.is_empty()
+        && required_count_dict
+            .iter()
+            .all(|(&part, &required_count)| part_hit_count(part) >= required_count)
+        && part_iter
+            .filter(|not_part| !not_part.is_empty())
+            .all(|not_part| part_hit_count(not_part) == 0)
+}
+
+// 各match_id命中的 (table_id, 词)，按字典序排列，豁免的match_id以及没有命中的match_id不出现
+pub fn naive_word_match(
+    table_map: &BTreeMap<String, Vec<OwnedMatchTable>>,
+    text: &str,
+) -> BTreeMap<String, Vec<(u32, String)>> {
+    let exemption_simple_match_type =
+        SimpleMatchType::FanjianDeleteNormalize - SimpleMatchType::builtin_unavailable();
+    let mut result_dict = BTreeMap::new();
+
+    let is_blank = text.chars().all(|c| {
+        c.is_whitespace()
+            || matches!(
+                c,
+                '\u{180E}' | '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}'
+            )
+    });
+    if is_blank {
+        return result_dict;
+    }
+
+    for (match_id, table_list) in table_map {
+        let is_exempted = table_list.iter().any(|table| {
+            table
+                .exemption_wordlist
+                .iter()
+                .any(|word| word_hit(word, exemption_simple_match_type, text))
+        });
+        if is_exempted {
+            continue;
+        }
+
+        let mut result_list: Vec<(u32, String)> = table_list
+            .iter()
+            .filter(|table| matches!(table.match_table_type, MatchTableType::Simple))
+            .flat_map(|table| {
+                table
+                    .wordlist
+                    .iter()
+                    .filter(|word| word_hit(word, table.simple_match_type.canonicalize(), text))
+                    .map(|word| (table.table_id, word.to_owned()))
+            })
+            .collect();
+
+        if !result_list.is_empty() {
+            result_list.sort_unstable();
+            result_dict.insert(match_id.to_owned(), result_list);
+        }
+    }
+
+    result_dict
+}
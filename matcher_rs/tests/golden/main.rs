@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use serde_json::{json, Value};
+
+use matcher_rs::*;
+
+const UPDATE_ENV: &str = "MATCHER_GOLDEN_UPDATE"; // 设置为1时按当前结果重新生成黄金文件，而不是比对
+
+fn golden_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden")
+}
+
+// 还原语料中的转义，eg. "你\u{200b}好" -> "你​好"
+fn unescape(line: &str) -> String {
+    let mut text = String::with_capacity(line.len());
+    let mut char_iter = line.chars();
+
+    while let Some(c) = char_iter.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match char_iter.next() {
+            Some('n') => text.push('\n'),
+            Some('t') => text.push('\t'),
+            Some('\\') => text.push('\\'),
+            Some('u') => {
+                assert_eq!(Some('{'), char_iter.next(), "bad escape in line: {line}");
+                let hex: String = char_iter.by_ref().take_while(|&c| c != '}').collect();
+                text.push(
+                    u32::from_str_radix(&hex, 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .unwrap_or_else(|| panic!("bad escape in line: {line}")),
+                );
+            }
+            other => panic!("bad escape \\{other:?} in line: {line}"),
+        }
+    }
+
+    text
+}
+
+fn load_corpus() -> Vec<String> {
+    fs::read_to_string(golden_dir().join("corpus.txt"))
+        .unwrap()
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(unescape)
+        .collect()
+}
+
+// match_id按字典序，同match_id下的结果按序列化后的字符串排序，不受哈希顺序影响
+fn normalize(result_dict: std::collections::HashMap<&str, String>) -> BTreeMap<String, Vec<Value>> {
+    result_dict
+        .into_iter()
+        .map(|(match_id, result)| {
+            let mut result_list: Vec<Value> = serde_json::from_str(&result).unwrap();
+            result_list.sort_by_cached_key(|match_result| match_result.to_string());
+            (match_id.to_owned(), result_list)
+        })
+        .collect()
+}
+
+#[test]
+fn golden_word_match() {
+    let table_map_json = fs::read_to_string(golden_dir().join("table_map.json")).unwrap();
+    let match_table_dict: MatchTableDict = serde_json::from_str(&table_map_json).unwrap();
+    let matcher = Matcher::new(&match_table_dict);
+
+    let actual_line_list: Vec<String> = load_corpus()
+        .iter()
+        .map(|text| {
+            json!({"text": text, "result": normalize(matcher.word_match(text))}).to_string()
+        })
+        .collect();
+
+    let golden_path = golden_dir().join("golden.jsonl");
+    if env::var(UPDATE_ENV).is_ok_and(|value| value == "1") {
+        fs::write(&golden_path, actual_line_list.join("\n") + "\n").unwrap();
+        return;
+    }
+
+    let golden = fs::read_to_string(&golden_path).unwrap_or_default();
+    let golden_line_list: Vec<&str> = golden.lines().collect();
+
+    let diff_list: Vec<String> = actual_line_list
+        .iter()
+        .enumerate()
+        .filter(|(index, actual_line)| golden_line_list.get(*index) != Some(&actual_line.as_str()))
+        .map(|(index, actual_line)| {
+            format!(
+                "line {}\n  golden: {}\n  actual: {actual_line}",
+                index + 1,
+                golden_line_list.get(index).unwrap_or(&"<missing>")
+            )
+        })
+        .collect();
+
+    assert!(
+        diff_list.is_empty() && golden_line_list.len() == actual_line_list.len(),
+        "{} of {} golden lines differ (golden has {} lines), rerun with {UPDATE_ENV}=1 to regenerate if the change is intended:\n{}",
+        diff_list.len(),
+        actual_line_list.len(),
+        golden_line_list.len(),
+        diff_list.join("\n")
+    );
+}
@@ -0,0 +1,77 @@
+use ahash::AHashMap;
+
+use matcher_rs::*;
+
+// 不依赖serde的最小构建，只使用SimpleMatcher以及文本转换
+#[test]
+fn minimal_simple_match() {
+    // 未开启feature的转换类型没有内置映射表，不能出现在匹配类型中
+    let simple_wordlist_dict = AHashMap::from([(
+        SimpleMatchType::FanjianDeleteNormalize - SimpleMatchType::builtin_unavailable(),
+        vec![
+            SimpleWord {
+                word_id: 1,
+                word: "你好",
+            },
+            SimpleWord {
+                word_id: 2,
+                word: "无,法,无,天",
+            },
+        ],
+    )]);
+    let simple_matcher = SimpleMatcher::new(&simple_wordlist_dict);
+
+    assert!(simple_matcher.is_match("你好"));
+    assert!(simple_matcher.is_match("无无法天"));
+    assert!(!simple_matcher.is_match("无法天"));
+    assert_eq!(simple_matcher.process("你好")[0].word_id, 1);
+
+    #[cfg(feature = "fanjian")]
+    assert!(simple_matcher.is_match("妳好"));
+    #[cfg(not(feature = "fanjian"))]
+    assert!(!simple_matcher.is_match("妳好"));
+
+    #[cfg(feature = "delete")]
+    assert!(simple_matcher.is_match("你，好"));
+    #[cfg(not(feature = "delete"))]
+    assert!(!simple_matcher.is_match("你，好"));
+}
+
+#[cfg(not(feature = "pinyin"))]
+#[test]
+fn disabled_process_type() {
+    let simple_wordlist_dict = AHashMap::from([(
+        SimpleMatchType::PinYin,
+        vec![SimpleWord {
+            word_id: 1,
+            word: "你好",
+        }],
+    )]);
+
+    assert_eq!(
+        Err(SimpleMatcherError::DisabledProcessType {
+            simple_match_type: SimpleMatchType::PinYin,
+            str_conv_type: SimpleMatchType::PinYin,
+            feature: "pinyin",
+        }),
+        SimpleMatcher::try_new(&simple_wordlist_dict).map(|_| ())
+    );
+
+    // 覆盖映射表后不再依赖内置映射表
+    let simple_matcher = SimpleMatcher::try_new_with_options(
+        &simple_wordlist_dict,
+        SimpleMatcherOptions {
+            process_dict_override: AHashMap::from([(
+                SimpleMatchType::PinYin,
+                vec![
+                    ("你".to_owned(), " ni ".to_owned()),
+                    ("妳".to_owned(), " ni ".to_owned()),
+                    ("好".to_owned(), " hao ".to_owned()),
+                ],
+            )]),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert!(simple_matcher.is_match("妳好"));
+}
@@ -1,4 +1,4 @@
-use ahash::AHashMap;
+use ahash::{AHashMap, AHashSet};
 use zerovec::VarZeroVec;
 
 use matcher_rs::*;
@@ -103,18 +103,30 @@ fn regex_match() {
             match_id: "1",
             match_table_type: &MatchTableType::SimilarChar,
             wordlist: &similar_wordlist,
+            acrostic_position: AcrosticPosition::Initial,
+            prefer_matched_text: false,
+            regex_flags: RegexFlags::default(),
+            acrostic_separator: AcrosticSeparator::Punctuation,
         },
         RegexTable {
             table_id: 2,
             match_id: "2",
             match_table_type: &MatchTableType::Acrostic,
             wordlist: &acrostic_wordlist,
+            acrostic_position: AcrosticPosition::Initial,
+            prefer_matched_text: false,
+            regex_flags: RegexFlags::default(),
+            acrostic_separator: AcrosticSeparator::Punctuation,
         },
         RegexTable {
             table_id: 3,
             match_id: "3",
             match_table_type: &MatchTableType::Regex,
             wordlist: &regex_wordlist,
+            acrostic_position: AcrosticPosition::Initial,
+            prefer_matched_text: false,
+            regex_flags: RegexFlags::default(),
+            acrostic_separator: AcrosticSeparator::Punctuation,
         },
     ];
     let regex_matcher = RegexMatcher::new(&regex_table_list);
@@ -127,6 +139,51 @@ fn regex_match() {
     assert!(regex_matcher.is_match("15651781111"));
 }
 
+#[test]
+fn regex_matched_text() {
+    let similar_wordlist = VarZeroVec::from(&["hello,hi,H,你好", "world,word,🌍,世界"]);
+    let regex_matcher = RegexMatcher::new(&vec![RegexTable {
+        table_id: 1,
+        match_id: "1",
+        match_table_type: &MatchTableType::SimilarChar,
+        wordlist: &similar_wordlist,
+        acrostic_position: AcrosticPosition::Initial,
+        prefer_matched_text: false,
+        regex_flags: RegexFlags::default(),
+        acrostic_separator: AcrosticSeparator::Punctuation,
+    }]);
+
+    let regex_result_list = regex_matcher.process("说声你好 🌍！");
+    assert_eq!("你好🌍", regex_result_list[0].word);
+    assert_eq!(
+        Some("你好 🌍"),
+        regex_result_list[0].matched_text.as_deref()
+    );
+
+    let regex_result_list = regex_matcher.process("hi,word");
+    assert_eq!("hiword", regex_result_list[0].word);
+    assert_eq!(
+        Some("hi,word"),
+        regex_result_list[0].matched_text.as_deref()
+    );
+
+    let match_table_dict = AHashMap::from([(
+        "test",
+        vec![MatchTable {
+            table_id: 1,
+            match_table_type: MatchTableType::SimilarChar,
+            wordlist: similar_wordlist.clone(),
+            prefer_matched_text: true,
+            ..Default::default()
+        }],
+    )]);
+    let matcher = Matcher::new(&match_table_dict);
+    assert_eq!(
+        r#"[{"table_id":1,"word":"你好 🌍"}]"#,
+        matcher.word_match("说声你好 🌍！").get("test").unwrap()
+    );
+}
+
 #[test]
 fn sim_match() {
     let wordlist = VarZeroVec::from(&["你真是太棒了真的太棒了", "你真棒"]);
@@ -135,6 +192,11 @@ fn sim_match() {
         table_id: 1,
         match_id: "1",
         wordlist: &wordlist,
+        threshold: DEFAULT_SIM_THRESHOLD,
+        simple_match_type: SimpleMatchType::None,
+        process_words: true,
+        sim_match_type: SimMatchType::Levenshtein,
+        top_k: None,
     }];
     let sim_matcher = SimMatcher::new(&sim_table_list);
 
@@ -146,6 +208,69 @@ fn sim_match() {
     assert!(sim_matcher.is_match("你真棒"));
 }
 
+#[test]
+fn sim_calibrate() {
+    let wordlist = VarZeroVec::from(&["abcdefghij"]);
+    let sim_table = SimTable {
+        table_id: 1,
+        match_id: "1",
+        wordlist: &wordlist,
+        threshold: DEFAULT_SIM_THRESHOLD,
+        simple_match_type: SimpleMatchType::None,
+        process_words: true,
+        sim_match_type: SimMatchType::Levenshtein,
+        top_k: None,
+    };
+    // 与词表的相似度依次为 0.9 / 0.8 / 0.5 / 0.0
+    let negatives = ["abcdefghix", "abcdefghxx", "abcdexxxxx", "zzzzzzzzzz"];
+
+    assert_eq!(0.91, SimMatcher::calibrate(&sim_table, &negatives, 0.0));
+    assert_eq!(0.81, SimMatcher::calibrate(&sim_table, &negatives, 0.25));
+    assert_eq!(0.51, SimMatcher::calibrate(&sim_table, &negatives, 0.5));
+    assert_eq!(0.01, SimMatcher::calibrate(&sim_table, &negatives, 0.75));
+    assert_eq!(0.0, SimMatcher::calibrate(&sim_table, &negatives, 1.0));
+
+    // 校准得到的阈值在匹配时恰好不产生误报
+    let sim_matcher = SimMatcher::new(&vec![SimTable {
+        threshold: SimMatcher::calibrate(&sim_table, &negatives, 0.0),
+        ..sim_table
+    }]);
+    assert!(negatives.iter().all(|text| !sim_matcher.is_match(text)));
+    assert!(sim_matcher.is_match("abcdefghij"));
+}
+
+#[test]
+fn sim_matcher_process_words() {
+    let wordlist = VarZeroVec::from(&["西安"]);
+    let sim_table = SimTable {
+        table_id: 1,
+        match_id: "1",
+        wordlist: &wordlist,
+        threshold: DEFAULT_SIM_THRESHOLD,
+        simple_match_type: SimpleMatchType::PinYinChar,
+        process_words: true,
+        sim_match_type: SimMatchType::Levenshtein,
+        top_k: None,
+    };
+
+    // 词与文本都转换为拼音字符后比较，结果中保留原词
+    let sim_matcher = SimMatcher::new(&vec![sim_table.clone()]);
+    assert!(sim_matcher.is_match("xian"));
+    let result_list = sim_matcher.process("xian");
+    assert_eq!(result_list.len(), 1);
+    assert_eq!(result_list[0].word, "西安");
+    assert_eq!(result_list[0].similarity, 1.0);
+    assert!(sim_matcher.is_match("先"));
+
+    // 关闭转换时原词与文本直接比较
+    let sim_matcher = SimMatcher::new(&vec![SimTable {
+        process_words: false,
+        ..sim_table
+    }]);
+    assert!(!sim_matcher.is_match("xian"));
+    assert!(sim_matcher.is_match("西安"));
+}
+
 #[test]
 fn word_match() {
     let match_table_dict = AHashMap::from([(
@@ -155,16 +280,16 @@ fn word_match() {
                 table_id: 1,
                 match_table_type: MatchTableType::Simple,
                 wordlist: VarZeroVec::from(&["无,法,无,天"]),
-                exemption_wordlist: VarZeroVec::new(),
                 simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                ..Default::default()
             },
             MatchTable {
                 table_id: 2,
                 match_table_type: MatchTableType::Simple,
                 wordlist: VarZeroVec::from(&["你好"]),
-                exemption_wordlist: VarZeroVec::new(),
                 simple_match_type: SimpleMatchType::FanjianDeleteNormalize
                     | SimpleMatchType::PinYin,
+                ..Default::default()
             },
         ],
     )]);
@@ -178,3 +303,5693 @@ fn word_match() {
     assert!(matcher.word_match("无法天").is_empty());
     assert!(!matcher.word_match("你豪").is_empty());
 }
+
+#[test]
+fn word_match_table_type() {
+    let match_table_dict = AHashMap::from([
+        (
+            "simple",
+            vec![MatchTable {
+                table_id: 1,
+                match_table_type: MatchTableType::Simple,
+                wordlist: VarZeroVec::from(&["你好"]),
+                simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                ..Default::default()
+            }],
+        ),
+        (
+            "regex",
+            vec![
+                MatchTable {
+                    table_id: 2,
+                    match_table_type: MatchTableType::Regex,
+                    wordlist: VarZeroVec::from(&["你好.*世界"]),
+                    ..Default::default()
+                },
+                MatchTable {
+                    table_id: 3,
+                    match_table_type: MatchTableType::Acrostic,
+                    wordlist: VarZeroVec::from(&["你,好"]),
+                    ..Default::default()
+                },
+            ],
+        ),
+        (
+            "similar",
+            vec![MatchTable {
+                table_id: 4,
+                match_table_type: MatchTableType::SimilarTextLevenshtein,
+                wordlist: VarZeroVec::from(&["你好世界"]),
+                ..Default::default()
+            }],
+        ),
+    ]);
+    // 默认不附带词表类型
+    assert_eq!(
+        r#"[{"table_id":1,"word":"你好"}]"#,
+        Matcher::new(&match_table_dict).word_match("你好世界")["simple"]
+    );
+
+    let (matcher, _) = Matcher::try_new_with_options(
+        &match_table_dict,
+        MatcherOptions {
+            report_table_type: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let result_dict = matcher.word_match("你好世界");
+    assert_eq!(
+        r#"[{"table_id":1,"table_type":"simple","word":"你好"}]"#,
+        result_dict.get("simple").unwrap()
+    );
+    assert_eq!(
+        r#"[{"table_id":4,"table_type":"similar","word":"你好世界"}]"#,
+        result_dict.get("similar").unwrap()
+    );
+
+    let result_dict = matcher.word_match("你好，世界。好的");
+    let regex_result_list: Vec<serde_json::Value> =
+        serde_json::from_str(result_dict.get("regex").unwrap()).unwrap();
+    assert!(!regex_result_list.is_empty());
+    assert!(regex_result_list
+        .iter()
+        .all(|regex_result| regex_result["table_type"] == "regex"));
+}
+
+#[test]
+fn dedup_words() {
+    let match_table_dict = AHashMap::from([(
+        "test",
+        vec![MatchTable {
+            table_id: 1,
+            match_table_type: MatchTableType::Simple,
+            wordlist: VarZeroVec::from(&["foo", "bar", "foo"]),
+            ..Default::default()
+        }],
+    )]);
+    let duplicate_word_warning = MatcherWarning::DuplicateWord {
+        match_id: "test".to_owned(),
+        table_id: 1,
+        word: "foo".to_owned(),
+    };
+
+    let (matcher, warning_list) = Matcher::try_new(&match_table_dict).unwrap();
+    assert_eq!(vec![duplicate_word_warning], warning_list);
+    assert_eq!(
+        r#"[{"table_id":1,"word":"foo"},{"table_id":1,"word":"foo"}]"#,
+        matcher.word_match("foo").get("test").unwrap()
+    );
+
+    let (matcher, warning_list) = Matcher::try_new_with_options(
+        &match_table_dict,
+        MatcherOptions {
+            dedup_words: DedupPolicy::KeepFirst,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert!(warning_list.is_empty());
+    assert_eq!(
+        r#"[{"table_id":1,"word":"foo"}]"#,
+        matcher.word_match("foo").get("test").unwrap()
+    );
+
+    assert_eq!(
+        MatcherError::DuplicateWord {
+            match_id: "test".to_owned(),
+            table_id: 1,
+            word: "foo".to_owned(),
+        },
+        Matcher::try_new_with_options(
+            &match_table_dict,
+            MatcherOptions {
+                dedup_words: DedupPolicy::Error,
+                ..Default::default()
+            },
+        )
+        .err()
+        .unwrap()
+    );
+
+    let simple_wordlist_dict = AHashMap::from([(
+        SimpleMatchType::None,
+        vec![
+            SimpleWord {
+                word_id: 1,
+                word: "foo",
+            },
+            SimpleWord {
+                word_id: 2,
+                word: "foo",
+            },
+        ],
+    )]);
+    assert_eq!(
+        2,
+        SimpleMatcher::new(&simple_wordlist_dict)
+            .process("foo")
+            .len()
+    );
+    let simple_matcher = SimpleMatcher::new_with_options(
+        &simple_wordlist_dict,
+        SimpleMatcherOptions {
+            dedup_words: true,
+            ..Default::default()
+        },
+    );
+    let simple_result_list = simple_matcher.process("foo");
+    assert_eq!(1, simple_result_list.len());
+    assert_eq!(1, simple_result_list[0].word_id);
+}
+
+#[test]
+fn runtime_exemption() {
+    let match_table_dict = AHashMap::from([
+        (
+            "test",
+            vec![MatchTable {
+                table_id: 1,
+                match_table_type: MatchTableType::Simple,
+                wordlist: VarZeroVec::from(&["你好"]),
+                exemption_wordlist: VarZeroVec::from(&["你好呀"]),
+                simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                ..Default::default()
+            }],
+        ),
+        (
+            "other",
+            vec![MatchTable {
+                table_id: 2,
+                match_table_type: MatchTableType::Simple,
+                wordlist: VarZeroVec::from(&["你好"]),
+                simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                ..Default::default()
+            }],
+        ),
+    ]);
+    let mut matcher = Matcher::new(&match_table_dict);
+    let result = r#"[{"table_id":1,"word":"你好"}]"#;
+
+    assert_eq!(result, matcher.word_match("你好世界").get("test").unwrap());
+
+    // commit前不生效
+    matcher.add_exemption("test", 1, "世界");
+    assert_eq!(result, matcher.word_match("你好世界").get("test").unwrap());
+
+    matcher.commit();
+    let result_dict = matcher.word_match("你好世界");
+    assert!(!result_dict.contains_key("test"));
+    assert!(result_dict.contains_key("other"));
+    // 运行时豁免词同样按繁简删除归一匹配
+    assert!(!matcher.word_match("你好世 界").contains_key("test"));
+
+    matcher.remove_exemption("test", 1, "世界");
+    matcher.commit();
+    assert_eq!(result, matcher.word_match("你好世界").get("test").unwrap());
+
+    // 构建时的豁免词同样可以移除以及恢复
+    assert!(!matcher.word_match("你好呀").contains_key("test"));
+    matcher.remove_exemption("test", 1, "你好呀");
+    matcher.commit();
+    assert_eq!(result, matcher.word_match("你好呀").get("test").unwrap());
+    matcher.add_exemption("test", 1, "你好呀");
+    matcher.commit();
+    assert!(!matcher.word_match("你好呀").contains_key("test"));
+}
+
+#[test]
+fn word_match_filtered() {
+    let match_table_dict = AHashMap::from([
+        (
+            "test",
+            vec![
+                MatchTable {
+                    table_id: 1,
+                    match_table_type: MatchTableType::Simple,
+                    wordlist: VarZeroVec::from(&["你好"]),
+                    simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                    ..Default::default()
+                },
+                MatchTable {
+                    table_id: 2,
+                    match_table_type: MatchTableType::Simple,
+                    wordlist: VarZeroVec::from(&["世界"]),
+                    simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                    ..Default::default()
+                },
+            ],
+        ),
+        (
+            "exemption",
+            vec![
+                MatchTable {
+                    table_id: 3,
+                    match_table_type: MatchTableType::Simple,
+                    wordlist: VarZeroVec::from(&["你好"]),
+                    simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                    ..Default::default()
+                },
+                MatchTable {
+                    table_id: 4,
+                    match_table_type: MatchTableType::Simple,
+                    wordlist: VarZeroVec::new(),
+                    exemption_wordlist: VarZeroVec::from(&["世界"]),
+                    simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                    ..Default::default()
+                },
+            ],
+        ),
+    ]);
+    let matcher = Matcher::new(&match_table_dict);
+
+    let result_dict = matcher.word_match_filtered("你好世界", |_, table_id, _| {
+        table_id == 1 || table_id == 3
+    });
+    assert_eq!(
+        r#"[{"table_id":1,"word":"你好"}]"#,
+        result_dict.get("test").unwrap()
+    );
+    // 豁免词不受过滤影响
+    assert!(!result_dict.contains_key("exemption"));
+    assert!(matcher
+        .word_match_filtered("你好世界", |_, _, _| false)
+        .is_empty());
+
+    // 各类词表的word_id均为词在词表中的下标，而不是simple词的全局词ID
+    let match_table_dict = AHashMap::from([(
+        "test",
+        vec![
+            MatchTable {
+                table_id: 4,
+                match_table_type: MatchTableType::Simple,
+                wordlist: VarZeroVec::from(&["foo", "bar"]),
+                ..Default::default()
+            },
+            MatchTable {
+                table_id: 1,
+                match_table_type: MatchTableType::Simple,
+                wordlist: VarZeroVec::from(&["你好", "世界"]),
+                ..Default::default()
+            },
+            MatchTable {
+                table_id: 2,
+                match_table_type: MatchTableType::Regex,
+                wordlist: VarZeroVec::from(&["你.", "世."]),
+                ..Default::default()
+            },
+            MatchTable {
+                table_id: 3,
+                match_table_type: MatchTableType::SimilarTextLevenshtein,
+                wordlist: VarZeroVec::from(&["hello", "world"]),
+                ..Default::default()
+            },
+        ],
+    )]);
+    let matcher = Matcher::new(&match_table_dict);
+    for text in ["你好", "世界", "hello", "world"] {
+        assert!(matcher.is_match(text), "{text}");
+    }
+    let result_dict = matcher.word_match_filtered("世界", |_, _, word_id| word_id == 1);
+    let result_list: Vec<serde_json::Value> = serde_json::from_str(&result_dict["test"]).unwrap();
+    assert_eq!(2, result_list.len());
+    assert!(matcher
+        .word_match_filtered("你好", |_, _, word_id| word_id == 1)
+        .is_empty());
+    assert!(matcher
+        .word_match_filtered("hello", |_, _, word_id| word_id == 1)
+        .is_empty());
+    assert!(!matcher
+        .word_match_filtered("world", |_, _, word_id| word_id == 1)
+        .is_empty());
+}
+
+#[test]
+fn acrostic_nth_match() {
+    let acrostic_wordlist = VarZeroVec::from(&["真,棒,事"]);
+    let regex_table_list = vec![RegexTable {
+        table_id: 1,
+        match_id: "1",
+        match_table_type: &MatchTableType::Acrostic,
+        wordlist: &acrostic_wordlist,
+        acrostic_position: AcrosticPosition::Nth(2),
+        prefer_matched_text: false,
+        regex_flags: RegexFlags::default(),
+        acrostic_separator: AcrosticSeparator::Punctuation,
+    }];
+    let regex_matcher = RegexMatcher::new(&regex_table_list);
+
+    assert_eq!(
+        "真,棒,事[2,3,4]",
+        regex_matcher.process("今天你好，我真的累，好棒的呀，没事啦，晚安了")[0].word
+    );
+    assert!(!regex_matcher.is_match("今天你好，我真的累，没事啦，好棒的呀，晚安了"));
+}
+
+#[test]
+fn empty_wordlist_table() {
+    let match_table_dict = AHashMap::from([
+        (
+            "test",
+            vec![MatchTable {
+                table_id: 1,
+                match_table_type: MatchTableType::Simple,
+                wordlist: VarZeroVec::from(&["你好"]),
+                simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                ..Default::default()
+            }],
+        ),
+        (
+            "empty",
+            vec![MatchTable {
+                table_id: 2,
+                match_table_type: MatchTableType::Simple,
+                wordlist: VarZeroVec::new(),
+                exemption_wordlist: VarZeroVec::from(&["你好"]),
+                simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                ..Default::default()
+            }],
+        ),
+    ]);
+    let (matcher, warning_list) = Matcher::try_new(&match_table_dict).unwrap();
+
+    assert!(warning_list.contains(&MatcherWarning::EmptyWordlist {
+        match_id: "empty".to_owned(),
+        table_id: 2,
+    }));
+
+    let result_dict = matcher.word_match("你好");
+    assert!(result_dict.contains_key("test"));
+    assert!(!result_dict.contains_key("empty"));
+
+    // 空词表的豁免词仍作用于同match_id下的其他词表
+    let mut match_table_dict = AHashMap::from([(
+        "test",
+        vec![
+            MatchTable {
+                table_id: 1,
+                match_table_type: MatchTableType::Simple,
+                wordlist: VarZeroVec::from(&["你好"]),
+                simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                ..Default::default()
+            },
+            MatchTable {
+                table_id: 2,
+                match_table_type: MatchTableType::Simple,
+                wordlist: VarZeroVec::new(),
+                exemption_wordlist: VarZeroVec::from(&["你好呀"]),
+                simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                ..Default::default()
+            },
+        ],
+    )]);
+    let matcher = Matcher::new(&match_table_dict);
+    assert!(matcher.word_match("你好").contains_key("test"));
+    assert!(matcher.word_match("你好呀").is_empty());
+
+    // Reject时空词表返回错误，只含豁免词的词表不视为空词表
+    match_table_dict.get_mut("test").unwrap()[1].exemption_wordlist = VarZeroVec::new();
+    assert_eq!(
+        Matcher::try_new_with_options(
+            &match_table_dict,
+            MatcherOptions {
+                empty_wordlist_policy: EmptyWordlistPolicy::Reject,
+                ..Default::default()
+            },
+        )
+        .err(),
+        Some(MatcherError::EmptyWordlist {
+            match_id: "test".to_owned(),
+            table_id: 2,
+        })
+    );
+}
+
+#[test]
+fn variant_strategy() {
+    let simple_wordlist_dict = AHashMap::from([(
+        SimpleMatchType::DeleteNormalize,
+        vec![SimpleWord {
+            word_id: 1,
+            word: "+V",
+        }],
+    )]);
+    let simple_matcher = SimpleMatcher::new(&simple_wordlist_dict);
+    let simple_matcher_final_only = SimpleMatcher::new_with_options(
+        &simple_wordlist_dict,
+        SimpleMatcherOptions {
+            variant_strategy: VariantStrategy::FinalOnly,
+            ..Default::default()
+        },
+    );
+
+    // "+" 只存在于删除归一前的文本中
+    assert!(simple_matcher.is_match("加+V"));
+    assert!(!simple_matcher_final_only.is_match("加+V"));
+}
+
+#[cfg(feature = "arc-swap")]
+#[test]
+fn matcher_handle_swap() {
+    let match_table_dict_1 = AHashMap::from([(
+        "test",
+        vec![MatchTable {
+            table_id: 1,
+            match_table_type: MatchTableType::Simple,
+            wordlist: VarZeroVec::from(&["你好"]),
+            simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+            ..Default::default()
+        }],
+    )]);
+    let match_table_dict_2 = AHashMap::from([(
+        "test",
+        vec![MatchTable {
+            table_id: 1,
+            match_table_type: MatchTableType::Simple,
+            wordlist: VarZeroVec::from(&["世界"]),
+            simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+            ..Default::default()
+        }],
+    )]);
+    let matcher_handle = MatcherHandle::new(Matcher::new(&match_table_dict_1));
+
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for _ in 0..1000 {
+                    let matcher = matcher_handle.load();
+                    assert!(matcher.is_match("你好") != matcher.is_match("世界"));
+                }
+            });
+        }
+
+        for index in 0..20 {
+            if index % 2 == 0 {
+                matcher_handle.swap(Matcher::new(&match_table_dict_2));
+            } else {
+                matcher_handle.swap(Matcher::new(&match_table_dict_1));
+            }
+        }
+    });
+
+    matcher_handle.rebuild_from(&match_table_dict_2).unwrap();
+    assert!(matcher_handle.load().is_match("世界"));
+    assert!(!matcher_handle.load().is_match("你好"));
+}
+
+#[test]
+fn simple_match_detailed() {
+    let simple_wordlist_dict = AHashMap::from([(
+        SimpleMatchType::None,
+        vec![SimpleWord {
+            word_id: 1,
+            word: "你,好,呀",
+        }],
+    )]);
+    let simple_matcher = SimpleMatcher::new_with_options(
+        &simple_wordlist_dict,
+        SimpleMatcherOptions {
+            detailed: true,
+            ..Default::default()
+        },
+    );
+
+    let result_list = simple_matcher.process("你你好呀呀呀");
+    let part_count_dict: AHashMap<&str, u32> = result_list[0]
+        .parts
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|part_hit| (part_hit.part.as_ref(), part_hit.count))
+        .collect();
+    assert_eq!(
+        AHashMap::from([("你", 2), ("好", 1), ("呀", 3)]),
+        part_count_dict
+    );
+
+    assert!(
+        SimpleMatcher::new(&simple_wordlist_dict).process("你你好呀呀呀")[0]
+            .parts
+            .is_none()
+    );
+}
+
+#[test]
+fn word_match_raw() {
+    let match_table_dict = AHashMap::from([(
+        "test",
+        vec![MatchTable {
+            table_id: 1,
+            match_table_type: MatchTableType::Simple,
+            wordlist: VarZeroVec::from(&["你好"]),
+            exemption_wordlist: VarZeroVec::from(&["世界"]),
+            simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+            ..Default::default()
+        }],
+    )]);
+    let matcher = Matcher::new(&match_table_dict);
+
+    assert!(matcher.word_match("你好世界").is_empty());
+    // 豁免词本身不出现在结果中
+    assert_eq!(
+        r#"[{"table_id":1,"word":"你好"}]"#,
+        matcher.word_match_raw("你好世界").get("test").unwrap()
+    );
+}
+
+#[test]
+fn process_type_tree_differential() {
+    // 所有匹配类型共用一棵转换树的结果，应与每个匹配类型单独构建的结果一致
+    let simple_match_type_list: Vec<SimpleMatchType> = (0..16u8)
+        .map(|bits| {
+            let mut simple_match_type = SimpleMatchType::None;
+            for (index, str_conv_type) in [
+                SimpleMatchType::Fanjian,
+                SimpleMatchType::Delete,
+                SimpleMatchType::Normalize,
+                SimpleMatchType::PinYin,
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                if bits & (1 << index) != 0 {
+                    simple_match_type |= str_conv_type;
+                }
+            }
+            simple_match_type
+        })
+        .collect();
+
+    let simple_wordlist_dict = simple_match_type_list
+        .iter()
+        .enumerate()
+        .map(|(index, &simple_match_type)| {
+            (
+                simple_match_type,
+                vec![SimpleWord {
+                    word_id: index as u64,
+                    word: "你好,世界",
+                }],
+            )
+        })
+        .collect::<SimpleWordlistDict>();
+    let simple_matcher = SimpleMatcher::new(&simple_wordlist_dict);
+    assert_eq!(
+        16,
+        simple_matcher
+            .process_type_tree()
+            .simple_match_type_list()
+            .count()
+    );
+
+    let single_simple_matcher_list: Vec<SimpleMatcher> = simple_match_type_list
+        .iter()
+        .enumerate()
+        .map(|(index, &simple_match_type)| {
+            SimpleMatcher::new(&AHashMap::from([(
+                simple_match_type,
+                vec![SimpleWord {
+                    word_id: index as u64,
+                    word: "你好,世界",
+                }],
+            )]))
+        })
+        .collect();
+
+    for text in ["你好，世界", "妳好 世 界", "你号视界", "ni好世jie", "你好"] {
+        let word_id_set: AHashSet<u64> = simple_matcher
+            .process(text)
+            .iter()
+            .map(|simple_result| simple_result.word_id)
+            .collect();
+
+        for (index, single_simple_matcher) in single_simple_matcher_list.iter().enumerate() {
+            assert_eq!(
+                single_simple_matcher.is_match(text),
+                word_id_set.contains(&(index as u64)),
+                "text: {text}, simple_match_type: {:?}",
+                simple_match_type_list[index]
+            );
+        }
+    }
+}
+
+#[test]
+fn validate_simple_match_type() {
+    let conflicting_match_table_dict = AHashMap::from([(
+        "test",
+        vec![MatchTable {
+            table_id: 1,
+            match_table_type: MatchTableType::Simple,
+            wordlist: VarZeroVec::from(&["你好"]),
+            simple_match_type: SimpleMatchType::PinYin | SimpleMatchType::PinYinChar,
+            ..Default::default()
+        }],
+    )]);
+    assert!(matches!(
+        Matcher::try_new(&conflicting_match_table_dict),
+        Err(MatcherError::ConflictingSimpleMatchType { table_id: 1, .. })
+    ));
+
+    let redundant_match_table_dict = AHashMap::from([(
+        "test",
+        vec![
+            MatchTable {
+                table_id: 1,
+                match_table_type: MatchTableType::Simple,
+                wordlist: VarZeroVec::from(&["你好"]),
+                simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                ..Default::default()
+            },
+            MatchTable {
+                table_id: 2,
+                match_table_type: MatchTableType::Simple,
+                wordlist: VarZeroVec::from(&["世界"]),
+                simple_match_type: SimpleMatchType::Fanjian,
+                ..Default::default()
+            },
+        ],
+    )]);
+    assert_eq!(
+        vec![MatcherWarning::RedundantSimpleMatchType {
+            match_id: "test".to_owned(),
+            table_id: 2,
+            covered_by_table_id: 1,
+        }],
+        Matcher::validate(&redundant_match_table_dict).unwrap()
+    );
+
+    assert_eq!(
+        SimpleMatchType::Delete,
+        SimpleMatchType::WordDelete.canonicalize()
+    );
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn simple_match_mmap() {
+    let simple_wordlist_dict = AHashMap::from([(
+        SimpleMatchType::FanjianDeleteNormalize,
+        vec![
+            SimpleWord {
+                word_id: 1,
+                word: "你好,世界",
+            },
+            SimpleWord {
+                word_id: 2,
+                word: "学生",
+            },
+        ],
+    )]);
+    let path = std::env::temp_dir().join("matcher_rs_simple_match_mmap.json");
+    std::fs::write(&path, serde_json::to_string(&simple_wordlist_dict).unwrap()).unwrap();
+
+    let simple_matcher = SimpleMatcher::new(&simple_wordlist_dict);
+    let mmap_simple_matcher =
+        SimpleMatcher::open_mmap(&path, SimpleMatcherOptions::default()).unwrap();
+
+    for text in ["你好，世界", "學生", "你好學生世界", "无关文本"] {
+        let mut result_list = simple_matcher.process(text);
+        let mut mmap_result_list = mmap_simple_matcher.process(text);
+        result_list.sort_by_key(|simple_result| simple_result.word_id);
+        mmap_result_list.sort_by_key(|simple_result| simple_result.word_id);
+
+        assert_eq!(
+            serde_json::to_string(&result_list).unwrap(),
+            serde_json::to_string(&mmap_result_list).unwrap()
+        );
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn build_report() {
+    let wordlist: Vec<String> = (0..1000).map(|index| format!("词{index}")).collect();
+    let match_table_dict = AHashMap::from([
+        (
+            "test",
+            vec![
+                MatchTable {
+                    table_id: 1,
+                    match_table_type: MatchTableType::Simple,
+                    wordlist: VarZeroVec::from(&wordlist),
+                    exemption_wordlist: VarZeroVec::from(&["豁免"]),
+                    simple_match_type: SimpleMatchType::FanjianDeleteNormalize
+                        | SimpleMatchType::PinYin,
+                    ..Default::default()
+                },
+                MatchTable {
+                    table_id: 2,
+                    match_table_type: MatchTableType::Regex,
+                    wordlist: VarZeroVec::from(&["^foo"]),
+                    ..Default::default()
+                },
+            ],
+        ),
+        (
+            "sim",
+            vec![MatchTable {
+                table_id: 3,
+                match_table_type: MatchTableType::SimilarTextLevenshtein,
+                wordlist: VarZeroVec::from(&["你真是太棒了"]),
+                ..Default::default()
+            }],
+        ),
+    ]);
+    let (_, _, build_report) =
+        Matcher::try_new_with_report(&match_table_dict, MatcherOptions::default()).unwrap();
+
+    assert_eq!(2, build_report.match_id_count);
+    assert_eq!(3, build_report.table_count);
+    assert_eq!(1000, build_report.simple_word_count);
+    assert_eq!(1, build_report.exemption_word_count);
+    assert_eq!(1, build_report.regex_table_count);
+    assert_eq!(1, build_report.sim_table_count);
+    assert!(build_report.ac_pattern_count >= 1001);
+    assert!(build_report.variant_expansion_duration > std::time::Duration::ZERO);
+    assert!(build_report.ac_build_duration > std::time::Duration::ZERO);
+}
+
+#[test]
+fn regex_set_fallback() {
+    let regex_wordlist = VarZeroVec::from(&["^foo", r"(?<!\d)1[3-9]\d{9}(?!\d)", "("]);
+    let regex_table_list = vec![RegexTable {
+        table_id: 1,
+        match_id: "1",
+        match_table_type: &MatchTableType::Regex,
+        wordlist: &regex_wordlist,
+        acrostic_position: AcrosticPosition::Initial,
+        prefer_matched_text: false,
+        regex_flags: RegexFlags::default(),
+        acrostic_separator: AcrosticSeparator::Punctuation,
+    }];
+
+    let regex_matcher = RegexMatcher::new_with_options(
+        &regex_table_list,
+        RegexMatcherOptions {
+            split_regex_table: false,
+            ..Default::default()
+        },
+    );
+    let table_stats = &regex_matcher.stats().table_stats_list[0];
+    assert_eq!(0, table_stats.set_pattern_count);
+    assert_eq!(2, table_stats.list_pattern_count);
+    assert_eq!(
+        r"(?<!\d)1[3-9]\d{9}(?!\d)",
+        table_stats.fallback_pattern_list[0].0
+    );
+    assert_eq!(1, table_stats.invalid_pattern_list.len());
+    assert_eq!("(", table_stats.invalid_pattern_list[0].0);
+
+    // 无法编译的正则作为告警返回，附带fancy_regex的编译错误
+    let (_, warning_list) = RegexMatcher::try_new(&regex_table_list).unwrap();
+    assert_eq!(1, warning_list.len());
+    assert_eq!(1, warning_list[0].table_id);
+    assert_eq!("(", warning_list[0].pattern);
+    assert_eq!(
+        table_stats.invalid_pattern_list[0].1.to_string(),
+        warning_list[0].error
+    );
+    assert!(!warning_list[0].error.is_empty());
+
+    let split_regex_matcher = RegexMatcher::new(&regex_table_list);
+    let table_stats = &split_regex_matcher.stats().table_stats_list[0];
+    assert_eq!(1, table_stats.set_pattern_count);
+    assert_eq!(1, table_stats.list_pattern_count);
+
+    // 词ID始终为词在词表中的下标，不受无效正则以及拆分的影响
+    for regex_matcher in [&regex_matcher, &split_regex_matcher] {
+        let mut word_id_list: Vec<u64> = regex_matcher
+            .process("foo 15651781111")
+            .iter()
+            .map(|regex_result| regex_result.word_id)
+            .collect();
+        word_id_list.sort_unstable();
+        assert_eq!(vec![0, 1], word_id_list);
+    }
+
+    let match_table_dict = AHashMap::from([(
+        "test",
+        vec![MatchTable {
+            table_id: 1,
+            match_table_type: MatchTableType::Regex,
+            wordlist: VarZeroVec::from(&["^foo", r"(?<!\d)1[3-9]\d{9}(?!\d)"]),
+            ..Default::default()
+        }],
+    )]);
+    let (_, warning_list) = Matcher::try_new(&match_table_dict).unwrap();
+    assert!(warning_list.contains(&MatcherWarning::RegexSetFallback {
+        match_id: "test".to_owned(),
+        table_id: 1,
+        pattern_list: vec![r"(?<!\d)1[3-9]\d{9}(?!\d)".to_owned()],
+    }));
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn simple_par_process() {
+    let simple_wordlist_dict = AHashMap::from([
+        (
+            SimpleMatchType::FanjianDeleteNormalize,
+            vec![
+                SimpleWord {
+                    word_id: 1,
+                    word: "你真好,123",
+                },
+                SimpleWord {
+                    word_id: 2,
+                    word: "无,法,无,天",
+                },
+            ],
+        ),
+        (
+            SimpleMatchType::FanjianDeleteNormalize | SimpleMatchType::PinYin,
+            vec![SimpleWord {
+                word_id: 3,
+                word: "你好",
+            }],
+        ),
+    ]);
+    let simple_matcher = SimpleMatcher::new(&simple_wordlist_dict);
+    let long_text = "你真好，１２３ 无法无天 nihao".repeat(100);
+
+    for text in ["你真好,123", "無法无天，你号", "无法天", long_text.as_str()] {
+        let mut word_id_list: Vec<u64> = simple_matcher
+            .process(text)
+            .iter()
+            .map(|simple_result| simple_result.word_id)
+            .collect();
+        word_id_list.sort_unstable();
+        let mut par_word_id_list: Vec<u64> = simple_matcher
+            .par_process(text)
+            .iter()
+            .map(|simple_result| simple_result.word_id)
+            .collect();
+        par_word_id_list.sort_unstable();
+
+        assert_eq!(word_id_list, par_word_id_list, "text: {text}");
+    }
+}
+
+#[test]
+fn over_limit_policy() {
+    let match_table_dict = AHashMap::from([(
+        "test",
+        vec![MatchTable {
+            table_id: 1,
+            match_table_type: MatchTableType::Simple,
+            wordlist: VarZeroVec::from(&["你好世界"]),
+            simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+            ..Default::default()
+        }],
+    )]);
+    // 8个字符共24字节，"你好世界" 位于第6到18字节，跨越了第一个12字节的窗口
+    let text = "啊啊你好世界啊啊";
+    let new_matcher = |over_limit_policy| {
+        Matcher::new_with_options(
+            &match_table_dict,
+            MatcherOptions {
+                max_input_bytes: Some(12),
+                over_limit_policy,
+                ..Default::default()
+            },
+        )
+    };
+
+    let truncate_matcher = new_matcher(OverLimitPolicy::Truncate);
+    assert!(truncate_matcher.word_match(text).is_empty());
+    assert!(!truncate_matcher.is_match(text));
+
+    let chunk_matcher = new_matcher(OverLimitPolicy::ChunkAndUnion { overlap_bytes: 6 });
+    assert_eq!(
+        r#"[{"table_id":1,"word":"你好世界"}]"#,
+        chunk_matcher.word_match(text).get("test").unwrap()
+    );
+    assert!(chunk_matcher.is_match(text));
+
+    let reject_matcher = new_matcher(OverLimitPolicy::Reject);
+    assert_eq!(
+        Err(MatcherError::InputTooLong {
+            text_bytes: 24,
+            max_input_bytes: 12,
+        }),
+        reject_matcher.try_word_match(text)
+    );
+    assert!(reject_matcher.word_match(text).is_empty());
+    assert!(reject_matcher.try_word_match("你好世界").is_ok());
+}
+
+#[test]
+fn chunk_and_union_overlap() {
+    let match_table_dict = AHashMap::from([(
+        "test",
+        vec![MatchTable {
+            table_id: 1,
+            match_table_type: MatchTableType::Simple,
+            wordlist: VarZeroVec::from(&["你好"]),
+            ..Default::default()
+        }],
+    )]);
+    let chunk_options = |overlap_bytes| MatcherOptions {
+        max_input_bytes: Some(12),
+        over_limit_policy: OverLimitPolicy::ChunkAndUnion { overlap_bytes },
+        ..Default::default()
+    };
+
+    // 重叠区不小于窗口时窗口每次只能前进一个字符，构建时即拒绝
+    for overlap_bytes in [12, 100] {
+        assert!(matches!(
+            Matcher::try_new_with_options(&match_table_dict, chunk_options(overlap_bytes)),
+            Err(MatcherError::InvalidOverlap {
+                max_input_bytes: 12,
+                ..
+            })
+        ));
+    }
+    assert!(Matcher::try_new_with_options(&match_table_dict, chunk_options(11)).is_ok());
+    // 不限制长度或不分块时不校验
+    assert!(Matcher::try_new_with_options(
+        &match_table_dict,
+        MatcherOptions {
+            over_limit_policy: OverLimitPolicy::ChunkAndUnion { overlap_bytes: 100 },
+            ..Default::default()
+        }
+    )
+    .is_ok());
+}
+
+#[test]
+fn chunk_and_union_dedup() {
+    let match_table_dict = AHashMap::from([(
+        "test",
+        vec![
+            MatchTable {
+                table_id: 1,
+                match_table_type: MatchTableType::SimilarChar,
+                wordlist: VarZeroVec::from(&["你,妳", "好,号"]),
+                ..Default::default()
+            },
+            MatchTable {
+                table_id: 2,
+                match_table_type: MatchTableType::Simple,
+                wordlist: VarZeroVec::from(&["你好", "你好"]),
+                ..Default::default()
+            },
+        ],
+    )]);
+    let result_list = |matcher: &Matcher, text: &str| -> Vec<(u64, String, usize)> {
+        let mut result_list: Vec<(u64, String, usize)> =
+            serde_json::from_str::<Vec<serde_json::Value>>(&matcher.word_match(text)["test"])
+                .unwrap()
+                .iter()
+                .map(|match_result| {
+                    (
+                        match_result["table_id"].as_u64().unwrap(),
+                        match_result["word"].as_str().unwrap().to_owned(),
+                        match_result["start"].as_u64().unwrap() as usize,
+                    )
+                })
+                .collect();
+        result_list.sort_unstable();
+        result_list
+    };
+    let new_matcher = |max_input_bytes| {
+        Matcher::new_with_options(
+            &match_table_dict,
+            MatcherOptions {
+                max_input_bytes,
+                over_limit_policy: OverLimitPolicy::ChunkAndUnion { overlap_bytes: 6 },
+                dedup_words: DedupPolicy::KeepAll,
+                report_span: true,
+                ..Default::default()
+            },
+        )
+    };
+    let matcher = new_matcher(None);
+    let chunk_matcher = new_matcher(Some(15));
+
+    // 15字节的窗口重叠6字节，即 [0, 15) [9, 24) [18, 30)
+    // 两次邻近字命中分别只在第一个和最后一个窗口中，不在重叠区内，均保留；重复的simple词各自保留
+    let text = "你好啊啊啊啊啊啊你好啊";
+    let unchunked_result_list = result_list(&matcher, text);
+    assert_eq!(
+        2,
+        unchunked_result_list
+            .iter()
+            .filter(|(table_id, ..)| *table_id == 1)
+            .count()
+    );
+    assert_eq!(unchunked_result_list.len(), 4);
+    // 分块后simple词的命中位置为各词所在窗口中最后完成命中的位置，只比较邻近字的命中位置
+    let chunk_result_list = result_list(&chunk_matcher, text);
+    assert_eq!(
+        unchunked_result_list
+            .iter()
+            .filter(|(table_id, ..)| *table_id == 1)
+            .collect::<Vec<_>>(),
+        chunk_result_list
+            .iter()
+            .filter(|(table_id, ..)| *table_id == 1)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(chunk_result_list.len(), 4);
+
+    // 位于重叠区内的命中在相邻两个窗口中各产生一次，只保留一个
+    let text = "啊啊啊你好啊啊啊";
+    assert_eq!(
+        vec![(1, "你好".to_owned(), 9)],
+        result_list(&chunk_matcher, text)
+            .into_iter()
+            .filter(|(table_id, ..)| *table_id == 1)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        result_list(&matcher, text),
+        result_list(&chunk_matcher, text)
+    );
+}
+
+#[test]
+fn word_match_to_writer() {
+    let match_table_dict = AHashMap::from([(
+        "test",
+        vec![MatchTable {
+            table_id: 1,
+            match_table_type: MatchTableType::Simple,
+            wordlist: VarZeroVec::from(&["你好", "世界"]),
+            simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+            ..Default::default()
+        }],
+    )]);
+    let matcher = Matcher::new(&match_table_dict);
+
+    let mut buf = Vec::new();
+    matcher.word_match_to_writer("你好世界", &mut buf).unwrap();
+    assert_eq!(
+        matcher.word_match_as_string("你好世界"),
+        String::from_utf8(buf).unwrap()
+    );
+
+    let simple_matcher = SimpleMatcher::new(&AHashMap::from([(
+        SimpleMatchType::FanjianDeleteNormalize,
+        vec![SimpleWord {
+            word_id: 1,
+            word: "你好",
+        }],
+    )]));
+
+    let mut buf = Vec::new();
+    simple_matcher.process_to_writer("你好", &mut buf).unwrap();
+    assert_eq!(
+        serde_json::to_string(&simple_matcher.process("你好")).unwrap(),
+        String::from_utf8(buf).unwrap()
+    );
+}
+
+#[test]
+fn normalize_result_case() {
+    let simple_wordlist_dict = AHashMap::from([(
+        SimpleMatchType::None,
+        vec![SimpleWord {
+            word_id: 1,
+            word: "FREE",
+        }],
+    )]);
+
+    for (normalize_result_case, word) in [
+        (None, "FREE"),
+        (Some(CaseMode::Lower), "free"),
+        (Some(CaseMode::Upper), "FREE"),
+    ] {
+        let simple_matcher = SimpleMatcher::new_with_options(
+            &simple_wordlist_dict,
+            SimpleMatcherOptions {
+                normalize_result_case,
+                ..Default::default()
+            },
+        );
+        assert_eq!(word, simple_matcher.process("get it free now")[0].word);
+    }
+}
+
+#[test]
+fn pinyin_dense_replacement() {
+    let simple_matcher = SimpleMatcher::new(&AHashMap::from([(
+        SimpleMatchType::PinYin,
+        vec![SimpleWord {
+            word_id: 1,
+            word: "你好",
+        }],
+    )]));
+    let traditional_text = "繁體中文測試數據".repeat(1000);
+
+    // 几乎每个字符都被替换时，转换结果仍保持正确
+    assert!(!simple_matcher.is_match(&traditional_text));
+    assert!(simple_matcher.is_match(&format!("{traditional_text}你号{traditional_text}")));
+}
+
+#[test]
+fn simple_match_type_from_str() {
+    for bits in 0..64u8 {
+        let simple_match_type = SimpleMatchType::from_bits_retain(bits);
+        assert_eq!(
+            simple_match_type,
+            simple_match_type
+                .to_string()
+                .parse::<SimpleMatchType>()
+                .unwrap()
+        );
+    }
+
+    assert_eq!(
+        SimpleMatchType::FanjianDeleteNormalize,
+        "fanjian_delete_normalize".parse().unwrap()
+    );
+    assert_eq!(
+        SimpleMatchType::None,
+        "none".parse::<SimpleMatchType>().unwrap()
+    );
+    assert!("fanjian_foo".parse::<SimpleMatchType>().is_err());
+
+    assert_eq!(
+        SimpleMatchType::PinYinChar,
+        serde_json::from_str::<SimpleMatchType>(r#""pinyin_char""#).unwrap()
+    );
+    assert_eq!(
+        SimpleMatchType::FanjianDeleteNormalize,
+        serde_json::from_str::<SimpleMatchType>("15").unwrap()
+    );
+}
+
+#[test]
+fn inherit_simple_match_type_for_exemptions() {
+    let match_table_dict = AHashMap::from([(
+        "test",
+        vec![MatchTable {
+            table_id: 1,
+            match_table_type: MatchTableType::Simple,
+            wordlist: VarZeroVec::from(&["你好"]),
+            exemption_wordlist: VarZeroVec::from(&["世界"]),
+            simple_match_type: SimpleMatchType::FanjianDeleteNormalize | SimpleMatchType::PinYin,
+            ..Default::default()
+        }],
+    )]);
+
+    // 豁免词默认不按拼音匹配，同音的 "视界" 无法豁免
+    let (matcher, warning_list) = Matcher::try_new(&match_table_dict).unwrap();
+    assert!(
+        warning_list.contains(&MatcherWarning::ExemptionSimpleMatchTypeNotCovered {
+            match_id: "test".to_owned(),
+            table_id: 1,
+            simple_match_type: SimpleMatchType::PinYin,
+        })
+    );
+    assert!(!matcher.word_match("你好视界").is_empty());
+    assert!(matcher.word_match("你好世界").is_empty());
+
+    let (matcher, warning_list) = Matcher::try_new_with_options(
+        &match_table_dict,
+        MatcherOptions {
+            inherit_simple_match_type_for_exemptions: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert!(warning_list.is_empty());
+    assert!(matcher.word_match("你好视界").is_empty());
+}
+
+#[test]
+fn matcher_builder() {
+    let matcher_builder = MatcherBuilder::new()
+        .match_id("test", |match_id_builder| {
+            match_id_builder
+                .simple_table(1, SimpleMatchType::FanjianDeleteNormalize, ["无,法,无,天"])
+                .simple_table(
+                    2,
+                    SimpleMatchType::FanjianDeleteNormalize | SimpleMatchType::PinYin,
+                    ["你好"],
+                )
+                .exemptions(["你好世界"])
+        })
+        .match_id(1, |match_id_builder| {
+            match_id_builder.regex_table(3, MatchTableType::Regex, ["^foo"])
+        });
+    let (matcher, _) = matcher_builder.build().unwrap();
+
+    assert_eq!(
+        r#"[{"table_id":1,"word":"无,法,无,天"}]"#,
+        matcher.word_match("无法无天").get("test").unwrap()
+    );
+    assert!(matcher.word_match("无法天").is_empty());
+    assert!(!matcher.word_match("你豪").is_empty());
+    assert!(matcher.word_match("你好世界").is_empty());
+    assert!(matcher.word_match("foo").contains_key("1"));
+
+    let json = matcher_builder.to_json();
+    let match_table_dict: MatchTableDict = serde_json::from_str(&json).unwrap();
+    assert_eq!(2, match_table_dict.get("test").unwrap().len());
+    assert!(Matcher::new(&match_table_dict).is_match("foo"));
+}
+
+#[cfg(feature = "unicode-segmentation")]
+#[test]
+fn grapheme_boundary() {
+    // 藏头位置按字素簇计数，家庭emoji及韩文字母组合各算一个字
+    let acrostic_wordlist = VarZeroVec::from(&["真,棒"]);
+    let regex_table_list = vec![RegexTable {
+        table_id: 1,
+        match_id: "1",
+        match_table_type: &MatchTableType::Acrostic,
+        wordlist: &acrostic_wordlist,
+        acrostic_position: AcrosticPosition::Nth(2),
+        prefer_matched_text: false,
+        regex_flags: RegexFlags::default(),
+        acrostic_separator: AcrosticSeparator::Punctuation,
+    }];
+    let regex_matcher = RegexMatcher::new(&regex_table_list);
+    assert_eq!(
+        "真,棒[1,2]",
+        regex_matcher.process("👨\u{200d}👩\u{200d}👧\u{200d}👦真的，\u{1112}\u{1161}\u{11ab}棒了")
+            [0]
+        .word
+    );
+
+    // 截断时不拆开 "한"（3个韩文字母共9字节），不会留下半个音节
+    let match_table_dict = AHashMap::from([(
+        "test",
+        vec![MatchTable {
+            table_id: 1,
+            match_table_type: MatchTableType::Simple,
+            wordlist: VarZeroVec::from(&["\u{1112}\u{1161}"]),
+            ..Default::default()
+        }],
+    )]);
+    let matcher = Matcher::new_with_options(
+        &match_table_dict,
+        MatcherOptions {
+            max_input_bytes: Some(9),
+            over_limit_policy: OverLimitPolicy::Truncate,
+            ..Default::default()
+        },
+    );
+    assert!(!matcher.is_match("你\u{1112}\u{1161}\u{11ab}"));
+    assert!(matcher.is_match("\u{1112}\u{1161}\u{11ab}你"));
+}
+
+#[test]
+fn processed_text_shared() {
+    let simple_wordlist_dict = AHashMap::from([
+        (
+            SimpleMatchType::FanjianDeleteNormalize,
+            vec![SimpleWord {
+                word_id: 1,
+                word: "无,法,无,天",
+            }],
+        ),
+        (
+            SimpleMatchType::FanjianDeleteNormalize | SimpleMatchType::PinYin,
+            vec![SimpleWord {
+                word_id: 2,
+                word: "你好",
+            }],
+        ),
+    ]);
+    let simple_matcher = SimpleMatcher::new(&simple_wordlist_dict);
+    // 匹配类型集合相同，转换树一致，变体可以直接复用
+    let other_simple_matcher = SimpleMatcher::new(&AHashMap::from([
+        (
+            SimpleMatchType::FanjianDeleteNormalize | SimpleMatchType::PinYin,
+            vec![SimpleWord {
+                word_id: 1,
+                word: "世界",
+            }],
+        ),
+        (
+            SimpleMatchType::FanjianDeleteNormalize,
+            vec![SimpleWord {
+                word_id: 2,
+                word: "学生",
+            }],
+        ),
+    ]));
+    let regex_wordlist = VarZeroVec::from(&["^foo"]);
+    let regex_matcher = RegexMatcher::new(&vec![RegexTable {
+        table_id: 1,
+        match_id: "1",
+        match_table_type: &MatchTableType::Regex,
+        wordlist: &regex_wordlist,
+        acrostic_position: AcrosticPosition::Initial,
+        prefer_matched_text: false,
+        regex_flags: RegexFlags::default(),
+        acrostic_separator: AcrosticSeparator::Punctuation,
+    }]);
+
+    let text = "foo 無法無天，你号视界學生";
+    let processed_text = simple_matcher.preprocess(text);
+    assert_eq!(text, processed_text.text());
+    assert!(processed_text.variant_count() > 1);
+
+    for (simple_matcher, word_id_list) in [
+        (&simple_matcher, vec![1, 2]),
+        (&other_simple_matcher, vec![1, 2]),
+    ] {
+        let mut processed_word_id_list: Vec<u64> = simple_matcher
+            .process_processed(&processed_text)
+            .iter()
+            .map(|simple_result| simple_result.word_id)
+            .collect();
+        processed_word_id_list.sort_unstable();
+        assert_eq!(word_id_list, processed_word_id_list);
+    }
+    assert!(regex_matcher.is_match_processed(&processed_text));
+    assert_eq!(1, regex_matcher.process_processed(&processed_text).len());
+
+    // 转换树不同的匹配器会重新生成变体，结果与直接匹配一致
+    let fanjian_simple_matcher = SimpleMatcher::new(&AHashMap::from([(
+        SimpleMatchType::Fanjian,
+        vec![SimpleWord {
+            word_id: 1,
+            word: "学生",
+        }],
+    )]));
+    assert!(fanjian_simple_matcher.is_match_processed(&processed_text));
+    assert!(!fanjian_simple_matcher.is_match_processed(&ProcessedText::from_text("学")));
+
+    let match_table_dict = AHashMap::from([(
+        "test",
+        vec![MatchTable {
+            table_id: 1,
+            match_table_type: MatchTableType::Simple,
+            wordlist: VarZeroVec::from(&["你好"]),
+            simple_match_type: SimpleMatchType::FanjianDeleteNormalize | SimpleMatchType::PinYin,
+            ..Default::default()
+        }],
+    )]);
+    let matcher = Matcher::new(&match_table_dict);
+    let processed_text_list = matcher.preprocess(text).unwrap();
+    assert!(matcher.is_match_processed(&processed_text_list));
+    assert_eq!(
+        matcher.word_match(text),
+        matcher.word_match_processed(&processed_text_list)
+    );
+}
+
+#[test]
+fn simple_prefilter_differential() {
+    let simple_wordlist_dict = AHashMap::from([
+        (
+            SimpleMatchType::FanjianDeleteNormalize,
+            vec![
+                SimpleWord {
+                    word_id: 1,
+                    word: "测试",
+                },
+                SimpleWord {
+                    word_id: 2,
+                    word: "无,法,无,天",
+                },
+                SimpleWord {
+                    word_id: 3,
+                    word: "free",
+                },
+                SimpleWord {
+                    word_id: 4,
+                    word: "你真好,123",
+                },
+            ],
+        ),
+        (
+            SimpleMatchType::FanjianDeleteNormalize | SimpleMatchType::PinYin,
+            vec![SimpleWord {
+                word_id: 5,
+                word: "西安",
+            }],
+        ),
+        (
+            SimpleMatchType::Fanjian,
+            vec![SimpleWord {
+                word_id: 6,
+                word: "學習",
+            }],
+        ),
+    ]);
+    let simple_matcher = SimpleMatcher::new(&simple_wordlist_dict);
+    let prefilter_simple_matcher = SimpleMatcher::new_with_options(
+        &simple_wordlist_dict,
+        SimpleMatcherOptions {
+            prefilter: true,
+            ..Default::default()
+        },
+    );
+
+    // 固定种子的线性同余生成器，从易触发转换的字符中随机拼接文本
+    let char_pool: Vec<char> =
+        "测試试無无法天天ＦｒＥeＥ你妳真眞好１①一2３西希安按學学習习 ，,.xianXIAN"
+            .chars()
+            .collect();
+    let mut seed: u64 = 42;
+    for _ in 0..2000 {
+        let mut text = String::new();
+        for _ in 0..8 {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            text.push(char_pool[(seed >> 33) as usize % char_pool.len()]);
+        }
+
+        let mut word_id_list: Vec<u64> = simple_matcher
+            .process(&text)
+            .iter()
+            .map(|simple_result| simple_result.word_id)
+            .collect();
+        word_id_list.sort_unstable();
+        let mut prefilter_word_id_list: Vec<u64> = prefilter_simple_matcher
+            .process(&text)
+            .iter()
+            .map(|simple_result| simple_result.word_id)
+            .collect();
+        prefilter_word_id_list.sort_unstable();
+
+        assert_eq!(word_id_list, prefilter_word_id_list, "text: {text}");
+    }
+
+    assert!(!prefilter_simple_matcher.is_candidate("他们在吃饭"));
+    assert!(prefilter_simple_matcher.is_candidate("測試"));
+    assert!(prefilter_simple_matcher.is_match("測試"));
+}
+
+#[test]
+fn process_dict_override() {
+    let simple_wordlist_dict = AHashMap::from([
+        (
+            SimpleMatchType::Fanjian,
+            vec![SimpleWord {
+                word_id: 1,
+                word: "测试",
+            }],
+        ),
+        (
+            SimpleMatchType::Normalize,
+            vec![SimpleWord {
+                word_id: 2,
+                word: "abc",
+            }],
+        ),
+    ]);
+
+    // 繁简沿用内置映射表，归一完全由覆盖的映射表生成
+    let simple_matcher = SimpleMatcher::new_with_options(
+        &simple_wordlist_dict,
+        SimpleMatcherOptions {
+            process_dict_override: AHashMap::from([(
+                SimpleMatchType::Normalize,
+                vec![("阿".to_owned(), "a".to_owned())],
+            )]),
+            ..Default::default()
+        },
+    );
+    assert!(simple_matcher.is_match("測試"));
+    assert!(simple_matcher.is_match("阿bc"));
+    assert!(!simple_matcher.is_match("ⓐbc"));
+
+    // 在内置映射表的基础上追加映射关系
+    let mut normalize_process_pair_list: Vec<(String, String)> =
+        SimpleMatcher::builtin_process_dict(SimpleMatchType::Normalize)
+            .into_iter()
+            .map(|(key, value)| (key.to_owned(), value.to_owned()))
+            .collect();
+    normalize_process_pair_list.push(("阿".to_owned(), "a".to_owned()));
+    let patched_simple_matcher = SimpleMatcher::new_with_options(
+        &simple_wordlist_dict,
+        SimpleMatcherOptions {
+            process_dict_override: AHashMap::from([(
+                SimpleMatchType::Normalize,
+                normalize_process_pair_list,
+            )]),
+            ..Default::default()
+        },
+    );
+    assert!(patched_simple_matcher.is_match("測試"));
+    assert!(patched_simple_matcher.is_match("阿bc"));
+    assert!(patched_simple_matcher.is_match("ⓐbc"));
+
+    // 使用内置映射表的匹配器生成的变体不能复用
+    let default_simple_matcher = SimpleMatcher::new(&simple_wordlist_dict);
+    assert!(!default_simple_matcher.is_match("阿bc"));
+    let processed_text = default_simple_matcher.preprocess("阿bc");
+    assert!(simple_matcher.is_match_processed(&processed_text));
+}
+
+#[test]
+fn concurrent_build_with_unknown_bits() {
+    // 每个匹配器各自构建转换ac自动机，不存在全局缓存，并发构建包含未定义bit的匹配类型时不应panic
+    let handle_list: Vec<std::thread::JoinHandle<()>> = (0..8u8)
+        .map(|bit| {
+            std::thread::spawn(move || {
+                let simple_match_type =
+                    SimpleMatchType::Fanjian | SimpleMatchType::from_bits_retain(1 << bit);
+                let simple_matcher = SimpleMatcher::new(&AHashMap::from([(
+                    simple_match_type,
+                    vec![SimpleWord {
+                        word_id: 1,
+                        word: "测试",
+                    }],
+                )]));
+                for _ in 0..100 {
+                    assert!(simple_matcher.is_match("測試"));
+                    assert!(!simple_matcher.is_match("你好"));
+                }
+            })
+        })
+        .collect();
+
+    for handle in handle_list {
+        handle.join().unwrap();
+    }
+}
+
+#[test]
+fn word_match_deadline() {
+    let sim_wordlist: Vec<String> = (0..5000).map(|index| format!("词{index}")).collect();
+    let match_table_dict = AHashMap::from([
+        (
+            "simple",
+            vec![MatchTable {
+                table_id: 1,
+                match_table_type: MatchTableType::Simple,
+                wordlist: VarZeroVec::from(&["你好"]),
+                ..Default::default()
+            }],
+        ),
+        (
+            "similar",
+            vec![MatchTable {
+                table_id: 2,
+                match_table_type: MatchTableType::SimilarTextLevenshtein,
+                wordlist: VarZeroVec::from(&sim_wordlist),
+                ..Default::default()
+            }],
+        ),
+    ]);
+    let matcher = Matcher::new(&match_table_dict);
+    let text = "你好".repeat(500);
+
+    assert_eq!(
+        Err(MatcherError::DeadlineExceeded),
+        matcher.try_word_match_until(&text, std::time::Instant::now())
+    );
+
+    // 超时前simple匹配已完成，返回部分结果
+    let (result_dict, deadline_exceeded) =
+        matcher.word_match_until(&text, std::time::Instant::now());
+    assert!(deadline_exceeded);
+    assert!(result_dict.contains_key("simple"));
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(600);
+    let (result_dict, deadline_exceeded) = matcher.word_match_until("你好", deadline);
+    assert!(!deadline_exceeded);
+    assert_eq!(matcher.word_match("你好"), result_dict);
+    assert_eq!(
+        matcher.word_match("词1"),
+        matcher.try_word_match_until("词1", deadline).unwrap()
+    );
+}
+
+#[test]
+fn max_variants_truncation() {
+    // 繁简、删除、归一、拼音的全部组合，构成一棵较宽的转换树
+    let base_type_list = [
+        SimpleMatchType::Fanjian,
+        SimpleMatchType::Delete,
+        SimpleMatchType::Normalize,
+        SimpleMatchType::PinYin,
+    ];
+    let simple_wordlist_dict: AHashMap<SimpleMatchType, Vec<SimpleWord>> = (0..16usize)
+        .map(|mask| {
+            let simple_match_type = base_type_list
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| mask & (1 << index) != 0)
+                .fold(SimpleMatchType::None, |acc, (_, &str_conv_type)| {
+                    acc | str_conv_type
+                });
+            (
+                simple_match_type,
+                vec![SimpleWord {
+                    word_id: mask as u64,
+                    word: "测试",
+                }],
+            )
+        })
+        .collect();
+    let text = "測試 Ⓐ";
+
+    let word_id_list = |simple_matcher: &SimpleMatcher| -> Vec<u64> {
+        let mut word_id_list: Vec<u64> = simple_matcher
+            .process(text)
+            .iter()
+            .map(|simple_result| simple_result.word_id)
+            .collect();
+        word_id_list.sort_unstable();
+        word_id_list
+    };
+
+    let simple_matcher = SimpleMatcher::new(&simple_wordlist_dict);
+    let processed_text = simple_matcher.preprocess(text);
+    let variant_count = processed_text.variant_count();
+    assert!(variant_count > 2);
+    assert!(!processed_text.variants_truncated());
+    let full_word_id_list = word_id_list(&simple_matcher);
+
+    let new_capped = |max_variants: usize| {
+        SimpleMatcher::new_with_options(
+            &simple_wordlist_dict,
+            SimpleMatcherOptions {
+                max_variants: Some(max_variants),
+                ..Default::default()
+            },
+        )
+    };
+
+    // 上限不小于实际变体数时不截断
+    let uncapped_simple_matcher = new_capped(variant_count);
+    assert!(!uncapped_simple_matcher
+        .preprocess(text)
+        .variants_truncated());
+    assert_eq!(full_word_id_list, word_id_list(&uncapped_simple_matcher));
+
+    // 截断后只保留树上靠前的节点，同一配置多次构建以及多次匹配的结果一致
+    let capped_simple_matcher = new_capped(2);
+    let capped_processed_text = capped_simple_matcher.preprocess(text);
+    assert!(capped_processed_text.variants_truncated());
+    assert_eq!(2, capped_processed_text.variant_count());
+
+    let capped_word_id_list = word_id_list(&capped_simple_matcher);
+    assert_eq!(vec![1], capped_word_id_list);
+    assert!(capped_word_id_list
+        .iter()
+        .all(|word_id| full_word_id_list.contains(word_id)));
+    assert_eq!(capped_word_id_list, word_id_list(&new_capped(2)));
+    assert_eq!(capped_word_id_list, word_id_list(&capped_simple_matcher));
+
+    // Matcher通过simple_matcher_options设置上限，预处理结果中带有截断标记
+    let matcher = Matcher::new_with_options(
+        &AHashMap::from([(
+            "test",
+            vec![MatchTable {
+                table_id: 1,
+                match_table_type: MatchTableType::Simple,
+                wordlist: VarZeroVec::from(&["测试"]),
+                simple_match_type: SimpleMatchType::FanjianDeleteNormalize
+                    | SimpleMatchType::PinYin,
+                ..Default::default()
+            }],
+        )]),
+        MatcherOptions {
+            simple_matcher_options: SimpleMatcherOptions {
+                max_variants: Some(1),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+    assert!(matcher
+        .preprocess(text)
+        .unwrap()
+        .iter()
+        .any(|processed_text| processed_text.variants_truncated()));
+    assert!(!matcher.is_match(text));
+
+    // word_match_full及其json同样带有截断标记
+    assert!(matcher.word_match_full(text).variants_truncated);
+    let full_match_result: serde_json::Value =
+        serde_json::from_str(&matcher.word_match_full_as_string(text)).unwrap();
+    assert_eq!(full_match_result["variants_truncated"], true);
+}
+
+#[test]
+fn owned_match_table_dict() {
+    let json = r#"{"te\"st": [{"table_id": 1, "match_table_type": "simple", "wordlist": ["It's /\\/\\y duty", "你好"], "exemption_wordlist": ["你好啊"], "simple_match_type": 15}]}"#;
+
+    // match_id含转义字符，无法借用输入
+    let error = parse_match_table_dict(json).err().unwrap();
+    assert!(error.to_string().contains("OwnedMatchTableDict"));
+
+    let matcher = {
+        let json_buffer = json.to_owned();
+        let owned_match_table_dict: OwnedMatchTableDict =
+            serde_json::from_str(&json_buffer).unwrap();
+        drop(json_buffer);
+        Matcher::from_owned_dict(&owned_match_table_dict)
+    };
+    assert_eq!(
+        r#"[{"table_id":1,"word":"你好"}]"#,
+        matcher.word_match("你好").get("te\"st").unwrap()
+    );
+    assert!(matcher.word_match("你好啊").is_empty());
+    assert!(matcher
+        .word_match(r"It's /\/\y duty")
+        .contains_key("te\"st"));
+
+    // 借用与拥有的词表之间互相转换，序列化结果一致
+    let owned_match_table_dict: OwnedMatchTableDict = serde_json::from_str(json).unwrap();
+    let match_table_dict = MatchTableDict::from(&owned_match_table_dict);
+    assert_eq!(
+        serde_json::to_value(&owned_match_table_dict).unwrap(),
+        serde_json::to_value(OwnedMatchTableDict::from(&match_table_dict)).unwrap()
+    );
+    assert_eq!(
+        serde_json::to_value(&owned_match_table_dict).unwrap(),
+        serde_json::to_value(&match_table_dict).unwrap()
+    );
+
+    // 不含转义字符时可直接借用
+    let borrowed_json = r#"{"test": [{"table_id": 1, "match_table_type": "simple", "wordlist": ["你好"], "exemption_wordlist": [], "simple_match_type": 1}]}"#;
+    let match_table_dict = parse_match_table_dict(borrowed_json).unwrap();
+    assert!(Matcher::new(&match_table_dict).is_match("你好"));
+}
+
+#[test]
+fn sim_result_sort() {
+    let wordlist = VarZeroVec::from(&["abcdy", "abcde", "abcdx"]);
+    let sim_table_list = vec![SimTable {
+        table_id: 1,
+        match_id: "test",
+        wordlist: &wordlist,
+        threshold: DEFAULT_SIM_THRESHOLD,
+        simple_match_type: SimpleMatchType::None,
+        process_words: true,
+        sim_match_type: SimMatchType::Levenshtein,
+        top_k: None,
+    }];
+
+    let word_id_list = |sort: SimSort| -> Vec<u64> {
+        SimMatcher::new_with_options(&sim_table_list, SimMatcherOptions { sort })
+            .process("abcde")
+            .iter()
+            .map(|sim_result| sim_result.word_id)
+            .collect()
+    };
+    // 相似度相同的 abcdy 与 abcdx 按词ID升序
+    assert_eq!(vec![1, 0, 2], word_id_list(SimSort::Similarity));
+    assert_eq!(vec![0, 1, 2], word_id_list(SimSort::WordId));
+    assert_eq!(vec![0, 1, 2], word_id_list(SimSort::None));
+
+    // Matcher保持sim匹配器的结果顺序
+    let matcher = Matcher::new(&AHashMap::from([(
+        "test",
+        vec![MatchTable {
+            table_id: 1,
+            match_table_type: MatchTableType::SimilarTextLevenshtein,
+            wordlist: wordlist.clone(),
+            ..Default::default()
+        }],
+    )]));
+    assert_eq!(
+        r#"[{"table_id":1,"word":"abcde"},{"table_id":1,"word":"abcdy"},{"table_id":1,"word":"abcdx"}]"#,
+        matcher.word_match("abcde").get("test").unwrap()
+    );
+}
+
+#[test]
+fn regex_empty_similar_char_table() {
+    let empty_wordlist = VarZeroVec::new();
+    let blank_wordlist = VarZeroVec::from(&["", ""]);
+    let regex_matcher = RegexMatcher::new(&vec![
+        RegexTable {
+            table_id: 1,
+            match_id: "test",
+            match_table_type: &MatchTableType::SimilarChar,
+            wordlist: &empty_wordlist,
+            acrostic_position: AcrosticPosition::Initial,
+            prefer_matched_text: false,
+            regex_flags: RegexFlags::default(),
+            acrostic_separator: AcrosticSeparator::Punctuation,
+        },
+        RegexTable {
+            table_id: 2,
+            match_id: "test",
+            match_table_type: &MatchTableType::SimilarChar,
+            wordlist: &blank_wordlist,
+            acrostic_position: AcrosticPosition::Initial,
+            prefer_matched_text: false,
+            regex_flags: RegexFlags::default(),
+            acrostic_separator: AcrosticSeparator::Punctuation,
+        },
+    ]);
+    assert!(!regex_matcher.is_match("anything"));
+    assert!(regex_matcher.process("anything").is_empty());
+    assert_eq!(
+        vec![("test".to_owned(), 1), ("test".to_owned(), 2)],
+        regex_matcher.stats().empty_table_list
+    );
+
+    let match_table_dict = AHashMap::from([(
+        "test",
+        vec![MatchTable {
+            table_id: 2,
+            match_table_type: MatchTableType::SimilarChar,
+            wordlist: blank_wordlist.clone(),
+            ..Default::default()
+        }],
+    )]);
+    assert_eq!(
+        vec![MatcherWarning::EmptyWordlist {
+            match_id: "test".to_owned(),
+            table_id: 2,
+        }],
+        Matcher::validate(&match_table_dict).unwrap()
+    );
+    assert!(!Matcher::new(&match_table_dict).is_match("anything"));
+}
+
+#[test]
+fn word_match_with_disabled_tables() {
+    let match_table_dict = AHashMap::from([
+        (
+            "test",
+            vec![
+                MatchTable {
+                    table_id: 1,
+                    match_table_type: MatchTableType::Simple,
+                    wordlist: VarZeroVec::from(&["你好"]),
+                    ..Default::default()
+                },
+                MatchTable {
+                    table_id: 2,
+                    match_table_type: MatchTableType::Regex,
+                    wordlist: VarZeroVec::from(&["你."]),
+                    ..Default::default()
+                },
+            ],
+        ),
+        (
+            "similar",
+            vec![MatchTable {
+                table_id: 3,
+                match_table_type: MatchTableType::SimilarTextLevenshtein,
+                wordlist: VarZeroVec::from(&["你好"]),
+                ..Default::default()
+            }],
+        ),
+    ]);
+    let matcher = Matcher::new(&match_table_dict);
+
+    let result_dict =
+        matcher.word_match_with_disabled_tables("你好", &matcher.table_id_set(&[("test", 1)]));
+    assert_eq!(
+        r#"[{"table_id":2,"word":"你."}]"#,
+        result_dict.get("test").unwrap()
+    );
+    assert!(result_dict.contains_key("similar"));
+
+    let result_dict = matcher.word_match_with_disabled_tables(
+        "你好",
+        &matcher.table_id_set(&[("test", 2), ("similar", 3)]),
+    );
+    assert_eq!(
+        r#"[{"table_id":1,"word":"你好"}]"#,
+        result_dict.get("test").unwrap()
+    );
+    assert!(!result_dict.contains_key("similar"));
+
+    // 空集合以及不存在的词表不影响结果
+    assert_eq!(
+        matcher.word_match("你好"),
+        matcher.word_match_with_disabled_tables("你好", &TableIdSet::default())
+    );
+    assert_eq!(
+        matcher.word_match("你好"),
+        matcher.word_match_with_disabled_tables("你好", &matcher.table_id_set(&[("test", 9)]))
+    );
+}
+
+#[test]
+fn simple_result_display_form() {
+    let simple_wordlist_dict = AHashMap::from([(
+        SimpleMatchType::None,
+        vec![
+            SimpleWord {
+                word_id: 1,
+                word: "无,法,无,天",
+            },
+            SimpleWord {
+                word_id: 2,
+                word: "hello,,World,spam",
+            },
+            SimpleWord {
+                word_id: 3,
+                word: "你好",
+            },
+        ],
+    )]);
+    let text = "无法无天 hello world spam 你好";
+
+    let simple_matcher = SimpleMatcher::new(&simple_wordlist_dict);
+    let mut simple_result_list = simple_matcher.process(text);
+    simple_result_list.sort_by_key(|simple_result| simple_result.word_id);
+    assert_eq!("无,法,无,天", simple_result_list[0].word);
+    assert_eq!(
+        vec!["无", "法", "无", "天"],
+        simple_result_list[0].word_parts()
+    );
+    assert_eq!(
+        vec!["hello", "World", "spam"],
+        simple_result_list[1].word_parts()
+    );
+    assert_eq!(vec!["你好"], simple_result_list[2].word_parts());
+
+    let display_simple_matcher = SimpleMatcher::new_with_options(
+        &simple_wordlist_dict,
+        SimpleMatcherOptions {
+            display_separator: Some(" / ".to_owned()),
+            normalize_result_case: Some(CaseMode::Lower),
+            ..Default::default()
+        },
+    );
+    let mut simple_result_list = display_simple_matcher.process(text);
+    simple_result_list.sort_by_key(|simple_result| simple_result.word_id);
+    let word_list: Vec<&str> = simple_result_list
+        .iter()
+        .map(|simple_result| simple_result.word.as_ref())
+        .collect();
+    assert_eq!(
+        vec!["无 / 法 / 无 / 天", "hello / world / spam", "你好"],
+        word_list
+    );
+    // 原始写法保留，可用于按词查找规则
+    assert_eq!("hello,,World,spam", simple_result_list[1].raw_word);
+    assert_eq!(
+        vec!["hello", "World", "spam"],
+        simple_result_list[1].word_parts()
+    );
+}
+
+#[test]
+fn lazy_exemption_differential() {
+    let new_match_table_dict = |exemption_wordlist: VarZeroVec<'static, str>| {
+        AHashMap::from([
+            (
+                "exempted",
+                vec![
+                    MatchTable {
+                        table_id: 1,
+                        match_table_type: MatchTableType::Simple,
+                        wordlist: VarZeroVec::from(&["你好", "测试"]),
+                        exemption_wordlist: exemption_wordlist.clone(),
+                        ..Default::default()
+                    },
+                    MatchTable {
+                        table_id: 2,
+                        match_table_type: MatchTableType::Regex,
+                        wordlist: VarZeroVec::from(&["^abc"]),
+                        ..Default::default()
+                    },
+                ],
+            ),
+            (
+                "plain",
+                vec![MatchTable {
+                    table_id: 3,
+                    match_table_type: MatchTableType::Simple,
+                    wordlist: VarZeroVec::from(&["你好"]),
+                    ..Default::default()
+                }],
+            ),
+        ])
+    };
+    let exemption_wordlist = VarZeroVec::from(&["你好世界", "測 試"]);
+    let matcher = Matcher::new(&new_match_table_dict(exemption_wordlist.clone()));
+
+    // 参照实现：不带豁免词的匹配结果，命中豁免词时整个match_id被豁免
+    let positive_matcher = Matcher::new(&new_match_table_dict(VarZeroVec::new()));
+    let exemption_simple_matcher = SimpleMatcher::new(&AHashMap::from([(
+        SimpleMatchType::FanjianDeleteNormalize,
+        exemption_wordlist
+            .iter()
+            .enumerate()
+            .map(|(word_id, word)| SimpleWord {
+                word_id: word_id as u64,
+                word,
+            })
+            .collect(),
+    )]));
+
+    for text in [
+        "你好",
+        "你好世界",
+        "你好，世 界",
+        "abc你好世界",
+        "abc",
+        "测试",
+        "测试 测试",
+        "无关的文本",
+        "你 好 世 界 测试",
+        "",
+    ] {
+        let mut expected_result_dict = positive_matcher.word_match(text);
+        if exemption_simple_matcher.is_match(text) {
+            expected_result_dict.remove("exempted");
+        }
+        assert_eq!(expected_result_dict, matcher.word_match(text), "{text}");
+    }
+
+    // 正常词只需原文本，豁免词独有的匹配类型不会生成变体
+    let processed_text_list = matcher.preprocess("無關的文本，你 好").unwrap();
+    assert_eq!(1, processed_text_list[0].variant_count());
+    assert!(!matcher.is_match("測 試"));
+}
+
+#[test]
+fn sim_nearest() {
+    let wordlist = VarZeroVec::from(&["abcd", "abcf", "xyz", "abce"]);
+    let other_wordlist = VarZeroVec::from(&["abcde"]);
+    let sim_matcher = SimMatcher::new(&vec![
+        SimTable {
+            table_id: 1,
+            match_id: "test",
+            wordlist: &wordlist,
+            threshold: DEFAULT_SIM_THRESHOLD,
+            simple_match_type: SimpleMatchType::None,
+            process_words: true,
+            sim_match_type: SimMatchType::Levenshtein,
+            top_k: None,
+        },
+        SimTable {
+            table_id: 2,
+            match_id: "other",
+            wordlist: &other_wordlist,
+            threshold: 1.0,
+            simple_match_type: SimpleMatchType::None,
+            process_words: true,
+            sim_match_type: SimMatchType::Levenshtein,
+            top_k: None,
+        },
+    ]);
+
+    // 忽略阈值，标点等非单词字符在打分前去除
+    let result_list = sim_matcher.nearest("a-b-c-d", 4);
+    let result_summary: Vec<(u32, &str, f64)> = result_list
+        .iter()
+        .map(|sim_result| {
+            (
+                sim_result.table_id,
+                sim_result.word.as_ref(),
+                sim_result.similarity,
+            )
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            (1, "abcd", 1.0),
+            (2, "abcde", 0.8),
+            (1, "abcf", 0.75),
+            (1, "abce", 0.75),
+        ],
+        result_summary
+    );
+
+    assert_eq!(1, sim_matcher.nearest("xyz", 1).len());
+    assert_eq!("xyz", sim_matcher.nearest("xyz", 1)[0].word);
+    assert_eq!(5, sim_matcher.nearest("xyz", 10).len());
+    assert!(sim_matcher.nearest("xyz", 0).is_empty());
+}
+
+#[test]
+fn matcher_clone_shares_internals() {
+    let simple_matcher = SimpleMatcher::new(&AHashMap::from([(
+        SimpleMatchType::FanjianDeleteNormalize,
+        vec![SimpleWord {
+            word_id: 1,
+            word: "你好,世界",
+        }],
+    )]));
+    let simple_matcher_clone = simple_matcher.clone();
+    assert!(simple_matcher.ptr_eq(&simple_matcher_clone));
+    assert!(!simple_matcher.ptr_eq(&SimpleMatcher::new(&AHashMap::new())));
+    assert_eq!(
+        simple_matcher.process("世界，你好").len(),
+        simple_matcher_clone.process("世界，你好").len()
+    );
+    assert_eq!(1, simple_matcher_clone.process("世界，你好").len());
+
+    let regex_wordlist = VarZeroVec::from(&["^abc"]);
+    let regex_matcher = RegexMatcher::new(&vec![RegexTable {
+        table_id: 1,
+        match_id: "1",
+        match_table_type: &MatchTableType::Regex,
+        wordlist: &regex_wordlist,
+        acrostic_position: AcrosticPosition::Initial,
+        prefer_matched_text: false,
+        regex_flags: RegexFlags::default(),
+        acrostic_separator: AcrosticSeparator::Punctuation,
+    }]);
+    let regex_matcher_clone = regex_matcher.clone();
+    assert!(regex_matcher.ptr_eq(&regex_matcher_clone));
+    assert_eq!(1, regex_matcher_clone.process("abcd").len());
+
+    let sim_wordlist = VarZeroVec::from(&["abcd"]);
+    let sim_matcher = SimMatcher::new(&vec![SimTable {
+        table_id: 1,
+        match_id: "1",
+        wordlist: &sim_wordlist,
+        threshold: DEFAULT_SIM_THRESHOLD,
+        simple_match_type: SimpleMatchType::None,
+        process_words: true,
+        sim_match_type: SimMatchType::Levenshtein,
+        top_k: None,
+    }]);
+    let sim_matcher_clone = sim_matcher.clone();
+    assert!(sim_matcher.ptr_eq(&sim_matcher_clone));
+    assert_eq!(
+        sim_matcher.nearest("abce", 1)[0].similarity,
+        sim_matcher_clone.nearest("abce", 1)[0].similarity
+    );
+
+    let match_table_dict = AHashMap::from([(
+        "test",
+        vec![MatchTable {
+            table_id: 1,
+            match_table_type: MatchTableType::Simple,
+            wordlist: VarZeroVec::from(&["你好", "测试"]),
+            simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+            ..Default::default()
+        }],
+    )]);
+    let matcher = Matcher::new(&match_table_dict);
+    let mut matcher_clone = matcher.clone();
+    assert!(matcher.ptr_eq(&matcher_clone));
+    assert_eq!(
+        matcher.word_match_as_string("你好，測試"),
+        matcher_clone.word_match_as_string("你好，測試")
+    );
+
+    // 运行时豁免词写时复制，只作用于clone，构建产物仍然共享
+    matcher_clone.add_exemption("test", 1, "测试");
+    matcher_clone.commit();
+    assert!(matcher.ptr_eq(&matcher_clone));
+    assert!(matcher.word_match("测试").contains_key("test"));
+    assert!(!matcher_clone.word_match("测试").contains_key("test"));
+}
+
+#[test]
+fn export_table_dict_round_trip() {
+    let match_table_dict = AHashMap::from([
+        (
+            "simple",
+            vec![
+                MatchTable {
+                    table_id: 1,
+                    match_table_type: MatchTableType::Simple,
+                    wordlist: VarZeroVec::from(&["你好,世界", "测试", "无,法,无,天"]),
+                    exemption_wordlist: VarZeroVec::from(&["测试一下"]),
+                    simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                    ..Default::default()
+                },
+                MatchTable {
+                    table_id: 2,
+                    match_table_type: MatchTableType::Simple,
+                    wordlist: VarZeroVec::from(&["xian"]),
+                    simple_match_type: SimpleMatchType::PinYin,
+                    ..Default::default()
+                },
+            ],
+        ),
+        (
+            "regex",
+            vec![
+                MatchTable {
+                    table_id: 3,
+                    match_table_type: MatchTableType::SimilarChar,
+                    wordlist: VarZeroVec::from(&["你,妳", "好,号"]),
+                    prefer_matched_text: true,
+                    ..Default::default()
+                },
+                MatchTable {
+                    table_id: 4,
+                    match_table_type: MatchTableType::Acrostic,
+                    wordlist: VarZeroVec::from(&["一,二"]),
+                    acrostic_position: AcrosticPosition::Nth(2),
+                    ..Default::default()
+                },
+                MatchTable {
+                    table_id: 5,
+                    match_table_type: MatchTableType::Regex,
+                    wordlist: VarZeroVec::from(&["^abc", r"(\w)\1", "(", "x(?=y)"]),
+                    ..Default::default()
+                },
+            ],
+        ),
+        (
+            "sim",
+            vec![MatchTable {
+                table_id: 6,
+                match_table_type: MatchTableType::SimilarTextLevenshtein,
+                wordlist: VarZeroVec::from(&["helloworld"]),
+                ..Default::default()
+            }],
+        ),
+    ]);
+    let mut matcher = Matcher::new(&match_table_dict);
+    matcher.add_exemption("sim", 6, "hellowor");
+    matcher.remove_exemption("simple", 1, "测试一下");
+    matcher.commit();
+
+    let owned_match_table_dict = matcher.export_table_dict();
+    let simple_table_list = owned_match_table_dict.0.get("simple").unwrap();
+    assert_eq!(
+        vec!["你好,世界", "测试", "无,法,无,天"],
+        simple_table_list[0].wordlist
+    );
+    assert!(simple_table_list[0].exemption_wordlist.is_empty());
+    assert_eq!(
+        SimpleMatchType::PinYin,
+        simple_table_list[1].simple_match_type
+    );
+    // 无法编译的正则未加载，不会导出
+    let regex_table = &owned_match_table_dict.0.get("regex").unwrap()[2];
+    assert_eq!(vec!["^abc", r"(\w)\1", "x(?=y)"], regex_table.wordlist);
+    assert_eq!(
+        vec!["hellowor"],
+        owned_match_table_dict.0.get("sim").unwrap()[0].exemption_wordlist
+    );
+
+    let rebuilt_matcher = Matcher::from_owned_dict(&owned_match_table_dict);
+    for text in [
+        "你好，世界",
+        "測試一下",
+        "无法无天",
+        "先",
+        "妳号",
+        "a一b。c二d",
+        "abc",
+        "aa",
+        "xy",
+        "helloworld",
+        "hellowor1d",
+        "",
+    ] {
+        let sorted_result_dict = |result_dict: std::collections::HashMap<&str, String>| {
+            result_dict
+                .into_iter()
+                .map(|(match_id, result_list)| {
+                    let mut result_list: Vec<String> =
+                        serde_json::from_str::<Vec<serde_json::Value>>(&result_list)
+                            .unwrap()
+                            .iter()
+                            .map(|result| result.to_string())
+                            .collect();
+                    result_list.sort();
+                    (match_id.to_owned(), result_list)
+                })
+                .collect::<AHashMap<String, Vec<String>>>()
+        };
+        assert_eq!(
+            sorted_result_dict(matcher.word_match(text)),
+            sorted_result_dict(rebuilt_matcher.word_match(text)),
+            "{text}"
+        );
+    }
+}
+
+#[test]
+fn lenient_build_mode() {
+    let match_table_dict = AHashMap::from([
+        (
+            "regex",
+            vec![
+                MatchTable {
+                    table_id: 1,
+                    match_table_type: MatchTableType::Regex,
+                    wordlist: VarZeroVec::from(&["^abc", "("]),
+                    ..Default::default()
+                },
+                MatchTable {
+                    table_id: 2,
+                    match_table_type: MatchTableType::Regex,
+                    wordlist: VarZeroVec::from(&["xyz$"]),
+                    ..Default::default()
+                },
+            ],
+        ),
+        (
+            "simple",
+            vec![
+                MatchTable {
+                    table_id: 3,
+                    match_table_type: MatchTableType::Simple,
+                    wordlist: VarZeroVec::from(&["你好"]),
+                    simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                    ..Default::default()
+                },
+                MatchTable {
+                    table_id: 4,
+                    match_table_type: MatchTableType::Simple,
+                    wordlist: VarZeroVec::from(&["世界"]),
+                    simple_match_type: SimpleMatchType::PinYin | SimpleMatchType::PinYinChar,
+                    ..Default::default()
+                },
+            ],
+        ),
+    ]);
+
+    // strict模式下校验失败即返回错误
+    assert!(Matcher::try_new(&match_table_dict).is_err());
+    assert!(Matcher::try_new_with_mode(
+        &match_table_dict,
+        MatcherOptions::default(),
+        BuildMode::Strict
+    )
+    .is_err());
+
+    let regex_matcher = RegexMatcher::new(&vec![RegexTable {
+        table_id: 1,
+        match_id: "regex",
+        match_table_type: &MatchTableType::Regex,
+        wordlist: &match_table_dict.get("regex").unwrap()[0].wordlist,
+        acrostic_position: AcrosticPosition::Initial,
+        prefer_matched_text: false,
+        regex_flags: RegexFlags::default(),
+        acrostic_separator: AcrosticSeparator::Punctuation,
+    }]);
+    let fancy_regex_error = regex_matcher.stats().table_stats_list[0].invalid_pattern_list[0]
+        .1
+        .clone();
+
+    let (matcher, _, mut skipped_table_list) = Matcher::try_new_with_mode(
+        &match_table_dict,
+        MatcherOptions::default(),
+        BuildMode::Lenient,
+    )
+    .unwrap();
+    skipped_table_list.sort_by_key(|skipped_table| skipped_table.table_id);
+    assert_eq!(
+        vec![
+            SkippedTable {
+                match_id: "regex".to_owned(),
+                table_id: 1,
+                reason: MatcherError::InvalidRegex {
+                    match_id: "regex".to_owned(),
+                    table_id: 1,
+                    pattern: "(".to_owned(),
+                    source: fancy_regex_error,
+                },
+            },
+            SkippedTable {
+                match_id: "simple".to_owned(),
+                table_id: 4,
+                reason: MatcherError::ConflictingSimpleMatchType {
+                    match_id: "simple".to_owned(),
+                    table_id: 4,
+                    simple_match_type: SimpleMatchType::PinYin | SimpleMatchType::PinYinChar,
+                },
+            },
+        ],
+        skipped_table_list
+    );
+
+    assert!(!matcher.is_match("abc"));
+    assert!(matcher.is_match("xyz"));
+    assert!(matcher.is_match("你好"));
+    assert!(!matcher.is_match("世界"));
+
+    // 全部词表均有效时与strict模式一致
+    let (_, _, skipped_table_list) = Matcher::try_new_with_mode(
+        &AHashMap::from([(
+            "regex",
+            vec![match_table_dict.get("regex").unwrap()[1].clone()],
+        )]),
+        MatcherOptions::default(),
+        BuildMode::Lenient,
+    )
+    .unwrap();
+    assert!(skipped_table_list.is_empty());
+}
+
+#[test]
+fn blank_input() {
+    let blank_text_list = ["", " ", "\u{3000}\u{200B}", "\t\r\n\u{FEFF}"];
+
+    let simple_matcher = SimpleMatcher::new(&AHashMap::from([(
+        SimpleMatchType::None,
+        vec![SimpleWord {
+            word_id: 1,
+            word: "",
+        }],
+    )]));
+    assert!(simple_matcher.process("").is_empty());
+    assert!(!simple_matcher.is_match(""));
+
+    let regex_wordlist = VarZeroVec::from(&["^$", r"^\s*$"]);
+    let regex_matcher = RegexMatcher::new(&vec![RegexTable {
+        table_id: 1,
+        match_id: "regex",
+        match_table_type: &MatchTableType::Regex,
+        wordlist: &regex_wordlist,
+        acrostic_position: AcrosticPosition::Initial,
+        prefer_matched_text: false,
+        regex_flags: RegexFlags::default(),
+        acrostic_separator: AcrosticSeparator::Punctuation,
+    }]);
+    assert!(regex_matcher.process("").is_empty());
+    assert!(!regex_matcher.is_match(""));
+
+    let sim_wordlist = VarZeroVec::from(&[""]);
+    let sim_matcher = SimMatcher::new(&vec![SimTable {
+        table_id: 2,
+        match_id: "sim",
+        wordlist: &sim_wordlist,
+        threshold: DEFAULT_SIM_THRESHOLD,
+        simple_match_type: SimpleMatchType::None,
+        process_words: true,
+        sim_match_type: SimMatchType::Levenshtein,
+        top_k: None,
+    }]);
+    assert!(sim_matcher.process("").is_empty());
+    assert!(!sim_matcher.is_match("!!!"));
+
+    let match_table_dict = AHashMap::from([(
+        "regex",
+        vec![MatchTable {
+            table_id: 1,
+            match_table_type: MatchTableType::Regex,
+            wordlist: regex_wordlist.clone(),
+            ..Default::default()
+        }],
+    )]);
+    let matcher = Matcher::new(&match_table_dict);
+    for text in blank_text_list {
+        assert!(!matcher.is_match(text));
+        assert!(matcher.word_match(text).is_empty());
+        assert!(matcher.preprocess(text).unwrap().is_empty());
+    }
+
+    // 关闭后只含空白的文本正常匹配，空文本仍然直接返回
+    let matcher = Matcher::new_with_options(
+        &match_table_dict,
+        MatcherOptions {
+            treat_blank_as_empty: false,
+            ..Default::default()
+        },
+    );
+    assert!(matcher.word_match("").is_empty());
+    assert!(matcher.is_match(" "));
+    assert!(matcher.word_match("\u{3000}").contains_key("regex"));
+    assert!(!matcher.is_match("\u{3000}\u{200B}"));
+}
+
+#[test]
+fn word_match_full_exemption_hit() {
+    let match_table_dict = AHashMap::from([
+        (
+            "test",
+            vec![MatchTable {
+                table_id: 1,
+                match_table_type: MatchTableType::Simple,
+                wordlist: VarZeroVec::from(&["你好"]),
+                exemption_wordlist: VarZeroVec::from(&["你好呀"]),
+                simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                ..Default::default()
+            }],
+        ),
+        (
+            "other",
+            vec![MatchTable {
+                table_id: 2,
+                match_table_type: MatchTableType::Simple,
+                wordlist: VarZeroVec::from(&["你好"]),
+                simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                ..Default::default()
+            }],
+        ),
+    ]);
+    let mut matcher = Matcher::new(&match_table_dict);
+
+    let full_match_result = matcher.word_match_full("你好呀");
+    assert!(full_match_result.matched.contains_key("other"));
+    assert!(!full_match_result.matched.contains_key("test"));
+    let exempted_result = &full_match_result.exempted["test"];
+    assert_eq!(
+        r#"[{"table_id":1,"word":"你好"}]"#,
+        serde_json::to_string(&exempted_result.result_list).unwrap()
+    );
+    assert_eq!(
+        vec![ExemptionHit {
+            table_id: 1,
+            word: "你好呀".into(),
+            similarity: 1.0,
+        }],
+        exempted_result.exemption_hit_list
+    );
+
+    // 运行时添加的豁免词同样返回
+    matcher.add_exemption("test", 1, "世界");
+    matcher.commit();
+    let full_match_result = matcher.word_match_full("你好世界");
+    assert_eq!(
+        vec![ExemptionHit {
+            table_id: 1,
+            word: "世界".into(),
+            similarity: 1.0,
+        }],
+        full_match_result.exempted["test"].exemption_hit_list
+    );
+
+    // 豁免判断与word_match一致
+    let full_match_result = matcher.word_match_full("你好");
+    assert!(full_match_result.exempted.is_empty());
+    assert_eq!(
+        matcher.word_match("你好").len(),
+        full_match_result.matched.len()
+    );
+}
+
+#[test]
+fn rule_bundle_round_trip() {
+    let match_table_dict = AHashMap::from([(
+        "test",
+        vec![MatchTable {
+            table_id: 1,
+            match_table_type: MatchTableType::Simple,
+            wordlist: VarZeroVec::from(&["abc"]),
+            exemption_wordlist: VarZeroVec::from(&["abcd"]),
+            simple_match_type: SimpleMatchType::Normalize,
+            ..Default::default()
+        }],
+    )]);
+    let mut rule_bundle = RuleBundle::new(
+        OwnedMatchTableDict::from(&match_table_dict),
+        MatcherOptions {
+            max_input_bytes: Some(16),
+            ..Default::default()
+        },
+    );
+    rule_bundle.process_overrides = AHashMap::from([(
+        SimpleMatchType::Normalize,
+        vec![("阿".to_owned(), "a".to_owned())],
+    )]);
+
+    let rule_bundle =
+        RuleBundle::from_bytes(&rule_bundle.to_bytes(BundleCompression::None).unwrap()).unwrap();
+    assert_eq!(RULE_BUNDLE_FORMAT_VERSION, rule_bundle.format_version);
+    assert_eq!(Some(16), rule_bundle.options.max_input_bytes);
+
+    let matcher = Matcher::from_bundle(&rule_bundle);
+    assert!(matcher.is_match("阿bc"));
+    assert!(!matcher.word_match("阿bcd").contains_key("test"));
+    // 超出max_input_bytes的部分被截断
+    assert!(!matcher.is_match("0123456789012345阿bc"));
+
+    #[cfg(feature = "zstd")]
+    assert!(Matcher::from_bundle(
+        &RuleBundle::from_bytes(&rule_bundle.to_bytes(BundleCompression::Zstd).unwrap()).unwrap()
+    )
+    .is_match("阿bc"));
+
+    assert_eq!(
+        Err(RuleBundleError::InvalidHeader),
+        RuleBundle::from_bytes(b"{}").map(|_| ())
+    );
+}
+
+#[test]
+fn rule_bundle_cross_version() {
+    #[derive(serde::Serialize)]
+    struct FutureRuleBundle<'a> {
+        #[serde(flatten)]
+        rule_bundle: &'a RuleBundle,
+        future_field: Vec<u32>,
+    }
+
+    let rule_bundle = RuleBundle::new(
+        OwnedMatchTableDict(AHashMap::from([(
+            "test".to_owned(),
+            vec![OwnedMatchTable {
+                table_id: 1,
+                match_table_type: MatchTableType::Simple,
+                wordlist: vec!["你好".to_owned()],
+                ..Default::default()
+            }],
+        )])),
+        MatcherOptions::default(),
+    );
+    let header = rule_bundle.to_bytes(BundleCompression::None).unwrap()[..5].to_vec();
+    let to_bytes = |future_rule_bundle: &FutureRuleBundle| {
+        [
+            header.clone(),
+            rmp_serde::to_vec_named(future_rule_bundle).unwrap(),
+        ]
+        .concat()
+    };
+
+    // 新版本新增的字段被忽略
+    let future_rule_bundle = FutureRuleBundle {
+        rule_bundle: &rule_bundle,
+        future_field: vec![1, 2, 3],
+    };
+    assert!(
+        Matcher::from_bundle(&RuleBundle::from_bytes(&to_bytes(&future_rule_bundle)).unwrap())
+            .is_match("你好")
+    );
+
+    // 不兼容的新版本被拒绝
+    let mut incompatible_rule_bundle = rule_bundle.clone();
+    incompatible_rule_bundle.format_version = RULE_BUNDLE_FORMAT_VERSION + 1;
+    assert_eq!(
+        Err(RuleBundleError::UnsupportedFormatVersion {
+            format_version: RULE_BUNDLE_FORMAT_VERSION + 1,
+            max_format_version: RULE_BUNDLE_FORMAT_VERSION,
+        }),
+        RuleBundle::from_bytes(&to_bytes(&FutureRuleBundle {
+            rule_bundle: &incompatible_rule_bundle,
+            future_field: Vec::new(),
+        }))
+        .map(|_| ())
+    );
+}
+
+#[test]
+fn simple_match_negated() {
+    let simple_wordlist_dict = AHashMap::from([
+        (
+            SimpleMatchType::None,
+            vec![SimpleWord {
+                word_id: 1,
+                word: "hello~world~there",
+            }],
+        ),
+        (
+            SimpleMatchType::Fanjian,
+            vec![SimpleWord {
+                word_id: 2,
+                word: "你好,朋友~坏人",
+            }],
+        ),
+    ]);
+    let simple_matcher = SimpleMatcher::new_with_options(
+        &simple_wordlist_dict,
+        SimpleMatcherOptions {
+            report_negated: true,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(simple_matcher.process("hello")[0].word_id, 1);
+    assert!(simple_matcher.process("hello world").is_empty());
+    assert!(simple_matcher.process("there, hello").is_empty());
+    assert!(simple_matcher.process("world").is_empty());
+    assert_eq!(simple_matcher.process("你好朋友")[0].word_id, 2);
+    // 否定部分在繁简转换后的文本中命中
+    assert!(simple_matcher.process("你好朋友壞人").is_empty());
+    #[cfg(feature = "rayon")]
+    for text in ["hello", "hello world", "你好朋友", "你好朋友壞人"] {
+        assert_eq!(
+            simple_matcher.process(text).len(),
+            simple_matcher.par_process(text).len(),
+            "text: {text}"
+        );
+    }
+
+    // 普通结果不变，被丢弃的词以及命中的否定部分单独返回
+    let simple_full_result = simple_matcher.process_full("there, hello world");
+    assert!(simple_full_result.matched.is_empty());
+    assert_eq!(simple_full_result.negated.len(), 1);
+    assert_eq!(simple_full_result.negated[0].result.word_id, 1);
+    assert_eq!(
+        simple_full_result.negated[0].not_part_list,
+        vec!["world", "there"]
+    );
+
+    let simple_full_result = simple_matcher.process_full("你好朋友壞人");
+    assert_eq!(simple_full_result.negated[0].result.word, "你好,朋友~坏人");
+    assert_eq!(simple_full_result.negated[0].not_part_list, vec!["坏人"]);
+    assert_eq!(
+        simple_full_result.negated[0].result.word_parts(),
+        vec!["你好", "朋友"]
+    );
+
+    // 组合词部分未全部命中时，否定部分命中不会被报告
+    assert!(simple_matcher.process_full("你好坏人").negated.is_empty());
+
+    // 未开启时不收集
+    let simple_matcher = SimpleMatcher::new(&simple_wordlist_dict);
+    let simple_full_result = simple_matcher.process_full("hello world");
+    assert!(simple_full_result.matched.is_empty());
+    assert!(simple_full_result.negated.is_empty());
+}
+
+#[test]
+fn astral_plane_process() {
+    // 辅助平面字符均为4字节，分别作为转换词、删除词以及不参与转换的字符出现
+    let simple_wordlist_dict = AHashMap::from([
+        (
+            SimpleMatchType::None,
+            vec![SimpleWord {
+                word_id: 1,
+                word: "𝐀你好",
+            }],
+        ),
+        (
+            SimpleMatchType::Fanjian,
+            vec![SimpleWord {
+                word_id: 2,
+                word: "弊端",
+            }],
+        ),
+        (
+            SimpleMatchType::Delete,
+            vec![SimpleWord {
+                word_id: 3,
+                word: "你好",
+            }],
+        ),
+        (
+            SimpleMatchType::Normalize,
+            vec![SimpleWord {
+                word_id: 4,
+                word: "12",
+            }],
+        ),
+        (
+            SimpleMatchType::PinYin,
+            vec![SimpleWord {
+                word_id: 5,
+                word: "𝐀西安",
+            }],
+        ),
+        (
+            SimpleMatchType::PinYinChar,
+            vec![SimpleWord {
+                word_id: 6,
+                word: "𝐀西安",
+            }],
+        ),
+    ]);
+    let simple_matcher = SimpleMatcher::new(&simple_wordlist_dict);
+    let word_id_list = |text: &str| {
+        let mut word_id_list: Vec<u64> = simple_matcher
+            .process(text)
+            .iter()
+            .map(|simple_result| simple_result.word_id)
+            .collect();
+        word_id_list.sort_unstable();
+        word_id_list
+    };
+
+    assert_eq!(word_id_list("𝐀你好"), vec![1, 3]);
+    assert_eq!(word_id_list("𡚁端"), vec![2]);
+    assert_eq!(word_id_list("你🏻好"), vec![3]);
+    assert_eq!(word_id_list("𝟙2"), vec![4]);
+    assert_eq!(word_id_list("𝐀息安"), vec![5, 6]);
+    assert_eq!(word_id_list("𝐀先"), vec![6]);
+    assert_eq!(word_id_list("𝐀xian"), vec![6]);
+
+    // 转换词前后紧邻辅助平面字符，逐个截断到字符边界，保证不会panic且结果与原文本一致
+    let text = "𝟙𡚁🏻𝐀你🏻好𝟙2𝐀西安🀀";
+    for (index, _) in text.char_indices() {
+        simple_matcher.process(&text[index..]);
+        simple_matcher.process(&text[..index]);
+    }
+    assert_eq!(word_id_list(text), vec![3, 4, 5, 6]);
+}
+
+#[test]
+fn word_match_by_table() {
+    let match_table_dict = AHashMap::from([(
+        "test",
+        vec![
+            MatchTable {
+                table_id: 1,
+                match_table_type: MatchTableType::Simple,
+                wordlist: VarZeroVec::from(&["你好", "世界"]),
+                ..Default::default()
+            },
+            MatchTable {
+                table_id: 2,
+                match_table_type: MatchTableType::Simple,
+                wordlist: VarZeroVec::from(&["世界"]),
+                ..Default::default()
+            },
+        ],
+    )]);
+    let matcher = Matcher::new(&match_table_dict);
+
+    let table_result_dict = &matcher.word_match_by_table("你好世界")["test"];
+    assert_eq!(table_result_dict.len(), 2);
+    let mut word_list: Vec<String> = table_result_dict[&1]
+        .iter()
+        .map(|match_result| {
+            serde_json::to_value(match_result).unwrap()["word"]
+                .as_str()
+                .unwrap()
+                .to_owned()
+        })
+        .collect();
+    word_list.sort_unstable();
+    assert_eq!(word_list, vec!["世界", "你好"]);
+    assert_eq!(
+        r#"[{"table_id":2,"word":"世界"}]"#,
+        serde_json::to_string(&table_result_dict[&2]).unwrap()
+    );
+
+    assert_eq!(
+        r#"{"test":{"1":[{"table_id":1,"word":"你好"}]}}"#,
+        matcher.word_match_by_table_as_string("你好")
+    );
+    assert!(matcher.word_match_by_table("").is_empty());
+}
+
+#[test]
+fn normalize_newlines() {
+    let match_table_dict = AHashMap::from([
+        (
+            "regex",
+            vec![MatchTable {
+                table_id: 1,
+                match_table_type: MatchTableType::Regex,
+                wordlist: VarZeroVec::from(&[r"(?m)^好的$"]),
+                ..Default::default()
+            }],
+        ),
+        (
+            "acrostic",
+            vec![MatchTable {
+                table_id: 2,
+                match_table_type: MatchTableType::Acrostic,
+                wordlist: VarZeroVec::from(&["你,好,真,棒"]),
+                acrostic_position: AcrosticPosition::Nth(1),
+                ..Default::default()
+            }],
+        ),
+    ]);
+    let text_list = [
+        "你先休息\r\n好的\r\n真的很棒\r\n棒到家了",
+        "你先休息\r好的\r真的很棒\r棒到家了",
+        "你先休息\u{2028}好的\u{2029}真的很棒\n棒到家了",
+    ];
+
+    // \r会留在行尾，多行模式下的$只认\n；藏头按空白及标点分句，不受换行符影响
+    let matcher = Matcher::new(&match_table_dict);
+    for text in text_list {
+        let result_dict = matcher.word_match(text);
+        assert!(!result_dict.contains_key("regex"), "text: {text:?}");
+        assert!(result_dict.contains_key("acrostic"), "text: {text:?}");
+    }
+
+    let matcher = Matcher::new_with_options(
+        &match_table_dict,
+        MatcherOptions {
+            normalize_newlines: true,
+            ..Default::default()
+        },
+    );
+    for text in text_list {
+        assert_eq!(
+            "你先休息\n好的\n真的很棒\n棒到家了",
+            matcher.normalize_input(text)
+        );
+        let result_dict = matcher.word_match(text);
+        assert!(result_dict.contains_key("regex"), "text: {text:?}");
+        assert!(result_dict.contains_key("acrostic"), "text: {text:?}");
+        assert!(matcher.is_match(text));
+    }
+    assert!(matches!(
+        matcher.normalize_input("好的\n"),
+        std::borrow::Cow::Borrowed(_)
+    ));
+}
+
+#[test]
+fn too_many_ac_patterns() {
+    let simple_wordlist_dict = AHashMap::from([(
+        SimpleMatchType::None,
+        vec![
+            SimpleWord {
+                word_id: 1,
+                word: "你好",
+            },
+            SimpleWord {
+                word_id: 2,
+                word: "世界,和平",
+            },
+        ],
+    )]);
+
+    // 组合词的每个部分各占一个ac词
+    let simple_matcher_options = SimpleMatcherOptions {
+        max_ac_pattern_count: Some(2),
+        ..Default::default()
+    };
+    assert_eq!(
+        Err(SimpleMatcherError::TooManyPatterns {
+            simple_match_type: SimpleMatchType::None,
+            pattern_count: 3,
+            max_pattern_count: 2,
+        }),
+        SimpleMatcher::try_new_with_options(&simple_wordlist_dict, simple_matcher_options.clone())
+            .map(|_| ())
+    );
+    assert!(SimpleMatcher::try_new_with_options(
+        &simple_wordlist_dict,
+        SimpleMatcherOptions {
+            max_ac_pattern_count: Some(3),
+            ..Default::default()
+        }
+    )
+    .is_ok());
+
+    let match_table_dict = AHashMap::from([(
+        "test",
+        vec![MatchTable {
+            table_id: 1,
+            match_table_type: MatchTableType::Simple,
+            wordlist: VarZeroVec::from(&["你好", "世界,和平"]),
+            ..Default::default()
+        }],
+    )]);
+    assert!(matches!(
+        Matcher::try_new_with_options(
+            &match_table_dict,
+            MatcherOptions {
+                simple_matcher_options,
+                ..Default::default()
+            },
+        ),
+        Err(MatcherError::TooManyPatterns {
+            pattern_count: 3,
+            max_pattern_count: 2,
+            ..
+        })
+    ));
+
+    let matcher_stats = Matcher::new(&match_table_dict).stats();
+    assert_eq!(matcher_stats.ac_pattern_count, 3);
+    assert_eq!(matcher_stats.max_table_ac_pattern_count, 3);
+}
+
+#[test]
+fn single_type_fast_path() {
+    let wordlist = [
+        "你好,世界",
+        "无,法,无,天",
+        "ni,hao",
+        "nihao",
+        "xian",
+        "西安",
+        "测 试",
+        "ABC",
+        "你好~世界",
+    ];
+    let text_list = [
+        "你好，世界",
+        "無法無天",
+        "你 好 世 界",
+        "西安",
+        "先",
+        "測 試",
+        "ａｂｃ",
+        "你好",
+        "",
+    ];
+
+    for simple_match_type in [
+        SimpleMatchType::None,
+        SimpleMatchType::Fanjian,
+        SimpleMatchType::Delete,
+        SimpleMatchType::Normalize,
+        SimpleMatchType::DeleteNormalize,
+        SimpleMatchType::FanjianDeleteNormalize,
+        SimpleMatchType::PinYin,
+        SimpleMatchType::PinYinChar,
+        SimpleMatchType::Fanjian | SimpleMatchType::PinYin,
+    ] {
+        let simple_wordlist_dict = AHashMap::from([(
+            simple_match_type,
+            wordlist
+                .iter()
+                .enumerate()
+                .map(|(word_id, &word)| SimpleWord {
+                    word_id: word_id as u64,
+                    word,
+                })
+                .collect(),
+        )]);
+
+        for variant_strategy in [VariantStrategy::AllStages, VariantStrategy::FinalOnly] {
+            let simple_matcher = SimpleMatcher::new_with_options(
+                &simple_wordlist_dict,
+                SimpleMatcherOptions {
+                    variant_strategy,
+                    ..Default::default()
+                },
+            );
+
+            // process走单一匹配类型的快速路径，process_processed按转换树生成变体，两者结果一致
+            for text in text_list {
+                let word_id_list = |simple_result_list: Vec<SimpleResult>| {
+                    let mut word_id_list: Vec<u64> = simple_result_list
+                        .iter()
+                        .map(|simple_result| simple_result.word_id)
+                        .collect();
+                    word_id_list.sort_unstable();
+                    word_id_list
+                };
+                assert_eq!(
+                    word_id_list(
+                        simple_matcher.process_processed(&simple_matcher.preprocess(text))
+                    ),
+                    word_id_list(simple_matcher.process(text)),
+                    "{simple_match_type:?} {variant_strategy:?} {text}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn match_result_fingerprint() {
+    let new_match_table_dict = || {
+        AHashMap::from([
+            (
+                "test",
+                vec![
+                    MatchTable {
+                        table_id: 1,
+                        match_table_type: MatchTableType::Simple,
+                        wordlist: VarZeroVec::from(&["你好", "世界", "测,试"]),
+                        simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                        ..Default::default()
+                    },
+                    MatchTable {
+                        table_id: 2,
+                        match_table_type: MatchTableType::Regex,
+                        wordlist: VarZeroVec::from(&["^你好"]),
+                        ..Default::default()
+                    },
+                ],
+            ),
+            (
+                "other",
+                vec![MatchTable {
+                    table_id: 1,
+                    match_table_type: MatchTableType::Simple,
+                    wordlist: VarZeroVec::from(&["你好"]),
+                    ..Default::default()
+                }],
+            ),
+        ])
+    };
+    let options = MatcherOptions {
+        result_fingerprint: true,
+        ..Default::default()
+    };
+    let matcher = Matcher::new_with_options(&new_match_table_dict(), options.clone());
+    let rebuilt_matcher = Matcher::new_with_options(&new_match_table_dict(), options);
+
+    // 算法固定，跨版本不变
+    assert_eq!(8886440068595581056, match_fingerprint("test", 1, "你好"));
+
+    let fingerprint_list = |matcher: &Matcher| {
+        let mut fingerprint_list: Vec<(String, u64)> = matcher
+            .word_match_full("你好世界，测一下试试")
+            .matched
+            .into_iter()
+            .flat_map(|(match_id, result_list)| {
+                result_list.into_iter().map(move |match_result| {
+                    let fingerprint = serde_json::to_value(&match_result).unwrap()["fingerprint"]
+                        .as_u64()
+                        .unwrap();
+                    assert_eq!(fingerprint, match_result.fingerprint(match_id));
+                    (match_id.to_owned(), fingerprint)
+                })
+            })
+            .collect();
+        fingerprint_list.sort_unstable();
+        fingerprint_list
+    };
+    let expected_fingerprint_list = fingerprint_list(&matcher);
+    assert_eq!(expected_fingerprint_list.len(), 5);
+    assert_eq!(
+        expected_fingerprint_list,
+        fingerprint_list(&rebuilt_matcher)
+    );
+    // 相同的词在不同的match_id以及词表中指纹不同
+    assert_eq!(
+        expected_fingerprint_list.len(),
+        expected_fingerprint_list
+            .iter()
+            .map(|(_, fingerprint)| fingerprint)
+            .collect::<AHashSet<_>>()
+            .len()
+    );
+
+    // 默认不附带指纹
+    assert!(!Matcher::new(&new_match_table_dict())
+        .word_match_as_string("你好")
+        .contains("fingerprint"));
+}
+
+#[test]
+fn rule_bundle_segmented_round_trip() {
+    // 词数超过单个分段的目标词数，词表被拆分为多个分段
+    let table_map = OwnedMatchTableDict(
+        (0..100)
+            .map(|match_index| {
+                (
+                    format!("match_{match_index}"),
+                    vec![OwnedMatchTable {
+                        table_id: match_index,
+                        match_table_type: MatchTableType::Simple,
+                        wordlist: (0..1000)
+                            .map(|word_index| format!("词{match_index}_{word_index}"))
+                            .collect(),
+                        exemption_wordlist: vec![format!("豁免{match_index}")],
+                        simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                        ..Default::default()
+                    }],
+                )
+            })
+            .collect(),
+    );
+    let rule_bundle = RuleBundle::new(table_map, MatcherOptions::default());
+    let bundle_bytes = rule_bundle.to_bytes(BundleCompression::None).unwrap();
+
+    let loaded_rule_bundle = RuleBundle::from_bytes(&bundle_bytes).unwrap();
+    assert_eq!(
+        serde_json::to_value(&rule_bundle.table_map).unwrap(),
+        serde_json::to_value(&loaded_rule_bundle.table_map).unwrap()
+    );
+    assert!(Matcher::from_bundle(&loaded_rule_bundle).is_match("詞42_420"));
+
+    // 截断的分段被拒绝
+    assert_eq!(
+        Err(RuleBundleError::InvalidHeader),
+        RuleBundle::from_bytes(&bundle_bytes[..bundle_bytes.len() - 1]).map(|_| ())
+    );
+}
+
+#[cfg(all(
+    feature = "fanjian",
+    feature = "delete",
+    feature = "normalize",
+    feature = "pinyin"
+))]
+#[test]
+fn builtin_process_pair_list_digest() {
+    // FNV-1a 64，键值以\t分隔、映射之间以\n分隔
+    let digest = |process_pair_list: Vec<(&str, &str)>| {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for (key, value) in process_pair_list {
+            for &byte in [key.as_bytes(), b"\t", value.as_bytes(), b"\n"]
+                .concat()
+                .iter()
+            {
+                hash ^= byte as u64;
+                hash = hash.wrapping_mul(0x100000001b3);
+            }
+        }
+        hash
+    };
+
+    // 内置映射表的模式顺序与环境无关，转换数据变更时需同步更新
+    for (str_conv_type, expected_digest) in [
+        (SimpleMatchType::Fanjian, 9713159506757883064),
+        (SimpleMatchType::WordDelete, 7441971964614817166),
+        (SimpleMatchType::TextDelete, 1049208999786814400),
+        (SimpleMatchType::Normalize, 15043456128002465512),
+        (SimpleMatchType::PinYin, 14294181195496041637),
+        (SimpleMatchType::PinYinChar, 6792766805638209941),
+    ] {
+        let process_pair_list = SimpleMatcher::builtin_process_pair_list(str_conv_type);
+        assert_eq!(
+            process_pair_list,
+            SimpleMatcher::builtin_process_pair_list(str_conv_type)
+        );
+        assert_eq!(
+            expected_digest,
+            digest(process_pair_list),
+            "{str_conv_type:?}"
+        );
+    }
+}
+
+fn assert_simple_is_match(cases: &[(SimpleMatchType, &str, &str, bool)]) {
+    for &(simple_match_type, word, text, expected) in cases {
+        let simple_matcher = SimpleMatcher::new(&AHashMap::from([(
+            simple_match_type,
+            vec![SimpleWord { word_id: 1, word }],
+        )]));
+        assert_eq!(
+            expected,
+            simple_matcher.is_match(text),
+            "{simple_match_type:?} {word:?} {text:?}"
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "delete")]
+fn simple_match_empty_variant() {
+    // 删除归一后为空的部分是空模式，不能在任意文本上命中
+    assert_simple_is_match(&[
+        (SimpleMatchType::Delete, " ", "abc", false),
+        (SimpleMatchType::Delete, " ", "", false),
+        (SimpleMatchType::Delete, "abc~ ", "abc", true),
+    ]);
+}
+
+#[test]
+#[cfg(feature = "normalize")]
+fn simple_match_case_duplicate_variant() {
+    // ac自动机大小写不敏感，"Ab" 与归一后的 "ab" 在同一位置只计一次，重复部分需要两处命中
+    assert_simple_is_match(&[
+        (SimpleMatchType::Normalize, "Ab,Ab", "xAbx", false),
+        (SimpleMatchType::Normalize, "Ab,Ab", "xAbxab", true),
+        (SimpleMatchType::Normalize, "Ab", "xaBx", true),
+    ]);
+}
+
+#[test]
+#[cfg(all(feature = "fanjian", feature = "delete", feature = "normalize"))]
+fn simple_match_min_text_len() {
+    // 删除与归一后词可能比原文本更短，不能按原词过滤短文本
+    assert_simple_is_match(&[
+        (SimpleMatchType::DeleteNormalize, "* ", "*", true),
+        (SimpleMatchType::Normalize, "10", "⑩", true),
+        (SimpleMatchType::None, "A,a", "a", true),
+        (SimpleMatchType::Fanjian, "測試", "测试", true),
+        (SimpleMatchType::Fanjian, "測試", "测", false),
+    ]);
+
+    let min_text_len =
+        |simple_match_type: SimpleMatchType, word: &str, options: SimpleMatcherOptions| {
+            SimpleMatcher::new_with_options(
+                &AHashMap::from([(simple_match_type, vec![SimpleWord { word_id: 1, word }])]),
+                options,
+            )
+            .min_text_len()
+        };
+    // 繁简、删除归一逐字符替换或删除，不会使文本变长，保留最小长度的限制
+    assert_eq!(
+        4,
+        min_text_len(SimpleMatchType::Fanjian, "無法無天,人", Default::default())
+    );
+    assert_eq!(
+        2,
+        min_text_len(
+            SimpleMatchType::Fanjian | SimpleMatchType::Delete,
+            "a b~cd",
+            Default::default()
+        )
+    );
+    assert_eq!(
+        2,
+        min_text_len(SimpleMatchType::None, "Ab,aB", Default::default())
+    );
+    // 归一可能使文本变长，eg. "⑩" -> "10"
+    assert_eq!(
+        1,
+        min_text_len(SimpleMatchType::Normalize, "abcd", Default::default())
+    );
+    assert_eq!(
+        1,
+        min_text_len(
+            SimpleMatchType::FanjianDeleteNormalize,
+            "無法無天",
+            Default::default()
+        )
+    );
+    // 覆盖的映射表使繁简变长时，同样不限制
+    assert_eq!(
+        1,
+        min_text_len(
+            SimpleMatchType::Fanjian,
+            "無法無天",
+            SimpleMatcherOptions {
+                process_dict_override: AHashMap::from([(
+                    SimpleMatchType::Fanjian,
+                    vec![("天".to_owned(), "天天".to_owned())],
+                )]),
+                ..Default::default()
+            }
+        )
+    );
+}
+
+#[test]
+fn matcher_report_span() {
+    let match_table_dict = AHashMap::from([(
+        "test",
+        vec![
+            MatchTable {
+                table_id: 1,
+                match_table_type: MatchTableType::Simple,
+                wordlist: VarZeroVec::from(&["你好", "测,试"]),
+                simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                ..Default::default()
+            },
+            MatchTable {
+                table_id: 2,
+                match_table_type: MatchTableType::Regex,
+                wordlist: VarZeroVec::from(&["w.rld"]),
+                ..Default::default()
+            },
+        ],
+    )]);
+    let matcher = Matcher::new_with_options(
+        &match_table_dict,
+        MatcherOptions {
+            report_span: true,
+            max_input_bytes: Some(24),
+            over_limit_policy: OverLimitPolicy::ChunkAndUnion { overlap_bytes: 6 },
+            ..Default::default()
+        },
+    );
+    let span_list = |matcher: &Matcher, text: &str| {
+        let mut span_list: Vec<_> =
+            serde_json::from_str::<Vec<serde_json::Value>>(&matcher.word_match(text)["test"])
+                .unwrap()
+                .into_iter()
+                .map(|match_result| {
+                    (
+                        match_result["word"].as_str().unwrap().to_owned(),
+                        match_result["start"].as_u64(),
+                        match_result["end"].as_u64(),
+                        match_result["variant"].as_u64(),
+                    )
+                })
+                .collect();
+        span_list.sort();
+        span_list
+    };
+
+    assert_eq!(
+        vec![
+            ("w.rld".to_owned(), Some(13), Some(18), Some(0)),
+            ("你好".to_owned(), Some(6), Some(12), Some(0)),
+        ],
+        span_list(&matcher, "hello 你好 world")
+    );
+
+    // 第二个窗口从18开始，窗口内的位置换算为原文本中的位置
+    assert_eq!(
+        vec![("你好".to_owned(), Some(30), Some(36), Some(0))],
+        span_list(&matcher, &("a".repeat(30) + "你好"))
+    );
+
+    // 只在删除归一后的文本变体中命中，位置在该变体中
+    let span = span_list(&matcher, "你 好").pop().unwrap();
+    assert_eq!((Some(0), Some(6)), (span.1, span.2));
+    assert_ne!(Some(0), span.3);
+
+    // 默认不返回位置
+    assert_eq!(
+        vec![("你好".to_owned(), None, None, None)],
+        span_list(&Matcher::new(&match_table_dict), "你好")
+    );
+}
+
+#[test]
+fn simple_add_remove_word() {
+    let simple_wordlist_dict = AHashMap::from([(
+        SimpleMatchType::None,
+        vec![
+            SimpleWord {
+                word_id: 1,
+                word: "你好",
+            },
+            SimpleWord {
+                word_id: 2,
+                word: "无,法,无,天",
+            },
+        ],
+    )]);
+    let mut simple_matcher = SimpleMatcher::new_with_options(
+        &simple_wordlist_dict,
+        SimpleMatcherOptions {
+            prefilter: true,
+            ..Default::default()
+        },
+    );
+    let origin_matcher = simple_matcher.clone();
+    let word_id_list = |simple_matcher: &SimpleMatcher, text: &str| {
+        let mut word_id_list: Vec<u64> = simple_matcher
+            .process(text)
+            .iter()
+            .map(|simple_result| simple_result.word_id)
+            .collect();
+        word_id_list.sort_unstable();
+        word_id_list
+    };
+
+    // 删除立即生效
+    assert!(simple_matcher.remove_word(1));
+    assert!(!simple_matcher.remove_word(1));
+    assert!(!simple_matcher.is_match("你好"));
+    assert!(simple_matcher.has_pending_changes());
+
+    // 新增在rebuild后生效，组合词以及否定部分与构建时一致
+    simple_matcher
+        .add_word(SimpleMatchType::None, 3, "hello,world~bye")
+        .unwrap();
+    simple_matcher
+        .add_word(SimpleMatchType::Fanjian, 4, "国家")
+        .unwrap();
+    assert!(!simple_matcher.is_match("hello world"));
+    simple_matcher.rebuild().unwrap();
+    assert!(!simple_matcher.has_pending_changes());
+    assert_eq!(vec![3], word_id_list(&simple_matcher, "world, hello"));
+    assert!(!simple_matcher.is_match("hello world, bye"));
+    assert_eq!(vec![4], word_id_list(&simple_matcher, "國家"));
+    assert_eq!(vec![2], word_id_list(&simple_matcher, "无无法天"));
+    assert!(!simple_matcher.is_match("无法天"));
+
+    // 已存在的词ID替换原词，原词立即失效
+    simple_matcher
+        .add_word(SimpleMatchType::None, 2, "无法")
+        .unwrap();
+    assert!(!simple_matcher.is_match("无无法天"));
+    simple_matcher.rebuild().unwrap();
+    assert_eq!(vec![2], word_id_list(&simple_matcher, "无法"));
+
+    // 尚未生效的新增词也可以删除
+    simple_matcher
+        .add_word(SimpleMatchType::None, 5, "再见")
+        .unwrap();
+    assert!(simple_matcher.remove_word(5));
+    assert!(simple_matcher.remove_word(4));
+    simple_matcher.rebuild().unwrap();
+    assert!(!simple_matcher.is_match("再见"));
+    assert!(!simple_matcher.is_match("國家"));
+    assert_eq!(vec![3], word_id_list(&simple_matcher, "hello world"));
+
+    // clone出的匹配器不受影响
+    assert!(!origin_matcher.ptr_eq(&simple_matcher));
+    assert_eq!(vec![1], word_id_list(&origin_matcher, "你好"));
+    assert!(!origin_matcher.is_match("hello world"));
+
+    // 不可用的匹配类型在新增时报错
+    if !SimpleMatchType::builtin_unavailable().is_empty() {
+        assert!(simple_matcher
+            .add_word(SimpleMatchType::builtin_unavailable(), 6, "你好")
+            .is_err());
+    }
+}
+
+#[test]
+fn simple_process_iter() {
+    let simple_wordlist_dict = AHashMap::from([
+        (
+            SimpleMatchType::FanjianDeleteNormalize,
+            vec![
+                SimpleWord {
+                    word_id: 1,
+                    word: "你好",
+                },
+                SimpleWord {
+                    word_id: 2,
+                    word: "无,法,无,天",
+                },
+                SimpleWord {
+                    word_id: 3,
+                    word: "hello~world",
+                },
+            ],
+        ),
+        (
+            SimpleMatchType::None,
+            vec![SimpleWord {
+                word_id: 4,
+                word: "bye",
+            }],
+        ),
+    ]);
+    let word_id_list = |simple_result_list: Vec<SimpleResult>| {
+        let mut word_id_list: Vec<u64> = simple_result_list
+            .iter()
+            .map(|simple_result| simple_result.word_id)
+            .collect();
+        word_id_list.sort_unstable();
+        word_id_list
+    };
+
+    // 多匹配类型走转换树，单匹配类型直接链式转换
+    for simple_matcher in [
+        SimpleMatcher::new(&simple_wordlist_dict),
+        SimpleMatcher::new(&AHashMap::from([(
+            SimpleMatchType::FanjianDeleteNormalize,
+            simple_wordlist_dict[&SimpleMatchType::FanjianDeleteNormalize]
+                .iter()
+                .map(|simple_word| SimpleWord {
+                    word_id: simple_word.word_id,
+                    word: simple_word.word,
+                })
+                .collect(),
+        )])),
+    ] {
+        for text in [
+            "",
+            "你好",
+            "妳 好",
+            "无无法天 hello bye",
+            "hello world 你好",
+            "HELLO",
+            "无法天",
+        ] {
+            assert_eq!(
+                word_id_list(simple_matcher.process(text)),
+                word_id_list(simple_matcher.process_iter(text).collect()),
+                "text: {text}"
+            );
+        }
+    }
+
+    // 提前停止时不扫描剩余的长文本
+    let simple_matcher = SimpleMatcher::new(&AHashMap::from([(
+        SimpleMatchType::None,
+        vec![SimpleWord {
+            word_id: 1,
+            word: "你好",
+        }],
+    )]));
+    let text = "你好".to_owned() + &"a".repeat(1 << 24);
+
+    // 并行运行的测试可能使单次计时偏大，取多次中的最小值
+    let iter_duration = (0..5)
+        .map(|_| {
+            let start = std::time::Instant::now();
+            assert_eq!(
+                1,
+                simple_matcher.process_iter(&text).next().unwrap().word_id
+            );
+            start.elapsed()
+        })
+        .min()
+        .unwrap();
+
+    let start = std::time::Instant::now();
+    assert_eq!(1, simple_matcher.process(&text).len());
+    let process_duration = start.elapsed();
+
+    assert!(
+        iter_duration * 10 < process_duration,
+        "{iter_duration:?} vs {process_duration:?}"
+    );
+}
+
+#[test]
+fn simple_is_match_early_exit() {
+    let simple_matcher = SimpleMatcher::new(&AHashMap::from([
+        (
+            SimpleMatchType::FanjianDeleteNormalize,
+            vec![
+                SimpleWord {
+                    word_id: 1,
+                    word: "你好~坏人",
+                },
+                SimpleWord {
+                    word_id: 2,
+                    word: "无,法,无,天",
+                },
+            ],
+        ),
+        (
+            SimpleMatchType::None,
+            vec![SimpleWord {
+                word_id: 3,
+                word: "bye",
+            }],
+        ),
+    ]));
+    let other_matcher = SimpleMatcher::new(&AHashMap::from([(
+        SimpleMatchType::Fanjian,
+        vec![SimpleWord {
+            word_id: 1,
+            word: "你好",
+        }],
+    )]));
+
+    // 否定部分只在删除归一后的变体中出现，仍需丢弃
+    for text in [
+        "你好",
+        "你好 坏 人",
+        "你好 坏 人 bye",
+        "无无法天",
+        "无法天",
+        "",
+        "bye",
+    ] {
+        let is_match = !simple_matcher.process(text).is_empty();
+        assert_eq!(is_match, simple_matcher.is_match(text), "text: {text}");
+
+        // 同一转换树生成的变体直接复用，其他转换树生成的变体重新生成
+        let processed_text = simple_matcher.preprocess(text);
+        assert_eq!(
+            is_match,
+            simple_matcher.is_match_processed(&processed_text),
+            "text: {text}"
+        );
+        let other_processed_text = other_matcher.preprocess(text);
+        assert_eq!(
+            is_match,
+            simple_matcher.is_match_processed(&other_processed_text),
+            "text: {text}"
+        );
+    }
+}
+
+#[test]
+fn find_first() {
+    let simple_matcher = SimpleMatcher::new(&AHashMap::from([(
+        SimpleMatchType::FanjianDeleteNormalize,
+        vec![
+            SimpleWord {
+                word_id: 1,
+                word: "你好~坏人",
+            },
+            SimpleWord {
+                word_id: 2,
+                word: "世界",
+            },
+        ],
+    )]));
+    for text in ["你好", "你好 坏 人", "你好世界", "再见", ""] {
+        let result_list = simple_matcher.process(text);
+        match simple_matcher.find_first(text) {
+            Some(result) => assert!(
+                result_list
+                    .iter()
+                    .any(|simple_result| simple_result.word_id == result.word_id),
+                "text: {text}"
+            ),
+            None => assert!(result_list.is_empty(), "text: {text}"),
+        }
+    }
+
+    let regex_wordlist = VarZeroVec::from(&["h.llo", "w.rld"]);
+    let regex_matcher = RegexMatcher::new(&vec![RegexTable {
+        table_id: 1,
+        match_id: "1",
+        match_table_type: &MatchTableType::Regex,
+        wordlist: &regex_wordlist,
+        acrostic_position: AcrosticPosition::Initial,
+        prefer_matched_text: false,
+        regex_flags: RegexFlags::default(),
+        acrostic_separator: AcrosticSeparator::Punctuation,
+    }]);
+    assert_eq!("w.rld", regex_matcher.find_first("say world").unwrap().word);
+    assert!(regex_matcher.find_first("say hi").is_none());
+
+    // 返回扫描顺序中第一个达到阈值的结果，不一定是相似度最高的结果
+    let sim_wordlist = VarZeroVec::from(&["hello worl", "hello world"]);
+    let sim_matcher = SimMatcher::new(&vec![SimTable {
+        table_id: 1,
+        match_id: "1",
+        wordlist: &sim_wordlist,
+        threshold: DEFAULT_SIM_THRESHOLD,
+        simple_match_type: SimpleMatchType::None,
+        process_words: true,
+        sim_match_type: SimMatchType::Levenshtein,
+        top_k: None,
+    }]);
+    assert_eq!(1, sim_matcher.process("hello world")[0].word_id);
+    assert_eq!(0, sim_matcher.find_first("hello world").unwrap().word_id);
+    assert!(sim_matcher.find_first("你好").is_none());
+
+    let match_table_dict = AHashMap::from([
+        (
+            "a",
+            vec![MatchTable {
+                table_id: 1,
+                match_table_type: MatchTableType::Simple,
+                wordlist: VarZeroVec::from(&["你好"]),
+                exemption_wordlist: VarZeroVec::from(&["坏人"]),
+                simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                ..Default::default()
+            }],
+        ),
+        (
+            "b",
+            vec![MatchTable {
+                table_id: 2,
+                match_table_type: MatchTableType::Regex,
+                wordlist: VarZeroVec::from(&["w.rld"]),
+                ..Default::default()
+            }],
+        ),
+    ]);
+    let matcher = Matcher::new(&match_table_dict);
+    let first_word = |text: &str| {
+        matcher
+            .find_first(text)
+            .map(|match_result| serde_json::to_value(match_result).unwrap()["word"].clone())
+    };
+
+    // simple结果先于regex结果，被豁免的match_id不返回结果
+    assert_eq!(Some("你好".into()), first_word("你好 world"));
+    assert_eq!(Some("w.rld".into()), first_word("你好 坏人 world"));
+    assert_eq!(None, first_word("你好 坏人"));
+    assert_eq!(None, first_word(""));
+}
+
+#[test]
+fn match_count() {
+    let match_table_dict = AHashMap::from([
+        (
+            "a",
+            vec![
+                MatchTable {
+                    table_id: 1,
+                    match_table_type: MatchTableType::Simple,
+                    wordlist: VarZeroVec::from(&["你好", "世界", "你,界~坏"]),
+                    exemption_wordlist: VarZeroVec::from(&["坏人"]),
+                    simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                    ..Default::default()
+                },
+                MatchTable {
+                    table_id: 2,
+                    match_table_type: MatchTableType::Regex,
+                    wordlist: VarZeroVec::from(&["w.rld", "h.llo"]),
+                    ..Default::default()
+                },
+            ],
+        ),
+        (
+            "b",
+            vec![
+                MatchTable {
+                    table_id: 3,
+                    match_table_type: MatchTableType::SimilarTextLevenshtein,
+                    wordlist: VarZeroVec::from(&["hello world"]),
+                    ..Default::default()
+                },
+                MatchTable {
+                    table_id: 4,
+                    match_table_type: MatchTableType::Simple,
+                    wordlist: VarZeroVec::from(&["hello", "你好"]),
+                    ..Default::default()
+                },
+            ],
+        ),
+    ]);
+    let word_match_count =
+        |matcher: &Matcher, text: &str| -> std::collections::HashMap<String, usize> {
+            matcher
+                .word_match(text)
+                .into_iter()
+                .map(|(match_id, result)| {
+                    (
+                        match_id.to_owned(),
+                        serde_json::from_str::<Vec<serde_json::Value>>(&result)
+                            .unwrap()
+                            .len(),
+                    )
+                })
+                .collect()
+        };
+
+    // 超长文本分块匹配时重叠区内的词只计一次
+    let chunk_matcher = Matcher::new_with_options(
+        &match_table_dict,
+        MatcherOptions {
+            max_input_bytes: Some(24),
+            over_limit_policy: OverLimitPolicy::ChunkAndUnion { overlap_bytes: 6 },
+            ..Default::default()
+        },
+    );
+    for matcher in [&Matcher::new(&match_table_dict), &chunk_matcher] {
+        for text in [
+            "hello world",
+            "你好 世界 hello world",
+            "你好 世界 坏人 hello world",
+            "你 世 界 坏",
+            "再见",
+            "",
+            "aaaaaaaaaaaaaaaaaaaaaaaa你好 world",
+        ] {
+            let expected_count_dict = word_match_count(matcher, text);
+            let count_dict: std::collections::HashMap<String, usize> = matcher
+                .word_match_count(text)
+                .into_iter()
+                .map(|(match_id, count)| (match_id.to_owned(), count))
+                .collect();
+            assert_eq!(expected_count_dict, count_dict, "text: {text}");
+            assert_eq!(
+                expected_count_dict.values().sum::<usize>(),
+                matcher.match_count(text),
+                "text: {text}"
+            );
+        }
+    }
+
+    let simple_matcher = SimpleMatcher::new(&AHashMap::from([(
+        SimpleMatchType::FanjianDeleteNormalize,
+        vec![
+            SimpleWord {
+                word_id: 1,
+                word: "你好~坏人",
+            },
+            SimpleWord {
+                word_id: 2,
+                word: "世,界",
+            },
+        ],
+    )]));
+    for text in ["你好 世界", "你好 坏 人 世界", "再见", ""] {
+        assert_eq!(
+            simple_matcher.process(text).len(),
+            simple_matcher.match_count(text),
+            "text: {text}"
+        );
+    }
+}
+
+#[test]
+fn redact() {
+    let simple_matcher = SimpleMatcher::new(&AHashMap::from([(
+        SimpleMatchType::FanjianDeleteNormalize,
+        vec![
+            SimpleWord {
+                word_id: 1,
+                word: "你好",
+            },
+            SimpleWord {
+                word_id: 2,
+                word: "好世界",
+            },
+            SimpleWord {
+                word_id: 3,
+                word: "测,试~坏人",
+            },
+            SimpleWord {
+                word_id: 4,
+                word: "再见",
+            },
+        ],
+    )]));
+
+    // 重叠的命中词合并替换
+    assert_eq!("说****!", simple_matcher.redact("说你好世界!", '*'));
+    // 组合词的各部分都会被替换，否定部分命中时整个词不命中
+    assert_eq!("#是一个#!", simple_matcher.redact("测是一个试!", '#'));
+    assert_eq!("测试坏人", simple_matcher.redact("测试坏人", '*'));
+    // 只在转换后的变体中出现的部分无法对应回原文本
+    assert_eq!("再 见, **", simple_matcher.redact("再 见, 你好", '*'));
+    assert!(matches!(
+        simple_matcher.redact("没有命中", '*'),
+        std::borrow::Cow::Borrowed("没有命中")
+    ));
+
+    let match_table_dict = AHashMap::from([
+        (
+            "a",
+            vec![MatchTable {
+                table_id: 1,
+                match_table_type: MatchTableType::Simple,
+                wordlist: VarZeroVec::from(&["你好", "测,试"]),
+                exemption_wordlist: VarZeroVec::from(&["坏人"]),
+                simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                ..Default::default()
+            }],
+        ),
+        (
+            "b",
+            vec![MatchTable {
+                table_id: 2,
+                match_table_type: MatchTableType::Regex,
+                wordlist: VarZeroVec::from(&["w.rld"]),
+                ..Default::default()
+            }],
+        ),
+    ]);
+    let matcher = Matcher::new(&match_table_dict);
+    assert_eq!("** *****", matcher.redact("你好 world", '*'));
+    assert_eq!("** *****, *是*", matcher.redact("你好 world, 测是试", '*'));
+    // 被豁免的match_id不替换
+    assert_eq!("你好 坏人 *****", matcher.redact("你好 坏人 world", '*'));
+    assert_eq!("", matcher.redact("", '*'));
+}
+
+#[test]
+fn highlight() {
+    let match_table_dict = AHashMap::from([
+        (
+            "a",
+            vec![MatchTable {
+                table_id: 1,
+                match_table_type: MatchTableType::Simple,
+                wordlist: VarZeroVec::from(&["你好", "hello", "测,试"]),
+                exemption_wordlist: VarZeroVec::from(&["坏人"]),
+                simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                ..Default::default()
+            }],
+        ),
+        (
+            "b",
+            vec![MatchTable {
+                table_id: 2,
+                match_table_type: MatchTableType::Regex,
+                wordlist: VarZeroVec::from(&["你好世界", "a*"]),
+                ..Default::default()
+            }],
+        ),
+    ]);
+    let segment_list = |matcher: &Matcher, text: &str| -> Vec<(String, Option<String>)> {
+        let segment_list = matcher.highlight(text);
+        assert_eq!(
+            text,
+            segment_list
+                .iter()
+                .map(|segment| segment.text)
+                .collect::<String>()
+        );
+        segment_list
+            .into_iter()
+            .map(|segment| {
+                (
+                    segment.text.to_owned(),
+                    segment.hit.map(|match_result| {
+                        serde_json::to_value(match_result).unwrap()["word"]
+                            .as_str()
+                            .unwrap()
+                            .to_owned()
+                    }),
+                )
+            })
+            .collect()
+    };
+    let matcher = Matcher::new(&match_table_dict);
+
+    // 重叠的命中合并为一个片段，取位置最长的命中；位于首尾的命中以及相邻的命中各自成段
+    assert_eq!(
+        vec![
+            ("你好世界".to_owned(), Some("你好世界".to_owned())),
+            ("hello".to_owned(), Some("hello".to_owned())),
+            ("!".to_owned(), None),
+        ],
+        segment_list(&matcher, "你好世界hello!")
+    );
+    // 组合词的各部分分别成段，空匹配不成段
+    assert_eq!(
+        vec![
+            ("测".to_owned(), Some("测,试".to_owned())),
+            ("一下".to_owned(), None),
+            ("试".to_owned(), Some("测,试".to_owned())),
+        ],
+        segment_list(&matcher, "测一下试")
+    );
+    // 被豁免的match_id不成段
+    assert_eq!(
+        vec![("你好坏人".to_owned(), None)],
+        segment_list(&matcher, "你好坏人")
+    );
+    assert!(matcher.highlight("").is_empty());
+
+    // 统一换行后的命中位置换算回原文本
+    let matcher = Matcher::new_with_options(
+        &match_table_dict,
+        MatcherOptions {
+            normalize_newlines: true,
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        vec![
+            ("\r\n".to_owned(), None),
+            ("你好".to_owned(), Some("你好".to_owned())),
+            ("\u{2028}".to_owned(), None),
+            ("hello".to_owned(), Some("hello".to_owned())),
+            ("\r".to_owned(), None),
+        ],
+        segment_list(&matcher, "\r\n你好\u{2028}hello\r")
+    );
+}
+
+#[test]
+fn matcher_process_batch() {
+    let match_table_dict = AHashMap::from([(
+        "test",
+        vec![
+            MatchTable {
+                table_id: 1,
+                match_table_type: MatchTableType::Simple,
+                wordlist: VarZeroVec::from(&["你好", "测,试"]),
+                exemption_wordlist: VarZeroVec::from(&["坏人"]),
+                simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                ..Default::default()
+            },
+            MatchTable {
+                table_id: 2,
+                match_table_type: MatchTableType::Regex,
+                wordlist: VarZeroVec::from(&["w.rld"]),
+                ..Default::default()
+            },
+        ],
+    )]);
+    let text_list: Vec<String> = (0..200)
+        .map(|index| match index % 4 {
+            0 => format!("{index} 你好"),
+            1 => format!("{index} 测一下试 world"),
+            2 => format!("{index} 你好 坏人"),
+            _ => format!("{index}"),
+        })
+        .collect();
+    let text_list: Vec<&str> = text_list.iter().map(String::as_str).collect();
+
+    // 低于并行阈值逐条处理，否则并行处理，两者结果均与逐条调用一致且保持输入顺序
+    for min_parallel_batch in [1, usize::MAX] {
+        let matcher = Matcher::new_with_options(
+            &match_table_dict,
+            MatcherOptions {
+                min_parallel_batch,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(
+            text_list
+                .iter()
+                .map(|&text| serde_json::to_string(&matcher.process(text)).unwrap())
+                .collect::<Vec<String>>(),
+            matcher
+                .process_batch(&text_list)
+                .iter()
+                .map(|result_list| serde_json::to_string(result_list).unwrap())
+                .collect::<Vec<String>>()
+        );
+        assert_eq!(
+            text_list
+                .iter()
+                .map(|&text| matcher.is_match(text))
+                .collect::<Vec<bool>>(),
+            matcher.is_match_batch(&text_list)
+        );
+        assert!(matcher.process_batch(&[]).is_empty());
+    }
+}
+
+#[test]
+fn matcher_try_new_collects_errors() {
+    let table = |table_id, match_table_type, wordlist: &[&str]| MatchTable {
+        table_id,
+        match_table_type,
+        wordlist: VarZeroVec::from(wordlist),
+        ..Default::default()
+    };
+
+    let match_table_dict = AHashMap::from([(
+        "test",
+        vec![
+            table(1, MatchTableType::Simple, &["你好", ""]),
+            table(1, MatchTableType::Simple, &["世界"]),
+        ],
+    )]);
+    match Matcher::try_new(&match_table_dict) {
+        Err(MatcherError::MultipleErrors { error_list }) => {
+            assert_eq!(error_list.len(), 2);
+            assert!(error_list
+                .iter()
+                .any(|error| matches!(error, MatcherError::DuplicateTableId { table_id: 1, .. })));
+            assert!(error_list
+                .iter()
+                .any(|error| matches!(error, MatcherError::EmptyWord { table_id: 1, .. })));
+        }
+        _ => panic!("expected MultipleErrors"),
+    }
+
+    let invalid_regex_match_table_dict = AHashMap::from([(
+        "test",
+        vec![table(1, MatchTableType::Regex, &["(", "你好"])],
+    )]);
+    let Err(error) = Matcher::try_new(&invalid_regex_match_table_dict) else {
+        panic!("expected InvalidRegex");
+    };
+    assert!(matches!(
+        &error,
+        MatcherError::InvalidRegex { table_id: 1, pattern, .. } if pattern == "("
+    ));
+    // source为fancy_regex的原始错误
+    let source = std::error::Error::source(&error).unwrap();
+    assert!(source.downcast_ref::<RegexCompileError>().is_some());
+    assert!(error.to_string().ends_with(&source.to_string()));
+
+    // 每个无法编译的正则各返回一个错误
+    let invalid_regex_match_table_dict = AHashMap::from([(
+        "test",
+        vec![table(1, MatchTableType::Regex, &["(", "[", "你好"])],
+    )]);
+    match Matcher::try_new(&invalid_regex_match_table_dict) {
+        Err(MatcherError::MultipleErrors { error_list }) => {
+            assert_eq!(
+                vec!["(", "["],
+                error_list
+                    .iter()
+                    .map(|error| match error {
+                        MatcherError::InvalidRegex { pattern, .. } => pattern.as_str(),
+                        _ => panic!("expected InvalidRegex"),
+                    })
+                    .collect::<Vec<&str>>()
+            );
+        }
+        _ => panic!("expected MultipleErrors"),
+    }
+
+    let (matcher, _, skipped_table_list) = Matcher::try_new_with_mode(
+        &match_table_dict,
+        MatcherOptions::default(),
+        BuildMode::Lenient,
+    )
+    .unwrap();
+    assert!(skipped_table_list.iter().any(|skipped_table| matches!(
+        skipped_table.reason,
+        MatcherError::DuplicateTableId { table_id: 1, .. }
+    )));
+    assert!(!matcher.is_match("世界"));
+}
+
+#[test]
+fn sim_invalid_threshold() {
+    let wordlist = VarZeroVec::from(&["你好世界"]);
+    let sim_table = |threshold| SimTable {
+        table_id: 1,
+        match_id: "test",
+        wordlist: &wordlist,
+        threshold,
+        simple_match_type: SimpleMatchType::None,
+        process_words: false,
+        sim_match_type: SimMatchType::Levenshtein,
+        top_k: None,
+    };
+
+    for threshold in [0.0, DEFAULT_SIM_THRESHOLD, 1.0] {
+        assert!(SimMatcher::try_new(&vec![sim_table(threshold)]).is_ok());
+    }
+    for threshold in [-0.1, 1.5, f64::NAN] {
+        let Err(error) = SimMatcher::try_new(&vec![sim_table(threshold)]) else {
+            panic!("expected InvalidThreshold");
+        };
+        // 转换为MatcherError后保留match_id以及table_id
+        let MatcherError::InvalidThreshold {
+            match_id, table_id, ..
+        } = MatcherError::from(error)
+        else {
+            panic!("expected InvalidThreshold");
+        };
+        assert_eq!(("test", 1), (match_id.as_str(), table_id));
+    }
+}
+
+#[test]
+fn simple_duplicate_word_id() {
+    // 同一词ID出现在不同匹配类型下
+    let simple_wordlist_dict = AHashMap::from([
+        (
+            SimpleMatchType::None,
+            vec![
+                SimpleWord {
+                    word_id: 1,
+                    word: "你好",
+                },
+                SimpleWord {
+                    word_id: 3,
+                    word: "再见",
+                },
+            ],
+        ),
+        (
+            SimpleMatchType::Fanjian,
+            vec![
+                SimpleWord {
+                    word_id: 3,
+                    word: "世界",
+                },
+                SimpleWord {
+                    word_id: 2,
+                    word: "你好",
+                },
+                SimpleWord {
+                    word_id: 1,
+                    word: "測試",
+                },
+            ],
+        ),
+    ]);
+    assert_eq!(
+        Err(SimpleMatcherError::DuplicateWordId { word_id: 1 }),
+        SimpleMatcher::try_new(&simple_wordlist_dict).map(|_| ())
+    );
+
+    // 同一匹配类型下的重复词ID同样报错
+    assert_eq!(
+        Err(SimpleMatcherError::DuplicateWordId { word_id: 7 }),
+        SimpleMatcher::try_new(&AHashMap::from([(
+            SimpleMatchType::None,
+            vec![
+                SimpleWord {
+                    word_id: 7,
+                    word: "你好",
+                },
+                SimpleWord {
+                    word_id: 7,
+                    word: "世界",
+                },
+            ],
+        )]))
+        .map(|_| ())
+    );
+}
+
+#[test]
+fn sim_match_jaro_winkler() {
+    let wordlist = VarZeroVec::from(&["johnsmith"]);
+    let sim_table = SimTable {
+        table_id: 1,
+        match_id: "1",
+        wordlist: &wordlist,
+        threshold: DEFAULT_SIM_THRESHOLD,
+        simple_match_type: SimpleMatchType::None,
+        process_words: true,
+        sim_match_type: SimMatchType::Levenshtein,
+        top_k: None,
+    };
+    let jaro_winkler_sim_table = SimTable {
+        sim_match_type: SimMatchType::JaroWinkler,
+        ..sim_table.clone()
+    };
+
+    // 前5个字符相同，编辑距离相似度为 5/9，Jaro-Winkler相似度约为0.82
+    let levenshtein_matcher = SimMatcher::new(&vec![sim_table]);
+    let jaro_winkler_matcher = SimMatcher::new(&vec![jaro_winkler_sim_table]);
+    assert!(!levenshtein_matcher.is_match("johnsxxxx"));
+    assert!(jaro_winkler_matcher.is_match("johnsxxxx"));
+    let sim_result_list = jaro_winkler_matcher.process("johnsxxxx");
+    assert_eq!(1, sim_result_list.len());
+    assert!(sim_result_list[0].similarity >= DEFAULT_SIM_THRESHOLD);
+
+    let match_table_dict: MatchTableDict = serde_json::from_str(
+        r#"{"test": [{"table_id": 1, "match_table_type": "similar_text_levenshtein", "wordlist": ["johnsmith"], "exemption_wordlist": [], "simple_match_type": 0, "sim_match_type": "jaro_winkler"}]}"#,
+    )
+    .unwrap();
+    let matcher = Matcher::new(&match_table_dict);
+    assert!(matcher.is_match("johnsxxxx"));
+    assert_eq!(
+        SimMatchType::JaroWinkler,
+        matcher.export_table_dict().0.get("test").unwrap()[0].sim_match_type
+    );
+}
+
+#[test]
+fn sim_match_damerau_levenshtein() {
+    let wordlist = VarZeroVec::from(&["hello", "今天天气真好"]);
+    let sim_table = SimTable {
+        table_id: 1,
+        match_id: "1",
+        wordlist: &wordlist,
+        threshold: DEFAULT_SIM_THRESHOLD,
+        simple_match_type: SimpleMatchType::None,
+        process_words: true,
+        sim_match_type: SimMatchType::Levenshtein,
+        top_k: None,
+    };
+    let damerau_levenshtein_sim_table = SimTable {
+        sim_match_type: SimMatchType::DamerauLevenshtein,
+        ..sim_table.clone()
+    };
+
+    // 相邻字符交换在编辑距离中计为两次编辑，在Damerau-Levenshtein中计为一次
+    let levenshtein_matcher = SimMatcher::new(&vec![sim_table]);
+    let damerau_levenshtein_matcher = SimMatcher::new(&vec![damerau_levenshtein_sim_table]);
+    for (text, word_id) in [("hlelo", 0), ("今天气天真好", 1)] {
+        assert!(!levenshtein_matcher.is_match(text), "{text}");
+        let sim_result_list = damerau_levenshtein_matcher.process(text);
+        assert_eq!(1, sim_result_list.len(), "{text}");
+        assert_eq!(word_id, sim_result_list[0].word_id);
+    }
+
+    let match_table_dict: MatchTableDict = serde_json::from_str(
+        r#"{"test": [{"table_id": 1, "match_table_type": "similar_text_levenshtein", "wordlist": ["hello"], "exemption_wordlist": [], "simple_match_type": 0, "sim_match_type": "damerau_levenshtein"}]}"#,
+    )
+    .unwrap();
+    assert!(Matcher::new(&match_table_dict).is_match("hlelo"));
+}
+
+#[test]
+fn sim_match_levenshtein_substring() {
+    let wordlist = VarZeroVec::from(&["helloworld", "今天天气真好"]);
+    let sim_table = SimTable {
+        table_id: 1,
+        match_id: "1",
+        wordlist: &wordlist,
+        threshold: DEFAULT_SIM_THRESHOLD,
+        simple_match_type: SimpleMatchType::None,
+        process_words: true,
+        sim_match_type: SimMatchType::Levenshtein,
+        top_k: None,
+    };
+    let substring_sim_table = SimTable {
+        sim_match_type: SimMatchType::LevenshteinSubstring,
+        ..sim_table.clone()
+    };
+
+    // 整段文本与词的相似度很低，只有滑动窗口能找到其中的近似词
+    let levenshtein_matcher = SimMatcher::new(&vec![sim_table]);
+    let substring_matcher = SimMatcher::new(&vec![substring_sim_table]);
+    let long_text = "this comment mentions helo world somewhere";
+    let cjk_text = "我们都觉得今天天汽真好，所以出去玩了";
+    assert!(!levenshtein_matcher.is_match(long_text));
+    assert!(!levenshtein_matcher.is_match(cjk_text));
+
+    let sim_result_list = substring_matcher.process(long_text);
+    assert_eq!(1, sim_result_list.len());
+    assert_eq!(0, sim_result_list[0].word_id);
+    assert!((sim_result_list[0].similarity - 0.9).abs() < 1e-9);
+    // 窗口位置基于去除非单词字符后的文本 "thiscommentmentionsheloworldsomewhere"
+    assert_eq!(Some((19, 28)), sim_result_list[0].window);
+
+    // 窗口按字符切分，位置为字节位置
+    let sim_result_list = substring_matcher.process(cjk_text);
+    assert_eq!(1, sim_result_list.len());
+    assert_eq!(1, sim_result_list[0].word_id);
+    assert!((sim_result_list[0].similarity - 5.0 / 6.0).abs() < 1e-9);
+    assert_eq!(Some((15, 33)), sim_result_list[0].window);
+
+    // 文本不长于词时整个文本即为窗口
+    let sim_result_list = substring_matcher.process("今天天气真");
+    assert_eq!(1, sim_result_list.len());
+    assert_eq!(Some((0, 15)), sim_result_list[0].window);
+}
+
+#[test]
+fn sim_match_ngram_cosine() {
+    let wordlist = VarZeroVec::from(&["helloworld", "goodbye"]);
+    let sim_table = SimTable {
+        table_id: 1,
+        match_id: "1",
+        wordlist: &wordlist,
+        threshold: DEFAULT_SIM_THRESHOLD,
+        simple_match_type: SimpleMatchType::None,
+        process_words: true,
+        sim_match_type: SimMatchType::Levenshtein,
+        top_k: None,
+    };
+    let ngram_cosine_sim_table = SimTable {
+        sim_match_type: SimMatchType::NgramCosine { n: 2 },
+        ..sim_table.clone()
+    };
+
+    // n-gram向量与词序无关，调换顺序的文本在编辑距离下相似度很低
+    let levenshtein_matcher = SimMatcher::new(&vec![sim_table]);
+    let ngram_cosine_matcher = SimMatcher::new(&vec![ngram_cosine_sim_table]);
+    assert!(!levenshtein_matcher.is_match("world hello"));
+    let sim_result_list = ngram_cosine_matcher.process("world hello");
+    assert_eq!(1, sim_result_list.len());
+    assert_eq!(0, sim_result_list[0].word_id);
+    assert!((sim_result_list[0].similarity - 8.0 / 9.0).abs() < 1e-9);
+    assert!(!ngram_cosine_matcher.is_match("hello"));
+
+    // 词或文本不足n个字符时整体作为一个n-gram
+    let nearest_list = ngram_cosine_matcher.nearest("g", 2);
+    assert_eq!(0.0, nearest_list[0].similarity);
+
+    let match_table_dict: MatchTableDict = serde_json::from_str(
+        r#"{"test": [{"table_id": 1, "match_table_type": "similar_text_levenshtein", "wordlist": ["helloworld"], "exemption_wordlist": [], "simple_match_type": 0, "sim_match_type": {"ngram_cosine": {"n": 2}}}]}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        SimMatchType::NgramCosine { n: 2 },
+        match_table_dict["test"][0].sim_match_type
+    );
+    assert!(Matcher::new(&match_table_dict).is_match("world hello"));
+}
+
+#[test]
+fn sim_match_top_k() {
+    let wordlist = VarZeroVec::from(&["helloworld", "helloworle", "helloworld", "hellowor"]);
+    let sim_table = SimTable {
+        table_id: 1,
+        match_id: "1",
+        wordlist: &wordlist,
+        threshold: DEFAULT_SIM_THRESHOLD,
+        simple_match_type: SimpleMatchType::None,
+        process_words: true,
+        sim_match_type: SimMatchType::Levenshtein,
+        top_k: None,
+    };
+    let top_k_sim_table = SimTable {
+        top_k: Some(1),
+        ..sim_table.clone()
+    };
+
+    let sim_matcher = SimMatcher::new(&vec![sim_table]);
+    assert_eq!(4, sim_matcher.process("helloworld").len());
+
+    // 每个词表只保留相似度最高的结果，相似度相同时取词ID较小的
+    let top_k_sim_matcher = SimMatcher::new(&vec![top_k_sim_table.clone(), top_k_sim_table]);
+    let sim_result_list = top_k_sim_matcher.process("helloworld");
+    assert_eq!(2, sim_result_list.len());
+    for sim_result in sim_result_list {
+        assert_eq!(0, sim_result.word_id);
+        assert_eq!(1.0, sim_result.similarity);
+    }
+    assert!(top_k_sim_matcher.is_match("helloworld"));
+}
+
+#[test]
+fn regex_flags() {
+    let regex_wordlist = VarZeroVec::from(&["^hello.world$", r"(?<!\d)abc"]);
+    let regex_table = RegexTable {
+        table_id: 1,
+        match_id: "1",
+        match_table_type: &MatchTableType::Regex,
+        wordlist: &regex_wordlist,
+        acrostic_position: AcrosticPosition::Initial,
+        prefer_matched_text: false,
+        regex_flags: RegexFlags::default(),
+        acrostic_separator: AcrosticSeparator::Punctuation,
+    };
+    let case_insensitive_regex_table = RegexTable {
+        regex_flags: RegexFlags {
+            case_insensitive: true,
+            ..RegexFlags::default()
+        },
+        ..regex_table.clone()
+    };
+
+    // 同一词表开启i标志后忽略大小写，拆分后RegexSet与逐个匹配的正则均生效
+    let regex_matcher = RegexMatcher::new(&vec![regex_table.clone()]);
+    let case_insensitive_regex_matcher = RegexMatcher::new_with_options(
+        &vec![case_insensitive_regex_table],
+        RegexMatcherOptions {
+            split_regex_table: true,
+            ..Default::default()
+        },
+    );
+    let table_stats = &case_insensitive_regex_matcher.stats().table_stats_list[0];
+    assert_eq!(1, table_stats.set_pattern_count);
+    assert_eq!(1, table_stats.list_pattern_count);
+    for text in ["HELLO WORLD", "ABC"] {
+        assert!(!regex_matcher.is_match(text), "{text}");
+        assert!(case_insensitive_regex_matcher.is_match(text), "{text}");
+    }
+
+    // m与s标志分别作用于行首行尾以及换行符
+    let multi_line_regex_matcher = RegexMatcher::new(&vec![RegexTable {
+        regex_flags: RegexFlags {
+            multi_line: true,
+            ..RegexFlags::default()
+        },
+        ..regex_table.clone()
+    }]);
+    let dot_matches_new_line_regex_matcher = RegexMatcher::new(&vec![RegexTable {
+        regex_flags: RegexFlags {
+            dot_matches_new_line: true,
+            ..RegexFlags::default()
+        },
+        ..regex_table.clone()
+    }]);
+    assert!(!regex_matcher.is_match("hi\nhello world\nbye"));
+    assert!(multi_line_regex_matcher.is_match("hi\nhello world\nbye"));
+    assert!(!regex_matcher.is_match("hello\nworld"));
+    assert!(dot_matches_new_line_regex_matcher.is_match("hello\nworld"));
+
+    let match_table_dict: MatchTableDict = serde_json::from_str(
+        r#"{"test": [{"table_id": 1, "match_table_type": "regex", "wordlist": ["^hello"], "exemption_wordlist": [], "simple_match_type": 0, "regex_flags": {"case_insensitive": true}}]}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        RegexFlags {
+            case_insensitive: true,
+            ..RegexFlags::default()
+        },
+        match_table_dict["test"][0].regex_flags
+    );
+    assert!(Matcher::new(&match_table_dict).is_match("HELLO"));
+}
+
+#[test]
+fn regex_backtrack_limit() {
+    let regex_wordlist = VarZeroVec::from(&["(a|b|ab)*(?=c)", "^ab"]);
+    let regex_table_list = vec![RegexTable {
+        table_id: 1,
+        match_id: "1",
+        match_table_type: &MatchTableType::Regex,
+        wordlist: &regex_wordlist,
+        acrostic_position: AcrosticPosition::Initial,
+        prefer_matched_text: false,
+        regex_flags: RegexFlags::default(),
+        acrostic_separator: AcrosticSeparator::Punctuation,
+    }];
+    let warning_list = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let callback_warning_list = std::sync::Arc::clone(&warning_list);
+    let regex_matcher = RegexMatcher::new_with_options(
+        &regex_table_list,
+        RegexMatcherOptions {
+            backtrack_limit: Some(100_000),
+            warning_callback: Some(RegexWarningCallback(std::sync::Arc::new(
+                move |pattern_warning: &PatternWarning| {
+                    callback_warning_list
+                        .lock()
+                        .unwrap()
+                        .push(pattern_warning.clone())
+                },
+            ))),
+            ..Default::default()
+        },
+    );
+
+    // 超出回溯上限的正则视为未命中，其余正则正常匹配
+    let text = "ab".repeat(1000);
+    let regex_result_list = regex_matcher.process(&text);
+    assert_eq!(1, regex_result_list.len());
+    assert_eq!(1, regex_result_list[0].word_id);
+    assert!(regex_matcher.is_match(&text));
+    assert!(!regex_matcher.is_match(&"ba".repeat(1000)));
+
+    let warning_list = warning_list.lock().unwrap();
+    assert!(!warning_list.is_empty());
+    assert_eq!("(a|b|ab)*(?=c)", warning_list[0].pattern);
+    assert_eq!(1, warning_list[0].table_id);
+}
+
+#[test]
+fn wildcard_table() {
+    let wildcard_wordlist = VarZeroVec::from(&["free*vbucks", "a.b+c", "你?好*世界", "1,2"]);
+    let regex_matcher = RegexMatcher::new(&vec![RegexTable {
+        table_id: 1,
+        match_id: "1",
+        match_table_type: &MatchTableType::Wildcard,
+        wordlist: &wildcard_wordlist,
+        acrostic_position: AcrosticPosition::Initial,
+        prefer_matched_text: false,
+        regex_flags: RegexFlags::default(),
+        acrostic_separator: AcrosticSeparator::Punctuation,
+    }]);
+
+    // *匹配任意个字符，?匹配一个字符，正则元字符以及逗号按字面匹配，结果返回原词
+    for (text, word_id, word) in [
+        ("free 1000\nvbucks", 0, "free*vbucks"),
+        ("xa.b+cx", 1, "a.b+c"),
+        ("你们好啊世界", 2, "你?好*世界"),
+        ("1,2", 3, "1,2"),
+    ] {
+        let regex_result_list = regex_matcher.process(text);
+        assert_eq!(1, regex_result_list.len(), "{text}");
+        assert_eq!(word_id, regex_result_list[0].word_id);
+        assert_eq!(word, regex_result_list[0].word);
+    }
+    for text in ["FREE vbucks", "aXbbc", "你好世界", "12"] {
+        assert!(!regex_matcher.is_match(text), "{text}");
+    }
+
+    let match_table_dict: MatchTableDict = serde_json::from_str(
+        r#"{"test": [{"table_id": 1, "match_table_type": "wildcard", "wordlist": ["free*vbucks"], "exemption_wordlist": [], "simple_match_type": 0, "regex_flags": {"case_insensitive": true}}]}"#,
+    )
+    .unwrap();
+    assert!(Matcher::new(&match_table_dict).is_match("FREE vbucks"));
+}
+
+#[test]
+fn acrostic_gap_separator() {
+    let acrostic_wordlist = VarZeroVec::from(&["你,好"]);
+    let regex_table = RegexTable {
+        table_id: 1,
+        match_id: "1",
+        match_table_type: &MatchTableType::Acrostic,
+        wordlist: &acrostic_wordlist,
+        acrostic_position: AcrosticPosition::Initial,
+        prefer_matched_text: false,
+        regex_flags: RegexFlags::default(),
+        acrostic_separator: AcrosticSeparator::Punctuation,
+    };
+    let punctuated_text = "你今天吃饭了吗？好的";
+    let unpunctuated_text = "你今天吃饭了吗好的";
+
+    // 默认要求每个字位于句首，无标点的文本不命中
+    let regex_matcher = RegexMatcher::new(&vec![regex_table.clone()]);
+    assert!(regex_matcher.is_match(punctuated_text));
+    assert!(!regex_matcher.is_match(unpunctuated_text));
+
+    // 相邻两字之间至多间隔max_gap个任意字符，标点也计入间隔
+    for (max_gap, punctuated_match, unpunctuated_match) in
+        [(2, false, false), (6, false, true), (10, true, true)]
+    {
+        let gap_regex_matcher = RegexMatcher::new(&vec![RegexTable {
+            acrostic_separator: AcrosticSeparator::Gap(max_gap),
+            ..regex_table.clone()
+        }]);
+        assert_eq!(
+            punctuated_match,
+            gap_regex_matcher.is_match(punctuated_text),
+            "{max_gap}"
+        );
+        assert_eq!(
+            unpunctuated_match,
+            gap_regex_matcher.is_match(unpunctuated_text),
+            "{max_gap}"
+        );
+    }
+
+    let match_table_dict: MatchTableDict = serde_json::from_str(
+        r#"{"test": [{"table_id": 1, "match_table_type": "acrostic", "wordlist": ["你,好"], "exemption_wordlist": [], "simple_match_type": 0, "acrostic_separator": {"gap": 6}}]}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        AcrosticSeparator::Gap(6),
+        match_table_dict["test"][0].acrostic_separator
+    );
+    assert!(Matcher::new(&match_table_dict).is_match(unpunctuated_text));
+}
+
+#[test]
+fn regex_named_captures() {
+    let regex_wordlist = VarZeroVec::from(&[
+        r"(?P<amount>\d+)(?P<unit>元|块)",
+        r"(?<!\d)(?P<phone>1[3-9]\d{9})",
+        r"(?P<optional>x)?hello",
+        "world",
+    ]);
+    let regex_table_list = vec![RegexTable {
+        table_id: 1,
+        match_id: "1",
+        match_table_type: &MatchTableType::Regex,
+        wordlist: &regex_wordlist,
+        acrostic_position: AcrosticPosition::Initial,
+        prefer_matched_text: false,
+        regex_flags: RegexFlags::default(),
+        acrostic_separator: AcrosticSeparator::Punctuation,
+    }];
+    let text = "转账100元到15651781111，hello world";
+
+    // RegexSet以及逐个匹配的正则均返回具名捕获组，未参与命中的组以及没有具名捕获组的正则不返回
+    for split_regex_table in [false, true] {
+        let regex_matcher = RegexMatcher::new_with_options(
+            &regex_table_list,
+            RegexMatcherOptions {
+                split_regex_table,
+                ..Default::default()
+            },
+        );
+        let mut regex_result_list = regex_matcher.process(text);
+        regex_result_list.sort_by_key(|regex_result| regex_result.word_id);
+        let capture_list: Vec<Vec<(String, String)>> = regex_result_list
+            .into_iter()
+            .map(|regex_result| regex_result.captures)
+            .collect();
+        assert_eq!(
+            vec![
+                vec![
+                    ("amount".to_owned(), "100".to_owned()),
+                    ("unit".to_owned(), "元".to_owned())
+                ],
+                vec![("phone".to_owned(), "15651781111".to_owned())],
+                vec![],
+                vec![],
+            ],
+            capture_list
+        );
+    }
+
+    let match_table_dict: MatchTableDict = serde_json::from_str(
+        r#"{"test": [{"table_id": 1, "match_table_type": "regex", "wordlist": ["(?P<amount>\\d+)元", "hello"], "exemption_wordlist": [], "simple_match_type": 0}]}"#,
+    )
+    .unwrap();
+    let matcher = Matcher::new(&match_table_dict);
+    let match_result_list = &matcher.word_match_full("100元").matched["test"];
+    assert_eq!(
+        &[("amount".to_owned(), "100".to_owned())],
+        match_result_list[0].captures()
+    );
+    assert!(matcher.word_match("100元 hello")["test"].contains(r#""captures":[["amount","100"]]"#));
+}
+
+#[test]
+fn regex_duplicate_pattern() {
+    let regex_wordlist = VarZeroVec::from(&["h.llo", "world", "h.llo", r"(?<=a)b", r"(?<=a)b"]);
+    let acrostic_wordlist = VarZeroVec::from(&["你,好", "你,好"]);
+    let text = "hello world ab 你吃了吗，好的";
+
+    // 重复的词只产生一条结果，词ID为第一次出现的下标，重复的词记录在stats中
+    for split_regex_table in [false, true] {
+        let regex_table_list = vec![
+            RegexTable {
+                table_id: 1,
+                match_id: "1",
+                match_table_type: &MatchTableType::Regex,
+                wordlist: &regex_wordlist,
+                acrostic_position: AcrosticPosition::Initial,
+                prefer_matched_text: false,
+                regex_flags: RegexFlags::default(),
+                acrostic_separator: AcrosticSeparator::Punctuation,
+            },
+            RegexTable {
+                table_id: 2,
+                match_id: "2",
+                match_table_type: &MatchTableType::Acrostic,
+                wordlist: &acrostic_wordlist,
+                acrostic_position: AcrosticPosition::Initial,
+                prefer_matched_text: false,
+                regex_flags: RegexFlags::default(),
+                acrostic_separator: AcrosticSeparator::Punctuation,
+            },
+            RegexTable {
+                table_id: 3,
+                match_id: "3",
+                match_table_type: &MatchTableType::Acrostic,
+                wordlist: &acrostic_wordlist,
+                acrostic_position: AcrosticPosition::Nth(1),
+                prefer_matched_text: false,
+                regex_flags: RegexFlags::default(),
+                acrostic_separator: AcrosticSeparator::Punctuation,
+            },
+        ];
+        let regex_matcher = RegexMatcher::new_with_options(
+            &regex_table_list,
+            RegexMatcherOptions {
+                split_regex_table,
+                ..Default::default()
+            },
+        );
+        let mut hit_list: Vec<(u32, u64)> = regex_matcher
+            .process(text)
+            .into_iter()
+            .map(|regex_result| (regex_result.table_id, regex_result.word_id))
+            .collect();
+        hit_list.sort();
+        assert_eq!(vec![(1, 0), (1, 1), (1, 3), (2, 0), (3, 0)], hit_list);
+        assert_eq!(
+            vec![
+                ("1".to_owned(), 1, "h.llo".to_owned()),
+                ("1".to_owned(), 1, r"(?<=a)b".to_owned()),
+                ("2".to_owned(), 2, "你,好".to_owned()),
+                ("3".to_owned(), 3, "你,好".to_owned()),
+            ],
+            regex_matcher.stats().duplicate_word_list
+        );
+    }
+}
+
+#[test]
+fn regex_size_limit() {
+    let regex_wordlist = VarZeroVec::from(&["hello", r"\w{10}", r"(?<=a)\w{10}"]);
+    let similar_char_wordlist = VarZeroVec::from(&["你,妳", "好"]);
+    let regex_table = RegexTable {
+        table_id: 1,
+        match_id: "1",
+        match_table_type: &MatchTableType::Regex,
+        wordlist: &regex_wordlist,
+        acrostic_position: AcrosticPosition::Initial,
+        prefer_matched_text: false,
+        regex_flags: RegexFlags::default(),
+        acrostic_separator: AcrosticSeparator::Punctuation,
+    };
+    let similar_char_table = RegexTable {
+        table_id: 2,
+        match_id: "2",
+        match_table_type: &MatchTableType::SimilarChar,
+        wordlist: &similar_char_wordlist,
+        acrostic_position: AcrosticPosition::Initial,
+        prefer_matched_text: false,
+        regex_flags: RegexFlags::default(),
+        acrostic_separator: AcrosticSeparator::Punctuation,
+    };
+
+    // 超出大小上限的正则被丢弃并返回告警，其余正则照常匹配；
+    // 含环视的正则由fancy_regex自行执行，不受委托给regex crate的大小上限约束
+    let (regex_matcher, warning_list) = RegexMatcher::try_new_with_options(
+        std::slice::from_ref(&regex_table),
+        RegexMatcherOptions {
+            size_limit: Some(100_000),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        vec![r"\w{10}"],
+        warning_list
+            .iter()
+            .map(|warning| warning.pattern.as_str())
+            .collect::<Vec<&str>>()
+    );
+    assert!(warning_list
+        .iter()
+        .all(|warning| warning.error.contains("exceeds size limit")));
+    assert!(regex_matcher.is_match("hello"));
+
+    // 默认上限下同样的正则可以编译
+    let (_, warning_list) = RegexMatcher::try_new(&[regex_table]).unwrap();
+    assert!(warning_list.is_empty());
+
+    // 生成的正则超出大小上限时构建失败
+    let Err(RegexBuildError::InvalidGeneratedPattern {
+        match_id, error, ..
+    }) = RegexMatcher::try_new_with_options(
+        &[similar_char_table],
+        RegexMatcherOptions {
+            size_limit: Some(1),
+            ..Default::default()
+        },
+    )
+    else {
+        panic!("expected InvalidGeneratedPattern");
+    };
+    assert_eq!("2", match_id);
+    assert!(error.to_string().contains("exceeds size limit"));
+}
+
+#[test]
+fn regex_hybrid_table() {
+    let regex_wordlist = VarZeroVec::from(&["foo", r"(?<=\$)\d+", r"b[a-z]r", r"(a)\1", r"qu+x"]);
+    let regex_table_list = vec![RegexTable {
+        table_id: 1,
+        match_id: "1",
+        match_table_type: &MatchTableType::Regex,
+        wordlist: &regex_wordlist,
+        acrostic_position: AcrosticPosition::Initial,
+        prefer_matched_text: false,
+        regex_flags: RegexFlags::default(),
+        acrostic_separator: AcrosticSeparator::Punctuation,
+    }];
+
+    // 默认拆分：regex crate兼容的正则进入RegexSet，环视及反向引用逐个匹配，词ID仍为词表下标
+    let regex_matcher = RegexMatcher::new(&regex_table_list);
+    let table_stats = &regex_matcher.stats().table_stats_list[0];
+    assert_eq!(3, table_stats.set_pattern_count);
+    assert_eq!(2, table_stats.list_pattern_count);
+
+    for (text, expected_word_id_list) in [
+        ("foo bar", vec![0, 2]),
+        ("price $100", vec![1]),
+        ("quux", vec![4]),
+        ("foo $5 aa quux bar", vec![0, 1, 2, 3, 4]),
+        ("nothing", vec![]),
+    ] {
+        let mut word_id_list: Vec<u64> = regex_matcher
+            .process(text)
+            .iter()
+            .map(|regex_result| regex_result.word_id)
+            .collect();
+        word_id_list.sort_unstable();
+        assert_eq!(expected_word_id_list, word_id_list, "{text}");
+        assert_eq!(
+            !expected_word_id_list.is_empty(),
+            regex_matcher.is_match(text),
+            "{text}"
+        );
+    }
+
+    // 只命中逐个匹配的正则时同样返回
+    assert_eq!(
+        Some(r"(?<=\$)\d+"),
+        regex_matcher
+            .find_first("$42")
+            .map(|regex_result| regex_result.word.into_owned())
+            .as_deref()
+    );
+
+    // 序列化形式不变，Matcher默认同样拆分
+    let match_table_dict: MatchTableDict = serde_json::from_str(
+        r#"{"test": [{"table_id": 1, "match_table_type": "regex", "wordlist": ["foo", "(?<=\\$)\\d+"], "exemption_wordlist": [], "simple_match_type": 0}]}"#,
+    )
+    .unwrap();
+    let matcher = Matcher::new(&match_table_dict);
+    let table_stats = &matcher.stats().regex_matcher_stats.table_stats_list[0];
+    assert_eq!(1, table_stats.set_pattern_count);
+    assert_eq!(1, table_stats.list_pattern_count);
+    assert!(matcher.is_match("$5"));
+    let match_result_str = &matcher.word_match("foo $5")["test"];
+    assert!(match_result_str.contains(r#""word":"foo""#));
+    assert!(match_result_str.contains(r#""word":"(?<=\\$)\\d+""#));
+}
+
+#[test]
+fn regex_table_exemption() {
+    // 正则及通配符词表的豁免词同样按正则 / 通配符匹配，命中后整个match_id被豁免，与词表顺序无关
+    let simple_table = r#"{"table_id": 1, "match_table_type": "simple", "wordlist": ["你好"], "exemption_wordlist": [], "simple_match_type": 0}"#;
+    let regex_table = r#"{"table_id": 2, "match_table_type": "regex", "wordlist": ["1[3-9]\\d{9}"], "exemption_wordlist": ["客服(电话|热线)[:：]?1[3-9]\\d{9}"], "simple_match_type": 0}"#;
+    let wildcard_table = r#"{"table_id": 3, "match_table_type": "wildcard", "wordlist": ["free*money"], "exemption_wordlist": ["*in-game currency*"], "simple_match_type": 0}"#;
+    for table_list in [
+        [simple_table, regex_table, wildcard_table],
+        [wildcard_table, regex_table, simple_table],
+        [regex_table, simple_table, wildcard_table],
+    ] {
+        let match_table_dict_str = format!(r#"{{"test": [{}]}}"#, table_list.join(","));
+        let match_table_dict: MatchTableDict = serde_json::from_str(&match_table_dict_str).unwrap();
+        let matcher = Matcher::new(&match_table_dict);
+
+        assert!(matcher.is_match("你好，电话13800138000"));
+        assert!(matcher.is_match("free money"));
+        for text in [
+            "你好，客服电话13800138000",
+            "你好，客服热线：13800138000",
+            "free money as in-game currency",
+            "你好 free money as in-game currency",
+        ] {
+            assert!(matcher.word_match(text).is_empty(), "{text}");
+            assert!(matcher.find_first(text).is_none(), "{text}");
+        }
+
+        let exempted_result = &matcher
+            .word_match_full("你好，客服电话13800138000")
+            .exempted["test"];
+        assert_eq!(2, exempted_result.result_list.len());
+        assert_eq!(
+            vec![ExemptionHit {
+                table_id: 2,
+                word: r"客服(电话|热线)[:：]?1[3-9]\d{9}".into(),
+                similarity: 1.0,
+            }],
+            exempted_result.exemption_hit_list
+        );
+    }
+
+    // 构建时的正则豁免词可在运行时移除及恢复，导出时保留原正则
+    let match_table_dict_str = format!(r#"{{"test": [{regex_table}]}}"#);
+    let match_table_dict: MatchTableDict = serde_json::from_str(&match_table_dict_str).unwrap();
+    let mut matcher = Matcher::new(&match_table_dict);
+    let text = "客服电话13800138000";
+    assert!(matcher.word_match(text).is_empty());
+    matcher.remove_exemption("test", 2, r"客服(电话|热线)[:：]?1[3-9]\d{9}");
+    matcher.commit();
+    assert!(matcher.word_match(text).contains_key("test"));
+    assert!(matcher.export_table_dict().0["test"][0]
+        .exemption_wordlist
+        .is_empty());
+    matcher.add_exemption("test", 2, r"客服(电话|热线)[:：]?1[3-9]\d{9}");
+    matcher.commit();
+    assert!(matcher.word_match(text).is_empty());
+    assert_eq!(
+        vec![r"客服(电话|热线)[:：]?1[3-9]\d{9}"],
+        matcher.export_table_dict().0["test"][0].exemption_wordlist
+    );
+
+    // 无法编译的豁免正则同样视为无效正则
+    let invalid_match_table_dict: MatchTableDict = serde_json::from_str(
+        r#"{"test": [{"table_id": 1, "match_table_type": "regex", "wordlist": ["hello"], "exemption_wordlist": ["("], "simple_match_type": 0}]}"#,
+    )
+    .unwrap();
+    assert!(matches!(
+        Matcher::try_new(&invalid_match_table_dict),
+        Err(MatcherError::InvalidRegex { table_id: 1, .. })
+    ));
+}
+
+#[test]
+fn sim_table_exemption() {
+    // 相似词表同样受豁免词影响，被豁免的match_id在sim匹配前即被跳过，word_match_full仍返回其命中结果
+    let sim_table = r#"{"table_id": 1, "match_table_type": "similar_text_levenshtein", "wordlist": ["freemoney"], "exemption_wordlist": ["in-game currency"], "simple_match_type": 0, "sim_match_type": "levenshtein_substring"}"#;
+    let simple_table = r#"{"table_id": 2, "match_table_type": "simple", "wordlist": ["游戏币"], "exemption_wordlist": [], "simple_match_type": 0}"#;
+    for table_list in [[sim_table, simple_table], [simple_table, sim_table]] {
+        let match_table_dict_str = format!(r#"{{"test": [{}]}}"#, table_list.join(","));
+        let match_table_dict: MatchTableDict = serde_json::from_str(&match_table_dict_str).unwrap();
+        let matcher = Matcher::new(&match_table_dict);
+
+        assert!(matcher.word_match("get free mony now").contains_key("test"));
+        for text in [
+            "get free mony now, paid in in-game currency",
+            "in-game currency: free mony 游戏币",
+        ] {
+            assert!(matcher.word_match(text).is_empty(), "{text}");
+            assert!(matcher.find_first(text).is_none(), "{text}");
+            assert_eq!(0, matcher.match_count(text), "{text}");
+        }
+
+        let full_match_result =
+            matcher.word_match_full("get free mony now, paid in in-game currency");
+        let exempted_result = &full_match_result.exempted["test"];
+        assert_eq!(
+            r#"[{"table_id":1,"word":"freemoney"}]"#,
+            serde_json::to_string(&exempted_result.result_list).unwrap()
+        );
+        assert_eq!(1, exempted_result.exemption_hit_list.len());
+        assert_eq!(
+            "in-game currency",
+            exempted_result.exemption_hit_list[0].word
+        );
+    }
+}
+
+#[test]
+fn match_id_exemption_table() {
+    // 只含豁免词的词表作用于整个match_id，不产生空词表告警
+    let matcher_builder = MatcherBuilder::new().match_id("test", |match_id_builder| {
+        match_id_builder
+            .simple_table(1, SimpleMatchType::None, ["免费领取"])
+            .regex_table(2, MatchTableType::Regex, [r"1[3-9]\d{9}"])
+            .sim_table(3, ["freemoney"])
+            .sim_match_type(SimMatchType::LevenshteinSubstring)
+            .exemption_table(9, ["新闻标题", "据报道"])
+    });
+    let (matcher, warning_list) = matcher_builder.build().unwrap();
+    assert!(warning_list.is_empty());
+
+    let text = "免费领取 13800138000 freemoney";
+    assert_eq!(
+        vec![1, 2, 3],
+        matcher
+            .process(text)
+            .iter()
+            .map(
+                |match_result| serde_json::to_value(match_result).unwrap()["table_id"]
+                    .as_u64()
+                    .unwrap()
+            )
+            .collect::<Vec<u64>>()
+    );
+    for exempted_text in [format!("新闻标题：{text}"), format!("{text}，据報道")] {
+        assert!(
+            matcher.word_match(&exempted_text).is_empty(),
+            "{exempted_text}"
+        );
+        let exempted_result = &matcher.word_match_full(&exempted_text).exempted["test"];
+        assert_eq!(3, exempted_result.result_list.len());
+        assert_eq!(9, exempted_result.exemption_hit_list[0].table_id);
+    }
+
+    let owned_match_table_dict = matcher.export_table_dict();
+    let exemption_table = owned_match_table_dict.0["test"]
+        .iter()
+        .find(|owned_table| owned_table.table_id == 9)
+        .unwrap();
+    assert!(exemption_table.wordlist.is_empty());
+    assert_eq!(
+        vec!["新闻标题", "据报道"],
+        exemption_table.exemption_wordlist
+    );
+
+    // 豁免词中的空词同样报错
+    let match_table_dict: MatchTableDict = serde_json::from_str(
+        r#"{"test": [{"table_id": 1, "match_table_type": "simple", "wordlist": ["你好"], "exemption_wordlist": [], "simple_match_type": 0}, {"table_id": 2, "match_table_type": "simple", "wordlist": [], "exemption_wordlist": [""], "simple_match_type": 0}]}"#,
+    )
+    .unwrap();
+    assert!(matches!(
+        Matcher::try_new(&match_table_dict),
+        Err(MatcherError::EmptyWord { table_id: 2, .. })
+    ));
+}
+
+#[test]
+fn word_match_full_as_string() {
+    let match_table_dict = AHashMap::from([(
+        "test",
+        vec![MatchTable {
+            table_id: 1,
+            match_table_type: MatchTableType::Simple,
+            wordlist: VarZeroVec::from(&["你好"]),
+            exemption_wordlist: VarZeroVec::from(&["你好呀"]),
+            simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+            ..Default::default()
+        }],
+    )]);
+    let matcher = Matcher::new(&match_table_dict);
+
+    // 被豁免的命中结果带着豁免词返回，word_match仍为空
+    assert_eq!(matcher.word_match_as_string("你好呀"), "{}");
+    assert_eq!(
+        r#"{"matched":{},"exempted":{"test":{"result_list":[{"table_id":1,"word":"你好"}],"exemption_hit_list":[{"table_id":1,"word":"你好呀","similarity":1.0}]}},"variants_truncated":false}"#,
+        matcher.word_match_full_as_string("你好呀")
+    );
+    assert_eq!(
+        r#"{"matched":{"test":[{"table_id":1,"word":"你好"}]},"exempted":{},"variants_truncated":false}"#,
+        matcher.word_match_full_as_string("你好")
+    );
+}
+
+#[test]
+fn simple_match_type_override() {
+    // 同一词表中的拼音词与字面词，命中结果归属同一table_id
+    let match_table_dict: MatchTableDict = serde_json::from_str(
+        r#"{"test": [{"table_id": 1, "match_table_type": "simple", "wordlist": ["你好", "测试"], "exemption_wordlist": [], "simple_match_type": "none", "simple_match_type_override": {"你好": "pinyin"}}]}"#,
+    )
+    .unwrap();
+    let matcher = Matcher::new(&match_table_dict);
+
+    assert_eq!(
+        r#"[{"table_id":1,"word":"你好"}]"#,
+        matcher.word_match("你号")["test"]
+    );
+    assert_eq!(
+        r#"[{"table_id":1,"word":"测试"}]"#,
+        matcher.word_match("测试")["test"]
+    );
+    assert!(!matcher.is_match("测式"));
+
+    // 导出时保留单独指定的匹配类型
+    let owned_match_table_dict = matcher.export_table_dict();
+    assert_eq!(
+        Some(&SimpleMatchType::PinYin),
+        owned_match_table_dict.0["test"][0]
+            .simple_match_type_override
+            .get("你好")
+    );
+
+    let matcher_builder = MatcherBuilder::new().match_id("test", |match_id_builder| {
+        match_id_builder
+            .simple_table(1, SimpleMatchType::None, ["你好", "测试"])
+            .simple_match_type_override(
+                "你好",
+                SimpleMatchType::PinYin | SimpleMatchType::PinYinChar,
+            )
+    });
+    assert!(matches!(
+        matcher_builder.build(),
+        Err(MatcherError::ConflictingSimpleMatchType { table_id: 1, .. })
+    ));
+}
+
+#[test]
+fn simple_match_or() {
+    let simple_wordlist_dict = AHashMap::from([(
+        SimpleMatchType::None,
+        vec![
+            SimpleWord {
+                word_id: 1,
+                word: "刷单|兼职,微信",
+            },
+            SimpleWord {
+                word_id: 2,
+                word: "dog,cat|fox",
+            },
+            SimpleWord {
+                word_id: 3,
+                word: "hello|hi~bye|later",
+            },
+            SimpleWord {
+                word_id: 4,
+                word: "哈|嘿,哈|嘿",
+            },
+            SimpleWord {
+                word_id: 5,
+                word: "测试,|",
+            },
+        ],
+    )]);
+    let simple_matcher = SimpleMatcher::new_with_options(
+        &simple_wordlist_dict,
+        SimpleMatcherOptions {
+            report_negated: true,
+            ..Default::default()
+        },
+    );
+    let word_id_list = |text: &str| {
+        let mut word_id_list: Vec<u64> = simple_matcher
+            .process(text)
+            .iter()
+            .map(|simple_result| simple_result.word_id)
+            .collect();
+        word_id_list.sort_unstable();
+        word_id_list
+    };
+
+    // '|'优先于','：(刷单 或 兼职) 且 微信
+    assert_eq!(word_id_list("刷单加微信"), vec![1]);
+    assert_eq!(word_id_list("兼职，微信联系"), vec![1]);
+    assert!(word_id_list("刷单兼职").is_empty());
+    assert!(word_id_list("微信").is_empty());
+    assert_eq!(
+        simple_matcher.process("兼职微信")[0].word_parts(),
+        vec!["刷单|兼职", "微信"]
+    );
+
+    // dog 且 (cat 或 fox)
+    assert_eq!(word_id_list("dog and fox"), vec![2]);
+    assert_eq!(word_id_list("cat and dog"), vec![2]);
+    assert!(word_id_list("cat and fox").is_empty());
+
+    // '~'优先级最低：(hello 或 hi) 且不含 (bye 或 later)
+    assert_eq!(word_id_list("hi there"), vec![3]);
+    assert!(word_id_list("hi, bye").is_empty());
+    assert!(word_id_list("hello, see you later").is_empty());
+    assert_eq!(
+        simple_matcher.process_full("hi, bye").negated[0].not_part_list,
+        vec!["bye|later"]
+    );
+
+    // 重复的部分按总命中次数计算，任一备选词均计入
+    assert_eq!(word_id_list("哈嘿"), vec![4]);
+    assert_eq!(word_id_list("哈哈"), vec![4]);
+    assert!(word_id_list("哈").is_empty());
+
+    // 备选词均为空的部分忽略
+    assert_eq!(word_id_list("测试"), vec![5]);
+}
+
+#[test]
+fn simple_match_escaped() {
+    let simple_wordlist_dict = AHashMap::from([(
+        SimpleMatchType::None,
+        vec![
+            SimpleWord {
+                word_id: 1,
+                word: "AT&T",
+            },
+            SimpleWord {
+                word_id: 2,
+                word: r"1\,000元",
+            },
+            SimpleWord {
+                word_id: 3,
+                word: r"a\~b\|c,d",
+            },
+            SimpleWord {
+                word_id: 4,
+                word: r"x\\,y",
+            },
+        ],
+    )]);
+    let simple_matcher = SimpleMatcher::new(&simple_wordlist_dict);
+    let word_id_list = |text: &str| {
+        simple_matcher
+            .process(text)
+            .iter()
+            .map(|simple_result| simple_result.word_id)
+            .collect::<Vec<u64>>()
+    };
+
+    // '&'不是分隔符，按字面匹配
+    assert_eq!(word_id_list("at&t"), vec![1]);
+    assert!(word_id_list("AT T").is_empty());
+
+    // 转义的','不切分组合词
+    assert_eq!(word_id_list("1,000元"), vec![2]);
+    assert!(word_id_list("1000元").is_empty());
+    assert_eq!(simple_matcher.process("1,000元")[0].word, r"1\,000元");
+
+    assert_eq!(word_id_list("a~b|c d"), vec![3]);
+    assert!(word_id_list("a d").is_empty());
+
+    // "\\"还原为反斜杠，其后的','仍是分隔符
+    assert_eq!(word_id_list(r"y x\"), vec![4]);
+
+    // 转义后的词按字面匹配
+    let word = escape_simple_word(r"A,B~C|D\E");
+    assert_eq!(word, r"A\,B\~C\|D\\E");
+    let simple_matcher = SimpleMatcher::new(&AHashMap::from([(
+        SimpleMatchType::None,
+        vec![SimpleWord {
+            word_id: 1,
+            word: &word,
+        }],
+    )]));
+    assert!(simple_matcher.is_match(r"A,B~C|D\E"));
+    assert!(!simple_matcher.is_match("A"));
+}
+
+#[test]
+fn simple_match_ordered() {
+    let simple_wordlist_dict = AHashMap::from([(
+        SimpleMatchType::None,
+        vec![
+            SimpleWord {
+                word_id: 1,
+                word: "转账>二维码",
+            },
+            SimpleWord {
+                word_id: 2,
+                word: "x1>y2>z3",
+            },
+            SimpleWord {
+                word_id: 3,
+                word: "汇款>收款码|银行卡,客服",
+            },
+            SimpleWord {
+                word_id: 4,
+                word: "哦>啊,哦>啊",
+            },
+            SimpleWord {
+                word_id: 5,
+                word: "ab>bc",
+            },
+            SimpleWord {
+                word_id: 6,
+                word: r"1\>2",
+            },
+        ],
+    )]);
+    let simple_matcher = SimpleMatcher::new(&simple_wordlist_dict);
+    let word_id_list = |text: &str| {
+        let mut word_id_list: Vec<u64> = simple_matcher
+            .process(text)
+            .iter()
+            .map(|simple_result| simple_result.word_id)
+            .collect();
+        word_id_list.sort_unstable();
+        #[cfg(feature = "rayon")]
+        assert_eq!(
+            word_id_list.len(),
+            simple_matcher.par_process(text).len(),
+            "text: {text}"
+        );
+        word_id_list
+    };
+
+    // 顺序命中
+    assert_eq!(word_id_list("先转账再扫二维码"), vec![1]);
+    assert_eq!(word_id_list("x1, y2, z3"), vec![2]);
+    // 逆序不命中
+    assert!(word_id_list("扫二维码后转账").is_empty());
+    assert!(word_id_list("z3 y2 x1").is_empty());
+    assert!(word_id_list("x1 z3 y2").is_empty());
+    // 交错出现时，存在合法的顺序即命中
+    assert_eq!(word_id_list("二维码，转账，二维码"), vec![1]);
+    assert_eq!(word_id_list("y2 x1 z3 y2 x1 z3"), vec![2]);
+    assert!(word_id_list("y2 x1 z3 x1 y2").is_empty());
+
+    // 有序部分可以与'|'以及','组合
+    assert_eq!(word_id_list("客服：汇款后发银行卡"), vec![3]);
+    assert!(word_id_list("汇款后发银行卡").is_empty());
+    assert!(word_id_list("客服：发银行卡后汇款").is_empty());
+
+    // 重复的有序部分需要依次命中多次
+    assert_eq!(word_id_list("哦啊哦啊"), vec![4]);
+    assert!(word_id_list("哦哦啊").is_empty());
+
+    // 后一步骤须在前一步骤结束之后开始
+    assert!(word_id_list("abc").is_empty());
+    assert_eq!(word_id_list("abbc"), vec![5]);
+
+    // 转义的'>'按字面匹配
+    assert_eq!(word_id_list("1>2"), vec![6]);
+    assert!(word_id_list("1 2").is_empty());
+}
+
+#[test]
+fn simple_match_window() {
+    let simple_wordlist_dict = AHashMap::from([(
+        SimpleMatchType::None,
+        vec![
+            SimpleWord {
+                word_id: 1,
+                word: "你好,世界@12",
+            },
+            SimpleWord {
+                word_id: 2,
+                word: "ab,cd@5~xy",
+            },
+            SimpleWord {
+                word_id: 3,
+                word: "哦,哦@6",
+            },
+            SimpleWord {
+                word_id: 4,
+                word: "x1>y2,z3@8",
+            },
+            SimpleWord {
+                word_id: 5,
+                word: r"q\@5",
+            },
+            SimpleWord {
+                word_id: 6,
+                word: "user@mail",
+            },
+        ],
+    )]);
+    let simple_matcher = SimpleMatcher::new(&simple_wordlist_dict);
+    let word_id_list = |text: &str| {
+        let mut word_id_list: Vec<u64> = simple_matcher
+            .process(text)
+            .iter()
+            .map(|simple_result| simple_result.word_id)
+            .collect();
+        word_id_list.sort_unstable();
+        #[cfg(feature = "rayon")]
+        assert_eq!(
+            word_id_list.len(),
+            simple_matcher.par_process(text).len(),
+            "text: {text}"
+        );
+        word_id_list
+    };
+
+    // 窗口按字节计算，一个中文字符3字节
+    assert_eq!(word_id_list("世界你好"), vec![1]);
+    assert!(word_id_list("你好啊世界").is_empty());
+    // 前面的命中超出窗口时，后续满足窗口的命中仍然命中
+    assert_eq!(word_id_list("你好啊世界，你好世界"), vec![1]);
+    assert_eq!(word_id_list("ab cd"), vec![2]);
+    assert!(word_id_list("ab  cd").is_empty());
+    // 否定部分不受窗口约束
+    assert!(word_id_list("ab cd ................ xy").is_empty());
+    // 重复部分须在同一窗口内命中对应次数
+    assert_eq!(word_id_list("哦哦"), vec![3]);
+    assert!(word_id_list("哦，哦").is_empty());
+    // 有序部分的窗口从其第一步骤命中开始计算
+    assert_eq!(word_id_list("z3 x1y2"), vec![4]);
+    assert!(word_id_list("x1 y2   z3").is_empty());
+    assert!(word_id_list("z3   x1 y2").is_empty());
+    // 转义的'@'以及非数字的'@'按字面匹配
+    assert_eq!(word_id_list("q@5"), vec![5]);
+    assert_eq!(word_id_list("user@mail"), vec![6]);
+
+    // 窗口部分不计入word_parts
+    let result_list = simple_matcher.process("你好世界");
+    assert_eq!(result_list[0].word, "你好,世界@12");
+    assert_eq!(result_list[0].word_parts(), vec!["你好", "世界"]);
+}
+
+#[test]
+fn simple_match_detailed_parts() {
+    let simple_wordlist_dict = AHashMap::from([(
+        SimpleMatchType::None,
+        vec![
+            SimpleWord {
+                word_id: 1,
+                word: "招代理,微信~官方",
+            },
+            SimpleWord {
+                word_id: 2,
+                word: "无,法,无,天",
+            },
+            SimpleWord {
+                word_id: 3,
+                word: "你好",
+            },
+        ],
+    )]);
+    let simple_matcher = SimpleMatcher::new(&simple_wordlist_dict);
+    let part_list = |detailed_result: &DetailedResult| {
+        detailed_result
+            .parts
+            .iter()
+            .map(|part_detail| {
+                (
+                    part_detail.part.to_string(),
+                    part_detail.negated,
+                    part_detail.matched,
+                    part_detail.variant,
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // 两个组合词部分只命中一个，不影响process的结果
+    let text = "招代理，无法无天";
+    let detailed_list = simple_matcher.process_detailed(text);
+    assert_eq!(detailed_list.len(), 2);
+    assert_eq!(detailed_list[0].word_id, 1);
+    assert!(!detailed_list[0].matched);
+    assert_eq!(
+        part_list(&detailed_list[0]),
+        vec![
+            ("招代理".to_owned(), false, true, Some(0)),
+            ("微信".to_owned(), false, false, None),
+            ("官方".to_owned(), true, false, None),
+        ]
+    );
+    // 组合词部分按词表中首次出现的顺序，重复的部分须命中对应次数
+    assert_eq!(detailed_list[1].word_id, 2);
+    assert!(detailed_list[1].matched);
+    assert_eq!(
+        part_list(&detailed_list[1]),
+        vec![
+            ("无".to_owned(), false, true, Some(0)),
+            ("法".to_owned(), false, true, Some(0)),
+            ("天".to_owned(), false, true, Some(0)),
+        ]
+    );
+    assert_eq!(
+        simple_matcher
+            .process(text)
+            .iter()
+            .map(|simple_result| simple_result.word_id)
+            .collect::<Vec<_>>(),
+        vec![2]
+    );
+
+    // 否定部分命中时同样返回
+    let detailed_list = simple_matcher.process_detailed("官方招代理，加微信");
+    assert_eq!(detailed_list.len(), 1);
+    assert!(!detailed_list[0].matched);
+    assert!(detailed_list[0]
+        .parts
+        .iter()
+        .all(|part_detail| part_detail.matched));
+
+    // 短于最小长度的文本仍返回部分命中
+    let detailed_list = simple_matcher.process_detailed("无");
+    assert_eq!(detailed_list.len(), 1);
+    assert_eq!(
+        part_list(&detailed_list[0])[0],
+        ("无".to_owned(), false, false, None)
+    );
+    assert!(simple_matcher.process_detailed("").is_empty());
+}
+
+#[test]
+fn simple_matcher_words() {
+    let simple_wordlist_dict = AHashMap::from([
+        (
+            SimpleMatchType::None,
+            vec![
+                SimpleWord {
+                    word_id: 1,
+                    word: "你好,世界",
+                },
+                SimpleWord {
+                    word_id: 2,
+                    word: "hello",
+                },
+            ],
+        ),
+        (
+            SimpleMatchType::PinYin,
+            vec![SimpleWord {
+                word_id: 3,
+                word: "西安",
+            }],
+        ),
+    ]);
+    let mut simple_matcher = SimpleMatcher::new(&simple_wordlist_dict);
+
+    assert_eq!(simple_matcher.get_word(1), Some("你好,世界"));
+    assert_eq!(simple_matcher.get_word(4), None);
+    assert_eq!(simple_matcher.word_count(), 3);
+    let mut word_list: Vec<(u64, &str, SimpleMatchType)> = simple_matcher.words().collect();
+    word_list.sort_unstable_by_key(|&(word_id, ..)| word_id);
+    assert_eq!(
+        word_list,
+        vec![
+            (1, "你好,世界", SimpleMatchType::None),
+            (2, "hello", SimpleMatchType::None),
+            (3, "西安", SimpleMatchType::PinYin),
+        ]
+    );
+    assert_eq!(
+        simple_matcher.simple_match_types(),
+        vec![SimpleMatchType::None, SimpleMatchType::PinYin]
+    );
+
+    // 删除立即生效，新增词在rebuild后生效
+    simple_matcher.remove_word(2);
+    simple_matcher
+        .add_word(SimpleMatchType::None, 5, "再见")
+        .unwrap();
+    assert_eq!(simple_matcher.get_word(2), None);
+    assert_eq!(simple_matcher.get_word(5), None);
+    assert_eq!(simple_matcher.word_count(), 2);
+    simple_matcher.rebuild().unwrap();
+    assert_eq!(simple_matcher.get_word(5), Some("再见"));
+    assert_eq!(simple_matcher.word_count(), 3);
+
+    // 某匹配类型的词全部删除后，该类型不再列出
+    simple_matcher.remove_word(3);
+    assert_eq!(
+        simple_matcher.simple_match_types(),
+        vec![SimpleMatchType::None]
+    );
+}
+
+#[test]
+fn matcher_tables() {
+    let match_table_dict = AHashMap::from([
+        (
+            "test",
+            vec![MatchTable {
+                table_id: 1,
+                match_table_type: MatchTableType::Simple,
+                wordlist: VarZeroVec::from(&["你好,世界", "测试"]),
+                exemption_wordlist: VarZeroVec::from(&["测试一下"]),
+                simple_match_type: SimpleMatchType::FanjianDeleteNormalize,
+                ..Default::default()
+            }],
+        ),
+        (
+            "regex",
+            vec![MatchTable {
+                table_id: 2,
+                match_table_type: MatchTableType::Regex,
+                wordlist: VarZeroVec::from(&["^abc", "("]),
+                ..Default::default()
+            }],
+        ),
+    ]);
+    let mut matcher = Matcher::new(&match_table_dict);
+    matcher.add_exemption("other", 3, "你好");
+    matcher.commit();
+
+    assert_eq!(matcher.match_ids(), vec!["other", "regex", "test"]);
+    // 无法编译的正则未加载，不计入词数；运行时添加的豁免词所属词表不存在时，单独作为simple词表
+    assert_eq!(
+        matcher.tables(),
+        vec![
+            TableInfo {
+                match_id: "other".to_owned(),
+                table_id: 3,
+                match_table_type: MatchTableType::Simple,
+                word_count: 0,
+                exemption_count: 1,
+            },
+            TableInfo {
+                match_id: "regex".to_owned(),
+                table_id: 2,
+                match_table_type: MatchTableType::Regex,
+                word_count: 1,
+                exemption_count: 0,
+            },
+            TableInfo {
+                match_id: "test".to_owned(),
+                table_id: 1,
+                match_table_type: MatchTableType::Simple,
+                word_count: 2,
+                exemption_count: 1,
+            },
+        ]
+    );
+    assert_eq!(
+        matcher.tables_as_string(),
+        serde_json::to_string(&matcher.tables()).unwrap()
+    );
+    assert!(matcher
+        .tables_as_string()
+        .contains(r#""match_table_type":"regex""#));
+
+    // 移除构建时的豁免词、向已有词表添加豁免词后，计数与export_table_dict导出的词表一致
+    matcher.remove_exemption("test", 1, "测试一下");
+    matcher.add_exemption("test", 1, "测试完毕");
+    matcher.add_exemption("test", 1, "测试结束");
+    matcher.add_exemption("other", 3, "世界");
+    matcher.commit();
+    let mut exported_table_list: Vec<TableInfo> = matcher
+        .export_table_dict()
+        .0
+        .into_iter()
+        .flat_map(|(match_id, owned_table_list)| {
+            owned_table_list
+                .into_iter()
+                .map(move |owned_table| TableInfo {
+                    match_id: match_id.clone(),
+                    table_id: owned_table.table_id,
+                    match_table_type: owned_table.match_table_type,
+                    word_count: owned_table.wordlist.len(),
+                    exemption_count: owned_table.exemption_wordlist.len(),
+                })
+        })
+        .collect();
+    exported_table_list
+        .sort_unstable_by(|a, b| (&a.match_id, a.table_id).cmp(&(&b.match_id, b.table_id)));
+    assert_eq!(matcher.tables(), exported_table_list);
+    assert_eq!(matcher.tables()[0].exemption_count, 2);
+    assert_eq!(matcher.tables()[2].exemption_count, 2);
+}